@@ -4,11 +4,11 @@
 //! both as a plugin (when invoked via `atlas local`) and as a standalone CLI (when invoked directly as `atlas-local`).
 //!
 //! The commands are defined in the [`LocalArgs`](super::LocalArgs) enum.
-use std::env::args;
+use std::{env::args, path::PathBuf, time::Duration};
 
 use clap::{Args, Subcommand};
 
-use crate::formatting::Format;
+use crate::formatting::{ErrorsTo, Format};
 
 use super::LocalArgs;
 
@@ -90,10 +90,82 @@ pub struct GlobalArgs {
     #[arg(global = true, long = "output", short = 'o')]
     pub format: Option<Format>,
 
+    /// Flag that indicates whether to wrap JSON output in an envelope with a schema version.
+    ///
+    /// The output becomes {"schema_version": 1, "result": {...}}. The schema version is bumped when fields are removed or renamed, new fields can be added without a bump. Has no effect on text output.
+    #[arg(global = true, long)]
+    pub envelope: bool,
+
+    /// Flag that indicates whether to wrap single-result JSON output in a one-element array.
+    ///
+    /// Commands that already output an array, like list, are not changed, so the same jq expression works for every command. Has no effect on text output.
+    #[arg(global = true, long)]
+    pub json_array: bool,
+
     /// Name of the profile to use from your configuration file.
     /// To learn about profiles for the Atlas CLI, see https://dochub.mongodb.org/core/atlas-cli-save-connection-settings.
     #[arg(global = true, long, short = 'P')]
     pub profile: Option<String>,
+
+    /// Name of the saved context to use for connecting to Docker.
+    ///
+    /// Contexts are saved with the context set command. When no context is used, the Docker context, the DOCKER_HOST environment variable or the local Docker socket is used. Without a Docker socket, the socket of a running Podman service is used.
+    #[arg(global = true, long)]
+    pub context: Option<String>,
+
+    /// Name of the Docker context to use for connecting to Docker, e.g. desktop-linux.
+    ///
+    /// The endpoint is read from the metadata of the Docker CLI, see docker context ls. When omitted, the DOCKER_CONTEXT environment variable or the current context of the Docker CLI is used, unless DOCKER_HOST is set. Can't be used together with --context.
+    #[arg(global = true, long, conflicts_with = "context")]
+    pub docker_context: Option<String>,
+
+    /// Docker host to connect to, e.g. tcp://10.0.0.5:2376.
+    ///
+    /// Overrides the DOCKER_HOST environment variable. Can't be used together with --context or --dockerContext.
+    #[arg(global = true, long, conflicts_with_all = ["context", "docker_context"])]
+    pub docker_host: Option<String>,
+
+    /// CA certificate to verify the Docker host with, in PEM format.
+    ///
+    /// Must be used together with --tlsCert and --tlsKey. Without these options, the certificates in DOCKER_CERT_PATH are used when DOCKER_TLS_VERIFY is set.
+    #[arg(global = true, long, requires_all = ["tls_cert", "tls_key"])]
+    pub tls_ca_cert: Option<PathBuf>,
+
+    /// Client certificate to authenticate to the Docker host with, in PEM format.
+    ///
+    /// Must be used together with --tlsCaCert and --tlsKey.
+    #[arg(global = true, long, requires_all = ["tls_ca_cert", "tls_key"])]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Private key of the client certificate, in PEM format.
+    ///
+    /// Must be used together with --tlsCaCert and --tlsCert.
+    #[arg(global = true, long, requires_all = ["tls_ca_cert", "tls_cert"])]
+    pub tls_key: Option<PathBuf>,
+
+    /// Flag that indicates whether to reconnect to Docker when the connection drops during a long wait.
+    ///
+    /// Applies while waiting for a deployment to become healthy, e.g. when the Docker daemon restarts. The connection is re-established up to 5 times within the wait timeout. By default, the command fails when the connection drops.
+    #[arg(global = true, long)]
+    pub reconnect_on_drop: bool,
+
+    /// Time an operation runs before its spinner is shown, e.g. 500ms.
+    ///
+    /// Operations that complete faster never show a spinner, so fast operations don't flicker. Use 0s to show spinners right away.
+    #[arg(global = true, long, default_value = "200ms", value_parser = super::parse_duration)]
+    pub spinner_delay: Duration,
+
+    /// Directory where the CLI saves its state, e.g. the saved contexts.
+    ///
+    /// Overrides the MONGODB_ATLAS_LOCAL_CONFIG_DIR environment variable. Useful to isolate CI runs and tests from the user's configuration.
+    #[arg(global = true, long)]
+    pub config_dir: Option<PathBuf>,
+
+    /// Output stream for errors.
+    ///
+    /// Successful output is always written to stdout.
+    #[arg(global = true, long, value_enum, default_value_t)]
+    pub errors_to: ErrorsTo,
 }
 
 /// Enum representing the different ways the CLI can be invoked.
@@ -0,0 +1,44 @@
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand)]
+#[command(about = "Manage saved settings of deployments.")]
+pub enum Config {
+    Set(Set),
+    Get(Get),
+    Unset(Unset),
+}
+
+/// Save settings of a deployment, in the key=value format.
+///
+/// The settings are used when the matching flag is omitted, e.g. atlas local config set analytics connectWith=mongosh connects to the analytics deployment with mongosh. Supported settings are connectWith and defaultDb for connect, and waitForHealthy for start.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Set {
+    /// Name of the deployment.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+    /// Settings to save, in the key=value format.
+    #[arg(index = 2, required = true, value_parser = super::parse_key_value)]
+    pub settings: Vec<(String, String)>,
+}
+
+/// Show the saved settings of a deployment.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Get {
+    /// Name of the deployment.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+}
+
+/// Remove saved settings of a deployment.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Unset {
+    /// Name of the deployment.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+    /// Keys of the settings to remove.
+    #[arg(index = 2, required = true)]
+    pub keys: Vec<String>,
+}
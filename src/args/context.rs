@@ -0,0 +1,39 @@
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand)]
+#[command(about = "Manage saved contexts for connecting to Docker.")]
+pub enum Context {
+    Set(Set),
+    #[command(alias = "ls")]
+    List(List),
+    #[command(alias = "rm")]
+    Remove(Remove),
+}
+
+/// Save a context, replacing the context with the same name.
+///
+/// Use the context with the global --context option, e.g. atlas local --context ci list.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Set {
+    /// Name of the context.
+    #[arg(index = 1)]
+    pub context_name: String,
+    /// Docker host to connect to, e.g. unix:///var/run/docker.sock or tcp://127.0.0.1:2375.
+    #[arg(long)]
+    pub docker_host: String,
+}
+
+/// List all saved contexts.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct List;
+
+/// Remove a saved context.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Remove {
+    /// Name of the context.
+    #[arg(index = 1)]
+    pub context_name: String,
+}
@@ -5,13 +5,24 @@
 //!
 //! The business logic layer is [`crate::commands`], which receives these parsed arguments.
 
-use std::{path::PathBuf, time::Duration};
+use std::{
+    net::IpAddr,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 use atlas_local::models::ImageTag;
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 mod cli;
+pub mod config;
+pub mod context;
+pub mod profile;
+pub mod sample_data;
 pub mod search;
+pub mod snapshot;
 
 pub use cli::{Cli, GlobalArgs};
 pub use search::{Indexes, Search};
@@ -22,6 +33,7 @@ pub use search::{Indexes, Search};
 pub enum LocalArgs {
     Setup(Setup),
     Connect(Connect),
+    Attach(Attach),
     #[command(alias = "ls")]
     List(List),
     Start(Start),
@@ -29,42 +41,258 @@ pub enum LocalArgs {
     Stop(Stop),
     #[command(alias = "log")]
     Logs(Logs),
+    Inspect(Inspect),
+    Capabilities(Capabilities),
+    Exists(Exists),
+    Diff(Diff),
+    Events(Events),
+    Clone(CloneDeployment),
+    #[command(subcommand)]
+    Snapshot(snapshot::Snapshot),
+    Export(Export),
+    Import(Import),
+    Seed(Seed),
+    #[command(subcommand)]
+    SampleData(sample_data::SampleData),
+    Exec(Exec),
+    Eval(Eval),
     #[command(alias = "rm")]
     Delete(Delete),
     #[command(subcommand)]
     Search(Search),
+    #[command(subcommand)]
+    Context(context::Context),
+    #[command(subcommand)]
+    Config(config::Config),
+    #[command(subcommand)]
+    Profile(profile::Profile),
+    Version(Version),
+    Doctor(Doctor),
+    Completion(Completion),
 }
 
 /// List all local deployments.
+///
+/// With --showConnectionString the port and connection string of each deployment are added, stopped deployments are marked as not running.
 #[derive(Parser)]
 #[command(rename_all = "camelCase")]
-pub struct List;
+pub struct List {
+    /// Flag that indicates whether to list no deployments instead of failing when Docker is not running.
+    ///
+    /// Only an unreachable Docker daemon is ignored, other errors still fail the command.
+    #[arg(long)]
+    pub only_if_docker_running: bool,
+
+    /// Flag that indicates whether to add the port and connection string of each deployment.
+    ///
+    /// Credentials are removed from the connection strings unless the --showSecrets option is used.
+    #[arg(long)]
+    pub show_connection_string: bool,
+
+    /// Flag that indicates whether to show the credentials in the connection strings instead of removing them.
+    #[arg(long, requires = "show_connection_string")]
+    pub show_secrets: bool,
+
+    /// Only list the deployments that match the filter, in the key=value format.
+    ///
+    /// Supported keys are state, e.g. state=running, version, e.g. version=8.x, and source, the tool that created the deployment, e.g. source=atlas-local. When the flag is repeated, a deployment must match every filter.
+    #[arg(long, value_parser = parse_list_filter)]
+    pub filter: Vec<ListFilter>,
+}
+
+/// Filter of the list command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListFilter {
+    /// State of the deployment, e.g. running.
+    State(String),
+    /// MongoDB version of the deployment, where x matches any number, e.g. 8.x or 7.0.x.
+    Version(String),
+    /// Tool that created the deployment, e.g. atlas-local.
+    Source(String),
+}
 
 /// Delete a deployment.
 ///
 /// The command prompts you to confirm the operation when you run the command without the --force option.
+/// Multiple deployments can be deleted at once by passing several names, the confirmation prompt is shown once for all of them.
 ///
-/// Deleting a Local deployment also deletes any local data volumes.
+/// Deleting a Local deployment also deletes any local data volumes, unless the --keepVolumes option is used.
 /// Deleting a deployment will not remove saved connections from MongoDB for VS Code. This must be done manually. To learn more, see https://www.mongodb.com/docs/mongodb-vscode/connect/#remove-a-connection.
 #[derive(Parser)]
 #[command(rename_all = "camelCase")]
 pub struct Delete {
-    /// Name of the deployment to delete.
-    #[arg(index = 1)]
-    pub deployment_name: String,
+    /// Names of the deployments to delete.
+    #[arg(index = 1, required_unless_present = "all")]
+    pub deployment_names: Vec<String>,
+
+    /// Flag that indicates whether to delete all local deployments.
+    #[arg(long, conflicts_with = "deployment_names")]
+    pub all: bool,
+
+    #[command(flatten)]
+    pub bulk: BulkArgs,
 
     /// Flag that indicates whether to skip the confirmation prompt before proceeding with the requested action.
     #[arg(long)]
     pub force: bool,
+
+    /// Flag that indicates whether to keep the data volumes of the deployment and only remove its container.
+    ///
+    /// The kept volumes are not removed automatically, use docker volume rm or docker volume prune to remove them when they are no longer needed.
+    #[arg(long)]
+    pub keep_volumes: bool,
+}
+
+/// Show the version of the CLI, with host and Docker details for troubleshooting.
+///
+/// The Docker details are best-effort, they are reported as unavailable when the Docker daemon can't be reached.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Version {}
+
+/// Check the local environment for common problems with local deployments.
+///
+/// Checks whether Docker is reachable and its version, the available space on the disk of this machine, whether a port is available, whether the deployment image is pulled and the health of the existing deployments. Every problem is reported with a hint to fix it. Use --output json for a report to attach to support tickets. The command exits with code 1 when a check failed.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Doctor {
+    /// Port to check the availability of, e.g. the port you intend to pass to setup --port.
+    ///
+    /// The port must be between 1 and 65535. The default is 27017.
+    #[arg(long, default_value_t = 27017)]
+    pub port: u16,
+}
+
+/// Generate the shell completion script.
+///
+/// The script completes the commands, the flags and the names of local deployments, e.g. for start, stop, connect and delete. To load the completions in every session, add the script to the startup file of your shell, e.g. source <(atlas-local completion bash) in ~/.bashrc.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Completion {
+    /// Shell to generate the completion script for.
+    ///
+    /// Valid values are bash, zsh, fish and powershell.
+    #[arg(index = 1, value_enum)]
+    pub shell: CompletionShell,
+}
+
+/// Shell supported by the completion command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    #[value(name = "bash")]
+    Bash,
+    #[value(name = "zsh")]
+    Zsh,
+    #[value(name = "fish")]
+    Fish,
+    #[value(name = "powershell")]
+    Powershell,
+}
+
+/// Check whether a deployment exists.
+///
+/// The command exits with code 0 when the deployment exists and 1 when it doesn't, without printing anything. With --output json, true or false is printed.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Exists {
+    /// Name of the deployment to check.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+}
+
+/// Compare a deployment with its spec in a deployments file.
+///
+/// The deployments file uses the format of setup --fromFile, the entry with the name of the deployment is compared. Only the settings in the entry are compared, except for the username and initdb, which are also reported when only the deployment has them. The command exits with code 0 when the deployment matches the spec and 1 when it doesn't.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Diff {
+    /// Name of the deployment to compare.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Path of the TOML file that lists the deployment specs.
+    #[arg(long)]
+    pub config: PathBuf,
 }
 
 /// Get deployment logs.
+///
+/// Use --grep to only show the log lines that match a regular expression, and --invert to show the lines that don't match instead.
+/// Use --sinceStart to skip the logs from before the deployment was last started, e.g. when debugging a restart.
 #[derive(Parser)]
 #[command(rename_all = "camelCase")]
 pub struct Logs {
     /// Name of the deployment to get logs from.
     #[arg(index = 1)]
     pub deployment_name: String,
+
+    /// Regular expression the log lines must match to be shown, e.g. "(?i)error|warning".
+    #[arg(long, value_parser = Regex::new)]
+    pub grep: Option<Regex>,
+
+    /// Flag that indicates whether to show the log lines that don't match --grep instead.
+    #[arg(long, requires = "grep")]
+    pub invert: bool,
+
+    /// Flag that indicates whether to only show the logs since the deployment was last started.
+    #[arg(long)]
+    pub since_start: bool,
+}
+
+/// Show the details of a deployment.
+///
+/// Secrets like the root password are redacted unless the --showSecrets option is used.
+/// With --raw the unprocessed Docker container inspect output is printed as JSON, which is useful for debugging.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Inspect {
+    /// Name of the deployment to inspect.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Flag that indicates whether to print the raw Docker container inspect output.
+    #[arg(long)]
+    pub raw: bool,
+
+    /// Flag that indicates whether to show secrets like the root password instead of redacting them.
+    #[arg(long)]
+    pub show_secrets: bool,
+}
+
+/// Report what a deployment supports, like search, vector search and authentication.
+///
+/// The report is derived from the settings of the deployment. With --deep, the deployment is also queried to confirm its version and whether search is configured, this requires the deployment to be running.
+/// Use --output json for a machine-readable report, for human readable details use inspect instead.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// Name of the deployment to report the capabilities of.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Flag that indicates whether to query the running deployment to confirm the capabilities.
+    #[arg(long)]
+    pub deep: bool,
+}
+
+/// Show events for local deployments.
+///
+/// Prints create, start, stop, pause, unpause, die and destroy events of local deployments as they occur, until interrupted with Ctrl-C.
+/// With --output json, every event is printed as a JSON object on its own line.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Events {
+    /// Show events created since this time.
+    ///
+    /// The time is either an RFC 3339 timestamp (e.g. 2026-01-02T15:04:05Z) or a duration relative to now (e.g. 10m, 1h).
+    #[arg(long, value_parser = parse_time)]
+    pub since: Option<SystemTime>,
+
+    /// Stop streaming events at this time.
+    ///
+    /// The time is either an RFC 3339 timestamp (e.g. 2026-01-02T15:04:05Z) or a duration relative to now (e.g. 10m, 1h).
+    #[arg(long, value_parser = parse_time)]
+    pub until: Option<SystemTime>,
 }
 
 /// Start a deployment.
@@ -76,6 +304,8 @@ pub struct Start {
     pub deployment_name: String,
 
     /// Flag that indicates whether to wait for the deployment to be healthy before returning.
+    ///
+    /// Can be saved for the deployment with config set, e.g. atlas local config set local1 waitForHealthy=true.
     #[arg(long, default_value = "false")]
     pub wait_for_healthy: bool,
 
@@ -84,6 +314,34 @@ pub struct Start {
     /// When no unit is provided, the unit is assumed to be seconds.
     #[arg(long, default_value = "10m", value_parser = parse_duration)]
     pub wait_for_healthy_timeout: Duration,
+
+    /// Action to take when waiting for the deployment to become healthy times out.
+    ///
+    /// Valid values are fail and continue. With continue, the deployment is reported as started with a warning, it may still become healthy later.
+    #[arg(long, default_value = "fail")]
+    pub timeout_action: TimeoutAction,
+
+    /// Flag that indicates whether to wait for the deployment to have a primary before returning.
+    ///
+    /// This is stricter than waiting for the deployment to be healthy, it prevents "no primary available" errors when the deployment is used right away. Waits up to 60 seconds once the deployment is healthy.
+    #[arg(long)]
+    pub wait_for_primary: bool,
+
+    /// Flag that indicates whether to recreate the container of an exited or dead deployment before starting it.
+    ///
+    /// The container is removed and created again from the configuration of the deployment, its data volumes are kept.
+    #[arg(long)]
+    pub recreate: bool,
+
+    /// Flag that indicates whether to skip the confirmation prompt before proceeding with the requested action.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Flag that indicates whether to show a desktop notification when the command completes.
+    ///
+    /// The notification is only shown when the command takes longer than 10 seconds.
+    #[arg(long)]
+    pub notify: bool,
 }
 
 /// Create a local deployment.
@@ -96,15 +354,75 @@ pub struct Setup {
     #[arg(index = 1)]
     pub deployment_name: Option<String>,
 
+    /// Path of a TOML file that lists multiple deployments to set up at once.
+    ///
+    /// Every distinct image is pulled once before the deployments are created. Each entry of the deployments array supports the name, imageTag, port, bindIpAll, initdb, loadSampleData, username, password and image fields. The settings of the deployments can only be set in the file, the flags of a single deployment can't be combined with --fromFile.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "deployment_name",
+            "from_profile",
+            "image_tag",
+            "fail_on_eol",
+            "port",
+            "bind_ip_all",
+            "i_understand",
+            "initdb",
+            "force",
+            "load_sample_data",
+            "datasets",
+            "username",
+            "password",
+            "image",
+            "registry",
+            "pull_policy",
+            "skip_pull_image",
+            "connect_with",
+            "default_connector",
+            "uri_template",
+            "append_options",
+            "default_db",
+            "hide_connection_string",
+            "strip_credentials",
+            "connect_host",
+            "connector_args",
+            "wait_for_primary",
+            "resume",
+            "reuse_volume",
+            "stream",
+            "summary",
+            "output_connection_string_file",
+            "notify",
+        ]
+    )]
+    pub from_file: Option<PathBuf>,
+
+    /// Name of a saved profile to use the settings of, for the flags that are omitted.
+    ///
+    /// Profiles are saved with the profile add command and can set the image tag, image, whether to load sample data and the connection method. Flags that are set take precedence over the settings of the profile.
+    #[arg(long)]
+    pub from_profile: Option<String>,
+
+    #[command(flatten)]
+    pub bulk: BulkArgs,
+
     /// Image tag to use for the deployment.
     ///
     /// Expected format: 'preview', 'latest', semver (e.g. 8, 8.2, 8.2.1), or semver+timestamp (e.g. 8.2.4-20260217T084055Z).
     #[arg(long, alias = "mdbVersion", value_parser = parse_image_tag)]
     pub image_tag: Option<ImageTag>,
 
+    /// Flag that indicates whether to fail the setup when the requested MongoDB version reached its end of life.
+    ///
+    /// By default a warning is shown and the setup continues. The latest and preview image tags are never end of life.
+    #[arg(long)]
+    pub fail_on_eol: bool,
+
     /// Port that the MongoDB server listens to for client connections.
     ///
     /// The port must be between 1 and 65535.
+    ///
+    /// When not set, the port from the MONGODB_ATLAS_LOCAL_PORT environment variable is used. If that is not set either, a port is auto-assigned.
     #[arg(long)]
     pub port: Option<u16>,
 
@@ -114,10 +432,16 @@ pub struct Setup {
     #[arg(long, default_value = "false")]
     pub bind_ip_all: bool,
 
+    /// Flag that indicates whether to bind to all interfaces without authentication, without asking for confirmation.
+    ///
+    /// Required with --bindIpAll and --force when no --username is set, as anyone who can reach the machine can access the deployment.
+    #[arg(long, requires = "bind_ip_all")]
+    pub i_understand: bool,
+
     /// Flag that uses a folder to be mapped into LOCAL deployment for initialization
     ///
-    /// The folder must exist and be a directory.
-    #[arg(long, value_parser = parse_directory)]
+    /// The path must exist and be a file or a directory, it is resolved to an absolute path before it is mounted.
+    #[arg(long)]
     pub initdb: Option<PathBuf>,
 
     /// Flag that indicates whether to skip the confirmation prompt before proceeding with the requested action.
@@ -132,6 +456,12 @@ pub struct Setup {
     #[arg(long)]
     pub load_sample_data: Option<bool>,
 
+    /// Sample datasets to load into the deployment, e.g. sample_mflix,sample_airbnb.
+    ///
+    /// Only the listed datasets are restored once the deployment is healthy, instead of all the datasets of --loadSampleData. The whole sample data archive is still downloaded. Can be repeated or separated by commas.
+    #[arg(long = "dataset", value_delimiter = ',', value_parser = sample_data::parse_sample_dataset)]
+    pub datasets: Vec<String>,
+
     /// Username for authenticating to MongoDB.
     #[arg(long)]
     pub username: Option<String>,
@@ -147,12 +477,24 @@ pub struct Setup {
     #[arg(long)]
     pub image: Option<String>,
 
-    /// Flag that indicates whether to skip the pull image step.
+    /// Registry or mirror to pull the deployment image from, e.g. mirror.example.com:5000.
     ///
-    /// This will prevent the CLI from pulling the latest MongoDB Atlas Local image. Use with caution as you might end up with an outdated image.
+    /// The registry of the image is replaced, the repository and tag are kept. When not set, the registry from the MONGODB_ATLAS_LOCAL_REGISTRY environment variable is used. Credentials for the registry are not sent, pull the image with docker pull first when the registry requires authentication.
+    #[arg(long, value_parser = parse_registry)]
+    pub registry: Option<String>,
+
+    /// Policy for pulling the deployment image.
     ///
-    /// The default is false.
-    #[arg(long, default_value = "false")]
+    /// always pulls the image every time, missing only pulls the image when it isn't available locally and never fails when the image isn't available locally.
+    ///
+    /// The default is missing.
+    #[arg(long, value_enum)]
+    pub pull_policy: Option<PullPolicy>,
+
+    /// Deprecated, use --pullPolicy never instead.
+    ///
+    /// Flag that indicates whether to skip the pull image step.
+    #[arg(long, default_value = "false", conflicts_with = "pull_policy")]
     pub skip_pull_image: bool,
 
     /// Method for connecting to the deployment after setup.
@@ -160,6 +502,189 @@ pub struct Setup {
     /// If not provided, the user will be prompted to select a connection method.
     #[arg(long)]
     pub connect_with: Option<ConnectWith>,
+
+    /// Connection method to use when --connectWith is not provided.
+    ///
+    /// The method is pre-selected when prompting for a connection method, and used without prompting with --force. Overrides the MONGODB_ATLAS_LOCAL_DEFAULT_CONNECTOR environment variable.
+    #[arg(long)]
+    pub default_connector: Option<ConnectWith>,
+
+    #[command(flatten)]
+    pub connection_string: ConnectionStringArgs,
+
+    /// Hostname or IP address that replaces the host of the connection string, e.g. when connecting from another machine.
+    ///
+    /// The connection string is rewritten before it is printed, written to a file or passed to a connector. The deployment must be reachable from the client machine at this host, e.g. with --bindIpAll.
+    #[arg(long, value_parser = parse_host)]
+    pub connect_host: Option<String>,
+
+    /// Connector specific launch option, in the key=value format.
+    ///
+    /// Can be repeated to pass multiple options. Supported options are theme for compass, eval, file and quiet for mongosh, and workspace for vscode. Unknown options are ignored with a warning.
+    #[arg(long = "connectorArg", value_parser = parse_key_value)]
+    pub connector_args: Vec<(String, String)>,
+
+    /// Flag that indicates whether to wait for the deployment to have a primary before returning.
+    ///
+    /// This is stricter than waiting for the deployment to be healthy, it prevents "no primary available" errors when the deployment is used right away. Waits up to 60 seconds once the deployment is healthy.
+    #[arg(long)]
+    pub wait_for_primary: bool,
+
+    /// Flag that indicates whether to resume the setup of a deployment that was interrupted before it became healthy.
+    ///
+    /// When a created or exited deployment with the same name and matching settings exists, it is started instead of failing because the name is taken.
+    #[arg(long)]
+    pub resume: bool,
+
+    /// Name of an existing Docker volume with a MongoDB data directory to copy into the new deployment, e.g. a shared golden dataset.
+    ///
+    /// The deployment is created, stopped while the volume is copied into its data directory, and started again. The volume itself is not changed. --initdb, --loadSampleData and --dataset are ignored with a warning, as the data comes from the volume.
+    #[arg(long, conflicts_with = "resume")]
+    pub reuse_volume: Option<String>,
+
+    /// Flag that indicates whether to write the outcome of every setup step as a JSON line when it completes.
+    ///
+    /// Every line looks like {"step": "create_container", "outcome": "success"}, the final result is written last. Requires --output json.
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Flag that indicates whether to print the result as a single line, e.g. name=myDeployment version=8.2.2 port=27017.
+    ///
+    /// The line is easy to parse in scripts. The connection string is added as uri when --connectWith connectionString is used. Only applies to text output.
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Path of a file to write the connection string of the deployment to, e.g. for a later step of a CI pipeline.
+    ///
+    /// The file only contains the connection string and is replaced when it exists. When --connectWith is not provided, the connection string is returned as with --connectWith connectionString instead of prompting. The command fails when the file can't be written.
+    #[arg(long)]
+    pub output_connection_string_file: Option<PathBuf>,
+
+    /// Flag that indicates whether to show a desktop notification when the command completes.
+    ///
+    /// The notification is only shown when the command takes longer than 10 seconds.
+    #[arg(long)]
+    pub notify: bool,
+}
+
+/// Create a new deployment with the configuration and data of an existing deployment.
+///
+/// The new deployment uses the same MongoDB version and settings on a free port. The data volumes are copied while both deployments are stopped, a running source deployment is started again afterwards. Useful to test migrations against a copy of real data.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct CloneDeployment {
+    /// Name of the deployment to copy.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Name of the new deployment.
+    #[arg(index = 2)]
+    pub target_deployment_name: String,
+}
+
+/// Export the data of a deployment with mongodump.
+///
+/// The deployment must be running. The mongodump installed on your machine is used, or the mongodump in the deployment container when mongodump is not installed. The output directory has the layout of mongodump, so it can be restored with mongorestore or the import command.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Export {
+    /// Name of the deployment to export.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Name of the database to export, all databases are exported when omitted.
+    #[arg(long)]
+    pub db: Option<String>,
+
+    /// Directory to write the dump to.
+    ///
+    /// The default is the dump directory in the current directory, like mongodump.
+    #[arg(long, default_value = "dump")]
+    pub out: PathBuf,
+}
+
+/// Import a dump into a deployment with mongorestore.
+///
+/// The deployment must be running. The dump directory has the layout of mongodump, e.g. a directory written by the export command. The mongorestore installed on your machine is used, or the mongorestore in the deployment container when mongorestore is not installed. Documents that already exist in the deployment are kept, unless the --drop option is used.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Import {
+    /// Name of the deployment to import the dump into.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Directory with the dump to import.
+    #[arg(long)]
+    pub dir: PathBuf,
+
+    /// Flag that indicates whether to drop the collections of the dump from the deployment before importing them.
+    #[arg(long)]
+    pub drop: bool,
+}
+
+/// Run seed scripts against a running deployment.
+///
+/// Setup only runs the scripts of --initdb when the deployment is created, seed runs them again, e.g. to refresh fixtures without recreating the deployment. The .js scripts are run with the mongosh in the deployment container, connected to the database of the deployment, and the .sh scripts with bash in the container. The scripts are run in alphabetical order, the command stops at the first script that fails.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Seed {
+    /// Name of the deployment to seed.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Directory with the seed scripts, e.g. the directory passed to setup --initdb.
+    #[arg(long)]
+    pub dir: PathBuf,
+}
+
+/// Run a command inside the container of a deployment.
+///
+/// Without a command, an interactive mongosh session is opened with the mongosh inside the deployment container, so mongosh doesn't have to be installed on your machine. Pass the command after --, e.g. exec local1 -- bash. The deployment must be running. The exit code is the exit code of the command. With --capture, the output of the command is printed as a result, e.g. exec local1 --capture --output json -- ls /data/db.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Exec {
+    /// Name of the deployment to run the command in.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Flag that indicates whether to capture the output of the command instead of attaching it to the terminal.
+    ///
+    /// The exit code, stdout and stderr of the command are printed when it exits, as JSON with --output json. The command can't read from the terminal. Requires a command.
+    #[arg(long, requires = "command")]
+    pub capture: bool,
+
+    /// Maximum number of bytes of stdout and of stderr to capture, e.g. 1048576.
+    ///
+    /// The output after the maximum is dropped and the result is marked as truncated. By default, the whole output is captured.
+    #[arg(long, requires = "capture")]
+    pub max_output: Option<usize>,
+
+    /// Command to run in the container, after --.
+    #[arg(last = true)]
+    pub command: Vec<String>,
+}
+
+/// Evaluate a JavaScript expression against a deployment and print the result.
+///
+/// The expression is evaluated with the mongosh in the deployment container, e.g. eval local1 'db.users.countDocuments()'. The deployment must be running. With --json the result is printed as relaxed Extended JSON, so scripts can parse it, e.g. with jq.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Eval {
+    /// Name of the deployment to evaluate the expression against.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// JavaScript expression to evaluate, e.g. db.users.countDocuments().
+    #[arg(index = 2)]
+    pub expression: String,
+
+    /// Name of the database that db refers to, the database of the deployment is used when omitted.
+    #[arg(long, value_parser = parse_database_name)]
+    pub db: Option<String>,
+
+    /// Flag that indicates whether to print the result as relaxed Extended JSON instead of the mongosh format.
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// Stop (pause) a deployment.
@@ -180,43 +705,310 @@ pub struct Connect {
     pub deployment_name: String,
 
     /// Method for connecting to the deployment.
+    ///
+    /// Required unless a connection method is saved for the deployment with config set, or the MONGODB_ATLAS_LOCAL_DEFAULT_CONNECTOR environment variable sets a default connection method.
     #[arg(long)]
-    pub connect_with: ConnectWith,
+    pub connect_with: Option<ConnectWith>,
+
+    #[command(flatten)]
+    pub connection_string: ConnectionStringArgs,
+
+    /// Connector specific launch option, in the key=value format.
+    ///
+    /// Can be repeated to pass multiple options. Supported options are theme for compass, eval, file and quiet for mongosh, and workspace for vscode. Unknown options are ignored with a warning.
+    #[arg(long = "connectorArg", value_parser = parse_key_value)]
+    pub connector_args: Vec<(String, String)>,
+
+    /// Number of times to relaunch the connector when it fails to connect.
+    ///
+    /// A connector that exits with an error within 10 seconds is relaunched after a short delay. This helps when the deployment is healthy but not yet accepting connections.
+    #[arg(long, default_value_t = 0)]
+    pub reconnect: u32,
+
+    /// Action to take when waiting for the deployment to become healthy times out.
+    ///
+    /// Valid values are fail and continue. With continue, the connector is launched anyway.
+    #[arg(long, default_value = "fail")]
+    pub timeout_action: TimeoutAction,
+
+    /// Flag that indicates whether to wait for the deployment to have a primary before connecting.
+    ///
+    /// This is stricter than waiting for the deployment to be healthy, it prevents "no primary available" errors right after the deployment started. Waits up to 60 seconds once the deployment is healthy.
+    #[arg(long)]
+    pub wait_for_primary: bool,
+
+    /// Flag that indicates whether to ping the deployment with the connection string before printing it.
+    ///
+    /// Only applies to --connectWith connectionString. The connection string is only printed once a connection with it succeeded, the command fails otherwise.
+    #[arg(long)]
+    pub ping: bool,
+
+    /// Flag that indicates whether to print the connection string as a Compass favorite instead.
+    ///
+    /// Only applies to --connectWith connectionString. The output is a connections file that can be imported in Compass, with the deployment as a favorite.
+    #[arg(long, conflicts_with = "hide_connection_string")]
+    pub compass_favorite: bool,
+
+    /// Flag that indicates whether to fail instead of starting the deployment when it isn't running.
+    ///
+    /// By default, a stopped or paused deployment is started before connecting. With --noStart, connect never changes the state of the deployment.
+    #[arg(long)]
+    pub no_start: bool,
+
+    /// Path to a script file for mongosh to run against the deployment.
+    ///
+    /// Only applies to --connectWith mongosh. mongosh exits after running the script, and the command exits with the exit code of mongosh.
+    #[arg(long, conflicts_with = "reconnect")]
+    pub eval_file: Option<PathBuf>,
+
+    /// Path to a folder for VS Code to open, with the connection to the deployment added.
+    ///
+    /// Only applies to --connectWith vscode. Overrides the workspace connector option.
+    #[arg(long)]
+    pub open_folder: Option<PathBuf>,
+
+    /// Flag that indicates whether to return a connection string that other containers can use to reach the deployment.
+    ///
+    /// Only applies to --connectWith connectionString. The connection string uses the name of the deployment container and the port inside the container instead of the port on the host. The deployment must be attached to a user-defined Docker network, e.g. with docker network connect, that the other containers are attached to as well.
+    #[arg(long, conflicts_with = "compass_favorite")]
+    pub for_container: bool,
+}
+
+/// Open an interactive mongosh session for a deployment.
+///
+/// This is a shortcut for connect --connectWith mongosh. The deployment is started when it isn't running. When mongosh is not installed, the mongosh inside the deployment container is used.
+#[derive(Parser)]
+#[command(rename_all = "camelCase")]
+pub struct Attach {
+    /// Name of the deployment that you want to attach to.
+    #[arg(index = 1)]
+    pub deployment_name: String,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ValueEnum)]
+/// Flags to select what bulk operations do when an item fails.
+///
+/// Bulk operations are delete --all, setup --fromFile and search indexes create --allCollections, the flags have no effect on other operations.
+#[derive(Args, Clone, Copy, Debug, Default)]
+#[command(rename_all = "camelCase")]
+pub struct BulkArgs {
+    /// Flag that indicates whether to stop a bulk operation at the first item that fails.
+    ///
+    /// The remaining items are not processed. With or without this flag, the exit code is 1 when any item failed.
+    #[arg(long, conflicts_with = "keep_going")]
+    pub fail_fast: bool,
+
+    /// Flag that indicates whether to continue a bulk operation with the other items when an item fails.
+    ///
+    /// This is the default, the outcome of every item is reported.
+    #[arg(long)]
+    pub keep_going: bool,
+}
+
+/// Flags to customize the connection string of a deployment.
+#[derive(Args, Clone, Debug, Default)]
+#[command(rename_all = "camelCase")]
+pub struct ConnectionStringArgs {
+    /// Template for the connection string.
+    ///
+    /// Supports the {connectionString}, {host}, {port}, {username} and {password} placeholders, e.g. mongodb://{host}:{port}/myDatabase.
+    #[arg(long)]
+    pub uri_template: Option<String>,
+
+    /// Option to append to the connection string, in the key=value format.
+    ///
+    /// Can be repeated to append multiple options, e.g. --appendOption appName=myApp --appendOption retryWrites=false.
+    #[arg(long = "appendOption", value_parser = parse_key_value)]
+    pub append_options: Vec<(String, String)>,
+
+    /// Database to add to the connection string, e.g. mongodb://127.0.0.1:27017/myDatabase.
+    ///
+    /// With setup, the database is saved as the defaultDb setting of the deployment and connect adds it to the connection string. With connect, it overrides the saved database. A database in the --uriTemplate takes precedence.
+    #[arg(long, value_parser = parse_database_name)]
+    pub default_db: Option<String>,
+
+    /// Flag that indicates whether to hide the connection string in the output, as it may contain credentials.
+    ///
+    /// The connection string is replaced with [hidden] in the text and JSON output. Connectors still receive the real connection string.
+    #[arg(long)]
+    pub hide_connection_string: bool,
+
+    /// Flag that indicates whether to remove the username and password from the connection string in the output.
+    ///
+    /// The output is still a valid connection string, e.g. to log or share it. Connectors still receive the connection string with the credentials.
+    #[arg(long)]
+    pub strip_credentials: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize)]
 pub enum ConnectWith {
     #[value(name = "compass")]
+    #[serde(rename = "compass")]
     Compass,
     #[value(name = "mongosh")]
+    #[serde(rename = "mongosh")]
     Mongosh,
     #[value(name = "vscode")]
+    #[serde(rename = "vscode")]
     VsCode,
     #[value(name = "connectionString")]
+    #[serde(rename = "connectionString")]
     ConnectionString,
 }
 
+/// When to pull the image of a deployment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PullPolicy {
+    /// Pull the image every time.
+    #[value(name = "always")]
+    Always,
+    /// Pull the image only when it isn't available locally.
+    #[default]
+    #[value(name = "missing")]
+    Missing,
+    /// Never pull the image, fail when it isn't available locally.
+    #[value(name = "never")]
+    Never,
+}
+
+/// Action to take when waiting for a deployment to become healthy times out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum TimeoutAction {
+    /// Fail the command.
+    #[default]
+    #[value(name = "fail")]
+    Fail,
+    /// Continue as if the deployment became healthy.
+    #[value(name = "continue")]
+    Continue,
+}
+
 fn parse_duration(s: &str) -> Result<Duration, String> {
     duration_str::parse(s).map_err(|e| e.to_string())
 }
 
-fn parse_directory(s: &str) -> Result<PathBuf, String> {
-    let path = PathBuf::from(s);
+fn parse_time(s: &str) -> Result<SystemTime, String> {
+    // Try to parse the time as a timestamp first, fall back to a duration relative to now.
+    if let Ok(time) = humantime::parse_rfc3339_weak(s) {
+        return Ok(time);
+    }
 
-    // Check if the path exists.
-    if !path.exists() {
-        return Err(format!("The directory {} does not exist", s));
+    let duration = parse_duration(s)
+        .map_err(|_| format!("expected an RFC 3339 timestamp or a duration, got '{s}'"))?;
+    SystemTime::now()
+        .checked_sub(duration)
+        .ok_or_else(|| format!("the duration {s} is too long"))
+}
+
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!(
+            "expected an option in the key=value format, got '{s}'"
+        )),
     }
+}
+
+fn parse_list_filter(s: &str) -> Result<ListFilter, String> {
+    let (key, value) = parse_key_value(s)?;
+    match key.as_str() {
+        "state" => Ok(ListFilter::State(value)),
+        "version" => Ok(ListFilter::Version(value)),
+        "source" => Ok(ListFilter::Source(value)),
+        _ => Err(format!(
+            "unknown filter '{key}', expected one of state, version or source"
+        )),
+    }
+}
+
+/// Characters MongoDB doesn't allow in database names.
+const INVALID_DATABASE_NAME_CHARACTERS: &[char] = &[
+    '/', '\\', '.', ' ', '"', '$', '*', '<', '>', ':', '|', '?', '\0',
+];
+
+/// Maximum length of a database name in bytes.
+const MAX_DATABASE_NAME_LENGTH: usize = 63;
+
+pub(crate) fn parse_database_name(s: &str) -> Result<String, String> {
+    if s.is_empty() {
+        return Err("database name can't be empty".to_string());
+    }
+    if s.len() > MAX_DATABASE_NAME_LENGTH {
+        return Err(format!(
+            "database name can't be longer than {MAX_DATABASE_NAME_LENGTH} bytes"
+        ));
+    }
+    if let Some(c) = s
+        .chars()
+        .find(|c| INVALID_DATABASE_NAME_CHARACTERS.contains(c))
+    {
+        return Err(format!("database name can't contain {c:?}"));
+    }
+
+    Ok(s.to_string())
+}
+
+/// Maximum length of a hostname.
+const MAX_HOSTNAME_LENGTH: usize = 253;
+
+/// Maximum length of a label, a part between dots, of a hostname.
+const MAX_HOSTNAME_LABEL_LENGTH: usize = 63;
+
+fn parse_host(s: &str) -> Result<String, String> {
+    // IPv6 addresses are accepted with and without the brackets used in URLs
+    let ip = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .unwrap_or(s);
+    if let Ok(ip) = ip.parse::<IpAddr>() {
+        return Ok(ip.to_string());
+    }
+
+    let is_valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= MAX_HOSTNAME_LABEL_LENGTH
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if s.len() > MAX_HOSTNAME_LENGTH || !s.split('.').all(is_valid_label) {
+        return Err(format!("expected a hostname or IP address, got '{s}'"));
+    }
+
+    Ok(s.to_string())
+}
 
-    // Check if the path is a directory.
-    if !path.is_dir() {
-        return Err(format!("The path {} is not a directory", s));
+/// Parse an image registry, a host with an optional port and path, e.g. mirror.example.com:5000/dockerhub.
+pub(crate) fn parse_registry(s: &str) -> Result<String, String> {
+    let invalid = || {
+        format!(
+            "expected a registry host with an optional port and path, e.g. mirror.example.com:5000, got '{s}'"
+        )
+    };
+
+    let (host_and_port, path) = match s.trim_end_matches('/').split_once('/') {
+        Some((host_and_port, path)) => (host_and_port, Some(path)),
+        None => (s.trim_end_matches('/'), None),
+    };
+    let host = match host_and_port.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => host,
+        _ => host_and_port,
+    };
+    parse_host(host).map_err(|_| invalid())?;
+
+    // Path components follow the rules of repository names, e.g. no uppercase letters
+    let is_valid_component = |component: &str| {
+        !component.is_empty()
+            && component
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c))
+    };
+    if path.is_some_and(|path| !path.split('/').all(is_valid_component)) {
+        return Err(invalid());
     }
 
-    // Return the path as a PathBuf.
-    Ok(path)
+    Ok(s.trim_end_matches('/').to_string())
 }
 
-fn parse_image_tag(s: &str) -> Result<ImageTag, String> {
+pub(crate) fn parse_image_tag(s: &str) -> Result<ImageTag, String> {
     ImageTag::try_from(s).map_err(|e| e.to_string())
 }
@@ -0,0 +1,71 @@
+use clap::{Args, Subcommand};
+
+use super::ConnectWith;
+
+#[derive(Subcommand)]
+#[command(about = "Manage named profiles with settings for setup.")]
+pub enum Profile {
+    Add(Add),
+    #[command(alias = "ls")]
+    List(List),
+    #[command(alias = "rm")]
+    Remove(Remove),
+    Show(Show),
+}
+
+/// Save a profile, replacing the profile with the same name.
+///
+/// Use the profile with setup --fromProfile, e.g. atlas local setup --fromProfile fast. The settings of the profile are used for the flags that are omitted, flags that are set take precedence.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Add {
+    /// Name of the profile.
+    #[arg(index = 1)]
+    pub profile_name: String,
+
+    /// Image tag to use for the deployment.
+    ///
+    /// Expected format: 'preview', 'latest', semver (e.g. 8, 8.2, 8.2.1), or semver+timestamp (e.g. 8.2.4-20260217T084055Z).
+    #[arg(long, alias = "mdbVersion", value_parser = validate_image_tag)]
+    pub image_tag: Option<String>,
+
+    /// Alternative docker image to use for the deployment.
+    #[arg(long)]
+    pub image: Option<String>,
+
+    /// Flag that indicates whether to load sample data into the deployment.
+    #[arg(long)]
+    pub load_sample_data: Option<bool>,
+
+    /// Method for connecting to the deployment after setup.
+    #[arg(long)]
+    pub connect_with: Option<ConnectWith>,
+}
+
+/// List all saved profiles.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct List;
+
+/// Remove a saved profile.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Remove {
+    /// Name of the profile.
+    #[arg(index = 1)]
+    pub profile_name: String,
+}
+
+/// Show the settings of a saved profile.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Show {
+    /// Name of the profile.
+    #[arg(index = 1)]
+    pub profile_name: String,
+}
+
+/// The image tag is saved as it was provided, so it's only validated.
+fn validate_image_tag(s: &str) -> Result<String, String> {
+    super::parse_image_tag(s).map(|_| s.to_string())
+}
@@ -0,0 +1,58 @@
+use clap::{Args, Subcommand};
+
+/// The databases in the sample data archive of Atlas.
+pub const SAMPLE_DATASETS: &[&str] = &[
+    "sample_airbnb",
+    "sample_analytics",
+    "sample_geospatial",
+    "sample_guides",
+    "sample_mflix",
+    "sample_restaurants",
+    "sample_supplies",
+    "sample_training",
+    "sample_weatherdata",
+];
+
+#[derive(Subcommand)]
+#[command(about = "Manage the Atlas sample datasets of deployments.")]
+pub enum SampleData {
+    Load(Load),
+    Status(Status),
+}
+
+/// Load the Atlas sample datasets into an existing deployment.
+///
+/// Setup only loads sample data when the deployment is created, with --loadSampleData. The sample data archive is downloaded and restored with mongorestore in the deployment container, so the deployment needs access to the internet. Datasets that are already loaded are replaced, use --dataset to only load some of the datasets.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Load {
+    /// Name of the deployment to load the sample data into.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// Sample datasets to load, e.g. sample_mflix,sample_airbnb.
+    ///
+    /// Can be repeated or separated by commas, only the listed datasets are restored. All datasets are loaded when omitted.
+    #[arg(long = "dataset", value_delimiter = ',', value_parser = parse_sample_dataset)]
+    pub datasets: Vec<String>,
+}
+
+/// Show which Atlas sample datasets are loaded in a deployment.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Status {
+    /// Name of the deployment.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+}
+
+pub(super) fn parse_sample_dataset(s: &str) -> Result<String, String> {
+    if !SAMPLE_DATASETS.contains(&s) {
+        return Err(format!(
+            "unknown sample dataset '{s}', expected one of {}",
+            SAMPLE_DATASETS.join(", ")
+        ));
+    }
+
+    Ok(s.to_string())
+}
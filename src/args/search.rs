@@ -1,4 +1,6 @@
-use clap::{Args, Subcommand};
+use std::time::Duration;
+
+use clap::{Args, Subcommand, ValueEnum};
 
 #[derive(Subcommand)]
 #[command(about = "Manage search for local deployments.")]
@@ -16,6 +18,8 @@ pub enum Indexes {
     List(List),
     #[command(alias = "rm")]
     Delete(Delete),
+    Update(Update),
+    Watch(Watch),
 }
 
 #[derive(Args)]
@@ -27,6 +31,41 @@ pub struct Create {
     /// Flag that indicates whether to watch the command until it completes its execution or the watch times out.
     #[arg(long = "watch", short = 'w', default_value = "false")]
     pub watch: bool,
+    /// Flag that indicates whether to check the index status less often the longer the index takes to build.
+    ///
+    /// The interval between checks starts at 1 second and doubles after every check, up to --watchMaxInterval.
+    #[arg(long, requires = "watch")]
+    pub watch_backoff: bool,
+    /// Maximum interval between index status checks when --watchBackoff is used.
+    ///
+    /// The format is a number followed by a unit, e.g. 30s or 1m. The default is 30s.
+    #[arg(long, value_parser = super::parse_duration, requires = "watch_backoff")]
+    pub watch_max_interval: Option<Duration>,
+    /// Maximum time for the whole command, creating the index and watching it.
+    ///
+    /// The command fails when the timeout expires, the index is not deleted and may still become ready. The format is a number followed by a unit, e.g. 30s or 5m. By default, there is no timeout.
+    #[arg(long, value_parser = super::parse_duration, conflicts_with = "all_collections")]
+    pub timeout: Option<Duration>,
+    /// Flag that indicates whether to stop watching once any of the indexes created with --allCollections is ready.
+    ///
+    /// The other indexes keep building, the result reports which indexes are ready and which are still building.
+    #[arg(long, requires = "watch", requires = "all_collections")]
+    pub watch_any: bool,
+    /// Flag that indicates whether to stop watching once all indexes created with --allCollections are ready.
+    ///
+    /// This is the default, use --watchAny to stop at the first ready index instead.
+    #[arg(
+        long,
+        requires = "watch",
+        requires = "all_collections",
+        conflicts_with = "watch_any"
+    )]
+    pub watch_all: bool,
+    /// Flag that indicates whether to succeed without changes when an index with the same name and definition already exists.
+    ///
+    /// When an index with the same name but a different definition exists, the command fails.
+    #[arg(long)]
+    pub idempotent: bool,
 
     /// Username for authenticating to MongoDB.
     #[arg(long = "username", requires = "password")]
@@ -52,6 +91,87 @@ pub struct Create {
     /// Name of the collection.
     #[arg(long, conflicts_with = "file")]
     pub collection: Option<String>,
+
+    /// Type of the search index.
+    ///
+    /// Valid values are search and vectorSearch. A vectorSearch index requires --path, --dimensions and --similarity.
+    #[arg(
+        long = "type",
+        value_enum,
+        default_value = "search",
+        conflicts_with = "file"
+    )]
+    pub index_type: IndexType,
+    /// Path of the field that contains the vectors, for vectorSearch indexes.
+    #[arg(
+        long,
+        required_if_eq("index_type", "vectorSearch"),
+        conflicts_with = "file"
+    )]
+    pub path: Option<String>,
+    /// Number of dimensions of the vectors, for vectorSearch indexes.
+    ///
+    /// Must be a positive integer.
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u32).range(1..),
+        required_if_eq("index_type", "vectorSearch"),
+        conflicts_with = "file"
+    )]
+    pub dimensions: Option<u32>,
+    /// Function used to measure the similarity of vectors, for vectorSearch indexes.
+    ///
+    /// Valid values are cosine, euclidean and dotProduct.
+    #[arg(
+        long,
+        value_enum,
+        required_if_eq("index_type", "vectorSearch"),
+        conflicts_with = "file"
+    )]
+    pub similarity: Option<VectorSimilarity>,
+
+    /// Flag that indicates whether to create the default dynamic search index on every collection of the database.
+    ///
+    /// Collections that already have a search index with the same name are skipped. When no index name is provided, the index is named default.
+    #[arg(long, requires = "database_name", conflicts_with_all = ["file", "collection", "idempotent", "path", "dimensions", "similarity"])]
+    pub all_collections: bool,
+    /// Names of the collections to skip when using --allCollections, separated by commas.
+    #[arg(long, value_delimiter = ',', requires = "all_collections")]
+    pub exclude: Vec<String>,
+    /// Maximum number of search indexes to create at the same time when using --allCollections.
+    ///
+    /// The results are reported in the order of the collections, a failure doesn't stop the other creations. The default is 1, which creates the indexes one after another.
+    #[arg(
+        long,
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u32).range(1..),
+        requires = "all_collections"
+    )]
+    pub concurrency: u32,
+
+    #[command(flatten)]
+    pub bulk: super::BulkArgs,
+}
+
+/// Type of search index to create from flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum IndexType {
+    #[default]
+    #[value(name = "search")]
+    Search,
+    #[value(name = "vectorSearch")]
+    VectorSearch,
+}
+
+/// Function used to measure the similarity of vectors in a vector search index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum VectorSimilarity {
+    #[value(name = "cosine")]
+    Cosine,
+    #[value(name = "euclidean")]
+    Euclidean,
+    #[value(name = "dotProduct")]
+    DotProduct,
 }
 
 /// List all Atlas Search indexes for a deployment.
@@ -75,9 +195,43 @@ pub struct List {
     /// Name of the collection.
     #[arg(long)]
     pub collection: Option<String>,
+
+    /// Status of the search indexes to list.
+    ///
+    /// The current status of every index is queried, only the indexes with this status are listed. Useful to find failed or stuck indexes.
+    ///
+    /// Valid values are building, deleting, doesNotExist, failed, pending, ready and stale.
+    #[arg(long, value_enum)]
+    pub status: Option<IndexStatus>,
+    /// Flag that indicates whether to list only the search indexes that are not ready.
+    ///
+    /// The current status of every index is queried, indexes that are building, pending, failed, deleting or stale are listed.
+    #[arg(long, conflicts_with = "status")]
+    pub not_ready: bool,
+}
+
+/// Status of a search index to filter on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum IndexStatus {
+    #[value(name = "building")]
+    Building,
+    #[value(name = "deleting")]
+    Deleting,
+    #[value(name = "doesNotExist")]
+    DoesNotExist,
+    #[value(name = "failed")]
+    Failed,
+    #[value(name = "pending")]
+    Pending,
+    #[value(name = "ready")]
+    Ready,
+    #[value(name = "stale")]
+    Stale,
 }
 
 /// Describe a search index for the specified deployment.
+///
+/// The status of the index is shown together with its latest definition.
 #[derive(Args)]
 #[command(rename_all = "camelCase")]
 pub struct Describe {
@@ -112,6 +266,11 @@ pub struct Delete {
     /// Flag that indicates whether to skip the confirmation prompt before proceeding with the requested action.
     #[arg(long)]
     pub force: bool,
+    /// Flag that indicates whether to watch the command until the index no longer exists.
+    ///
+    /// Search indexes are deleted in the background, without this flag the command completes while the index may still be deleting.
+    #[arg(long = "watch", short = 'w')]
+    pub watch: bool,
 
     /// Username for authenticating to MongoDB.
     #[arg(long = "username", requires = "password")]
@@ -127,3 +286,64 @@ pub struct Delete {
     #[arg(long)]
     pub collection: Option<String>,
 }
+
+/// Update a search index for the specified deployment.
+///
+/// The index is identified by the database, collectionName and name fields of the index configuration file, its definition is replaced with the definition of the file.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Update {
+    /// Name of the deployment.
+    #[arg(long)]
+    pub deployment_name: String,
+
+    /// Name of the JSON index configuration file to use.
+    ///
+    /// The file uses the same format as search indexes create --file. When the flag is omitted or set to -, the configuration is read from stdin.
+    #[arg(long)]
+    pub file: Option<String>,
+
+    /// Flag that indicates whether to watch the command until the index is ready with the new definition.
+    #[arg(long = "watch", short = 'w')]
+    pub watch: bool,
+
+    /// Username for authenticating to MongoDB.
+    #[arg(long = "username", requires = "password")]
+    pub username: Option<String>,
+    /// Password for authenticating to MongoDB.
+    #[arg(long = "password", requires = "username")]
+    pub password: Option<String>,
+}
+
+/// Watch a search index until it is ready.
+///
+/// Use this command to monitor an index build that was started elsewhere, e.g. by an application or by search indexes create without --watch.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Watch {
+    /// ID of the index.
+    #[arg(index = 1)]
+    pub index_id: Option<String>,
+
+    /// Name of the deployment.
+    #[arg(long)]
+    pub deployment_name: String,
+
+    /// Interval between index status checks.
+    ///
+    /// The format is a number followed by a unit, e.g. 500ms or 5s. The default is 1s.
+    #[arg(long, default_value = "1s", value_parser = super::parse_duration)]
+    pub interval: Duration,
+    /// Maximum time to watch the index.
+    ///
+    /// The command fails when the timeout expires, the index keeps building. The format is a number followed by a unit, e.g. 30s or 5m. By default, there is no timeout.
+    #[arg(long, value_parser = super::parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Username for authenticating to MongoDB.
+    #[arg(long = "username", requires = "password")]
+    pub username: Option<String>,
+    /// Password for authenticating to MongoDB.
+    #[arg(long = "password", requires = "username")]
+    pub password: Option<String>,
+}
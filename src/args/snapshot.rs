@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand)]
+#[command(about = "Manage snapshots of the data of deployments.")]
+pub enum Snapshot {
+    Create(Create),
+    #[command(alias = "ls")]
+    List(List),
+    #[command(alias = "rm")]
+    Delete(Delete),
+    Restore(Restore),
+}
+
+/// Save a point-in-time archive of the data volumes of a deployment.
+///
+/// A running deployment is locked for writes with fsyncLock while its volumes are archived, reads keep working. A stopped deployment is archived as it is. The ID of the snapshot is the time it was created, e.g. 20261016T120000Z.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Create {
+    /// Name of the deployment.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    #[command(flatten)]
+    pub snapshots_dir: SnapshotsDir,
+}
+
+/// List the saved snapshots, sorted by deployment and creation time.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct List {
+    /// Name of the deployment to list the snapshots of, the snapshots of all deployments are listed when omitted.
+    #[arg(index = 1)]
+    pub deployment_name: Option<String>,
+
+    #[command(flatten)]
+    pub snapshots_dir: SnapshotsDir,
+}
+
+/// Delete a saved snapshot.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Delete {
+    /// Name of the deployment the snapshot was created from.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// ID of the snapshot, as shown by snapshot list.
+    #[arg(long)]
+    pub snapshot: String,
+
+    #[command(flatten)]
+    pub snapshots_dir: SnapshotsDir,
+}
+
+/// Replace the data of a deployment with a saved snapshot.
+///
+/// The data volumes are replaced while the deployment is stopped, a running deployment is started again afterwards. The command prompts you to confirm the operation when you run the command without the --force option. With --into, the snapshot is restored into a new deployment with the configuration of the deployment saved in the snapshot instead, the deployment is left untouched and may have been deleted. Otherwise, the snapshot must have been created with the MongoDB version of the deployment.
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct Restore {
+    /// Name of the deployment the snapshot was created from.
+    #[arg(index = 1)]
+    pub deployment_name: String,
+
+    /// ID of the snapshot, as shown by snapshot list.
+    #[arg(long)]
+    pub snapshot: String,
+
+    /// Name of a new deployment to restore the snapshot into, on a free port.
+    #[arg(long)]
+    pub into: Option<String>,
+
+    /// Flag that indicates whether to skip the confirmation prompt before proceeding with the requested action.
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub snapshots_dir: SnapshotsDir,
+}
+
+#[derive(Args)]
+#[command(rename_all = "camelCase")]
+pub struct SnapshotsDir {
+    /// Directory where the snapshots are stored.
+    ///
+    /// Overrides the MONGODB_ATLAS_LOCAL_SNAPSHOTS_DIR environment variable. Defaults to the snapshots directory in the directory where the CLI saves its state, see --configDir.
+    #[arg(long)]
+    pub snapshots_dir: Option<PathBuf>,
+}
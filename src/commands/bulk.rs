@@ -0,0 +1,194 @@
+//! Shared execution of bulk operations.
+//!
+//! Bulk operations, like `delete --all`, `setup --fromFile` and `search indexes create --allCollections`, run an
+//! operation on many items where every item succeeds or fails on its own. This module implements the failure policy
+//! selected with `--failFast` and `--keepGoing` once, and the exit code that reports whether any item failed.
+
+use std::{future::Future, process::exit};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::{StreamExt, stream};
+
+use crate::{
+    args,
+    commands::{Command, CommandWithOutput},
+    formatting::{Format, Formattable},
+};
+
+/// Exit code used when any item of a bulk operation failed, with either failure policy.
+pub const BULK_FAILURE_EXIT_CODE: i32 = 1;
+
+/// What a bulk operation does when an item fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Continue with the other items and report every outcome, the default and `--keepGoing`.
+    #[default]
+    KeepGoing,
+    /// Stop at the first item that fails, the remaining items are not processed.
+    FailFast,
+}
+
+impl From<args::BulkArgs> for FailurePolicy {
+    fn from(args: args::BulkArgs) -> Self {
+        // --keepGoing is the default, clap rejects it together with --failFast
+        if args.fail_fast && !args.keep_going {
+            Self::FailFast
+        } else {
+            Self::KeepGoing
+        }
+    }
+}
+
+/// Outcome of a single item of a bulk operation.
+pub trait BulkItemResult {
+    /// Whether the operation failed for this item.
+    fn is_failure(&self) -> bool;
+}
+
+/// Run the operation on every item, up to `concurrency` items at the same time.
+///
+/// The results are in the order of the items. With [`FailurePolicy::FailFast`], no items are started after the first
+/// failure and the results stop at the failure, operations of later items that are already running are cancelled.
+pub async fn run_bulk<T, R, F, Fut>(
+    items: impl IntoIterator<Item = T>,
+    policy: FailurePolicy,
+    concurrency: usize,
+    operation: F,
+) -> Vec<R>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = R>,
+    R: BulkItemResult,
+{
+    // `buffered` keeps the results in the order of the items
+    let mut results_stream = stream::iter(items).map(operation).buffered(concurrency);
+
+    let mut results = Vec::new();
+    while let Some(result) = results_stream.next().await {
+        let failed = result.is_failure();
+        results.push(result);
+
+        if failed && policy == FailurePolicy::FailFast {
+            break;
+        }
+    }
+
+    results
+}
+
+/// Output of a bulk operation, with one result per item.
+pub trait BulkOutput: Formattable {
+    /// Whether the operation failed for any item.
+    fn has_failures(&self) -> bool;
+}
+
+/// Bulk command extensions trait.
+pub trait BulkCommandExt {
+    /// Convert the bulk command into a [`Command`] that prints the output to stdout, and exits with
+    /// [`BULK_FAILURE_EXIT_CODE`] when any item failed.
+    fn with_print_to_stdout_and_exit_code(self, format: Format) -> Result<Box<dyn Command>>;
+}
+
+/// Wrapper command that prints the output of a bulk command, and reports failed items with the exit code.
+pub struct PrintBulkOutputCommand<C> {
+    command: C,
+    format: Format,
+}
+
+#[async_trait]
+impl<C, O> Command for PrintBulkOutputCommand<C>
+where
+    C: CommandWithOutput<Output = O> + Send,
+    O: BulkOutput,
+{
+    async fn execute(&mut self) -> Result<()> {
+        let output = self.command.execute().await?;
+        println!("{}", output.format(self.format)?);
+
+        // The outcome of every item is printed, so a failed item is not an error but it still fails the command
+        if output.has_failures() {
+            exit(BULK_FAILURE_EXIT_CODE);
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, O> BulkCommandExt for C
+where
+    C: CommandWithOutput<Output = O> + Send + 'static,
+    O: BulkOutput + 'static,
+{
+    fn with_print_to_stdout_and_exit_code(self, format: Format) -> Result<Box<dyn Command>> {
+        Ok(Box::new(PrintBulkOutputCommand {
+            command: self,
+            format,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    impl BulkItemResult for Result<u32, String> {
+        fn is_failure(&self) -> bool {
+            self.is_err()
+        }
+    }
+
+    async fn divide_by(
+        items: Vec<u32>,
+        policy: FailurePolicy,
+    ) -> (Vec<Result<u32, String>>, usize) {
+        let calls = AtomicUsize::new(0);
+        let results = run_bulk(items, policy, 1, |item| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                100u32
+                    .checked_div(item)
+                    .ok_or_else(|| "division by zero".to_string())
+            }
+        })
+        .await;
+
+        (results, calls.into_inner())
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk_keep_going() {
+        let (results, calls) = divide_by(vec![10, 0, 50], FailurePolicy::KeepGoing).await;
+
+        assert_eq!(
+            results,
+            vec![Ok(10), Err("division by zero".to_string()), Ok(2)]
+        );
+        assert_eq!(calls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk_fail_fast() {
+        let (results, calls) = divide_by(vec![10, 0, 50], FailurePolicy::FailFast).await;
+
+        assert_eq!(results, vec![Ok(10), Err("division by zero".to_string())]);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_failure_policy_from_args() {
+        assert_eq!(
+            FailurePolicy::from(args::BulkArgs::default()),
+            FailurePolicy::KeepGoing
+        );
+        assert_eq!(
+            FailurePolicy::from(args::BulkArgs {
+                fail_fast: true,
+                keep_going: false,
+            }),
+            FailurePolicy::FailFast
+        );
+    }
+}
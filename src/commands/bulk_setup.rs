@@ -0,0 +1,527 @@
+//! Command to set up multiple deployments at once.
+//!
+//! This module implements `setup --fromFile`, which reads a list of deployment specs from a TOML file.
+//! Every distinct image is pulled once up front, after which all deployments are created without pulling the image again.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{
+    Client, CreateDeploymentError,
+    models::{BindingType, CreateDeploymentOptions, CreationSource, ImageTag, MongoDBPortBinding},
+};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        bulk::{BulkItemResult, BulkOutput, FailurePolicy, run_bulk},
+        setup::{DEFAULT_IMAGE, DEFAULT_IMAGE_TAG, SetupResult},
+    },
+    dependencies::{DeploymentCreator, DeploymentImagePuller, FileReader, TokioFs, connect_docker},
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+    settings::Settings,
+};
+
+// Interaction dependencies for the bulk setup command
+pub trait BulkSetupInteraction: SpinnerInteraction + Send + Sync {}
+impl<T: SpinnerInteraction + Send + Sync> BulkSetupInteraction for T {}
+
+/// Command to set up all deployments listed in a file.
+pub struct BulkSetup {
+    file: PathBuf,
+    failure_policy: FailurePolicy,
+
+    interaction: Box<dyn BulkSetupInteraction>,
+    file_reader: Box<dyn FileReader + Send + Sync>,
+    image_puller: Box<dyn DeploymentImagePuller + Send + Sync>,
+    deployment_creator: Box<dyn DeploymentCreator + Send + Sync>,
+}
+
+impl TryFrom<(args::Setup, &Settings)> for BulkSetup {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Setup, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            // Clap only dispatches to the bulk setup when --fromFile is set
+            file: args.from_file.unwrap_or_default(),
+            failure_policy: args.bulk.into(),
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            file_reader: Box::new(TokioFs::new()),
+            image_puller: Box::new(docker.clone()),
+            deployment_creator: Box::new(Client::new(docker)),
+        })
+    }
+}
+
+/// File listing the deployments to set up.
+///
+/// ```toml
+/// [[deployments]]
+/// name = "orders"
+/// imageTag = "8.2"
+/// port = 27018
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) struct DeploymentsFile {
+    pub(crate) deployments: Vec<DeploymentSpec>,
+}
+
+/// A single deployment to set up, the fields mirror the flags of the setup command.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) struct DeploymentSpec {
+    pub(crate) name: String,
+    #[serde(alias = "mdbVersion")]
+    pub(crate) image_tag: Option<String>,
+    pub(crate) port: Option<u16>,
+    #[serde(default)]
+    pub(crate) bind_ip_all: bool,
+    pub(crate) initdb: Option<PathBuf>,
+    pub(crate) load_sample_data: Option<bool>,
+    pub(crate) username: Option<String>,
+    pub(crate) password: Option<String>,
+    pub(crate) image: Option<String>,
+}
+
+impl DeploymentSpec {
+    /// The image and tag the deployment is created from.
+    fn image_reference(&self) -> (String, String) {
+        (
+            self.image
+                .clone()
+                .unwrap_or_else(|| DEFAULT_IMAGE.to_string()),
+            self.image_tag
+                .clone()
+                .unwrap_or_else(|| DEFAULT_IMAGE_TAG.to_string()),
+        )
+    }
+}
+
+/// Result of the bulk setup command, one setup result per deployment in the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BulkSetupResult(Vec<SetupResult>);
+
+impl Display for BulkSetupResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, result) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", result.to_string().trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+// Errors are not outcomes of a deployment, they fail the whole command
+impl BulkItemResult for Result<SetupResult> {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Ok(SetupResult::Failed { .. } | SetupResult::Canceled { .. }) | Err(_)
+        )
+    }
+}
+
+impl BulkOutput for BulkSetupResult {
+    fn has_failures(&self) -> bool {
+        self.0.iter().any(|result| {
+            matches!(
+                result,
+                SetupResult::Failed { .. } | SetupResult::Canceled { .. }
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for BulkSetup {
+    type Output = BulkSetupResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let deployments_file = read_deployments_file(self.file_reader.as_ref(), &self.file).await?;
+
+        // Pull every distinct image once, instead of once per deployment
+        let mut pull_results: HashMap<(String, String), Result<(), String>> = HashMap::new();
+        for spec in &deployments_file.deployments {
+            let image_reference = spec.image_reference();
+            if pull_results.contains_key(&image_reference) {
+                continue;
+            }
+
+            let (image, tag) = &image_reference;
+            let _pull_spinner = self.interaction.start_spinner(
+                message(Message::PullImage).replace("{image}", &format!("{image}:{tag}")),
+            )?;
+
+            debug!(image, tag, "pulling image");
+            let pull_result = self
+                .image_puller
+                .pull_image(image, tag)
+                .await
+                .map_err(|e| e.to_string());
+            pull_results.insert(image_reference, pull_result);
+        }
+
+        // By default every deployment gets its own outcome, with --failFast the setup stops at the first failure
+        let this = &*self;
+        let pull_results = &pull_results;
+        let results = run_bulk(
+            deployments_file.deployments,
+            self.failure_policy,
+            1,
+            |spec| async move {
+                let image_reference = spec.image_reference();
+                match pull_results.get(&image_reference) {
+                    Some(Err(error)) => Ok(SetupResult::Failed {
+                        deployment_name: Some(spec.name),
+                        error: format!(
+                            "pulling image {}:{} failed: {error}",
+                            image_reference.0, image_reference.1
+                        ),
+                    }),
+                    _ => this.setup_deployment(spec).await,
+                }
+            },
+        )
+        .await;
+
+        Ok(BulkSetupResult(results.into_iter().collect::<Result<_>>()?))
+    }
+}
+
+/// Read the file listing the deployments, the diff command compares a deployment against the same file.
+pub(crate) async fn read_deployments_file(
+    file_reader: &(dyn FileReader + Send + Sync),
+    path: &Path,
+) -> Result<DeploymentsFile> {
+    let contents = file_reader
+        .read_to_string(path)
+        .await
+        .with_context(|| format!("reading deployments file {}", path.display()))?;
+
+    toml::from_str(&contents)
+        .with_context(|| format!("parsing deployments file {}", path.display()))
+}
+
+impl BulkSetup {
+    /// Create a single deployment, the image is expected to be pulled already.
+    async fn setup_deployment(&self, spec: DeploymentSpec) -> Result<SetupResult> {
+        let image_tag = match spec
+            .image_tag
+            .as_deref()
+            .map(ImageTag::try_from)
+            .transpose()
+        {
+            Ok(image_tag) => image_tag,
+            Err(e) => {
+                return Ok(SetupResult::Failed {
+                    deployment_name: Some(spec.name),
+                    error: format!("invalid image tag: {e}"),
+                });
+            }
+        };
+
+        let create_deployment_options = CreateDeploymentOptions {
+            name: Some(spec.name.clone()),
+            image_tag,
+            creation_source: Some(CreationSource::AtlasLocal),
+            wait_until_healthy: Some(true),
+            local_seed_location: spec
+                .initdb
+                .as_ref()
+                .map(|path| path.to_string_lossy().to_string()),
+            mongodb_initdb_root_username: spec.username,
+            mongodb_initdb_root_password: spec.password,
+            load_sample_data: spec.load_sample_data,
+            mongodb_port_binding: Some(MongoDBPortBinding::new(
+                spec.port,
+                if spec.bind_ip_all {
+                    BindingType::AnyInterface
+                } else {
+                    BindingType::Loopback
+                },
+            )),
+            image: spec.image,
+            skip_pull_image: Some(true),
+            ..Default::default()
+        };
+
+        let _create_spinner = self
+            .interaction
+            .start_spinner(message(Message::CreateNamedDeployment).replace("{name}", &spec.name))?;
+
+        match self
+            .deployment_creator
+            .create_deployment(create_deployment_options)
+            .wait_for_deployment_outcome()
+            .await
+        {
+            Ok(deployment) => Ok(SetupResult::Setup {
+                deployment_name: deployment.name.clone().unwrap_or(spec.name),
+                mongodb_version: deployment.mongodb_version,
+                port: deployment
+                    .port_bindings
+                    .as_ref()
+                    .and_then(|bindings| bindings.port)
+                    .unwrap_or(0),
+                load_sample_data: deployment.mongodb_load_sample_data.unwrap_or(false),
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: None,
+            }),
+            Err(CreateDeploymentError::ReceiveDeployment(error)) => {
+                Err(error).context("receiving deployment outcome")
+            }
+            Err(e) => Ok(SetupResult::Failed {
+                deployment_name: Some(spec.name),
+                error: e.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::create_progress;
+    use crate::dependencies::mocks::{MockDocker, MockTokioFs};
+    use crate::interaction::SpinnerHandle;
+    use crate::interaction::mocks::MockInteraction;
+    use atlas_local::models::{Deployment, MongodbType, State};
+    use semver::Version;
+
+    const DEPLOYMENTS_FILE: &str = r#"
+[[deployments]]
+name = "orders"
+imageTag = "8.2.2"
+port = 27018
+
+[[deployments]]
+name = "users"
+imageTag = "8.2.2"
+loadSampleData = true
+"#;
+
+    fn create_deployment(options: &CreateDeploymentOptions) -> Deployment {
+        Deployment {
+            name: options.name.clone(),
+            container_id: "container-id".to_string(),
+            mongodb_version: Version::parse("8.2.2").unwrap(),
+            state: State::Running,
+            port_bindings: Some(MongoDBPortBinding::new(
+                options
+                    .mongodb_port_binding
+                    .as_ref()
+                    .and_then(|binding| binding.port)
+                    .or(Some(27017)),
+                BindingType::Loopback,
+            )),
+            mongodb_type: MongodbType::Community,
+            creation_source: Some(CreationSource::AtlasLocal),
+            local_seed_location: None,
+            mongodb_initdb_database: None,
+            mongodb_initdb_root_password_file: None,
+            mongodb_initdb_root_password: None,
+            mongodb_initdb_root_username_file: None,
+            mongodb_initdb_root_username: None,
+            mongodb_load_sample_data: options.load_sample_data,
+            mongot_log_file: None,
+            runner_log_file: None,
+            do_not_track: true,
+            telemetry_base_url: None,
+            voyage_api_key: None,
+        }
+    }
+
+    fn create_interaction() -> MockInteraction {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+        mock_interaction
+    }
+
+    fn create_file_reader(contents: &'static str) -> MockTokioFs {
+        let mut mock_file_reader = MockTokioFs::new();
+        mock_file_reader
+            .expect_read_to_string()
+            .withf(|path| path == Path::new("deployments.toml"))
+            .return_once(move |_| Ok(contents.to_string()));
+        mock_file_reader
+    }
+
+    fn create_bulk_setup_command(
+        file_reader: MockTokioFs,
+        image_puller: MockDocker,
+        deployment_creator: MockDocker,
+    ) -> BulkSetup {
+        BulkSetup {
+            file: PathBuf::from("deployments.toml"),
+            failure_policy: FailurePolicy::KeepGoing,
+            interaction: Box::new(create_interaction()),
+            file_reader: Box::new(file_reader),
+            image_puller: Box::new(image_puller),
+            deployment_creator: Box::new(deployment_creator),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_setup_pulls_shared_image_once() {
+        let mut mock_image_puller = MockDocker::new();
+        mock_image_puller
+            .expect_pull_image()
+            .times(1)
+            .withf(|image, tag| image == DEFAULT_IMAGE && tag == "8.2.2")
+            .returning(|_, _| Ok(()));
+
+        let mut mock_deployment_creator = MockDocker::new();
+        mock_deployment_creator
+            .expect_create_deployment()
+            .times(2)
+            .withf(|options| options.skip_pull_image == Some(true))
+            .returning(|options| {
+                let deployment = create_deployment(&options);
+                create_progress(Ok(deployment))
+            });
+
+        let mut bulk_setup_command = create_bulk_setup_command(
+            create_file_reader(DEPLOYMENTS_FILE),
+            mock_image_puller,
+            mock_deployment_creator,
+        );
+
+        let result = bulk_setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            BulkSetupResult(vec![
+                SetupResult::Setup {
+                    deployment_name: "orders".to_string(),
+                    mongodb_version: Version::parse("8.2.2").unwrap(),
+                    port: 27018,
+                    load_sample_data: false,
+                    resumed: false,
+                    image: None,
+                    warnings: vec![],
+                    connect_result: None,
+                },
+                SetupResult::Setup {
+                    deployment_name: "users".to_string(),
+                    mongodb_version: Version::parse("8.2.2").unwrap(),
+                    port: 27017,
+                    load_sample_data: true,
+                    resumed: false,
+                    image: None,
+                    warnings: vec![],
+                    connect_result: None,
+                },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_setup_pull_failure_fails_deployments_using_the_image() {
+        let mut mock_image_puller = MockDocker::new();
+        mock_image_puller.expect_pull_image().returning(|_, _| {
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404,
+                message: "manifest unknown".to_string(),
+            })
+        });
+
+        let mut mock_deployment_creator = MockDocker::new();
+        mock_deployment_creator.expect_create_deployment().never();
+
+        let mut bulk_setup_command = create_bulk_setup_command(
+            create_file_reader(DEPLOYMENTS_FILE),
+            mock_image_puller,
+            mock_deployment_creator,
+        );
+
+        let result = bulk_setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(result.0.len(), 2);
+        for setup_result in result.0 {
+            let SetupResult::Failed { error, .. } = setup_result else {
+                panic!("expected a failed result, got {setup_result:?}");
+            };
+            assert!(
+                error.starts_with("pulling image mongodb/mongodb-atlas-local:8.2.2 failed"),
+                "unexpected error: {error}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_setup_invalid_file() {
+        let mut bulk_setup_command = create_bulk_setup_command(
+            create_file_reader("[[deployments]]\nport = 27017\n"),
+            MockDocker::new(),
+            MockDocker::new(),
+        );
+
+        let error = bulk_setup_command
+            .execute()
+            .await
+            .expect_err("execute should fail");
+
+        assert!(
+            error
+                .to_string()
+                .contains("parsing deployments file deployments.toml")
+        );
+    }
+
+    #[test]
+    fn test_from_file_conflicts_with_deployment_flags() {
+        use clap::Parser;
+
+        for flags in [
+            vec!["--port", "27018"],
+            vec!["--mdbVersion", "8.2"],
+            vec!["--username", "admin"],
+            vec!["--connectWith", "mongosh"],
+            vec!["--waitForPrimary"],
+            vec!["--defaultDb", "shop"],
+        ] {
+            let args = ["setup", "--fromFile", "deployments.toml"]
+                .into_iter()
+                .chain(flags.iter().copied());
+
+            assert!(
+                crate::args::Setup::try_parse_from(args).is_err(),
+                "--fromFile should conflict with {flags:?}"
+            );
+        }
+
+        let args = crate::args::Setup::try_parse_from([
+            "setup",
+            "--fromFile",
+            "deployments.toml",
+            "--failFast",
+        ])
+        .expect("bulk flags are allowed with --fromFile");
+        assert!(args.bulk.fail_fast);
+    }
+}
@@ -0,0 +1,328 @@
+//! Command to report the capabilities of a deployment.
+//!
+//! This module implements the `capabilities` command, a machine-readable report of what a deployment supports for
+//! tools that integrate with local deployments. The report is derived from the settings of the deployment, with
+//! `--deep` the deployment is also queried with the MongoDB driver. Use `inspect` for the human readable details.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{
+    Client,
+    models::{Deployment, MongodbType, State},
+};
+use semver::Version;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{CommandWithOutput, with_mongodb::LocalDeploymentCapabilityProber},
+    dependencies::{
+        CapabilityProber, DeploymentGetDeployment, ProbedCapabilities, connect_docker,
+        find_deployment,
+    },
+    settings::Settings,
+};
+
+/// Command to report the capabilities of a deployment.
+pub struct Capabilities {
+    deployment_name: String,
+    deep: bool,
+
+    deployment_getter: Box<dyn DeploymentGetDeployment + Send + Sync>,
+    capability_prober: Box<dyn CapabilityProber + Send + Sync>,
+}
+
+impl TryFrom<(args::Capabilities, &Settings)> for Capabilities {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Capabilities, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            deep: args.deep,
+
+            deployment_getter: Box::new(Client::new(docker)),
+            capability_prober: Box::new(LocalDeploymentCapabilityProber::new(
+                settings.docker.clone(),
+            )),
+        })
+    }
+}
+
+/// Result of the capabilities command.
+///
+/// With `--output json` the capabilities are top-level fields:
+/// `{"outcome":"capabilities","deployment_name":"local1","mongodb_version":"8.2.2","search":true,"vector_search":true,"auth":false,...}`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum CapabilitiesResult {
+    Capabilities {
+        deployment_name: String,
+        #[serde(flatten)]
+        capabilities: DeploymentCapabilities,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+/// What a deployment supports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeploymentCapabilities {
+    pub mongodb_version: Version,
+    pub enterprise: bool,
+    pub search: bool,
+    pub vector_search: bool,
+    /// Whether a Voyage AI API key is set, which is needed to generate embeddings.
+    pub auto_embedding: bool,
+    pub sample_data: bool,
+    pub auth: bool,
+    /// Whether the version and search support were confirmed by querying the deployment, with `--deep`.
+    pub probed: bool,
+}
+
+impl DeploymentCapabilities {
+    fn new(deployment: &Deployment) -> Self {
+        // The deployment only configures mongot, the search process, when search is enabled
+        let search = deployment.mongot_log_file.is_some();
+
+        Self {
+            mongodb_version: deployment.mongodb_version.clone(),
+            enterprise: matches!(deployment.mongodb_type, MongodbType::Enterprise),
+            search,
+            vector_search: search && supports_vector_search(&deployment.mongodb_version),
+            auto_embedding: deployment.voyage_api_key.is_some(),
+            sample_data: deployment.mongodb_load_sample_data == Some(true),
+            auth: deployment.mongodb_initdb_root_username.is_some()
+                || deployment.mongodb_initdb_root_username_file.is_some(),
+            probed: false,
+        }
+    }
+
+    /// Replace the capabilities derived from the settings with the ones reported by the deployment.
+    fn with_probed(self, probed: ProbedCapabilities) -> Self {
+        Self {
+            vector_search: probed.search && supports_vector_search(&probed.mongodb_version),
+            mongodb_version: probed.mongodb_version,
+            search: probed.search,
+            probed: true,
+            ..self
+        }
+    }
+}
+
+/// Whether the MongoDB version supports `$vectorSearch`, which was added in 6.0.11 and 7.0.2.
+fn supports_vector_search(version: &Version) -> bool {
+    match version.major {
+        6 => *version >= Version::new(6, 0, 11),
+        7 => *version >= Version::new(7, 0, 2),
+        major => major > 7,
+    }
+}
+
+impl Display for CapabilitiesResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Capabilities {
+                deployment_name,
+                capabilities,
+            } => {
+                writeln!(f, "Deployment: {deployment_name}")?;
+                writeln!(f, "MongoDB version: {}", capabilities.mongodb_version)?;
+                writeln!(f, "Enterprise: {}", capabilities.enterprise)?;
+                writeln!(f, "Search: {}", capabilities.search)?;
+                writeln!(f, "Vector search: {}", capabilities.vector_search)?;
+                writeln!(f, "Auto embedding: {}", capabilities.auto_embedding)?;
+                writeln!(f, "Sample data: {}", capabilities.sample_data)?;
+                writeln!(f, "Auth: {}", capabilities.auth)?;
+                write!(f, "Probed: {}", capabilities.probed)
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Getting the capabilities of deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Capabilities {
+    type Output = CapabilitiesResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let Some(deployment) =
+            find_deployment(self.deployment_getter.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found"));
+        };
+
+        let mut capabilities = DeploymentCapabilities::new(&deployment);
+
+        if self.deep {
+            // Only a running deployment can be queried, --deep never changes the state of the deployment
+            if !matches!(deployment.state, State::Running) {
+                return Ok(
+                    self.failed("deployment is not running, --deep requires a running deployment")
+                );
+            }
+
+            let probed = self
+                .capability_prober
+                .probe_capabilities(&self.deployment_name)
+                .await
+                .context("querying the deployment")?;
+            capabilities = capabilities.with_probed(probed);
+        }
+
+        Ok(CapabilitiesResult::Capabilities {
+            deployment_name: self.deployment_name.clone(),
+            capabilities,
+        })
+    }
+}
+
+impl Capabilities {
+    fn failed(&self, error: &str) -> CapabilitiesResult {
+        CapabilitiesResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support;
+    use crate::dependencies::mocks::{MockDocker, MockMongoDB};
+
+    fn create_deployment(state: State) -> Deployment {
+        Deployment {
+            mongodb_initdb_root_password: Some("secret-password".to_string()),
+            mongodb_initdb_root_username: Some("admin".to_string()),
+            mongodb_load_sample_data: Some(true),
+            mongot_log_file: Some("/dev/stdout".to_string()),
+            ..test_support::create_deployment("test-deployment", state)
+        }
+    }
+
+    fn create_capabilities_command(
+        deep: bool,
+        deployment_getter: MockDocker,
+        capability_prober: MockMongoDB,
+    ) -> Capabilities {
+        Capabilities {
+            deployment_name: "test-deployment".to_string(),
+            deep,
+            deployment_getter: Box::new(deployment_getter),
+            capability_prober: Box::new(capability_prober),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_from_deployment_settings() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter
+            .expect_get_deployment()
+            .withf(|name| name == "test-deployment")
+            .return_once(|_| Ok(create_deployment(State::Running)));
+
+        let mut mock_prober = MockMongoDB::new();
+        mock_prober.expect_probe_capabilities().never();
+
+        let result = create_capabilities_command(false, mock_getter, mock_prober)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CapabilitiesResult::Capabilities {
+                deployment_name: "test-deployment".to_string(),
+                capabilities: DeploymentCapabilities {
+                    mongodb_version: Version::parse("8.2.2").unwrap(),
+                    enterprise: false,
+                    search: true,
+                    vector_search: true,
+                    auto_embedding: false,
+                    sample_data: true,
+                    auth: true,
+                    probed: false,
+                },
+            }
+        );
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["outcome"], "capabilities");
+        assert_eq!(json["vector_search"], true);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_deep_uses_probed_capabilities() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter
+            .expect_get_deployment()
+            .return_once(|_| Ok(create_deployment(State::Running)));
+
+        let mut mock_prober = MockMongoDB::new();
+        mock_prober
+            .expect_probe_capabilities()
+            .withf(|name| name == "test-deployment")
+            .return_once(|_| {
+                Ok(ProbedCapabilities {
+                    mongodb_version: Version::parse("7.0.1").unwrap(),
+                    search: true,
+                })
+            });
+
+        let result = create_capabilities_command(true, mock_getter, mock_prober)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let CapabilitiesResult::Capabilities { capabilities, .. } = result else {
+            panic!("expected capabilities, got {result:?}");
+        };
+        assert_eq!(
+            capabilities.mongodb_version,
+            Version::parse("7.0.1").unwrap()
+        );
+        assert!(capabilities.search);
+        // $vectorSearch is only supported from 7.0.2
+        assert!(!capabilities.vector_search);
+        assert!(capabilities.probed);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_deep_requires_running_deployment() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter
+            .expect_get_deployment()
+            .return_once(|_| Ok(create_deployment(State::Exited)));
+
+        let mut mock_prober = MockMongoDB::new();
+        mock_prober.expect_probe_capabilities().never();
+
+        let result = create_capabilities_command(true, mock_getter, mock_prober)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CapabilitiesResult::Failed {
+                deployment_name: "test-deployment".to_string(),
+                error: "deployment is not running, --deep requires a running deployment"
+                    .to_string(),
+            }
+        );
+    }
+}
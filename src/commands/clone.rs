@@ -0,0 +1,763 @@
+//! Command to create a copy of a deployment.
+//!
+//! The new deployment is created with the configuration of the source deployment on a free port. Its data volumes are
+//! then replaced with copies of the data volumes of the source deployment. Both deployments are stopped while the
+//! volumes are copied, so MongoDB doesn't write to the files that are copied. The source deployment is put back in
+//! its state afterwards, also when copying fails, and a new deployment that could not be copied into is deleted.
+
+use std::{collections::HashMap, fmt::Display};
+
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use atlas_local::{
+    Client,
+    models::{CreateDeploymentOptions, State},
+};
+use bollard::models::{ContainerInspectResponse, MountPointTypeEnum};
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use crate::{
+    args,
+    commands::{CommandWithOutput, deployment_config::DeploymentConfig},
+    dependencies::{
+        DeploymentContainerInspector, DeploymentCreator, DeploymentDeleter,
+        DeploymentGetDeployment, DeploymentPauser, DeploymentStarter, DeploymentStopper,
+        DockerError, VolumeCopier, connect_docker, find_deployment,
+    },
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+    settings::Settings,
+};
+
+// Deployment dependencies for the clone command
+pub trait CloneDeploymentManagement:
+    DeploymentGetDeployment
+    + DeploymentCreator
+    + DeploymentDeleter
+    + DeploymentStarter
+    + DeploymentStopper
+{
+}
+impl<
+    T: DeploymentGetDeployment
+        + DeploymentCreator
+        + DeploymentDeleter
+        + DeploymentStarter
+        + DeploymentStopper,
+> CloneDeploymentManagement for T
+{
+}
+
+// Docker dependencies to copy the data volumes, and to pause a paused source deployment again
+pub trait CloneVolumeManagement:
+    DeploymentContainerInspector + VolumeCopier + DeploymentPauser
+{
+}
+impl<T: DeploymentContainerInspector + VolumeCopier + DeploymentPauser> CloneVolumeManagement
+    for T
+{
+}
+
+pub struct CloneDeployment {
+    source_deployment_name: String,
+    deployment_name: String,
+
+    interaction: Box<dyn SpinnerInteraction + Send>,
+    deployment_management: Box<dyn CloneDeploymentManagement + Send + Sync>,
+    volume_management: Box<dyn CloneVolumeManagement + Send + Sync>,
+}
+
+impl TryFrom<(args::CloneDeployment, &Settings)> for CloneDeployment {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::CloneDeployment, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            source_deployment_name: args.deployment_name,
+            deployment_name: args.target_deployment_name,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker.clone())),
+            volume_management: Box::new(docker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum CloneResult {
+    Cloned {
+        source_deployment_name: String,
+        deployment_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for CloneResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cloned {
+                source_deployment_name,
+                deployment_name,
+                port,
+            } => {
+                write!(
+                    f,
+                    "Deployment '{}' cloned from '{}'",
+                    deployment_name, source_deployment_name
+                )?;
+                if let Some(port) = port {
+                    write!(f, " on port {}", port)?;
+                }
+                Ok(())
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => {
+                write!(
+                    f,
+                    "Cloning deployment '{}' failed: {}",
+                    deployment_name, error
+                )
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for CloneDeployment {
+    type Output = CloneResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(
+            source_deployment_name = ?self.source_deployment_name,
+            deployment_name = ?self.deployment_name,
+            "executing clone command"
+        );
+
+        // When spinner goes out of scope, the spinner will be stopped
+        let _spinner = self
+            .interaction
+            .start_spinner(message(Message::CloneDeployment).to_string())?;
+
+        let Some(source) = find_deployment(
+            self.deployment_management.as_ref(),
+            &self.source_deployment_name,
+        )
+        .await?
+        else {
+            return Ok(self.failed(format!(
+                "deployment '{}' not found",
+                self.source_deployment_name
+            )));
+        };
+
+        if find_deployment(self.deployment_management.as_ref(), &self.deployment_name)
+            .await?
+            .is_some()
+        {
+            return Ok(self.failed(format!(
+                "deployment '{}' already exists",
+                self.deployment_name
+            )));
+        }
+
+        // The new deployment is created from the image of the source container
+        let image = self
+            .inspect_container(&self.source_deployment_name)
+            .await?
+            .config
+            .and_then(|config| config.image);
+        let create_deployment_options =
+            copy_deployment_options(DeploymentConfig::new(&source, image), &self.deployment_name)?;
+
+        debug!("creating the new deployment");
+
+        if let Err(e) = self
+            .deployment_management
+            .create_deployment(create_deployment_options)
+            .wait_for_deployment_outcome()
+            .await
+        {
+            return Ok(self.failed(format!("failed to create the deployment: {e}")));
+        }
+
+        if let Err(e) = self.copy_deployment(&source.state).await {
+            let mut error = format!("failed to copy the data volumes: {e:#}");
+            // The new deployment has no data of the source, so it is not kept
+            debug!("deleting the new deployment");
+            if let Err(e) = self
+                .deployment_management
+                .delete(&self.deployment_name)
+                .await
+            {
+                error.push_str(&format!(
+                    ", the new deployment '{}' could not be deleted: {e}",
+                    self.deployment_name
+                ));
+            }
+            return Ok(self.failed(error));
+        }
+
+        self.deployment_management
+            .start(&self.deployment_name)
+            .await
+            .context("starting the new deployment")?;
+
+        // The free port is picked by Docker when the deployment starts
+        let port = find_deployment(self.deployment_management.as_ref(), &self.deployment_name)
+            .await?
+            .and_then(|deployment| deployment.port_bindings)
+            .and_then(|binding| binding.port);
+
+        Ok(CloneResult::Cloned {
+            source_deployment_name: self.source_deployment_name.clone(),
+            deployment_name: self.deployment_name.clone(),
+            port,
+        })
+    }
+}
+
+impl CloneDeployment {
+    fn failed(&self, error: String) -> CloneResult {
+        CloneResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+
+    /// Copy the data volumes of the source deployment into the new deployment, which is left stopped.
+    ///
+    /// Both deployments are stopped while the volumes are copied. The source deployment is put back in its state
+    /// afterwards, also when stopping it or copying the volumes failed.
+    async fn copy_deployment(&self, source_state: &State) -> Result<()> {
+        // The volumes are only copied while no MongoDB process uses them
+        self.deployment_management
+            .stop(&self.deployment_name)
+            .await
+            .context("stopping the new deployment")?;
+
+        if !matches!(
+            source_state,
+            State::Running | State::Restarting | State::Paused
+        ) {
+            return self.copy_volumes().await;
+        }
+
+        debug!("stopping the source deployment while its volumes are copied");
+        let copy_result = match self
+            .deployment_management
+            .stop(&self.source_deployment_name)
+            .await
+            .context("stopping the source deployment")
+        {
+            Ok(()) => self.copy_volumes().await,
+            Err(e) => Err(e),
+        };
+
+        match (copy_result, self.restore_source_state(source_state).await) {
+            (Ok(()), restore_result) => restore_result,
+            (Err(e), Ok(())) => Err(e),
+            (Err(e), Err(restore_error)) => Err(anyhow!(
+                "{e:#}, and the source deployment could not be restored: {restore_error:#}"
+            )),
+        }
+    }
+
+    /// Start the source deployment again after its volumes were copied, a paused deployment is paused again.
+    async fn restore_source_state(&self, source_state: &State) -> Result<()> {
+        self.deployment_management
+            .start(&self.source_deployment_name)
+            .await
+            .context("starting the source deployment")?;
+
+        if matches!(source_state, State::Paused) {
+            self.volume_management
+                .pause(&self.source_deployment_name)
+                .await
+                .map_err(DockerError::from)
+                .context("pausing the source deployment")?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the data volumes of the new deployment with copies of the volumes of the source deployment.
+    ///
+    /// The volumes are matched on the path they are mounted on in the container, e.g. /data/db.
+    async fn copy_volumes(&self) -> Result<()> {
+        let source = self.inspect_container(&self.source_deployment_name).await?;
+        let target = self.inspect_container(&self.deployment_name).await?;
+
+        // The helper container runs the image of the new deployment, it is pulled already
+        let image = target
+            .config
+            .as_ref()
+            .and_then(|config| config.image.clone())
+            .context("the container of the new deployment has no image")?;
+
+        let target_volumes = volumes_by_destination(&target);
+        for (destination, source_volume) in volumes_by_destination(&source) {
+            let Some(target_volume) = target_volumes.get(&destination) else {
+                debug!(
+                    destination,
+                    "the new deployment has no volume for the path, skipping"
+                );
+                continue;
+            };
+
+            debug!(destination, source_volume, target_volume, "copying volume");
+            self.volume_management
+                .copy_volume(&image, &source_volume, target_volume)
+                .await
+                .map_err(DockerError::from)
+                .with_context(|| format!("copying the volume mounted on {destination}"))?;
+        }
+
+        Ok(())
+    }
+
+    async fn inspect_container(&self, deployment_name: &str) -> Result<ContainerInspectResponse> {
+        self.volume_management
+            .inspect_container_raw(deployment_name)
+            .await
+            .map_err(DockerError::from)
+            .with_context(|| format!("inspecting deployment '{deployment_name}'"))?
+            .with_context(|| format!("deployment '{deployment_name}' not found"))
+    }
+}
+
+/// Options to create a new deployment with the configuration of the source deployment.
+pub(super) fn copy_deployment_options(
+    mut config: DeploymentConfig,
+    deployment_name: &str,
+) -> Result<CreateDeploymentOptions> {
+    // The source deployment keeps its port, the copy gets a free port of the same interface
+    config.port = None;
+    config.create_deployment_options(deployment_name)
+}
+
+/// The names of the volumes of a container, by the path they are mounted on.
+pub(super) fn volumes_by_destination(
+    container: &ContainerInspectResponse,
+) -> HashMap<String, String> {
+    container
+        .mounts
+        .iter()
+        .flatten()
+        .filter(|mount| mount.typ == Some(MountPointTypeEnum::VOLUME))
+        .filter_map(|mount| Some((mount.destination.clone()?, mount.name.clone()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{self, create_spinner_handle, create_successful_progress};
+    use crate::dependencies::mocks::MockDocker;
+    use crate::interaction::mocks::MockInteraction;
+    use atlas_local::{
+        GetDeploymentError,
+        models::{BindingType, Deployment as AtlasDeployment, MongoDBPortBinding},
+    };
+    use bollard::errors::Error as BollardError;
+    use bollard::models::{ContainerConfig, MountPoint};
+    use std::sync::{Arc, Mutex};
+
+    fn create_deployment(name: &str, state: State, port: u16) -> AtlasDeployment {
+        AtlasDeployment {
+            port_bindings: Some(MongoDBPortBinding::new(Some(port), BindingType::Loopback)),
+            mongodb_initdb_root_password: Some("secret".to_string()),
+            mongodb_initdb_root_username: Some("admin".to_string()),
+            ..test_support::create_deployment(name, state)
+        }
+    }
+
+    fn create_container(volumes: &[(&str, &str)]) -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            config: Some(ContainerConfig {
+                image: Some("mongodb/mongodb-atlas-local:8.2.2".to_string()),
+                ..Default::default()
+            }),
+            mounts: Some(
+                volumes
+                    .iter()
+                    .map(|(destination, name)| MountPoint {
+                        typ: Some(MountPointTypeEnum::VOLUME),
+                        name: Some(name.to_string()),
+                        destination: Some(destination.to_string()),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn not_found() -> GetDeploymentError {
+        GetDeploymentError::ContainerInspect(BollardError::DockerResponseServerError {
+            status_code: 404,
+            message: "No such container".to_string(),
+        })
+    }
+
+    fn create_interaction() -> MockInteraction {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Cloning deployment...")
+            .return_once(|_| Ok(create_spinner_handle()));
+        mock_interaction
+    }
+
+    #[tokio::test]
+    async fn test_clone_running_deployment() {
+        let mut mock_deployment_management = MockDocker::new();
+        let target_gets = Arc::new(Mutex::new(0));
+        mock_deployment_management
+            .expect_get_deployment()
+            .returning(move |name| match name {
+                "source" => Ok(create_deployment("source", State::Running, 27017)),
+                // The new deployment doesn't exist until it is created
+                _ => {
+                    let mut target_gets = target_gets.lock().unwrap();
+                    *target_gets += 1;
+                    if *target_gets == 1 {
+                        Err(not_found())
+                    } else {
+                        Ok(create_deployment("copy", State::Running, 32768))
+                    }
+                }
+            });
+        mock_deployment_management
+            .expect_create_deployment()
+            .withf(|options| {
+                options.name.as_deref() == Some("copy")
+                    && options.image.as_deref() == Some("mongodb/mongodb-atlas-local")
+                    && options.skip_pull_image == Some(true)
+                    && options.mongodb_initdb_root_username.as_deref() == Some("admin")
+                    && options
+                        .mongodb_port_binding
+                        .as_ref()
+                        .is_some_and(|binding| binding.port.is_none())
+            })
+            .return_once(|_| {
+                create_successful_progress(create_deployment("copy", State::Running, 32768))
+            });
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let stop_calls = calls.clone();
+        mock_deployment_management
+            .expect_stop()
+            .times(2)
+            .returning(move |name| {
+                stop_calls.lock().unwrap().push(format!("stop {name}"));
+                Ok(())
+            });
+        let start_calls = calls.clone();
+        mock_deployment_management
+            .expect_start()
+            .times(2)
+            .returning(move |name| {
+                start_calls.lock().unwrap().push(format!("start {name}"));
+                Ok(())
+            });
+
+        let mut mock_volume_management = MockDocker::new();
+        mock_volume_management
+            .expect_inspect_container_raw()
+            .returning(|name| {
+                Ok(Some(match name {
+                    "source" => create_container(&[
+                        ("/data/db", "source-db"),
+                        ("/data/configdb", "source-configdb"),
+                    ]),
+                    _ => create_container(&[
+                        ("/data/db", "copy-db"),
+                        ("/data/configdb", "copy-configdb"),
+                    ]),
+                }))
+            });
+        let copy_calls = calls.clone();
+        mock_volume_management
+            .expect_copy_volume()
+            .times(2)
+            .returning(move |image, source_volume, target_volume| {
+                assert_eq!(image, "mongodb/mongodb-atlas-local:8.2.2");
+                copy_calls
+                    .lock()
+                    .unwrap()
+                    .push(format!("copy {source_volume} {target_volume}"));
+                Ok(())
+            });
+
+        let mut clone_command = CloneDeployment {
+            source_deployment_name: "source".to_string(),
+            deployment_name: "copy".to_string(),
+            interaction: Box::new(create_interaction()),
+            deployment_management: Box::new(mock_deployment_management),
+            volume_management: Box::new(mock_volume_management),
+        };
+
+        let result = clone_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CloneResult::Cloned {
+                source_deployment_name: "source".to_string(),
+                deployment_name: "copy".to_string(),
+                port: Some(32768),
+            }
+        );
+
+        // Both deployments are stopped while the volumes are copied
+        let mut calls = calls.lock().unwrap().clone();
+        calls[2..4].sort();
+        assert_eq!(
+            calls,
+            vec![
+                "stop copy",
+                "stop source",
+                "copy source-configdb copy-configdb",
+                "copy source-db copy-db",
+                "start source",
+                "start copy",
+            ]
+        );
+    }
+
+    /// Deployment management of a clone of a source deployment in the state, the calls are recorded.
+    fn create_recording_deployment_management(
+        source_state: State,
+        calls: Arc<Mutex<Vec<String>>>,
+    ) -> MockDocker {
+        let mut mock_deployment_management = MockDocker::new();
+        let target_gets = Arc::new(Mutex::new(0));
+        mock_deployment_management
+            .expect_get_deployment()
+            .returning(move |name| match name {
+                "source" => Ok(create_deployment("source", source_state.clone(), 27017)),
+                _ => {
+                    let mut target_gets = target_gets.lock().unwrap();
+                    *target_gets += 1;
+                    if *target_gets == 1 {
+                        Err(not_found())
+                    } else {
+                        Ok(create_deployment("copy", State::Running, 32768))
+                    }
+                }
+            });
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(|_| {
+                create_successful_progress(create_deployment("copy", State::Running, 32768))
+            });
+        let stop_calls = calls.clone();
+        mock_deployment_management
+            .expect_stop()
+            .returning(move |name| {
+                stop_calls.lock().unwrap().push(format!("stop {name}"));
+                Ok(())
+            });
+        let start_calls = calls.clone();
+        mock_deployment_management
+            .expect_start()
+            .returning(move |name| {
+                start_calls.lock().unwrap().push(format!("start {name}"));
+                Ok(())
+            });
+        let delete_calls = calls;
+        mock_deployment_management
+            .expect_delete()
+            .returning(move |name| {
+                delete_calls.lock().unwrap().push(format!("delete {name}"));
+                Ok(())
+            });
+        mock_deployment_management
+    }
+
+    /// Volume management of a clone with a single data volume, the copy and pause calls are recorded.
+    fn create_recording_volume_management(
+        copy_result: Result<(), BollardError>,
+        calls: Arc<Mutex<Vec<String>>>,
+    ) -> MockDocker {
+        let mut mock_volume_management = MockDocker::new();
+        mock_volume_management
+            .expect_inspect_container_raw()
+            .returning(|name| {
+                Ok(Some(match name {
+                    "source" => create_container(&[("/data/db", "source-db")]),
+                    _ => create_container(&[("/data/db", "copy-db")]),
+                }))
+            });
+        let copy_calls = calls.clone();
+        mock_volume_management.expect_copy_volume().return_once(
+            move |_, source_volume, target_volume| {
+                copy_calls
+                    .lock()
+                    .unwrap()
+                    .push(format!("copy {source_volume} {target_volume}"));
+                copy_result
+            },
+        );
+        let pause_calls = calls;
+        mock_volume_management
+            .expect_pause()
+            .returning(move |name| {
+                pause_calls.lock().unwrap().push(format!("pause {name}"));
+                Ok(())
+            });
+        mock_volume_management
+    }
+
+    #[tokio::test]
+    async fn test_clone_paused_deployment_is_paused_again() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut clone_command = CloneDeployment {
+            source_deployment_name: "source".to_string(),
+            deployment_name: "copy".to_string(),
+            interaction: Box::new(create_interaction()),
+            deployment_management: Box::new(create_recording_deployment_management(
+                State::Paused,
+                calls.clone(),
+            )),
+            volume_management: Box::new(create_recording_volume_management(Ok(()), calls.clone())),
+        };
+
+        let result = clone_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert!(matches!(result, CloneResult::Cloned { .. }));
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "stop copy",
+                "stop source",
+                "copy source-db copy-db",
+                "start source",
+                "pause source",
+                "start copy",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_copy_fails_restarts_source_and_deletes_new_deployment() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut clone_command = CloneDeployment {
+            source_deployment_name: "source".to_string(),
+            deployment_name: "copy".to_string(),
+            interaction: Box::new(create_interaction()),
+            deployment_management: Box::new(create_recording_deployment_management(
+                State::Running,
+                calls.clone(),
+            )),
+            volume_management: Box::new(create_recording_volume_management(
+                Err(BollardError::DockerResponseServerError {
+                    status_code: 500,
+                    message: "no space left on device".to_string(),
+                }),
+                calls.clone(),
+            )),
+        };
+
+        let result = clone_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let CloneResult::Failed { error, .. } = result else {
+            panic!("expected the clone to fail, got {result:?}");
+        };
+        assert!(error.starts_with(
+            "failed to copy the data volumes: copying the volume mounted on /data/db"
+        ));
+        // The source deployment is running again and the new deployment is not left behind
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "stop copy",
+                "stop source",
+                "copy source-db copy-db",
+                "start source",
+                "delete copy",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_existing_target() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .returning(|name| Ok(create_deployment(name, State::Running, 27017)));
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut clone_command = CloneDeployment {
+            source_deployment_name: "source".to_string(),
+            deployment_name: "copy".to_string(),
+            interaction: Box::new(create_interaction()),
+            deployment_management: Box::new(mock_deployment_management),
+            volume_management: Box::new(MockDocker::new()),
+        };
+
+        let result = clone_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CloneResult::Failed {
+                deployment_name: "copy".to_string(),
+                error: "deployment 'copy' already exists".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_source_not_found() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|_| Err(not_found()));
+
+        let mut clone_command = CloneDeployment {
+            source_deployment_name: "source".to_string(),
+            deployment_name: "copy".to_string(),
+            interaction: Box::new(create_interaction()),
+            deployment_management: Box::new(mock_deployment_management),
+            volume_management: Box::new(MockDocker::new()),
+        };
+
+        let result = clone_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CloneResult::Failed {
+                deployment_name: "copy".to_string(),
+                error: "deployment 'source' not found".to_string(),
+            }
+        );
+    }
+}
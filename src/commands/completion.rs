@@ -0,0 +1,201 @@
+//! Command to generate the shell completion script.
+//!
+//! This module implements the `completion` command. The script it prints doesn't contain the completions, it calls
+//! back into the CLI with the [`COMPLETE_ENV_VAR`] environment variable set while the user is typing. The CLI then
+//! answers with the candidates instead of running a command, see [`complete_from_env`]. This way the names of local
+//! deployments are completed, which a static script can't do.
+
+use std::{io::Write, time::Duration};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::Client;
+use clap::CommandFactory;
+use clap_complete::{ArgValueCandidates, CompleteEnv, CompletionCandidate, env::Shells};
+use tracing::debug;
+
+use crate::{
+    args::{self, Cli, CompletionShell},
+    commands::Command,
+    dependencies::{DeploymentLister, DockerSettings, connect_docker},
+};
+
+/// Environment variable that makes the CLI answer a completion request instead of running a command.
+const COMPLETE_ENV_VAR: &str = "ATLAS_LOCAL_COMPLETE";
+
+/// Name of the binary the completion script is registered for.
+const BIN_NAME: &str = "atlas-local";
+
+/// Ids of the arguments that take the name of a local deployment.
+const DEPLOYMENT_NAME_ARGS: [&str; 2] = ["deployment_name", "deployment_names"];
+
+/// Commands that take the name of a new deployment, existing names are not completed for them.
+const NEW_DEPLOYMENT_COMMANDS: [&str; 1] = ["setup"];
+
+/// Completions must not hang the shell, deployment names are not completed when Docker is slower than this.
+const LIST_DEPLOYMENTS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Answer the completion request of the completion script and exit, when the CLI is called by the script.
+///
+/// This must run before the arguments are parsed and before anything is written to stdout.
+pub fn complete_from_env() {
+    CompleteEnv::with_factory(command_with_completers)
+        .var(COMPLETE_ENV_VAR)
+        .bin(BIN_NAME)
+        .complete();
+}
+
+/// The clap command of the CLI, with the completion of deployment names on every argument that takes one.
+///
+/// The completers are added here instead of on the arguments, so the argument definitions don't depend on Docker.
+fn command_with_completers() -> clap::Command {
+    add_deployment_name_completers(Cli::command())
+}
+
+fn add_deployment_name_completers(command: clap::Command) -> clap::Command {
+    if NEW_DEPLOYMENT_COMMANDS.contains(&command.get_name()) {
+        return command;
+    }
+
+    command
+        .mut_args(|arg| {
+            if DEPLOYMENT_NAME_ARGS.contains(&arg.get_id().as_str()) {
+                arg.add(ArgValueCandidates::new(deployment_name_candidates))
+            } else {
+                arg
+            }
+        })
+        .mut_subcommands(add_deployment_name_completers)
+}
+
+/// Names of the local deployments, none when Docker can't be reached.
+fn deployment_name_candidates() -> Vec<CompletionCandidate> {
+    // Clap calls the completers synchronously from within the async main function
+    let names = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(list_deployment_names())
+    });
+
+    names.into_iter().map(CompletionCandidate::new).collect()
+}
+
+async fn list_deployment_names() -> Vec<String> {
+    // Completions are answered before the global options are parsed, so the default Docker host is used
+    let docker = match connect_docker(&DockerSettings::default().resolve_host()) {
+        Ok(docker) => docker,
+        Err(e) => {
+            debug!(error = %e, "connecting to Docker failed, not completing deployment names");
+            return Vec::new();
+        }
+    };
+
+    match tokio::time::timeout(LIST_DEPLOYMENTS_TIMEOUT, Client::new(docker).list()).await {
+        Ok(Ok(deployments)) => deployments
+            .into_iter()
+            .filter_map(|deployment| deployment.name)
+            .collect(),
+        Ok(Err(e)) => {
+            debug!(error = %e, "listing deployments failed, not completing deployment names");
+            Vec::new()
+        }
+        Err(_) => {
+            debug!("listing deployments timed out, not completing deployment names");
+            Vec::new()
+        }
+    }
+}
+
+/// Command to generate the shell completion script.
+pub struct Completion {
+    shell: CompletionShell,
+}
+
+impl From<args::Completion> for Completion {
+    fn from(args: args::Completion) -> Self {
+        Self { shell: args.shell }
+    }
+}
+
+/// The completion command prints the script as it is, it has no JSON output.
+#[async_trait]
+impl Command for Completion {
+    async fn execute(&mut self) -> Result<()> {
+        // The script calls back into this executable, so completions keep working when it isn't on the PATH
+        let completer = std::env::current_exe()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| BIN_NAME.to_string());
+
+        let script = registration_script(self.shell, &completer)?;
+        std::io::stdout()
+            .write_all(script.as_bytes())
+            .context("writing the completion script")
+    }
+}
+
+/// The script that registers the completions of the CLI with the shell.
+fn registration_script(shell: CompletionShell, completer: &str) -> Result<String> {
+    let name = match shell {
+        CompletionShell::Bash => "bash",
+        CompletionShell::Zsh => "zsh",
+        CompletionShell::Fish => "fish",
+        CompletionShell::Powershell => "powershell",
+    };
+    let shell = Shells::builtins()
+        .completer(name)
+        .with_context(|| format!("{name} completions are not supported"))?;
+
+    let mut script = Vec::new();
+    shell
+        .write_registration(COMPLETE_ENV_VAR, BIN_NAME, BIN_NAME, completer, &mut script)
+        .context("generating the completion script")?;
+
+    String::from_utf8(script).context("the completion script is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registration_script() {
+        for shell in [
+            CompletionShell::Bash,
+            CompletionShell::Zsh,
+            CompletionShell::Fish,
+            CompletionShell::Powershell,
+        ] {
+            let script = registration_script(shell, "/usr/local/bin/atlas-local")
+                .expect("generating the script should succeed");
+
+            assert!(script.contains(COMPLETE_ENV_VAR), "{shell:?}: {script}");
+            assert!(
+                script.contains("/usr/local/bin/atlas-local"),
+                "{shell:?}: {script}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_deployment_names_are_completed() {
+        let mut command = command_with_completers();
+        command.build();
+
+        let start = command
+            .find_subcommand("start")
+            .expect("start should be a subcommand");
+        let deployment_name = start
+            .get_arguments()
+            .find(|arg| arg.get_id() == "deployment_name")
+            .expect("start should take a deployment name");
+        assert!(deployment_name.get::<ArgValueCandidates>().is_some());
+
+        // Setup creates a new deployment, the name of an existing deployment is never valid
+        let setup = command
+            .find_subcommand("setup")
+            .expect("setup should be a subcommand");
+        assert!(
+            setup
+                .get_arguments()
+                .all(|arg| arg.get::<ArgValueCandidates>().is_none())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Command to show the saved settings of a deployment.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        config::{Deployments, SettingEntry, deployments_file_path},
+    },
+    config_dir::{ConfigFs, load_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+    table::Table,
+};
+
+pub struct Get {
+    deployment_name: String,
+    deployments_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::config::Get, &Settings)> for Get {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::config::Get, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            deployments_file: deployments_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+/// Result of the config get command, a deployment without saved settings has no settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GetResult {
+    pub deployment_name: String,
+    pub settings: Vec<SettingEntry>,
+}
+
+impl From<&GetResult> for Table {
+    fn from(value: &GetResult) -> Self {
+        Table::from_iter(
+            &value.settings,
+            &[("KEY", |s| s.key.clone()), ("VALUE", |s| s.value.clone())],
+        )
+    }
+}
+
+impl Display for GetResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.settings.is_empty() {
+            return write!(
+                f,
+                "No settings saved for deployment '{}'",
+                self.deployment_name
+            );
+        }
+
+        Table::from(self).fmt(f)
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Get {
+    type Output = GetResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let deployments =
+            load_toml::<Deployments>(self.fs.as_ref(), &self.deployments_file).await?;
+
+        Ok(GetResult {
+            deployment_name: self.deployment_name.clone(),
+            settings: deployments
+                .deployments
+                .get(&self.deployment_name)
+                .map(|settings| settings.entries())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_get_shows_saved_settings() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.analytics]\nconnectWith = \"connectionString\"\nwaitForHealthy = true\n"
+                .to_string())
+        });
+
+        let mut get_command = Get {
+            deployment_name: "analytics".to_string(),
+            deployments_file: PathBuf::from("/config/atlas-local/deployments.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = get_command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result.settings,
+            vec![
+                SettingEntry {
+                    key: "connectWith".to_string(),
+                    value: "connectionString".to_string(),
+                },
+                SettingEntry {
+                    key: "waitForHealthy".to_string(),
+                    value: "true".to_string(),
+                },
+            ]
+        );
+    }
+}
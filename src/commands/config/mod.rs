@@ -0,0 +1,455 @@
+//! Commands to manage the saved settings of deployments.
+//!
+//! Settings are saved per deployment name in the configuration directory. They are used by `connect` and `start` when
+//! the matching flag is omitted, see [`load_deployment_settings`]. Precedence: the flag, then the saved setting of the
+//! deployment, then the global default. The settings of a deployment are removed when it is deleted. `setup` saves
+//! the database of `--defaultDb` as the `defaultDb` setting of the new deployment.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::{
+    args::{self, ConnectWith},
+    config_dir::{ConfigFs, config_dir, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub mod get;
+pub mod set;
+pub mod unset;
+
+/// Name of the file the settings of the deployments are saved in, inside the configuration directory.
+const DEPLOYMENTS_FILE_NAME: &str = "deployments.toml";
+
+/// Keys of the supported settings.
+const CONNECT_WITH_KEY: &str = "connectWith";
+const DEFAULT_DB_KEY: &str = "defaultDb";
+const WAIT_FOR_HEALTHY_KEY: &str = "waitForHealthy";
+
+/// The saved settings of all deployments, as stored in the deployments file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deployments {
+    #[serde(default)]
+    pub deployments: BTreeMap<String, DeploymentSettings>,
+}
+
+/// Saved settings of a deployment, a setting that is not saved uses the global default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentSettings {
+    /// Method for connecting to the deployment, when `connect` is used without `--connectWith`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_with: Option<ConnectWith>,
+    /// Database that `connect` adds to the connection string, when used without `--defaultDb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_db: Option<String>,
+    /// Whether `start` waits for the deployment to be healthy, when used without `--waitForHealthy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for_healthy: Option<bool>,
+}
+
+/// A saved setting, as shown by `config get`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingEntry {
+    pub key: String,
+    pub value: String,
+}
+
+impl DeploymentSettings {
+    /// Set the setting with the key, the value is validated for the key.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            CONNECT_WITH_KEY => {
+                let connect_with = ConnectWith::from_str(value, false).map_err(|_| {
+                    let valid_values = ConnectWith::value_variants()
+                        .iter()
+                        .filter_map(|variant| variant.to_possible_value())
+                        .map(|value| value.get_name().to_string())
+                        .collect::<Vec<_>>();
+                    format!(
+                        "invalid value for {}: expected one of {}, got '{}'",
+                        key,
+                        valid_values.join(", "),
+                        value
+                    )
+                })?;
+                self.connect_with = Some(connect_with);
+            }
+            DEFAULT_DB_KEY => {
+                let default_db = args::parse_database_name(value)
+                    .map_err(|error| format!("invalid value for {}: {}", key, error))?;
+                self.default_db = Some(default_db);
+            }
+            WAIT_FOR_HEALTHY_KEY => {
+                let wait_for_healthy = value.parse().map_err(|_| {
+                    format!(
+                        "invalid value for {}: expected true or false, got '{}'",
+                        key, value
+                    )
+                })?;
+                self.wait_for_healthy = Some(wait_for_healthy);
+            }
+            _ => return Err(unsupported_key(key)),
+        }
+
+        Ok(())
+    }
+
+    /// Remove the setting with the key, so the global default is used again.
+    pub fn unset(&mut self, key: &str) -> Result<(), String> {
+        match key {
+            CONNECT_WITH_KEY => self.connect_with = None,
+            DEFAULT_DB_KEY => self.default_db = None,
+            WAIT_FOR_HEALTHY_KEY => self.wait_for_healthy = None,
+            _ => return Err(unsupported_key(key)),
+        }
+
+        Ok(())
+    }
+
+    /// Whether no setting is saved.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The saved settings, in the key=value form they are set with.
+    pub fn entries(&self) -> Vec<SettingEntry> {
+        let connect_with = self.connect_with.and_then(|connect_with| {
+            connect_with
+                .to_possible_value()
+                .map(|value| (CONNECT_WITH_KEY, value.get_name().to_string()))
+        });
+        let default_db = self
+            .default_db
+            .clone()
+            .map(|default_db| (DEFAULT_DB_KEY, default_db));
+        let wait_for_healthy = self
+            .wait_for_healthy
+            .map(|wait_for_healthy| (WAIT_FOR_HEALTHY_KEY, wait_for_healthy.to_string()));
+
+        [connect_with, default_db, wait_for_healthy]
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| SettingEntry {
+                key: key.to_string(),
+                value,
+            })
+            .collect()
+    }
+
+    /// Use the saved settings for the flags of `connect` that are omitted.
+    pub fn apply_to_connect(&self, args: &mut args::Connect) {
+        if args.connect_with.is_none() {
+            args.connect_with = self.connect_with;
+        }
+        if args.connection_string.default_db.is_none() {
+            args.connection_string.default_db = self.default_db.clone();
+        }
+    }
+
+    /// Use the saved settings for the flags of `start` that are omitted.
+    pub fn apply_to_start(&self, args: &mut args::Start) {
+        // An omitted flag is false, so only a saved true changes the behavior
+        if !args.wait_for_healthy {
+            args.wait_for_healthy = self.wait_for_healthy.unwrap_or_default();
+        }
+    }
+}
+
+fn unsupported_key(key: &str) -> String {
+    format!(
+        "unsupported setting '{}', expected one of {}, {}, {}",
+        key, CONNECT_WITH_KEY, DEFAULT_DB_KEY, WAIT_FOR_HEALTHY_KEY
+    )
+}
+
+/// Path of the file the settings of the deployments are saved in.
+pub fn deployments_file_path(settings: &Settings) -> Result<PathBuf> {
+    Ok(config_dir(settings)?.join(DEPLOYMENTS_FILE_NAME))
+}
+
+/// Load the saved settings of the deployment, a deployment without saved settings uses the global defaults.
+///
+/// Settings only fill in omitted flags, so a deployments file that can't be read or parsed doesn't fail the command,
+/// the global defaults are used instead.
+pub async fn load_deployment_settings(
+    deployment_name: &str,
+    settings: &Settings,
+) -> DeploymentSettings {
+    let deployments = match deployments_file_path(settings) {
+        Ok(path) => load_toml::<Deployments>(&TokioFs::new(), &path).await,
+        Err(e) => Err(e),
+    };
+
+    deployment_settings(deployments, deployment_name)
+}
+
+/// The saved settings of the deployment from the loaded deployments file, the defaults when loading failed.
+fn deployment_settings(
+    deployments: Result<Deployments>,
+    deployment_name: &str,
+) -> DeploymentSettings {
+    let mut deployments = match deployments {
+        Ok(deployments) => deployments,
+        Err(e) => {
+            warn!(
+                error = format!("{e:#}"),
+                "loading deployment settings failed, using the defaults"
+            );
+            return DeploymentSettings::default();
+        }
+    };
+
+    let settings = deployments
+        .deployments
+        .remove(deployment_name)
+        .unwrap_or_default();
+    debug!(deployment_name, ?settings, "using deployment settings");
+
+    settings
+}
+
+/// Save the default database of a deployment, `connect` adds it to the connection string.
+pub async fn save_default_db(
+    fs: &dyn ConfigFs,
+    path: &Path,
+    deployment_name: &str,
+    default_db: &str,
+) -> Result<()> {
+    let mut deployments: Deployments = load_toml(fs, path).await?;
+    deployments
+        .deployments
+        .entry(deployment_name.to_string())
+        .or_default()
+        .default_db = Some(default_db.to_string());
+
+    debug!(
+        deployment_name,
+        default_db, "saving the default database of the deployment"
+    );
+    save_toml(fs, path, &deployments).await
+}
+
+/// Remove the saved settings of deleted deployments, the file is only written when settings were saved for them.
+pub async fn remove_deployment_settings(
+    fs: &dyn ConfigFs,
+    path: &Path,
+    deployment_names: &[String],
+) -> Result<()> {
+    let mut deployments: Deployments = load_toml(fs, path).await?;
+
+    let removed = deployment_names
+        .iter()
+        .filter(|deployment_name| deployments.deployments.remove(*deployment_name).is_some())
+        .count();
+    if removed == 0 {
+        return Ok(());
+    }
+
+    debug!(
+        ?deployment_names,
+        "removing settings of deleted deployments"
+    );
+    save_toml(fs, path, &deployments).await
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_load_deployments_from_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.analytics]\nconnectWith = \"mongosh\"\n".to_string())
+        });
+
+        let deployments = load_toml::<Deployments>(&mock_fs, Path::new("deployments.toml"))
+            .await
+            .expect("loading deployments should succeed");
+
+        assert_eq!(
+            deployments.deployments.get("analytics"),
+            Some(&DeploymentSettings {
+                connect_with: Some(ConnectWith::Mongosh),
+                default_db: None,
+                wait_for_healthy: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_deployment_settings_fall_back_to_defaults() {
+        let deployments =
+            toml::from_str::<Deployments>("[deployments.analytics]\nwaitForHealthy = true\n")
+                .map_err(anyhow::Error::from);
+        assert_eq!(
+            deployment_settings(deployments, "analytics"),
+            DeploymentSettings {
+                connect_with: None,
+                default_db: None,
+                wait_for_healthy: Some(true),
+            }
+        );
+
+        let deployments = Err(anyhow::anyhow!("parsing deployments file deployments.toml"));
+        assert_eq!(
+            deployment_settings(deployments, "analytics"),
+            DeploymentSettings::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_deployment_settings() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.analytics]\nconnectWith = \"mongosh\"\n\n[deployments.orders]\nwaitForHealthy = true\n".to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|_, contents| {
+                contents.contains("[deployments.orders]") && !contents.contains("analytics")
+            })
+            .return_once(|_, _| Ok(()));
+
+        remove_deployment_settings(
+            &mock_fs,
+            Path::new("deployments.toml"),
+            &["analytics".to_string(), "users".to_string()],
+        )
+        .await
+        .expect("removing settings should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_save_default_db() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.analytics]\nconnectWith = \"mongosh\"\n".to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|_, contents| {
+                contents.contains("connectWith = \"mongosh\"")
+                    && contents.contains("defaultDb = \"inventory\"")
+            })
+            .return_once(|_, _| Ok(()));
+
+        save_default_db(
+            &mock_fs,
+            Path::new("deployments.toml"),
+            "analytics",
+            "inventory",
+        )
+        .await
+        .expect("saving the default database should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_remove_deployment_settings_without_saved_settings() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_fs.expect_write_string().never();
+
+        remove_deployment_settings(
+            &mock_fs,
+            Path::new("deployments.toml"),
+            &["analytics".to_string()],
+        )
+        .await
+        .expect("removing settings should succeed");
+    }
+
+    #[test]
+    fn test_set_validates_value() {
+        let mut settings = DeploymentSettings::default();
+
+        settings.set("connectWith", "vscode").unwrap();
+        settings.set("defaultDb", "inventory").unwrap();
+        settings.set("waitForHealthy", "true").unwrap();
+        assert_eq!(
+            settings,
+            DeploymentSettings {
+                connect_with: Some(ConnectWith::VsCode),
+                default_db: Some("inventory".to_string()),
+                wait_for_healthy: Some(true),
+            }
+        );
+
+        assert_eq!(
+            settings.set("waitForHealthy", "yes"),
+            Err("invalid value for waitForHealthy: expected true or false, got 'yes'".to_string())
+        );
+        assert!(
+            settings
+                .set("defaultDb", "in/ventory")
+                .unwrap_err()
+                .starts_with("invalid value for defaultDb: ")
+        );
+        assert_eq!(
+            settings.set("theme", "dark"),
+            Err(
+                "unsupported setting 'theme', expected one of connectWith, defaultDb, waitForHealthy"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_flags_take_precedence_over_settings() {
+        let settings = DeploymentSettings {
+            connect_with: Some(ConnectWith::Mongosh),
+            default_db: Some("inventory".to_string()),
+            wait_for_healthy: Some(true),
+        };
+
+        let mut connect_args = args::Connect::parse_from(["connect", "analytics"]);
+        settings.apply_to_connect(&mut connect_args);
+        assert_eq!(connect_args.connect_with, Some(ConnectWith::Mongosh));
+        assert_eq!(
+            connect_args.connection_string.default_db,
+            Some("inventory".to_string())
+        );
+
+        let mut connect_args = args::Connect::parse_from([
+            "connect",
+            "analytics",
+            "--connectWith",
+            "compass",
+            "--defaultDb",
+            "orders",
+        ]);
+        settings.apply_to_connect(&mut connect_args);
+        assert_eq!(connect_args.connect_with, Some(ConnectWith::Compass));
+        assert_eq!(
+            connect_args.connection_string.default_db,
+            Some("orders".to_string())
+        );
+
+        let mut start_args = args::Start::parse_from(["start", "analytics"]);
+        settings.apply_to_start(&mut start_args);
+        assert!(start_args.wait_for_healthy);
+    }
+}
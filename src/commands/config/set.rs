@@ -0,0 +1,183 @@
+//! Command to save settings of a deployment.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        config::{Deployments, deployments_file_path},
+    },
+    config_dir::{ConfigFs, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub struct Set {
+    deployment_name: String,
+    settings: Vec<(String, String)>,
+    deployments_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::config::Set, &Settings)> for Set {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::config::Set, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            settings: args.settings,
+            deployments_file: deployments_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum SetResult {
+    Saved {
+        deployment_name: String,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for SetResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Saved { deployment_name } => {
+                write!(f, "Settings of deployment '{}' saved", deployment_name)
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Saving settings of deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Set {
+    type Output = SetResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut deployments =
+            load_toml::<Deployments>(self.fs.as_ref(), &self.deployments_file).await?;
+        let settings = deployments
+            .deployments
+            .entry(self.deployment_name.clone())
+            .or_default();
+
+        // Nothing is saved when any of the settings is invalid
+        for (key, value) in &self.settings {
+            if let Err(error) = settings.set(key, value) {
+                return Ok(SetResult::Failed {
+                    deployment_name: self.deployment_name.clone(),
+                    error,
+                });
+            }
+        }
+
+        debug!(
+            deployment_name = %self.deployment_name,
+            path = %self.deployments_file.display(),
+            "saving deployment settings"
+        );
+        save_toml(self.fs.as_ref(), &self.deployments_file, &deployments).await?;
+
+        Ok(SetResult::Saved {
+            deployment_name: self.deployment_name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    fn create_set_command(settings: &[(&str, &str)], fs: MockTokioFs) -> Set {
+        Set {
+            deployment_name: "analytics".to_string(),
+            settings: settings
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            deployments_file: PathBuf::from("/config/atlas-local/deployments.toml"),
+            fs: Box::new(fs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_merges_with_saved_settings() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs
+            .expect_read_to_string()
+            .return_once(|_| Ok("[deployments.analytics]\nwaitForHealthy = true\n".to_string()));
+        mock_fs
+            .expect_create_dir_all()
+            .withf(|path| path == Path::new("/config/atlas-local"))
+            .return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/config/atlas-local/deployments.toml")
+                    && contents.contains("[deployments.analytics]")
+                    && contents.contains("connectWith = \"mongosh\"")
+                    && contents.contains("waitForHealthy = true")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut set_command = create_set_command(&[("connectWith", "mongosh")], mock_fs);
+
+        let result = set_command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetResult::Saved {
+                deployment_name: "analytics".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_invalid_setting_saves_nothing() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_fs.expect_write_string().never();
+
+        let mut set_command = create_set_command(
+            &[("waitForHealthy", "true"), ("connectWith", "robo3t")],
+            mock_fs,
+        );
+
+        let result = set_command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetResult::Failed {
+                deployment_name: "analytics".to_string(),
+                error: "invalid value for connectWith: expected one of compass, mongosh, vscode, connectionString, got 'robo3t'".to_string()
+            }
+        );
+    }
+}
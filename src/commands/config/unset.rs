@@ -0,0 +1,160 @@
+//! Command to remove saved settings of a deployment.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        config::{Deployments, deployments_file_path},
+    },
+    config_dir::{ConfigFs, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub struct Unset {
+    deployment_name: String,
+    keys: Vec<String>,
+    deployments_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::config::Unset, &Settings)> for Unset {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::config::Unset, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            keys: args.keys,
+            deployments_file: deployments_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum UnsetResult {
+    Removed {
+        deployment_name: String,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for UnsetResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Removed { deployment_name } => {
+                write!(f, "Settings of deployment '{}' removed", deployment_name)
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Removing settings of deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Unset {
+    type Output = UnsetResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut deployments =
+            load_toml::<Deployments>(self.fs.as_ref(), &self.deployments_file).await?;
+
+        // Removing a setting that isn't saved is not an error, the global default is used either way
+        let mut settings = deployments
+            .deployments
+            .remove(&self.deployment_name)
+            .unwrap_or_default();
+        for key in &self.keys {
+            if let Err(error) = settings.unset(key) {
+                return Ok(UnsetResult::Failed {
+                    deployment_name: self.deployment_name.clone(),
+                    error,
+                });
+            }
+        }
+
+        // A deployment without settings is removed from the file
+        if !settings.is_empty() {
+            deployments
+                .deployments
+                .insert(self.deployment_name.clone(), settings);
+        }
+
+        debug!(
+            deployment_name = %self.deployment_name,
+            path = %self.deployments_file.display(),
+            "removing deployment settings"
+        );
+        save_toml(self.fs.as_ref(), &self.deployments_file, &deployments).await?;
+
+        Ok(UnsetResult::Removed {
+            deployment_name: self.deployment_name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_unset_removes_deployment_without_settings() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.analytics]\nconnectWith = \"mongosh\"\n\n[deployments.ci]\nwaitForHealthy = true\n".to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/config/atlas-local/deployments.toml")
+                    && !contents.contains("analytics")
+                    && contents.contains("[deployments.ci]")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut unset_command = Unset {
+            deployment_name: "analytics".to_string(),
+            keys: vec!["connectWith".to_string()],
+            deployments_file: PathBuf::from("/config/atlas-local/deployments.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = unset_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            UnsetResult::Removed {
+                deployment_name: "analytics".to_string()
+            }
+        );
+    }
+}
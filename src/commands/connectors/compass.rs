@@ -1,8 +1,12 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::Serialize;
 use std::path::PathBuf;
 
-use super::{Connector, DeploymentParams, launch};
+use super::{Connector, ConnectorOptions, DeploymentParams, launch, warn_unknown_options};
+
+/// Connector arguments supported by Compass.
+const SUPPORTED_OPTIONS: &[&str] = &["theme"];
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Compass;
@@ -19,14 +23,69 @@ impl Connector for Compass {
         compass_bin().exists()
     }
 
-    async fn launch(&self, params: &DeploymentParams) -> Result<()> {
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()> {
+        warn_unknown_options("Compass", options, SUPPORTED_OPTIONS);
+
         launch(compass_bin(), |command| {
             command.arg(&params.connection_string);
+            // e.g. --connectorArg theme=DARK
+            if let Some(theme) = options.get("theme") {
+                command.arg(format!("--theme={theme}"));
+            }
         })
         .await
     }
 }
 
+/// Connections file that can be imported in Compass, with the deployment as a favorite.
+///
+/// Follows the format of the file created by "Export connections" in Compass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompassFavorites {
+    r#type: &'static str,
+    version: u32,
+    connections: Vec<CompassConnection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompassConnection {
+    id: String,
+    connection_options: CompassConnectionOptions,
+    favorite: CompassFavorite,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompassConnectionOptions {
+    connection_string: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct CompassFavorite {
+    name: String,
+}
+
+impl CompassFavorites {
+    /// Create the connections file with a single favorite for the deployment.
+    pub fn new(deployment_name: &str, connection_string: &str) -> Self {
+        Self {
+            r#type: "Compass Connections",
+            version: 1,
+            connections: vec![CompassConnection {
+                // Compass replaces a connection with the same id, importing the favorite again updates it
+                id: format!("atlas-local-{deployment_name}"),
+                connection_options: CompassConnectionOptions {
+                    connection_string: connection_string.to_string(),
+                },
+                favorite: CompassFavorite {
+                    name: deployment_name.to_string(),
+                },
+            }],
+        }
+    }
+}
+
 // returns the compass binary on macOS
 #[cfg(target_os = "macos")]
 fn compass_bin() -> PathBuf {
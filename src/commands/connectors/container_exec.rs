@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::dependencies::DockerSettings;
+
+use super::{
+    container_mongosh::{docker_bin, docker_cli_connection, docker_exec_interactive},
+    launch,
+};
+
+// Dependency to run a command in the container of a deployment, attached to the terminal
+#[async_trait]
+pub trait InteractiveExec {
+    async fn is_available(&self) -> bool;
+
+    /// Runs the command until it exits, a non-zero exit code is returned as a [`super::ConnectorExitError`].
+    async fn exec(&self, deployment_name: &str, cmd: &[String]) -> Result<()>;
+}
+
+/// Runs a command in the deployment container with `docker exec`, like the connectors that run in the container.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ContainerExec {
+    docker: DockerSettings,
+}
+
+impl ContainerExec {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl InteractiveExec for ContainerExec {
+    async fn is_available(&self) -> bool {
+        docker_bin().await.is_some_and(|path| path.exists())
+    }
+
+    async fn exec(&self, deployment_name: &str, cmd: &[String]) -> Result<()> {
+        let bin = docker_bin().await.context("docker not found")?;
+
+        launch(bin, |command| {
+            docker_cli_connection(command, &self.docker);
+            docker_exec_interactive(command, deployment_name);
+            command.args(cmd);
+        })
+        .await
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::process::Stdio;
+
+use crate::dependencies::DockerSettings;
+
+use super::{
+    Connector, ConnectorOptions, DeploymentParams,
+    container_mongosh::{container_connection_string, docker_bin, docker_cli_connection},
+    launch,
+    mongodump::{DEFAULT_DUMP_DIR, SUPPORTED_OPTIONS, mongodump_args},
+    warn_unknown_options,
+};
+
+/// Runs the mongodump that ships with the deployment image, using `docker exec`.
+///
+/// The dump is written to a temporary directory in the container and copied to the output directory on the host with
+/// `docker cp`. Used when mongodump is not installed on the host.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ContainerMongodump {
+    docker: DockerSettings,
+}
+
+impl ContainerMongodump {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl Connector for ContainerMongodump {
+    async fn is_available(&self) -> bool {
+        docker_bin().await.is_some_and(|path| path.exists())
+    }
+
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()> {
+        warn_unknown_options("mongodump", options, SUPPORTED_OPTIONS);
+
+        let bin = docker_bin().await.context("docker not found")?;
+        let connection_string = container_connection_string(&params.connection_string)?;
+        let out = options
+            .get("out")
+            .map_or(DEFAULT_DUMP_DIR, String::as_str)
+            .to_string();
+
+        // Concurrent exports of the same deployment don't share the directory
+        let container_dir = format!("/tmp/atlas-local-dump-{}", std::process::id());
+        let mut container_options = options.clone();
+        container_options.insert("out".to_string(), container_dir.clone());
+
+        let dump_result = async {
+            launch(&bin, |command| {
+                docker_cli_connection(command, &self.docker);
+                command.args(["exec", params.name.as_str(), "mongodump"]);
+                command.args(mongodump_args(&connection_string, &container_options));
+            })
+            .await?;
+
+            // The trailing /. copies the contents of the directory, like the dump directory of a local mongodump
+            tokio::fs::create_dir_all(&out)
+                .await
+                .with_context(|| format!("failed to create directory: {out}"))?;
+            launch(&bin, |command| {
+                docker_cli_connection(command, &self.docker);
+                command.arg("cp");
+                command.arg(format!("{}:{container_dir}/.", params.name));
+                command.arg(&out);
+                // docker cp reports the copied size on stdout, the command prints its own result
+                command.stdout(Stdio::null());
+            })
+            .await
+        }
+        .await;
+
+        // The dump is removed from the container, whether it was copied or not
+        let cleanup_result = launch(&bin, |command| {
+            docker_cli_connection(command, &self.docker);
+            command.args([
+                "exec",
+                params.name.as_str(),
+                "rm",
+                "-rf",
+                container_dir.as_str(),
+            ]);
+        })
+        .await;
+
+        dump_result.and(cleanup_result)
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::dependencies::DockerSettings;
+
+use super::{
+    BackgroundConnector, ConnectorOptions, DeploymentParams,
+    container_mongosh::{container_connection_string, docker_bin, docker_cli_connection},
+    mongorestore::{SUPPORTED_OPTIONS, mongorestore_args},
+    run_in_background, warn_unknown_options,
+};
+
+/// Runs the mongorestore that ships with the deployment image, using `docker exec`.
+///
+/// The dump is copied to a temporary directory in the container with `docker cp` first. Used when mongorestore is not
+/// installed on the host.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ContainerMongorestore {
+    docker: DockerSettings,
+}
+
+impl ContainerMongorestore {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl BackgroundConnector for ContainerMongorestore {
+    async fn is_available(&self) -> bool {
+        docker_bin().await.is_some_and(|path| path.exists())
+    }
+
+    async fn run(
+        &self,
+        params: &DeploymentParams,
+        options: &ConnectorOptions,
+        output: UnboundedSender<String>,
+    ) -> Result<()> {
+        warn_unknown_options("mongorestore", options, SUPPORTED_OPTIONS);
+
+        let bin = docker_bin().await.context("docker not found")?;
+        let connection_string = container_connection_string(&params.connection_string)?;
+        let dir = options.get("dir").context("no dump directory")?;
+
+        // Concurrent imports into the same deployment don't share the directory
+        let container_dir = format!("/tmp/atlas-local-restore-{}", std::process::id());
+
+        let restore_result = async {
+            // The trailing /. copies the contents of the directory, the directory is created in the container
+            run_in_background(&bin, &output, |command| {
+                docker_cli_connection(command, &self.docker);
+                command.arg("cp");
+                command.arg(format!("{dir}/."));
+                command.arg(format!("{}:{container_dir}", params.name));
+            })
+            .await?;
+
+            run_in_background(&bin, &output, |command| {
+                docker_cli_connection(command, &self.docker);
+                command.args(["exec", params.name.as_str(), "mongorestore"]);
+                command.args(mongorestore_args(
+                    &connection_string,
+                    options,
+                    &container_dir,
+                ));
+            })
+            .await
+        }
+        .await;
+
+        // The dump is removed from the container, whether it was restored or not
+        let cleanup_result = run_in_background(&bin, &output, |command| {
+            docker_cli_connection(command, &self.docker);
+            command.args([
+                "exec",
+                params.name.as_str(),
+                "rm",
+                "-rf",
+                container_dir.as_str(),
+            ]);
+        })
+        .await;
+
+        restore_result.and(cleanup_result)
+    }
+}
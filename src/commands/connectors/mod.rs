@@ -1,27 +1,158 @@
 use async_trait::async_trait;
 use std::{
+    collections::BTreeMap,
     env,
     ffi::OsStr,
     process::{Stdio, exit},
 };
-use tokio::process::Command;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    sync::mpsc::UnboundedSender,
+};
+use tracing::debug;
+
+use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
 
-use anyhow::Result;
+use crate::args::ConnectWith;
 
 mod compass;
+mod container_exec;
+mod container_mongodump;
+mod container_mongorestore;
+mod container_mongosh;
+mod mongodump;
+mod mongorestore;
 mod mongosh;
 mod vscode;
 
-pub use compass::Compass;
+pub use compass::{Compass, CompassFavorites};
+pub use container_exec::{ContainerExec, InteractiveExec};
+pub use container_mongodump::ContainerMongodump;
+pub use container_mongorestore::ContainerMongorestore;
+pub use container_mongosh::{
+    ContainerMongosh, container_connection_string, container_database_connection_string,
+};
+pub use mongodump::Mongodump;
+pub use mongorestore::Mongorestore;
 pub use mongosh::Mongosh;
 pub use vscode::VsCode;
 
+/// Connector to use when `--connectWith` is omitted.
+///
+/// Precedence: the `--defaultConnector` flag, then the `MONGODB_ATLAS_LOCAL_DEFAULT_CONNECTOR` environment variable.
+pub fn default_connector(flag: Option<ConnectWith>) -> Result<Option<ConnectWith>> {
+    if flag.is_some() {
+        return Ok(flag);
+    }
+
+    let key = crate::env::MONGODB_ATLAS_LOCAL_DEFAULT_CONNECTOR;
+    let value = match env::var(key) {
+        Ok(value) => value,
+        Err(env::VarError::NotPresent) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    ConnectWith::from_str(&value, false).map(Some).map_err(|_| {
+        let valid_values = ConnectWith::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect::<Vec<_>>();
+        anyhow!(
+            "invalid value for {}: expected one of {}, got '{}'",
+            key,
+            valid_values.join(", "),
+            value
+        )
+    })
+}
+
 #[async_trait]
 pub trait Connector {
     async fn is_available(&self) -> bool;
-    async fn launch(&self, params: &DeploymentParams) -> Result<()>;
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()>;
 }
 
+/// Connector that is run in the background of a command, e.g. behind a spinner.
+///
+/// The lines the connector writes to stderr are sent to `output` instead of being printed, so the command can report
+/// the progress. The sender is dropped when the connector exits.
+#[async_trait]
+pub trait BackgroundConnector {
+    async fn is_available(&self) -> bool;
+    async fn run(
+        &self,
+        params: &DeploymentParams,
+        options: &ConnectorOptions,
+        output: UnboundedSender<String>,
+    ) -> Result<()>;
+}
+
+/// Launches a fallback when the preferred connector is not available.
+pub struct Fallback<C: ?Sized> {
+    preferred: Box<C>,
+    fallback: Box<C>,
+}
+
+impl<C: ?Sized> Fallback<C> {
+    pub fn new(preferred: Box<C>, fallback: Box<C>) -> Self {
+        Self {
+            preferred,
+            fallback,
+        }
+    }
+}
+
+/// Connector that launches a fallback connector when the preferred connector is not available.
+pub type WithFallback = Fallback<dyn Connector + Send + Sync>;
+
+/// Background connector that runs a fallback connector when the preferred connector is not available.
+pub type BackgroundWithFallback = Fallback<dyn BackgroundConnector + Send + Sync>;
+
+#[async_trait]
+impl Connector for WithFallback {
+    async fn is_available(&self) -> bool {
+        self.preferred.is_available().await || self.fallback.is_available().await
+    }
+
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()> {
+        if self.preferred.is_available().await {
+            return self.preferred.launch(params, options).await;
+        }
+
+        debug!("preferred connector is not available, launching the fallback connector");
+        self.fallback.launch(params, options).await
+    }
+}
+
+#[async_trait]
+impl BackgroundConnector for BackgroundWithFallback {
+    async fn is_available(&self) -> bool {
+        self.preferred.is_available().await || self.fallback.is_available().await
+    }
+
+    async fn run(
+        &self,
+        params: &DeploymentParams,
+        options: &ConnectorOptions,
+        output: UnboundedSender<String>,
+    ) -> Result<()> {
+        if self.preferred.is_available().await {
+            return self.preferred.run(params, options, output).await;
+        }
+
+        debug!("preferred connector is not available, running the fallback connector");
+        self.fallback.run(params, options, output).await
+    }
+}
+
+/// Connector specific launch options, passed with `--connectorArg key=value`.
+///
+/// Every connector maps the options it supports to its command line, unknown options are ignored with a warning.
+pub type ConnectorOptions = BTreeMap<String, String>;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DeploymentParams {
     pub name: String,
@@ -37,6 +168,39 @@ impl DeploymentParams {
     }
 }
 
+/// Error returned when a connector exits with a non-zero exit code.
+#[derive(Debug, thiserror::Error)]
+#[error("connector exited with code {code}")]
+pub struct ConnectorExitError {
+    pub code: i32,
+}
+
+/// Exit the process with the connector's exit code when the connector exited with a non-zero exit code.
+///
+/// Other errors are returned as is.
+pub fn exit_on_connector_exit(result: Result<()>) -> Result<()> {
+    match result {
+        Err(e) => match e.downcast_ref::<ConnectorExitError>() {
+            Some(ConnectorExitError { code }) => exit(*code),
+            None => Err(e),
+        },
+        Ok(()) => Ok(()),
+    }
+}
+
+/// Print a warning for every option that is not supported by the connector.
+fn warn_unknown_options(connector_name: &str, options: &ConnectorOptions, supported: &[&str]) {
+    for key in options
+        .keys()
+        .filter(|key| !supported.contains(&key.as_str()))
+    {
+        eprintln!(
+            "Warning: ignoring unknown connector argument '{key}' for {connector_name}, supported arguments: {}",
+            supported.join(", ")
+        );
+    }
+}
+
 async fn launch<F, P>(bin: P, customizer: F) -> Result<()>
 where
     P: AsRef<OsStr>,
@@ -52,7 +216,49 @@ where
     let status = command.status().await?;
 
     if !status.success() {
-        exit(status.code().unwrap_or(1));
+        return Err(ConnectorExitError {
+            code: status.code().unwrap_or(1),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Launch the binary like [`launch`], the lines it writes to stderr are sent to `output` instead of being printed.
+///
+/// Nothing is read from stdin and stdout is discarded, the binary runs in the background of the command.
+async fn run_in_background<F, P>(
+    bin: P,
+    output: &UnboundedSender<String>,
+    customizer: F,
+) -> Result<()>
+where
+    P: AsRef<OsStr>,
+    F: FnOnce(&mut Command),
+{
+    let mut command = Command::new(bin);
+    command.stdin(Stdio::null());
+    command.stdout(Stdio::null());
+    command.stderr(Stdio::piped());
+    command.envs(env::vars());
+
+    customizer(&mut command);
+    let mut child = command.spawn()?;
+
+    let stderr = child.stderr.take().context("capturing stderr")?;
+    let mut lines = BufReader::new(stderr).lines();
+    while let Some(line) = lines.next_line().await? {
+        // The receiver only reports progress, the binary keeps running when it's gone
+        let _ = output.send(line);
+    }
+
+    let status = child.wait().await?;
+    if !status.success() {
+        return Err(ConnectorExitError {
+            code: status.code().unwrap_or(1),
+        }
+        .into());
     }
 
     Ok(())
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use which::which;
+
+use super::{Connector, ConnectorOptions, DeploymentParams, launch, warn_unknown_options};
+
+/// Connector arguments supported by mongodump.
+pub(super) const SUPPORTED_OPTIONS: &[&str] = &["db", "out"];
+
+/// Directory mongodump writes the dump to when no output directory is passed.
+pub(super) const DEFAULT_DUMP_DIR: &str = "dump";
+
+/// Dumps the data of the deployment with mongodump, the options select the database and the output directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Mongodump;
+
+impl Mongodump {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Connector for Mongodump {
+    async fn is_available(&self) -> bool {
+        mongodump_bin().await.is_some_and(|path| path.exists())
+    }
+
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()> {
+        warn_unknown_options("mongodump", options, SUPPORTED_OPTIONS);
+
+        let bin = mongodump_bin().await.context("mongodump not found")?;
+        launch(bin, |command| {
+            command.args(mongodump_args(&params.connection_string, options));
+        })
+        .await
+    }
+}
+
+/// Arguments to launch mongodump with, shared with the mongodump inside the deployment container.
+pub(super) fn mongodump_args(connection_string: &str, options: &ConnectorOptions) -> Vec<String> {
+    let mut args = vec![format!("--uri={connection_string}")];
+    if let Some(db) = options.get("db") {
+        args.push(format!("--db={db}"));
+    }
+    args.push(format!(
+        "--out={}",
+        options.get("out").map_or(DEFAULT_DUMP_DIR, String::as_str)
+    ));
+    args
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const MONGODUMP_BIN: &str = "mongodump";
+
+#[cfg(target_os = "windows")]
+const MONGODUMP_BIN: &str = "mongodump.exe";
+
+async fn mongodump_bin() -> Option<PathBuf> {
+    which(MONGODUMP_BIN).ok()
+}
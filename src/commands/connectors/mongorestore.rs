@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tokio::sync::mpsc::UnboundedSender;
+use which::which;
+
+use super::{
+    BackgroundConnector, ConnectorOptions, DeploymentParams, run_in_background,
+    warn_unknown_options,
+};
+
+/// Connector arguments supported by mongorestore.
+pub(super) const SUPPORTED_OPTIONS: &[&str] = &["dir", "drop"];
+
+/// Restores a dump into the deployment with mongorestore, the options select the dump directory and whether to drop
+/// the collections first.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Mongorestore;
+
+impl Mongorestore {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl BackgroundConnector for Mongorestore {
+    async fn is_available(&self) -> bool {
+        mongorestore_bin().await.is_some_and(|path| path.exists())
+    }
+
+    async fn run(
+        &self,
+        params: &DeploymentParams,
+        options: &ConnectorOptions,
+        output: UnboundedSender<String>,
+    ) -> Result<()> {
+        warn_unknown_options("mongorestore", options, SUPPORTED_OPTIONS);
+
+        let bin = mongorestore_bin().await.context("mongorestore not found")?;
+        let dir = options.get("dir").context("no dump directory")?;
+        run_in_background(bin, &output, |command| {
+            command.args(mongorestore_args(&params.connection_string, options, dir));
+        })
+        .await
+    }
+}
+
+/// Arguments to launch mongorestore with, shared with the mongorestore inside the deployment container.
+pub(super) fn mongorestore_args(
+    connection_string: &str,
+    options: &ConnectorOptions,
+    dir: &str,
+) -> Vec<String> {
+    let mut args = vec![format!("--uri={connection_string}")];
+    if options.get("drop").is_some_and(|drop| drop == "true") {
+        args.push("--drop".to_string());
+    }
+    args.push(dir.to_string());
+    args
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const MONGORESTORE_BIN: &str = "mongorestore";
+
+#[cfg(target_os = "windows")]
+const MONGORESTORE_BIN: &str = "mongorestore.exe";
+
+async fn mongorestore_bin() -> Option<PathBuf> {
+    which(MONGORESTORE_BIN).ok()
+}
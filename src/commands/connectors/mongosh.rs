@@ -3,7 +3,10 @@ use async_trait::async_trait;
 use std::path::PathBuf;
 use which::which;
 
-use super::{Connector, DeploymentParams, launch};
+use super::{Connector, ConnectorOptions, DeploymentParams, launch, warn_unknown_options};
+
+/// Connector arguments supported by mongosh.
+pub(super) const SUPPORTED_OPTIONS: &[&str] = &["eval", "file", "quiet"];
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Mongosh;
@@ -20,15 +23,34 @@ impl Connector for Mongosh {
         mongosh_bin().await.is_some_and(|path| path.exists())
     }
 
-    async fn launch(&self, params: &DeploymentParams) -> Result<()> {
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()> {
+        warn_unknown_options("mongosh", options, SUPPORTED_OPTIONS);
+
         let bin = mongosh_bin().await.context("mongosh not found")?;
         launch(bin, |command| {
-            command.arg(&params.connection_string);
+            command.args(mongosh_args(&params.connection_string, options));
         })
         .await
     }
 }
 
+/// Arguments to launch mongosh with, shared with the mongosh inside the deployment container.
+pub(super) fn mongosh_args(connection_string: &str, options: &ConnectorOptions) -> Vec<String> {
+    let mut args = vec![connection_string.to_string()];
+    if let Some(script) = options.get("eval") {
+        args.push("--eval".to_string());
+        args.push(script.clone());
+    }
+    if let Some(file) = options.get("file") {
+        args.push("--file".to_string());
+        args.push(file.clone());
+    }
+    if options.get("quiet").is_some_and(|quiet| quiet == "true") {
+        args.push("--quiet".to_string());
+    }
+    args
+}
+
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 const MONGOSH_BIN: &str = "mongosh";
 
@@ -4,7 +4,10 @@ use std::path::PathBuf;
 use url::Url;
 use which::which;
 
-use super::{Connector, DeploymentParams, launch};
+use super::{Connector, ConnectorOptions, DeploymentParams, launch, warn_unknown_options};
+
+/// Connector arguments supported by VS Code.
+const SUPPORTED_OPTIONS: &[&str] = &["workspace"];
 
 pub struct VsCode;
 
@@ -20,11 +23,17 @@ impl Connector for VsCode {
         vscode_bin().await.is_some_and(|path| path.exists())
     }
 
-    async fn launch(&self, params: &DeploymentParams) -> Result<()> {
+    async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()> {
+        warn_unknown_options("VS Code", options, SUPPORTED_OPTIONS);
+
         let bin = vscode_bin().await.context("vscode not found")?;
         let deeplink = build_deeplink(&params.connection_string, &params.name);
 
         launch(bin, |command| {
+            // Open the workspace (file or folder) before connecting, e.g. --connectorArg workspace=./my-project
+            if let Some(workspace) = options.get("workspace") {
+                command.arg(workspace);
+            }
             command.arg("--open-url");
             command.arg(&deeplink);
         })
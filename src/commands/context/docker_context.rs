@@ -0,0 +1,396 @@
+//! Selection of a context of the Docker CLI.
+//!
+//! The Docker CLI saves the endpoint of every context it knows, e.g. Docker Desktop or a remote engine, in the metadata
+//! in its configuration directory. The context is selected like the Docker CLI does it: the `--dockerContext` option,
+//! then `DOCKER_HOST`, then `DOCKER_CONTEXT`, then the current context set with `docker context use`. The TLS
+//! material of a context is not used, only its endpoint.
+
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result, bail};
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::{
+    dependencies::{DirectoryLister, FileReader, PathKind, PathResolver, TokioFs},
+    env,
+    settings::Settings,
+};
+
+/// Name of the Docker context that uses the defaults, it has no metadata.
+const DEFAULT_DOCKER_CONTEXT: &str = "default";
+
+/// Name of the Docker endpoint of a context, a context can also have e.g. a Kubernetes endpoint.
+const DOCKER_ENDPOINT: &str = "docker";
+
+// Filesystem dependencies to read the Docker contexts
+pub trait DockerContextFs: FileReader + PathResolver + DirectoryLister + Send + Sync {}
+impl<T: FileReader + PathResolver + DirectoryLister + Send + Sync> DockerContextFs for T {}
+
+/// Configuration of the Docker CLI, only the current context is used.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DockerCliConfig {
+    current_context: Option<String>,
+}
+
+/// Metadata of a Docker context, as stored in `contexts/meta/<hash>/meta.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DockerContextMeta {
+    name: String,
+    #[serde(default)]
+    endpoints: HashMap<String, DockerEndpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DockerEndpoint {
+    host: Option<String>,
+}
+
+/// Use the endpoint of the Docker context for every Docker connection of this invocation.
+///
+/// Nothing is selected when the default context is used, so the Docker defaults apply.
+pub async fn use_docker_context(
+    docker_context: Option<&str>,
+    settings: &mut Settings,
+) -> Result<()> {
+    let host = resolve_docker_context_host(&TokioFs::new(), docker_context, |name| {
+        std::env::var_os(name)
+    })
+    .await?;
+
+    if let Some(host) = host {
+        debug!(docker_host = %host, "using Docker context");
+        settings.docker.host = Some(host);
+    }
+
+    Ok(())
+}
+
+/// Resolve the endpoint of the selected Docker context, `var` looks up environment variables.
+///
+/// Returns `None` when the Docker defaults should be used. A context that is selected explicitly must exist, while a
+/// current context that no longer exists only logs a warning, like a stale configuration of the Docker CLI.
+async fn resolve_docker_context_host(
+    fs: &dyn DockerContextFs,
+    docker_context: Option<&str>,
+    var: impl Fn(&str) -> Option<OsString>,
+) -> Result<Option<String>> {
+    let config_dir = var(env::DOCKER_CONFIG).map(PathBuf::from).or_else(|| {
+        let home = if cfg!(target_os = "windows") {
+            var(env::USERPROFILE)
+        } else {
+            var(env::HOME)
+        };
+        home.map(|home| PathBuf::from(home).join(".docker"))
+    });
+
+    let (name, explicit) = match docker_context {
+        Some(name) => (name.to_string(), true),
+        // DOCKER_HOST takes precedence over the contexts that aren't selected with the option
+        None if var(env::DOCKER_HOST).is_some() => return Ok(None),
+        None => match var(env::DOCKER_CONTEXT) {
+            Some(name) => (name.to_string_lossy().into_owned(), true),
+            None => {
+                let current_context = match &config_dir {
+                    // A broken configuration of the Docker CLI must not break commands that don't need a context
+                    Some(config_dir) => current_context(fs, config_dir).await.unwrap_or_else(|e| {
+                        warn!(error = %e, "reading the current Docker context failed, using the Docker defaults");
+                        None
+                    }),
+                    None => None,
+                };
+                let Some(name) = current_context else {
+                    return Ok(None);
+                };
+                (name, false)
+            }
+        },
+    };
+
+    if name == DEFAULT_DOCKER_CONTEXT {
+        return Ok(None);
+    }
+
+    let host = match &config_dir {
+        Some(config_dir) => find_docker_context_host(fs, config_dir, &name).await?,
+        None => None,
+    };
+
+    match host {
+        Some(host) => Ok(Some(host)),
+        None if explicit => bail!("Docker context '{}' does not exist", name),
+        None => {
+            warn!(
+                docker_context = %name,
+                "the current Docker context does not exist, using the Docker defaults"
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// The current context of the Docker CLI, set with `docker context use`.
+async fn current_context(fs: &dyn DockerContextFs, config_dir: &Path) -> Result<Option<String>> {
+    let path = config_dir.join("config.json");
+    if fs.path_kind(&path).await? != Some(PathKind::File) {
+        return Ok(None);
+    }
+
+    let contents = fs.read_to_string(&path).await?;
+    let config: DockerCliConfig = serde_json::from_str(&contents)
+        .with_context(|| format!("parsing Docker configuration {}", path.display()))?;
+
+    Ok(config.current_context)
+}
+
+/// Find the Docker endpoint of a context, `None` when the context doesn't exist.
+///
+/// The metadata directories are named after a hash of the context name, so the name is matched on the metadata instead.
+/// Metadata that can't be read or parsed is skipped with a warning, a broken context doesn't hide the other contexts.
+async fn find_docker_context_host(
+    fs: &dyn DockerContextFs,
+    config_dir: &Path,
+    name: &str,
+) -> Result<Option<String>> {
+    let meta_dir = config_dir.join("contexts").join("meta");
+    if fs.path_kind(&meta_dir).await? != Some(PathKind::Directory) {
+        return Ok(None);
+    }
+
+    for context_dir in fs.read_dir(&meta_dir).await? {
+        let meta = match read_docker_context_meta(fs, &context_dir).await {
+            Ok(Some(meta)) => meta,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!(
+                    error = format!("{e:#}"),
+                    "skipping Docker context metadata that can't be read"
+                );
+                continue;
+            }
+        };
+        if meta.name != name {
+            continue;
+        }
+
+        return match meta
+            .endpoints
+            .get(DOCKER_ENDPOINT)
+            .and_then(|endpoint| endpoint.host.clone())
+        {
+            Some(host) => Ok(Some(host)),
+            None => bail!("Docker context '{}' has no Docker endpoint", name),
+        };
+    }
+
+    Ok(None)
+}
+
+/// Read the metadata in the directory of a context, `None` when the directory has no metadata.
+async fn read_docker_context_meta(
+    fs: &dyn DockerContextFs,
+    context_dir: &Path,
+) -> Result<Option<DockerContextMeta>> {
+    let path = context_dir.join("meta.json");
+    if fs.path_kind(&path).await? != Some(PathKind::File) {
+        return Ok(None);
+    }
+
+    let contents = fs.read_to_string(&path).await?;
+    let meta = serde_json::from_str(&contents)
+        .with_context(|| format!("parsing Docker context metadata {}", path.display()))?;
+
+    Ok(Some(meta))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::mocks::MockTokioFs;
+
+    const CONFIG_DIR: &str = "/home/user/.docker";
+
+    fn vars(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars = HashMap::<_, _>::from_iter(vars.iter().copied());
+        move |name| vars.get(name).map(OsString::from)
+    }
+
+    /// Filesystem with the Docker CLI configuration and the metadata of the desktop-linux and remote contexts, listed
+    /// after the metadata of a broken context.
+    fn docker_config_fs(config: Option<&'static str>) -> MockTokioFs {
+        let config_dir = Path::new(CONFIG_DIR);
+        let files = HashMap::from([
+            (
+                config_dir.join("contexts/meta/0a1b/meta.json"),
+                r#"{"Name":"broken","Endpoints":"#,
+            ),
+            (
+                config_dir.join("contexts/meta/1b2c/meta.json"),
+                r#"{"Name":"desktop-linux","Metadata":{},"Endpoints":{"docker":{"Host":"unix:///home/user/.docker/run/docker.sock","SkipTLSVerify":false}}}"#,
+            ),
+            (
+                config_dir.join("contexts/meta/9f8e/meta.json"),
+                r#"{"Name":"remote","Metadata":{},"Endpoints":{"docker":{"Host":"tcp://10.0.0.5:2376","SkipTLSVerify":false}}}"#,
+            ),
+        ]);
+        let config_path = config_dir.join("config.json");
+        let meta_dir = config_dir.join("contexts/meta");
+
+        let mut mock_fs = MockTokioFs::new();
+        let kinds_meta_dir = meta_dir.clone();
+        let kinds_files = files.clone();
+        let kinds_config_path = config_path.clone();
+        mock_fs.expect_path_kind().returning(move |path| {
+            Ok(if path == kinds_meta_dir {
+                Some(PathKind::Directory)
+            } else if kinds_files.contains_key(path)
+                || (path == kinds_config_path && config.is_some())
+            {
+                Some(PathKind::File)
+            } else {
+                None
+            })
+        });
+        mock_fs
+            .expect_read_dir()
+            .withf(move |path| path == meta_dir)
+            .returning(|path| {
+                Ok(vec![
+                    path.join("0a1b"),
+                    path.join("1b2c"),
+                    path.join("9f8e"),
+                ])
+            });
+        mock_fs.expect_read_to_string().returning(move |path| {
+            if path == config_path {
+                return Ok(config.unwrap_or_default().to_string());
+            }
+            Ok(files[path].to_string())
+        });
+        mock_fs
+    }
+
+    #[tokio::test]
+    async fn test_resolve_docker_context_from_option() {
+        let host = resolve_docker_context_host(
+            &docker_config_fs(None),
+            Some("remote"),
+            // The option takes precedence over the environment variables
+            vars(&[
+                (env::DOCKER_CONFIG, CONFIG_DIR),
+                (env::DOCKER_HOST, "unix:///var/run/docker.sock"),
+                (env::DOCKER_CONTEXT, "desktop-linux"),
+            ]),
+        )
+        .await
+        .expect("resolving the Docker context should succeed");
+
+        assert_eq!(host, Some("tcp://10.0.0.5:2376".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_docker_context_precedence() {
+        let config = Some(r#"{"auths":{},"currentContext":"desktop-linux"}"#);
+
+        let from_current_context = resolve_docker_context_host(
+            &docker_config_fs(config),
+            None,
+            vars(&[(env::DOCKER_CONFIG, CONFIG_DIR)]),
+        )
+        .await
+        .expect("resolving the Docker context should succeed");
+        assert_eq!(
+            from_current_context,
+            Some("unix:///home/user/.docker/run/docker.sock".to_string())
+        );
+
+        let from_env = resolve_docker_context_host(
+            &docker_config_fs(config),
+            None,
+            vars(&[
+                (env::DOCKER_CONFIG, CONFIG_DIR),
+                (env::DOCKER_CONTEXT, "remote"),
+            ]),
+        )
+        .await
+        .expect("resolving the Docker context should succeed");
+        assert_eq!(from_env, Some("tcp://10.0.0.5:2376".to_string()));
+
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().never();
+        let from_docker_host = resolve_docker_context_host(
+            &mock_fs,
+            None,
+            vars(&[
+                (env::DOCKER_CONFIG, CONFIG_DIR),
+                (env::DOCKER_HOST, "tcp://localhost:2375"),
+                (env::DOCKER_CONTEXT, "remote"),
+            ]),
+        )
+        .await
+        .expect("resolving the Docker context should succeed");
+        assert_eq!(from_docker_host, None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_docker_context_skips_broken_metadata() {
+        let host = resolve_docker_context_host(
+            &docker_config_fs(None),
+            Some("desktop-linux"),
+            vars(&[(env::DOCKER_CONFIG, CONFIG_DIR)]),
+        )
+        .await
+        .expect("a broken context should not fail resolving the other contexts");
+        assert_eq!(
+            host,
+            Some("unix:///home/user/.docker/run/docker.sock".to_string())
+        );
+
+        let error = resolve_docker_context_host(
+            &docker_config_fs(None),
+            Some("broken"),
+            vars(&[(env::DOCKER_CONFIG, CONFIG_DIR)]),
+        )
+        .await
+        .expect_err("resolving a broken Docker context should fail");
+        assert_eq!(error.to_string(), "Docker context 'broken' does not exist");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_default_and_missing_docker_context() {
+        let default = resolve_docker_context_host(
+            &docker_config_fs(Some(r#"{"currentContext":"default"}"#)),
+            None,
+            vars(&[(env::DOCKER_CONFIG, CONFIG_DIR)]),
+        )
+        .await
+        .expect("resolving the Docker context should succeed");
+        assert_eq!(default, None);
+
+        // A stale current context falls back to the defaults, a context selected explicitly must exist
+        let stale = resolve_docker_context_host(
+            &docker_config_fs(Some(r#"{"currentContext":"colima"}"#)),
+            None,
+            vars(&[(env::DOCKER_CONFIG, CONFIG_DIR)]),
+        )
+        .await
+        .expect("resolving the Docker context should succeed");
+        assert_eq!(stale, None);
+
+        let error = resolve_docker_context_host(
+            &docker_config_fs(None),
+            Some("colima"),
+            vars(&[(env::DOCKER_CONFIG, CONFIG_DIR)]),
+        )
+        .await
+        .expect_err("resolving a missing Docker context should fail");
+        assert_eq!(error.to_string(), "Docker context 'colima' does not exist");
+    }
+}
@@ -0,0 +1,133 @@
+//! Command to list the saved contexts.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        context::{Contexts, contexts_file_path},
+    },
+    config_dir::{ConfigFs, load_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+    table::Table,
+};
+
+pub struct List {
+    contexts_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::context::List, &Settings)> for List {
+    type Error = anyhow::Error;
+
+    fn try_from((_, settings): (args::context::List, &Settings)) -> Result<Self> {
+        Ok(Self {
+            contexts_file: contexts_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+/// A saved context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextEntry {
+    pub name: String,
+    pub docker_host: String,
+}
+
+/// Result of the context list command, sorted by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListResult(Vec<ContextEntry>);
+
+impl From<&ListResult> for Table {
+    fn from(value: &ListResult) -> Self {
+        Table::from_iter(
+            &value.0,
+            &[
+                ("NAME", |c| c.name.clone()),
+                ("DOCKER HOST", |c| c.docker_host.clone()),
+            ],
+        )
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Table::from(self).fmt(f)
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for List {
+    type Output = ListResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let contexts = load_toml::<Contexts>(self.fs.as_ref(), &self.contexts_file).await?;
+
+        Ok(ListResult(
+            contexts
+                .contexts
+                .into_iter()
+                .map(|(name, settings)| ContextEntry {
+                    name,
+                    docker_host: settings.docker_host,
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_list_contexts_sorted_by_name() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(concat!(
+                "[contexts.remote]\n",
+                "dockerHost = \"tcp://10.0.0.5:2375\"\n",
+                "[contexts.local]\n",
+                "dockerHost = \"unix:///var/run/docker.sock\"\n",
+            )
+            .to_string())
+        });
+
+        let mut list_command = List {
+            contexts_file: PathBuf::from("contexts.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = list_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ListResult(vec![
+                ContextEntry {
+                    name: "local".to_string(),
+                    docker_host: "unix:///var/run/docker.sock".to_string(),
+                },
+                ContextEntry {
+                    name: "remote".to_string(),
+                    docker_host: "tcp://10.0.0.5:2375".to_string(),
+                },
+            ])
+        );
+    }
+}
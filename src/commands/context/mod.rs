@@ -0,0 +1,102 @@
+//! Commands to manage contexts.
+//!
+//! A context is a named set of connection settings, e.g. the Docker host, that is saved in the configuration directory.
+//! The context to use is selected with the global `--context` option, see [`use_context`]. Without a saved context,
+//! the contexts of the Docker CLI are used, see [`docker_context`].
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    config_dir::{config_dir, load_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub mod docker_context;
+pub mod list;
+pub mod remove;
+pub mod set;
+
+/// Name of the file the contexts are saved in, inside the configuration directory.
+const CONTEXTS_FILE_NAME: &str = "contexts.toml";
+
+/// The saved contexts, as stored in the contexts file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contexts {
+    #[serde(default)]
+    pub contexts: BTreeMap<String, ContextSettings>,
+}
+
+/// Connection settings of a context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextSettings {
+    pub docker_host: String,
+}
+
+/// Path of the file the contexts are saved in.
+pub fn contexts_file_path(settings: &Settings) -> Result<PathBuf> {
+    Ok(config_dir(settings)?.join(CONTEXTS_FILE_NAME))
+}
+
+/// Use the saved context for every Docker connection of this invocation.
+pub async fn use_context(context_name: &str, settings: &mut Settings) -> Result<()> {
+    let path = contexts_file_path(settings)?;
+    let mut contexts = load_toml::<Contexts>(&TokioFs::new(), &path).await?;
+
+    let context = contexts
+        .contexts
+        .remove(context_name)
+        .ok_or_else(|| anyhow!("context '{}' does not exist", context_name))?;
+
+    debug!(context_name, docker_host = %context.docker_host, "using context");
+    settings.docker.host = Some(context.docker_host);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_load_contexts_without_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+
+        let contexts = load_toml::<Contexts>(&mock_fs, Path::new("contexts.toml"))
+            .await
+            .expect("loading contexts should succeed");
+
+        assert_eq!(contexts, Contexts::default());
+    }
+
+    #[tokio::test]
+    async fn test_load_contexts_from_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[contexts.ci]\ndockerHost = \"tcp://10.0.0.5:2375\"\n".to_string())
+        });
+
+        let contexts = load_toml::<Contexts>(&mock_fs, Path::new("contexts.toml"))
+            .await
+            .expect("loading contexts should succeed");
+
+        assert_eq!(
+            contexts.contexts.get("ci"),
+            Some(&ContextSettings {
+                docker_host: "tcp://10.0.0.5:2375".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Command to remove a saved context.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        context::{Contexts, contexts_file_path},
+    },
+    config_dir::{ConfigFs, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub struct Remove {
+    context_name: String,
+    contexts_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::context::Remove, &Settings)> for Remove {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::context::Remove, &Settings)) -> Result<Self> {
+        Ok(Self {
+            context_name: args.context_name,
+            contexts_file: contexts_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RemoveResult {
+    Removed { context_name: String },
+    Failed { context_name: String, error: String },
+}
+
+impl Display for RemoveResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Removed { context_name } => write!(f, "Context '{}' removed", context_name),
+            Self::Failed {
+                context_name,
+                error,
+            } => write!(f, "Removing context '{}' failed: {}", context_name, error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Remove {
+    type Output = RemoveResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut contexts = load_toml::<Contexts>(self.fs.as_ref(), &self.contexts_file).await?;
+
+        if contexts.contexts.remove(&self.context_name).is_none() {
+            return Ok(RemoveResult::Failed {
+                context_name: self.context_name.clone(),
+                error: "context not found".to_string(),
+            });
+        }
+
+        debug!(
+            context_name = %self.context_name,
+            path = %self.contexts_file.display(),
+            "removing context"
+        );
+        save_toml(self.fs.as_ref(), &self.contexts_file, &contexts).await?;
+
+        Ok(RemoveResult::Removed {
+            context_name: self.context_name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    fn create_remove_command(fs: MockTokioFs) -> Remove {
+        Remove {
+            context_name: "ci".to_string(),
+            contexts_file: PathBuf::from("contexts.toml"),
+            fs: Box::new(fs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_context() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(concat!(
+                "[contexts.ci]\n",
+                "dockerHost = \"tcp://10.0.0.5:2375\"\n",
+                "[contexts.local]\n",
+                "dockerHost = \"unix:///var/run/docker.sock\"\n",
+            )
+            .to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|_, contents| {
+                !contents.contains("[contexts.ci]") && contents.contains("[contexts.local]")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let result = create_remove_command(mock_fs)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RemoveResult::Removed {
+                context_name: "ci".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_context_not_found() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+
+        let result = create_remove_command(mock_fs)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RemoveResult::Failed {
+                context_name: "ci".to_string(),
+                error: "context not found".to_string()
+            }
+        );
+    }
+}
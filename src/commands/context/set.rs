@@ -0,0 +1,186 @@
+//! Command to save a context.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+use url::Url;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        context::{ContextSettings, Contexts, contexts_file_path},
+    },
+    config_dir::{ConfigFs, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+/// Docker host schemes the CLI can connect to.
+const SUPPORTED_DOCKER_HOST_SCHEMES: &[&str] = &["unix", "npipe", "tcp", "http"];
+
+pub struct Set {
+    context_name: String,
+    docker_host: String,
+    contexts_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::context::Set, &Settings)> for Set {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::context::Set, &Settings)) -> Result<Self> {
+        Ok(Self {
+            context_name: args.context_name,
+            docker_host: args.docker_host,
+            contexts_file: contexts_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum SetResult {
+    Saved { context_name: String },
+    Failed { context_name: String, error: String },
+}
+
+impl Display for SetResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Saved { context_name } => write!(f, "Context '{}' saved", context_name),
+            Self::Failed {
+                context_name,
+                error,
+            } => write!(f, "Saving context '{}' failed: {}", context_name, error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Set {
+    type Output = SetResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        // Validate the Docker host before saving, a typo would otherwise only show up when the context is used
+        if let Err(error) = validate_docker_host(&self.docker_host) {
+            return Ok(SetResult::Failed {
+                context_name: self.context_name.clone(),
+                error,
+            });
+        }
+
+        let mut contexts = load_toml::<Contexts>(self.fs.as_ref(), &self.contexts_file).await?;
+        contexts.contexts.insert(
+            self.context_name.clone(),
+            ContextSettings {
+                docker_host: self.docker_host.clone(),
+            },
+        );
+
+        debug!(
+            context_name = %self.context_name,
+            path = %self.contexts_file.display(),
+            "saving context"
+        );
+        save_toml(self.fs.as_ref(), &self.contexts_file, &contexts).await?;
+
+        Ok(SetResult::Saved {
+            context_name: self.context_name.clone(),
+        })
+    }
+}
+
+/// Check that the Docker host is a URL with a scheme the CLI can connect to.
+fn validate_docker_host(docker_host: &str) -> Result<(), String> {
+    let url = Url::parse(docker_host)
+        .map_err(|e| format!("invalid Docker host '{}': {}", docker_host, e))?;
+
+    if !SUPPORTED_DOCKER_HOST_SCHEMES.contains(&url.scheme()) {
+        return Err(format!(
+            "unsupported Docker host scheme '{}', expected one of: {}",
+            url.scheme(),
+            SUPPORTED_DOCKER_HOST_SCHEMES.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    fn create_set_command(docker_host: &str, fs: MockTokioFs) -> Set {
+        Set {
+            context_name: "ci".to_string(),
+            docker_host: docker_host.to_string(),
+            contexts_file: PathBuf::from("/config/atlas-local/contexts.toml"),
+            fs: Box::new(fs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_adds_context_to_existing_contexts() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[contexts.local]\ndockerHost = \"unix:///var/run/docker.sock\"\n".to_string())
+        });
+        mock_fs
+            .expect_create_dir_all()
+            .withf(|path| path == Path::new("/config/atlas-local"))
+            .return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/config/atlas-local/contexts.toml")
+                    && contents.contains("[contexts.local]")
+                    && contents.contains("[contexts.ci]")
+                    && contents.contains("dockerHost = \"tcp://10.0.0.5:2375\"")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut set_command = create_set_command("tcp://10.0.0.5:2375", mock_fs);
+
+        let result = set_command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetResult::Saved {
+                context_name: "ci".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_unsupported_docker_host() {
+        // Nothing is read or written when the Docker host is invalid
+        let mock_fs = MockTokioFs::new();
+
+        let mut set_command = create_set_command("ftp://10.0.0.5", mock_fs);
+
+        let result = set_command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetResult::Failed {
+                context_name: "ci".to_string(),
+                error:
+                    "unsupported Docker host scheme 'ftp', expected one of: unix, npipe, tcp, http"
+                        .to_string()
+            }
+        );
+    }
+}
@@ -11,9 +11,22 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::Serialize;
 
 use crate::formatting::{Format, Formattable};
 
+/// Why an operation was canceled, reported by the results of commands that can be canceled.
+///
+/// Serialized as the `reason` of the `canceled` outcome, so tooling can tell a declined prompt from an interruption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelReason {
+    /// The user canceled or declined a prompt.
+    Prompt,
+    /// The process received Ctrl-C or SIGTERM while the operation was running.
+    Signal,
+}
+
 /// Trait for all commands.
 ///
 /// A command is a unit of work that can be executed.
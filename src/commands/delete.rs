@@ -1,19 +1,30 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use atlas_local::{Client, DeleteDeploymentError};
-use bollard::Docker;
 use serde::Serialize;
+use tracing::warn;
 
 use crate::{
     args,
-    commands::CommandWithOutput,
-    dependencies::DeploymentDeleter,
+    commands::{
+        CancelReason, CommandWithOutput,
+        config::{deployments_file_path, remove_deployment_settings},
+    },
+    config_dir::ConfigFs,
+    dependencies::{
+        DeploymentContainerRemover, DeploymentDeleter, RemoveContainerError, TokioFs,
+        connect_docker,
+    },
     interaction::{
         ConfirmationPrompt, ConfirmationPromptOptions, ConfirmationPromptResult, Interaction,
         SpinnerInteraction,
     },
+    settings::Settings,
 };
 
 // Interaction dependencies for the delete command
@@ -23,23 +34,32 @@ impl<T: ConfirmationPrompt + SpinnerInteraction + Send> DeleteInteraction for T
 pub struct Delete {
     deployment_name: String,
     force: bool,
+    keep_volumes: bool,
+    deployments_file: PathBuf,
 
     interaction: Box<dyn DeleteInteraction>,
-    deployment_deleter: Box<dyn DeploymentDeleter + Send>,
+    deployment_deleter: Box<dyn DeploymentDeleter + Send + Sync>,
+    container_remover: Box<dyn DeploymentContainerRemover + Send + Sync>,
+    fs: Box<dyn ConfigFs>,
 }
 
-impl TryFrom<args::Delete> for Delete {
+impl TryFrom<(args::Delete, &Settings)> for Delete {
     type Error = anyhow::Error;
 
-    fn try_from(args: args::Delete) -> Result<Self> {
+    fn try_from((args, settings): (args::Delete, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
         Ok(Self {
-            deployment_name: args.deployment_name,
+            // Only a single deployment name is dispatched to this command, without --all
+            deployment_name: args.deployment_names.into_iter().next().unwrap_or_default(),
             force: args.force,
+            keep_volumes: args.keep_volumes,
+            deployments_file: deployments_file_path(settings)?,
 
-            interaction: Box::new(Interaction::new()),
-            deployment_deleter: Box::new(Client::new(
-                Docker::connect_with_defaults().context("connecting to Docker")?,
-            )),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_deleter: Box::new(Client::new(docker.clone())),
+            container_remover: Box::new(docker),
+            fs: Box::new(TokioFs::new()),
         })
     }
 }
@@ -56,6 +76,7 @@ pub enum DeleteResult {
     },
     Canceled {
         deployment_name: String,
+        reason: CancelReason,
     },
 }
 
@@ -88,7 +109,11 @@ impl CommandWithOutput for Delete {
                 .interaction
                 .confirm(
                     ConfirmationPromptOptions::builder()
-                        .pre_confirmation_help_text("This operation will delete the deployment, and all of its data. This action cannot be undone.".to_string())
+                        .pre_confirmation_help_text(if self.keep_volumes {
+                            "This operation will delete the deployment, its data volumes are kept.".to_string()
+                        } else {
+                            "This operation will delete the deployment, and all of its data. This action cannot be undone.".to_string()
+                        })
                         .message(format!("Are you sure you want to terminate '{}'?", self.deployment_name))
                         .default(false)
                         .build())
@@ -101,6 +126,7 @@ impl CommandWithOutput for Delete {
                 // Operation cancelled by user.
                 return Ok(DeleteResult::Canceled {
                     deployment_name: self.deployment_name.clone(),
+                    reason: CancelReason::Prompt,
                 });
             }
         }
@@ -111,42 +137,114 @@ impl CommandWithOutput for Delete {
             .interaction
             .start_spinner("Deleting deployment...".to_string())?;
 
-        // Delete the deployment and handle the errors
-        if let Err(e) = self.deployment_deleter.delete(&self.deployment_name).await {
-            // Convert the error to a more user-friendly error
-            // This type of error is not an "error", it's one of the possible outcomes of the operation
-            return Ok(DeleteResult::Failed {
-                deployment_name: self.deployment_name.clone(),
+        let result = delete_deployment(
+            &self.deployment_name,
+            self.keep_volumes,
+            self.deployment_deleter.as_ref(),
+            self.container_remover.as_ref(),
+        )
+        .await;
+
+        if matches!(result, DeleteResult::Deleted { .. }) {
+            forget_deployment_settings(
+                self.fs.as_ref(),
+                &self.deployments_file,
+                &[self.deployment_name.clone()],
+            )
+            .await;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Remove the saved settings of deleted deployments, so a new deployment with the same name starts without them.
+///
+/// The deployments are deleted at this point, so a failure is only logged.
+pub(crate) async fn forget_deployment_settings(
+    fs: &dyn ConfigFs,
+    deployments_file: &Path,
+    deployment_names: &[String],
+) {
+    if let Err(e) = remove_deployment_settings(fs, deployments_file, deployment_names).await {
+        warn!(
+            error = format!("{e:#}"),
+            "removing the settings of deleted deployments failed"
+        );
+    }
+}
+
+/// Delete a single deployment, only removing its container when the volumes are kept.
+///
+/// Failures are one of the possible outcomes of the operation, so they are returned as [`DeleteResult::Failed`].
+pub(crate) async fn delete_deployment(
+    deployment_name: &str,
+    keep_volumes: bool,
+    deployment_deleter: &(dyn DeploymentDeleter + Send + Sync),
+    container_remover: &(dyn DeploymentContainerRemover + Send + Sync),
+) -> DeleteResult {
+    // When keeping the volumes, only remove the container
+    if keep_volumes {
+        if let Err(e) = container_remover
+            .remove_container_keep_volumes(deployment_name)
+            .await
+        {
+            return DeleteResult::Failed {
+                deployment_name: deployment_name.to_string(),
                 error: match e {
-                    DeleteDeploymentError::GetDeployment(..) => "deployment not found".to_string(),
-                    DeleteDeploymentError::ContainerStop(..) => {
-                        "failed to stop the container".to_string()
-                    }
-                    DeleteDeploymentError::ContainerRemove(..) => {
-                        "failed to delete the container".to_string()
+                    RemoveContainerError::NotFound => "deployment not found".to_string(),
+                    RemoveContainerError::Docker(e) => {
+                        format!("failed to delete the container: {e}")
                     }
                 },
-            });
+            };
         }
 
-        Ok(DeleteResult::Deleted {
-            deployment_name: self.deployment_name.clone(),
-        })
+        return DeleteResult::Deleted {
+            deployment_name: deployment_name.to_string(),
+        };
+    }
+
+    // Delete the deployment and handle the errors
+    if let Err(e) = deployment_deleter.delete(deployment_name).await {
+        // Convert the error to a more user-friendly error
+        // This type of error is not an "error", it's one of the possible outcomes of the operation
+        return DeleteResult::Failed {
+            deployment_name: deployment_name.to_string(),
+            error: match e {
+                DeleteDeploymentError::GetDeployment(..) => "deployment not found".to_string(),
+                DeleteDeploymentError::ContainerStop(..) => {
+                    "failed to stop the container".to_string()
+                }
+                DeleteDeploymentError::ContainerRemove(..) => {
+                    "failed to delete the container".to_string()
+                }
+            },
+        };
+    }
+
+    DeleteResult::Deleted {
+        deployment_name: deployment_name.to_string(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::mocks::MockDocker;
-    use crate::interaction::SpinnerHandle;
+    use crate::commands::test_support::create_spinner_handle;
+    use crate::dependencies::PathKind;
+    use crate::dependencies::mocks::{MockDocker, MockTokioFs};
     use crate::interaction::mocks::MockInteraction;
     use anyhow::anyhow;
     use bollard::errors::Error as BollardError;
     use std::io;
 
-    fn create_spinner_handle() -> SpinnerHandle {
-        SpinnerHandle::new(Box::new(|| {}))
+    /// Filesystem without a deployments file, no settings are saved.
+    fn create_fs() -> MockTokioFs {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().returning(|_| Ok(None));
+        mock_fs.expect_write_string().never();
+        mock_fs
     }
 
     #[tokio::test]
@@ -173,8 +271,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: false,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -211,8 +313,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: true,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -240,8 +346,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: false,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(MockDocker::new()),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -252,7 +362,8 @@ mod tests {
         assert_eq!(
             result,
             DeleteResult::Canceled {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                reason: CancelReason::Prompt,
             }
         );
     }
@@ -269,8 +380,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: false,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(MockDocker::new()),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -281,7 +396,8 @@ mod tests {
         assert_eq!(
             result,
             DeleteResult::Canceled {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                reason: CancelReason::Prompt,
             }
         );
     }
@@ -298,8 +414,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: false,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(MockDocker::new()),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command.execute().await;
@@ -335,8 +455,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: true,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -372,8 +496,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: true,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -409,8 +537,12 @@ mod tests {
         let mut delete_command = Delete {
             deployment_name: deployment_name.clone(),
             force: true,
+            keep_volumes: false,
             interaction: Box::new(mock_interaction),
             deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
         };
 
         let result = delete_command
@@ -426,4 +558,220 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_delete_keep_volumes_removes_container_only() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter.expect_delete().never();
+
+        let mut mock_remover = MockDocker::new();
+        let deployment_name_clone = deployment_name.clone();
+        mock_remover
+            .expect_remove_container_keep_volumes()
+            .withf(move |name| name == &deployment_name_clone)
+            .return_once(|_| Ok(()));
+
+        let mut delete_command = Delete {
+            deployment_name: deployment_name.clone(),
+            force: true,
+            keep_volumes: true,
+            interaction: Box::new(mock_interaction),
+            deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(mock_remover),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
+        };
+
+        let result = delete_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Deleted {
+                deployment_name: deployment_name.clone()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_keep_volumes_not_found() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_remover = MockDocker::new();
+        mock_remover
+            .expect_remove_container_keep_volumes()
+            .return_once(|_| Err(RemoveContainerError::NotFound));
+
+        let mut delete_command = Delete {
+            deployment_name: deployment_name.clone(),
+            force: true,
+            keep_volumes: true,
+            interaction: Box::new(mock_interaction),
+            deployment_deleter: Box::new(MockDocker::new()),
+            container_remover: Box::new(mock_remover),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
+        };
+
+        let result = delete_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Failed {
+                deployment_name: deployment_name.clone(),
+                error: "deployment not found".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_keep_volumes_docker_error() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_remover = MockDocker::new();
+        mock_remover
+            .expect_remove_container_keep_volumes()
+            .return_once(|_| {
+                Err(RemoveContainerError::Docker(
+                    BollardError::RequestTimeoutError,
+                ))
+            });
+
+        let mut delete_command = Delete {
+            deployment_name: deployment_name.clone(),
+            force: true,
+            keep_volumes: true,
+            interaction: Box::new(mock_interaction),
+            deployment_deleter: Box::new(MockDocker::new()),
+            container_remover: Box::new(mock_remover),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
+        };
+
+        let result = delete_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Failed {
+                deployment_name: deployment_name.clone(),
+                error: format!(
+                    "failed to delete the container: {}",
+                    BollardError::RequestTimeoutError
+                )
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_deployment_settings() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter.expect_delete().return_once(|_| Ok(()));
+
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.test-deployment]\nwaitForHealthy = true\n".to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("deployments.toml") && !contents.contains("test-deployment")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut delete_command = Delete {
+            deployment_name: "test-deployment".to_string(),
+            force: true,
+            keep_volumes: false,
+            interaction: Box::new(mock_interaction),
+            deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = delete_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Deleted {
+                deployment_name: "test-deployment".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_settings_failure_is_not_a_delete_failure() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter.expect_delete().return_once(|_| Ok(()));
+
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Err(anyhow!("permission denied")));
+
+        let mut delete_command = Delete {
+            deployment_name: "test-deployment".to_string(),
+            force: true,
+            keep_volumes: false,
+            interaction: Box::new(mock_interaction),
+            deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = delete_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Deleted {
+                deployment_name: "test-deployment".to_string()
+            }
+        );
+    }
 }
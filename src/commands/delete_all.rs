@@ -0,0 +1,491 @@
+//! Command to delete many local deployments at once.
+//!
+//! This module implements `delete --all` and `delete` with multiple deployment names. Before anything is deleted, the
+//! confirmation prompt lists every deployment that will be deleted, together with what is lost when deleting them.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::Client;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        bulk::{BulkItemResult, BulkOutput, FailurePolicy, run_bulk},
+        config::deployments_file_path,
+        delete::{DeleteInteraction, DeleteResult, delete_deployment, forget_deployment_settings},
+    },
+    config_dir::ConfigFs,
+    dependencies::{
+        DeploymentContainerRemover, DeploymentDeleter, TokioFs, connect_docker,
+        docker::DeploymentLister,
+    },
+    interaction::{ConfirmationPromptOptions, ConfirmationPromptResult, Interaction},
+    messages::{Message, message},
+    models::Deployment,
+    settings::Settings,
+};
+
+/// Number of deployments that are deleted at the same time.
+const DELETE_CONCURRENCY: usize = 4;
+
+/// Command to delete all local deployments, or the deployments with the given names.
+pub struct DeleteAll {
+    /// The deployments to delete, all local deployments when not set.
+    deployment_names: Option<Vec<String>>,
+    force: bool,
+    keep_volumes: bool,
+    failure_policy: FailurePolicy,
+    concurrency: usize,
+    deployments_file: PathBuf,
+
+    interaction: Box<dyn DeleteInteraction>,
+    deployment_lister: Box<dyn DeploymentLister + Send>,
+    deployment_deleter: Box<dyn DeploymentDeleter + Send + Sync>,
+    container_remover: Box<dyn DeploymentContainerRemover + Send + Sync>,
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::Delete, &Settings)> for DeleteAll {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Delete, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_names: (!args.all).then_some(args.deployment_names),
+            force: args.force,
+            keep_volumes: args.keep_volumes,
+            failure_policy: args.bulk.into(),
+            concurrency: DELETE_CONCURRENCY,
+            deployments_file: deployments_file_path(settings)?,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_lister: Box::new(Client::new(docker.clone())),
+            deployment_deleter: Box::new(Client::new(docker.clone())),
+            container_remover: Box::new(docker),
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+/// Result of the delete all command, one delete result per deployment, in the order of the deployments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeleteAllResult(Vec<DeleteResult>);
+
+impl Display for DeleteAllResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return writeln!(f, "No deployments to delete");
+        }
+
+        for result in &self.0 {
+            match result {
+                // The single delete result doesn't mention the deployment name when canceled
+                DeleteResult::Canceled {
+                    deployment_name, ..
+                } => writeln!(f, "Deployment '{}' not deleted", deployment_name)?,
+                result => writeln!(f, "{}", result)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BulkItemResult for DeleteResult {
+    fn is_failure(&self) -> bool {
+        matches!(self, DeleteResult::Failed { .. })
+    }
+}
+
+impl BulkOutput for DeleteAllResult {
+    fn has_failures(&self) -> bool {
+        self.0.iter().any(DeleteResult::is_failure)
+    }
+}
+
+impl DeleteAll {
+    /// Options of the confirmation prompt, listing every deployment that will be deleted.
+    fn confirmation_options(&self, deployment_names: &[String]) -> ConfirmationPromptOptions {
+        let scope = if self.keep_volumes {
+            "This operation will delete the following deployments, their data volumes are kept:"
+        } else {
+            "This operation will delete the following deployments, and all of their local data volumes. This action cannot be undone:"
+        };
+        let deployments = deployment_names
+            .iter()
+            .map(|name| format!("  - {}", name))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        ConfirmationPromptOptions::builder()
+            .pre_confirmation_help_text(format!("{}\n{}", scope, deployments))
+            .post_confirmation_help_text(
+                "Deleting deployments will not remove saved connections from MongoDB for VS Code. This must be done manually.".to_string(),
+            )
+            .message(if self.deployment_names.is_some() {
+                format!(
+                    "Are you sure you want to terminate these {} deployments?",
+                    deployment_names.len()
+                )
+            } else {
+                format!(
+                    "Are you sure you want to terminate all {} deployments?",
+                    deployment_names.len()
+                )
+            })
+            .default(false)
+            .build()
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for DeleteAll {
+    type Output = DeleteAllResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let deployment_names = match &self.deployment_names {
+            Some(deployment_names) => deployment_names.clone(),
+            None => self
+                .deployment_lister
+                .list()
+                .await
+                .context("listing deployments")?
+                .into_iter()
+                .map(|deployment| Deployment::from(deployment).name)
+                .collect::<Vec<_>>(),
+        };
+
+        if deployment_names.is_empty() {
+            return Ok(DeleteAllResult(Vec::new()));
+        }
+
+        if !self.force {
+            let confirmation = self
+                .interaction
+                .confirm(self.confirmation_options(&deployment_names))
+                .context("confirming deletion")?;
+
+            if matches!(
+                confirmation,
+                ConfirmationPromptResult::No | ConfirmationPromptResult::Canceled
+            ) {
+                // Operation cancelled by user.
+                return Ok(DeleteAllResult(
+                    deployment_names
+                        .into_iter()
+                        .map(|deployment_name| DeleteResult::Canceled {
+                            deployment_name,
+                            reason: CancelReason::Prompt,
+                        })
+                        .collect(),
+                ));
+            }
+        }
+
+        // When _spinner goes out of scope, the spinner will be stopped
+        let _spinner = self
+            .interaction
+            .start_spinner(message(Message::DeleteDeployments).to_string())?;
+
+        // Delete up to `concurrency` deployments at the same time, the results are in the order of the deployments
+        // By default every deployment gets its own outcome, with --failFast the deletion stops at the first failure
+        let keep_volumes = self.keep_volumes;
+        let deployment_deleter = self.deployment_deleter.as_ref();
+        let container_remover = self.container_remover.as_ref();
+        let results = run_bulk(
+            deployment_names,
+            self.failure_policy,
+            self.concurrency,
+            |deployment_name: String| async move {
+                delete_deployment(
+                    &deployment_name,
+                    keep_volumes,
+                    deployment_deleter,
+                    container_remover,
+                )
+                .await
+            },
+        )
+        .await;
+
+        // The settings of all deleted deployments are removed at once, they are saved in a single file
+        let deleted = results
+            .iter()
+            .filter_map(|result| match result {
+                DeleteResult::Deleted { deployment_name } => Some(deployment_name.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !deleted.is_empty() {
+            forget_deployment_settings(self.fs.as_ref(), &self.deployments_file, &deleted).await;
+        }
+
+        Ok(DeleteAllResult(results))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use atlas_local::models::State;
+
+    use super::*;
+    use crate::commands::test_support;
+    use crate::dependencies::PathKind;
+    use crate::dependencies::mocks::{MockDocker, MockTokioFs};
+    use crate::interaction::SpinnerHandle;
+    use crate::interaction::mocks::MockInteraction;
+
+    fn create_deployment(name: &str) -> atlas_local::models::Deployment {
+        atlas_local::models::Deployment {
+            container_id: format!("{}-container-id", name),
+            ..test_support::create_deployment(name, State::Running)
+        }
+    }
+
+    /// Filesystem without a deployments file, no settings are saved.
+    fn create_fs() -> MockTokioFs {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().returning(|_| Ok(None));
+        mock_fs.expect_write_string().never();
+        mock_fs
+    }
+
+    fn create_delete_all_command(
+        force: bool,
+        interaction: MockInteraction,
+        deployment_deleter: MockDocker,
+    ) -> DeleteAll {
+        let mut deployment_lister = MockDocker::new();
+        deployment_lister.expect_list().return_once(|| {
+            Ok(vec![
+                create_deployment("orders"),
+                create_deployment("users"),
+            ])
+        });
+
+        DeleteAll {
+            deployment_names: None,
+            force,
+            keep_volumes: false,
+            failure_policy: FailurePolicy::KeepGoing,
+            concurrency: 1,
+            interaction: Box::new(interaction),
+            deployment_lister: Box::new(deployment_lister),
+            deployment_deleter: Box::new(deployment_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(create_fs()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_confirmation_lists_deployments() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_confirm()
+            .withf(|options| {
+                *options
+                    == ConfirmationPromptOptions::builder()
+                        .pre_confirmation_help_text(
+                            concat!(
+                                "This operation will delete the following deployments, and all of their local data volumes. This action cannot be undone:\n",
+                                "  - orders\n",
+                                "  - users",
+                            )
+                            .to_string(),
+                        )
+                        .post_confirmation_help_text(
+                            "Deleting deployments will not remove saved connections from MongoDB for VS Code. This must be done manually.".to_string(),
+                        )
+                        .message("Are you sure you want to terminate all 2 deployments?".to_string())
+                        .default(false)
+                        .build()
+            })
+            .return_once(|_| Ok(ConfirmationPromptResult::No));
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter.expect_delete().never();
+
+        let result = create_delete_all_command(false, mock_interaction, mock_deleter)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteAllResult(vec![
+                DeleteResult::Canceled {
+                    deployment_name: "orders".to_string(),
+                    reason: CancelReason::Prompt,
+                },
+                DeleteResult::Canceled {
+                    deployment_name: "users".to_string(),
+                    reason: CancelReason::Prompt,
+                },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_force_deletes_every_deployment() {
+        let mut mock_interaction = MockInteraction::new();
+        // When force is true, confirm should not be called
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Deleting deployments...")
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter.expect_delete().times(2).returning(|_| Ok(()));
+
+        let result = create_delete_all_command(true, mock_interaction, mock_deleter)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteAllResult(vec![
+                DeleteResult::Deleted {
+                    deployment_name: "orders".to_string()
+                },
+                DeleteResult::Deleted {
+                    deployment_name: "users".to_string()
+                },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_fail_fast_stops_at_first_failure() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter
+            .expect_delete()
+            .times(1)
+            .withf(|name| name == "orders")
+            .returning(|_| {
+                Err(atlas_local::DeleteDeploymentError::GetDeployment(
+                    atlas_local::GetDeploymentError::from(
+                        bollard::errors::Error::RequestTimeoutError,
+                    ),
+                ))
+            });
+
+        let mut command = create_delete_all_command(true, mock_interaction, mock_deleter);
+        command.failure_policy = FailurePolicy::FailFast;
+
+        let result = command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteAllResult(vec![DeleteResult::Failed {
+                deployment_name: "orders".to_string(),
+                error: "deployment not found".to_string(),
+            }])
+        );
+        assert!(result.has_failures());
+    }
+
+    #[tokio::test]
+    async fn test_delete_by_names_does_not_list_deployments() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_confirm()
+            .withf(|options| {
+                *options
+                    == ConfirmationPromptOptions::builder()
+                        .pre_confirmation_help_text(
+                            concat!(
+                                "This operation will delete the following deployments, and all of their local data volumes. This action cannot be undone:\n",
+                                "  - orders\n",
+                                "  - payments",
+                            )
+                            .to_string(),
+                        )
+                        .post_confirmation_help_text(
+                            "Deleting deployments will not remove saved connections from MongoDB for VS Code. This must be done manually.".to_string(),
+                        )
+                        .message("Are you sure you want to terminate these 2 deployments?".to_string())
+                        .default(false)
+                        .build()
+            })
+            .return_once(|_| Ok(ConfirmationPromptResult::Yes));
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_lister = MockDocker::new();
+        mock_lister.expect_list().never();
+
+        let mut mock_deleter = MockDocker::new();
+        mock_deleter
+            .expect_delete()
+            .withf(|name| name == "orders")
+            .returning(|_| Ok(()));
+        mock_deleter
+            .expect_delete()
+            .withf(|name| name == "payments")
+            .returning(|_| {
+                Err(atlas_local::DeleteDeploymentError::GetDeployment(
+                    atlas_local::GetDeploymentError::from(
+                        bollard::errors::Error::RequestTimeoutError,
+                    ),
+                ))
+            });
+
+        // Only the settings of the deleted deployment are removed
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[deployments.orders]\nwaitForHealthy = true\n\n[deployments.payments]\nwaitForHealthy = true\n".to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|_, contents| {
+                contents.contains("[deployments.payments]") && !contents.contains("orders")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut command = DeleteAll {
+            deployment_names: Some(vec!["orders".to_string(), "payments".to_string()]),
+            force: false,
+            keep_volumes: false,
+            failure_policy: FailurePolicy::KeepGoing,
+            concurrency: 2,
+            interaction: Box::new(mock_interaction),
+            deployment_lister: Box::new(mock_lister),
+            deployment_deleter: Box::new(mock_deleter),
+            container_remover: Box::new(MockDocker::new()),
+            deployments_file: PathBuf::from("deployments.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteAllResult(vec![
+                DeleteResult::Deleted {
+                    deployment_name: "orders".to_string()
+                },
+                DeleteResult::Failed {
+                    deployment_name: "payments".to_string(),
+                    error: "deployment not found".to_string(),
+                },
+            ])
+        );
+    }
+}
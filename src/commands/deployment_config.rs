@@ -0,0 +1,250 @@
+//! Configuration of an existing deployment, to create a deployment like it.
+//!
+//! The configuration is read from the container of a deployment. It is used to recreate the container of a
+//! deployment, to clone a deployment and to restore a snapshot into a new deployment, so it can be stored with a
+//! snapshot.
+
+use anyhow::{Result, anyhow};
+use atlas_local::models::{
+    BindingType, CreateDeploymentOptions, CreationSource, Deployment, ImageTag, MongoDBPortBinding,
+};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// The configuration of a deployment, everything that is needed to create its container again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentConfig {
+    /// Reference of the image of the container, e.g. mongodb/mongodb-atlas-local:8.2.2.
+    pub image: Option<String>,
+    pub mongodb_version: Version,
+    /// Port on the host, `None` to let Docker pick a free port.
+    pub port: Option<u16>,
+    pub bind_ip_all: bool,
+    pub local_seed_location: Option<String>,
+    pub database: Option<String>,
+    pub username: Option<String>,
+    pub username_file: Option<String>,
+    pub password: Option<String>,
+    pub password_file: Option<String>,
+    pub load_sample_data: Option<bool>,
+    pub mongot_log_file: Option<String>,
+    pub runner_log_file: Option<String>,
+    pub do_not_track: bool,
+    pub telemetry_base_url: Option<String>,
+    pub voyage_api_key: Option<String>,
+}
+
+impl DeploymentConfig {
+    /// The configuration of a deployment, `image` is the image reference of its container.
+    pub fn new(deployment: &Deployment, image: Option<String>) -> Self {
+        let port_binding = deployment.port_bindings.as_ref();
+
+        Self {
+            image,
+            mongodb_version: deployment.mongodb_version.clone(),
+            port: port_binding.and_then(|binding| binding.port),
+            bind_ip_all: port_binding
+                .is_some_and(|binding| matches!(binding.binding_type, BindingType::AnyInterface)),
+            local_seed_location: deployment.local_seed_location.clone(),
+            database: deployment.mongodb_initdb_database.clone(),
+            username: deployment.mongodb_initdb_root_username.clone(),
+            username_file: deployment.mongodb_initdb_root_username_file.clone(),
+            password: deployment.mongodb_initdb_root_password.clone(),
+            password_file: deployment.mongodb_initdb_root_password_file.clone(),
+            load_sample_data: deployment.mongodb_load_sample_data,
+            mongot_log_file: deployment.mongot_log_file.clone(),
+            runner_log_file: deployment.runner_log_file.clone(),
+            do_not_track: deployment.do_not_track,
+            telemetry_base_url: deployment.telemetry_base_url.clone(),
+            voyage_api_key: deployment.voyage_api_key.clone(),
+        }
+    }
+
+    /// Options to create a deployment with this configuration, created by atlas-local.
+    ///
+    /// The deployment is created from the image of the container when its tag is known, the image is not pulled
+    /// again as the container used it. Otherwise the image of the same MongoDB version is used. Waiting for the
+    /// deployment to become healthy is left to the caller.
+    pub fn create_deployment_options(&self, name: &str) -> Result<CreateDeploymentOptions> {
+        let (image, tag) = match self.image.as_deref().map(split_image_reference) {
+            Some((image, tag)) => (Some(image.to_string()), tag),
+            None => (None, None),
+        };
+
+        let (image_tag, skip_pull_image) = match tag.and_then(|tag| ImageTag::try_from(tag).ok()) {
+            Some(image_tag) => (image_tag, true),
+            // The image of the version may not be available locally, so it is pulled
+            None => (
+                ImageTag::try_from(self.mongodb_version.to_string().as_str())
+                    .map_err(|e| anyhow!("invalid MongoDB version of the deployment: {e}"))?,
+                false,
+            ),
+        };
+
+        Ok(CreateDeploymentOptions {
+            name: Some(name.to_string()),
+            image,
+            image_tag: Some(image_tag),
+            skip_pull_image: Some(skip_pull_image),
+            creation_source: Some(CreationSource::AtlasLocal),
+            wait_until_healthy: Some(false),
+            local_seed_location: self.local_seed_location.clone(),
+            mongodb_initdb_database: self.database.clone(),
+            mongodb_initdb_root_username: self.username.clone(),
+            mongodb_initdb_root_username_file: self.username_file.clone(),
+            mongodb_initdb_root_password: self.password.clone(),
+            mongodb_initdb_root_password_file: self.password_file.clone(),
+            load_sample_data: self.load_sample_data,
+            mongot_log_file: self.mongot_log_file.clone(),
+            runner_log_file: self.runner_log_file.clone(),
+            do_not_track: Some(self.do_not_track),
+            telemetry_base_url: self.telemetry_base_url.clone(),
+            mongodb_port_binding: Some(MongoDBPortBinding::new(
+                self.port,
+                if self.bind_ip_all {
+                    BindingType::AnyInterface
+                } else {
+                    BindingType::Loopback
+                },
+            )),
+            voyage_api_key: self.voyage_api_key.clone(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Split an image reference into the image and its tag, e.g. localhost:5000/atlas-local:8.2.2.
+///
+/// References by digest have no tag.
+pub(super) fn split_image_reference(reference: &str) -> (&str, Option<&str>) {
+    let reference = reference
+        .split_once('@')
+        .map_or(reference, |(image, _)| image);
+
+    // A colon before the last slash separates the port of the registry, not the tag
+    match reference.rsplit_once(':') {
+        Some((image, tag)) if !tag.contains('/') => (image, Some(tag)),
+        _ => (reference, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support;
+    use atlas_local::models::State;
+
+    fn create_deployment() -> Deployment {
+        Deployment {
+            port_bindings: Some(MongoDBPortBinding::new(
+                Some(27018),
+                BindingType::AnyInterface,
+            )),
+            creation_source: Some(CreationSource::AtlasLocal),
+            local_seed_location: Some("/docker-entrypoint-initdb.d".to_string()),
+            mongodb_initdb_database: Some("shop".to_string()),
+            mongodb_initdb_root_password_file: Some("/run/secrets/password".to_string()),
+            mongodb_initdb_root_password: Some("secret".to_string()),
+            mongodb_initdb_root_username_file: Some("/run/secrets/username".to_string()),
+            mongodb_initdb_root_username: Some("admin".to_string()),
+            mongodb_load_sample_data: Some(true),
+            mongot_log_file: Some("/dev/stdout".to_string()),
+            runner_log_file: Some("/dev/stderr".to_string()),
+            telemetry_base_url: Some("https://telemetry.example.com".to_string()),
+            voyage_api_key: Some("voyage-key".to_string()),
+            ..test_support::create_deployment("local1", State::Exited)
+        }
+    }
+
+    #[test]
+    fn test_create_deployment_options_copies_every_setting() {
+        let deployment = create_deployment();
+        let config = DeploymentConfig::new(
+            &deployment,
+            Some("registry.example.com:5000/atlas-local:8.2.2".to_string()),
+        );
+
+        let options = config.create_deployment_options("local1").unwrap();
+
+        assert_eq!(options.name, Some("local1".to_string()));
+        assert_eq!(
+            options.image,
+            Some("registry.example.com:5000/atlas-local".to_string())
+        );
+        assert_eq!(
+            options.image_tag,
+            Some(ImageTag::try_from("8.2.2").unwrap())
+        );
+        assert_eq!(options.skip_pull_image, Some(true));
+        assert_eq!(options.wait_until_healthy, Some(false));
+        assert_eq!(options.creation_source, deployment.creation_source);
+        assert_eq!(options.local_seed_location, deployment.local_seed_location);
+        assert_eq!(
+            options.mongodb_initdb_database,
+            deployment.mongodb_initdb_database
+        );
+        assert_eq!(
+            options.mongodb_initdb_root_username,
+            deployment.mongodb_initdb_root_username
+        );
+        assert_eq!(
+            options.mongodb_initdb_root_username_file,
+            deployment.mongodb_initdb_root_username_file
+        );
+        assert_eq!(
+            options.mongodb_initdb_root_password,
+            deployment.mongodb_initdb_root_password
+        );
+        assert_eq!(
+            options.mongodb_initdb_root_password_file,
+            deployment.mongodb_initdb_root_password_file
+        );
+        assert_eq!(
+            options.load_sample_data,
+            deployment.mongodb_load_sample_data
+        );
+        assert_eq!(options.mongot_log_file, deployment.mongot_log_file);
+        assert_eq!(options.runner_log_file, deployment.runner_log_file);
+        assert_eq!(options.do_not_track, Some(deployment.do_not_track));
+        assert_eq!(options.telemetry_base_url, deployment.telemetry_base_url);
+        assert_eq!(options.voyage_api_key, deployment.voyage_api_key);
+
+        let binding = options.mongodb_port_binding.unwrap();
+        assert_eq!(binding.port, Some(27018));
+        assert!(matches!(binding.binding_type, BindingType::AnyInterface));
+    }
+
+    #[test]
+    fn test_create_deployment_options_without_image_tag_uses_version() {
+        let config = DeploymentConfig::new(
+            &create_deployment(),
+            Some("mongodb/mongodb-atlas-local@sha256:0123abcd".to_string()),
+        );
+
+        let options = config.create_deployment_options("local1").unwrap();
+
+        assert_eq!(
+            options.image,
+            Some("mongodb/mongodb-atlas-local".to_string())
+        );
+        assert_eq!(
+            options.image_tag,
+            Some(ImageTag::try_from("8.2.2").unwrap())
+        );
+        assert_eq!(options.skip_pull_image, Some(false));
+    }
+
+    #[test]
+    fn test_deployment_config_json_round_trip() {
+        let config = DeploymentConfig::new(
+            &create_deployment(),
+            Some("mongodb/mongodb-atlas-local:8.2.2".to_string()),
+        );
+
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: DeploymentConfig = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, config);
+    }
+}
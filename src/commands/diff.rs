@@ -0,0 +1,386 @@
+//! Command to compare a deployment with its spec.
+//!
+//! This module implements the `diff` command, which reports drift between a running deployment and the spec it was set up from.
+//! The spec is read from a deployments file, in the same format as `setup --fromFile`.
+//! The answer is also the exit code: 0 when the deployment matches the spec and 1 when it doesn't.
+
+use std::{fmt::Display, path::PathBuf, process::exit};
+
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use atlas_local::{
+    Client,
+    models::{BindingType, Deployment},
+};
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        Command,
+        bulk_setup::{DeploymentSpec, read_deployments_file},
+    },
+    dependencies::{DeploymentGetDeployment, FileReader, TokioFs, connect_docker, find_deployment},
+    formatting::{Format, Formattable},
+    settings::Settings,
+};
+
+/// Exit code used when the deployment doesn't match the spec.
+const DIFFERENCES_EXIT_CODE: i32 = 1;
+
+/// Command to compare a deployment with its spec.
+///
+/// Like the exists command, this command reports its outcome with the exit code, so it prints the outcome itself.
+pub struct Diff {
+    deployment_name: String,
+    config: PathBuf,
+    format: Format,
+
+    file_reader: Box<dyn FileReader + Send + Sync>,
+    deployment_getter: Box<dyn DeploymentGetDeployment + Send + Sync>,
+}
+
+impl TryFrom<(args::Diff, &Settings)> for Diff {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Diff, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            config: args.config,
+            format: Format::Text,
+
+            file_reader: Box::new(TokioFs::new()),
+            deployment_getter: Box::new(Client::new(
+                connect_docker(&settings.docker).context("connecting to Docker")?,
+            )),
+        })
+    }
+}
+
+/// How a setting of the deployment differs from the spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    /// The setting is only in the spec.
+    Added,
+    /// The setting is only set on the deployment.
+    Removed,
+    /// The setting has a different value in the spec.
+    Changed,
+}
+
+/// A setting that differs between the deployment and the spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub change: Change,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec: Option<String>,
+}
+
+impl FieldDiff {
+    /// Compare a setting, returns `None` when the deployment and the spec agree.
+    fn compare(
+        field: &'static str,
+        deployment: Option<String>,
+        spec: Option<String>,
+    ) -> Option<Self> {
+        let change = match (&deployment, &spec) {
+            (None, None) => return None,
+            (Some(deployment), Some(spec)) if deployment == spec => return None,
+            (None, Some(_)) => Change::Added,
+            (Some(_), None) => Change::Removed,
+            (Some(_), Some(_)) => Change::Changed,
+        };
+
+        Some(Self {
+            field,
+            change,
+            deployment,
+            spec,
+        })
+    }
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let deployment = self.deployment.as_deref().unwrap_or_default();
+        let spec = self.spec.as_deref().unwrap_or_default();
+        match self.change {
+            Change::Added => write!(f, "+ {}: {}", self.field, spec),
+            Change::Removed => write!(f, "- {}: {}", self.field, deployment),
+            Change::Changed => write!(f, "~ {}: {} -> {}", self.field, deployment, spec),
+        }
+    }
+}
+
+/// Result of the diff command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffResult {
+    pub deployment_name: String,
+    pub differences: Vec<FieldDiff>,
+}
+
+impl Display for DiffResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.differences.is_empty() {
+            return write!(f, "Deployment '{}' matches the spec", self.deployment_name);
+        }
+
+        write!(
+            f,
+            "Deployment '{}' differs from the spec:",
+            self.deployment_name
+        )?;
+        for difference in &self.differences {
+            write!(f, "\n  {}", difference)?;
+        }
+        Ok(())
+    }
+}
+
+impl Diff {
+    /// Set the format the outcome is printed in.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Compare the deployment with the entry of the deployments file that has the same name.
+    async fn diff(&self) -> Result<DiffResult> {
+        let deployments_file =
+            read_deployments_file(self.file_reader.as_ref(), &self.config).await?;
+        let spec = deployments_file
+            .deployments
+            .into_iter()
+            .find(|spec| spec.name == self.deployment_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "deployment '{}' is not listed in {}",
+                    self.deployment_name,
+                    self.config.display()
+                )
+            })?;
+
+        let Some(deployment) =
+            find_deployment(self.deployment_getter.as_ref(), &self.deployment_name).await?
+        else {
+            return Err(anyhow!(
+                "deployment '{}' does not exist",
+                self.deployment_name
+            ));
+        };
+
+        Ok(DiffResult {
+            deployment_name: self.deployment_name.clone(),
+            differences: differences(&deployment, &spec),
+        })
+    }
+}
+
+/// Compare the effective settings of the deployment with the spec.
+///
+/// Settings that are not in the spec are created with defaults, so they are not compared.
+/// The username and initdb are the exception, a deployment that has them while the spec doesn't has drifted.
+fn differences(deployment: &Deployment, spec: &DeploymentSpec) -> Vec<FieldDiff> {
+    let port_binding = deployment.port_bindings.as_ref();
+    let mut differences = Vec::new();
+
+    // The latest and preview tags move, they can't be compared with the version of the deployment
+    if let Some(image_tag) = spec
+        .image_tag
+        .as_deref()
+        .filter(|tag| !matches!(*tag, "latest" | "preview"))
+    {
+        let version = deployment.mongodb_version.to_string();
+        if !version_matches_tag(&version, image_tag) {
+            differences.push(FieldDiff {
+                field: "mongodbVersion",
+                change: Change::Changed,
+                deployment: Some(version),
+                spec: Some(image_tag.to_string()),
+            });
+        }
+    }
+
+    if let Some(port) = spec.port {
+        differences.extend(FieldDiff::compare(
+            "port",
+            port_binding
+                .and_then(|binding| binding.port)
+                .map(|port| port.to_string()),
+            Some(port.to_string()),
+        ));
+    }
+
+    let binds_all_interfaces = port_binding
+        .is_some_and(|binding| matches!(binding.binding_type, BindingType::AnyInterface));
+    differences.extend(FieldDiff::compare(
+        "bindIpAll",
+        Some(binds_all_interfaces.to_string()),
+        Some(spec.bind_ip_all.to_string()),
+    ));
+
+    // Auth is enabled when the deployment was set up with a username
+    differences.extend(FieldDiff::compare(
+        "username",
+        deployment.mongodb_initdb_root_username.clone(),
+        spec.username.clone(),
+    ));
+
+    if let Some(load_sample_data) = spec.load_sample_data {
+        differences.extend(FieldDiff::compare(
+            "loadSampleData",
+            Some(
+                deployment
+                    .mongodb_load_sample_data
+                    .unwrap_or(false)
+                    .to_string(),
+            ),
+            Some(load_sample_data.to_string()),
+        ));
+    }
+
+    differences.extend(FieldDiff::compare(
+        "initdb",
+        deployment.local_seed_location.clone(),
+        spec.initdb
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string()),
+    ));
+
+    differences
+}
+
+/// Whether the version of the deployment matches the image tag, e.g. 8.2.2 matches 8, 8.2 and 8.2.2.
+///
+/// The timestamp of a semver+timestamp tag is ignored, the deployment only knows its version.
+fn version_matches_tag(version: &str, image_tag: &str) -> bool {
+    let tag_version = image_tag.split('-').next().unwrap_or_default();
+    version == tag_version
+        || version
+            .strip_prefix(tag_version)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+#[async_trait]
+impl Command for Diff {
+    async fn execute(&mut self) -> Result<()> {
+        let result = self.diff().await?;
+        debug!(
+            deployment_name = %self.deployment_name,
+            differences = result.differences.len(),
+            "compared deployment with spec"
+        );
+
+        println!("{}", result.format(self.format)?);
+
+        if !result.differences.is_empty() {
+            exit(DIFFERENCES_EXIT_CODE);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use atlas_local::models::{MongoDBPortBinding, State};
+
+    use super::*;
+    use crate::commands::test_support;
+    use crate::dependencies::mocks::{MockDocker, MockTokioFs};
+
+    fn create_deployment() -> Deployment {
+        Deployment {
+            port_bindings: Some(MongoDBPortBinding::new(Some(27017), BindingType::Loopback)),
+            mongodb_initdb_root_username: Some("admin".to_string()),
+            ..test_support::create_deployment("orders", State::Running)
+        }
+    }
+
+    fn create_diff_command(spec: &'static str) -> Diff {
+        let mut file_reader = MockTokioFs::new();
+        file_reader
+            .expect_read_to_string()
+            .return_once(move |_| Ok(spec.to_string()));
+
+        let mut deployment_getter = MockDocker::new();
+        deployment_getter
+            .expect_get_deployment()
+            .return_once(|_| Ok(create_deployment()));
+
+        Diff {
+            deployment_name: "orders".to_string(),
+            config: PathBuf::from("deployments.toml"),
+            format: Format::Text,
+            file_reader: Box::new(file_reader),
+            deployment_getter: Box::new(deployment_getter),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_matching_spec() {
+        let diff_command = create_diff_command(concat!(
+            "[[deployments]]\n",
+            "name = \"orders\"\n",
+            "imageTag = \"8.2\"\n",
+            "port = 27017\n",
+            "username = \"admin\"\n",
+        ));
+
+        let result = diff_command.diff().await.expect("diff should succeed");
+
+        assert_eq!(result.differences, vec![]);
+        assert_eq!(result.to_string(), "Deployment 'orders' matches the spec");
+    }
+
+    #[tokio::test]
+    async fn test_diff_reports_differences() {
+        let diff_command = create_diff_command(concat!(
+            "[[deployments]]\n",
+            "name = \"orders\"\n",
+            "imageTag = \"8.0\"\n",
+            "port = 27018\n",
+            "initdb = \"./seed\"\n",
+        ));
+
+        let result = diff_command.diff().await.expect("diff should succeed");
+
+        assert_eq!(
+            result.to_string(),
+            concat!(
+                "Deployment 'orders' differs from the spec:\n",
+                "  ~ mongodbVersion: 8.2.2 -> 8.0\n",
+                "  ~ port: 27017 -> 27018\n",
+                "  - username: admin\n",
+                "  + initdb: ./seed",
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_deployment_not_in_spec() {
+        let diff_command = create_diff_command("[[deployments]]\nname = \"users\"\n");
+
+        let result = diff_command.diff().await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "deployment 'orders' is not listed in deployments.toml"
+        );
+    }
+
+    #[test]
+    fn test_version_matches_tag() {
+        assert!(version_matches_tag("8.2.2", "8"));
+        assert!(version_matches_tag("8.2.2", "8.2"));
+        assert!(version_matches_tag("8.2.2", "8.2.2"));
+        assert!(version_matches_tag("8.2.4", "8.2.4-20260217T084055Z"));
+        assert!(!version_matches_tag("8.2.2", "8.0"));
+        assert!(!version_matches_tag("18.2.2", "8"));
+    }
+}
@@ -0,0 +1,752 @@
+//! Command to diagnose the local environment.
+//!
+//! This module implements the `doctor` command. It runs a series of checks that cover the usual reasons for a
+//! deployment to fail: Docker that can't be reached, a full disk, a port that is already used, an image that can't
+//! be pulled and deployments that are unhealthy. Every problem comes with a hint to fix it, and the JSON output is
+//! meant to be attached to support tickets, which is why it also contains the version and the host details.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use bollard::secret::HealthStatusEnum;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        bulk::BulkOutput,
+        setup::{DEFAULT_IMAGE, DEFAULT_IMAGE_TAG},
+        version::{CLI_VERSION, HostInfo},
+    },
+    dependencies::{
+        DeploymentContainerInspector, DeploymentImageExists, DeploymentLister, DiskSpaceChecker,
+        DockerServerVersion, HostSystem, PortChecker, connect_docker, is_docker_unreachable,
+    },
+    env,
+    settings::Settings,
+};
+
+/// Below this amount of free disk space, pulling the image or writing data is likely to fail.
+const MIN_DISK_SPACE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+// Docker dependencies for the doctor command.
+pub trait DoctorDocker:
+    DockerServerVersion + DeploymentImageExists + DeploymentContainerInspector + Send + Sync
+{
+}
+impl<T: DockerServerVersion + DeploymentImageExists + DeploymentContainerInspector + Send + Sync>
+    DoctorDocker for T
+{
+}
+
+// Host dependencies for the doctor command.
+pub trait DoctorSystem: PortChecker + DiskSpaceChecker + Send + Sync {}
+impl<T: PortChecker + DiskSpaceChecker + Send + Sync> DoctorSystem for T {}
+
+/// Clients of the Docker daemon, they are only available when connecting to Docker succeeded.
+pub struct DoctorDockerClients {
+    docker: Box<dyn DoctorDocker>,
+    deployment_lister: Box<dyn DeploymentLister + Send + Sync>,
+}
+
+/// Command to diagnose the local environment.
+pub struct Doctor {
+    port: u16,
+    // The disk space is checked for the home directory, that's where Docker Desktop stores its data
+    home_dir: PathBuf,
+    // Set when Docker runs on another machine, its disk can't be checked
+    remote_docker_host: Option<String>,
+    host_os: &'static str,
+    host_arch: &'static str,
+
+    system: Box<dyn DoctorSystem>,
+    // Error message when connecting to Docker failed, e.g. because of an invalid DOCKER_HOST
+    docker: Result<DoctorDockerClients, String>,
+}
+
+/// Connecting to Docker is one of the checks, so failing to connect doesn't fail the command.
+impl From<(args::Doctor, &Settings)> for Doctor {
+    fn from((args, settings): (args::Doctor, &Settings)) -> Self {
+        Self {
+            port: args.port,
+            home_dir: std::env::var_os(env::HOME)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(".")),
+            remote_docker_host: settings
+                .docker
+                .host
+                .clone()
+                .or_else(|| std::env::var(env::DOCKER_HOST).ok())
+                .filter(|host| is_remote_docker_host(host)),
+            host_os: std::env::consts::OS,
+            host_arch: std::env::consts::ARCH,
+
+            system: Box::new(HostSystem::new()),
+            docker: connect_docker(&settings.docker)
+                .map(|docker| DoctorDockerClients {
+                    docker: Box::new(docker.clone()),
+                    deployment_lister: Box::new(Client::new(docker)),
+                })
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Outcome of a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Something might go wrong, but it doesn't prevent using local deployments.
+    Warning,
+    Failed,
+    /// The check couldn't run, e.g. because Docker can't be reached.
+    Skipped,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => write!(f, "ok"),
+            Self::Warning => write!(f, "warning"),
+            Self::Failed => write!(f, "failed"),
+            Self::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+/// Result of a single check, with a hint to fix the problem when it didn't pass.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn new(name: impl Into<String>, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn with_hint(self, hint: impl Into<String>) -> Self {
+        Self {
+            hint: Some(hint.into()),
+            ..self
+        }
+    }
+}
+
+/// Result of the doctor command.
+///
+/// With `--output json` the checks are listed in the order they ran:
+/// `{"version":"0.11.2","host":{"os":"macos","arch":"aarch64"},"checks":[{"name":"Docker","status":"ok","message":"Docker 28.0.1 (API 1.48) is reachable"}]}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorReport {
+    pub version: String,
+    pub host: HostInfo,
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl Display for DoctorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Host: {}/{}", self.host.os, self.host.arch)?;
+        writeln!(f)?;
+
+        for check in &self.checks {
+            writeln!(f, "[{}] {}: {}", check.status, check.name, check.message)?;
+            if let Some(hint) = &check.hint {
+                writeln!(f, "    Hint: {}", hint)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Warnings are reported but don't fail the command, only failed checks do.
+impl BulkOutput for DoctorReport {
+    fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.status == CheckStatus::Failed)
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Doctor {
+    type Output = DoctorReport;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut checks = Vec::new();
+
+        let docker = match &self.docker {
+            Ok(clients) => {
+                let docker_check = check_docker(clients.docker.as_ref()).await;
+                let reachable = docker_check.status == CheckStatus::Ok;
+                checks.push(docker_check);
+                reachable.then_some(clients)
+            }
+            Err(e) => {
+                checks.push(
+                    DoctorCheck::new(
+                        "Docker",
+                        CheckStatus::Failed,
+                        format!("connecting to Docker failed: {e}"),
+                    )
+                    .with_hint("Check the DOCKER_HOST environment variable, or unset it to use the default Docker socket"),
+                );
+                None
+            }
+        };
+
+        checks.push(match &self.remote_docker_host {
+            Some(host) => DoctorCheck::new(
+                "Disk space",
+                CheckStatus::Skipped,
+                format!("Docker runs on {host}, only the disk of this machine can be checked"),
+            ),
+            None => check_disk_space(self.system.as_ref(), &self.home_dir).await,
+        });
+
+        let Some(clients) = docker else {
+            // The port is still checked, it doesn't need Docker
+            checks.push(check_port(self.system.as_ref(), self.port, &[]).await);
+            for name in ["Image", "Deployments"] {
+                checks.push(DoctorCheck::new(
+                    name,
+                    CheckStatus::Skipped,
+                    "Docker is not reachable",
+                ));
+            }
+
+            return Ok(self.report(checks));
+        };
+
+        checks.push(check_image(clients.docker.as_ref()).await);
+
+        match clients.deployment_lister.list().await {
+            Ok(deployments) => {
+                checks.push(check_port(self.system.as_ref(), self.port, &deployments).await);
+
+                if deployments.is_empty() {
+                    checks.push(DoctorCheck::new(
+                        "Deployments",
+                        CheckStatus::Ok,
+                        "no local deployments",
+                    ));
+                }
+                for deployment in &deployments {
+                    checks.push(check_deployment(clients.docker.as_ref(), deployment).await);
+                }
+            }
+            Err(e) => {
+                checks.push(check_port(self.system.as_ref(), self.port, &[]).await);
+                checks.push(DoctorCheck::new(
+                    "Deployments",
+                    CheckStatus::Failed,
+                    format!("listing deployments failed: {e}"),
+                ));
+            }
+        }
+
+        Ok(self.report(checks))
+    }
+}
+
+impl Doctor {
+    fn report(&self, checks: Vec<DoctorCheck>) -> DoctorReport {
+        DoctorReport {
+            version: CLI_VERSION.to_string(),
+            host: HostInfo {
+                os: self.host_os.to_string(),
+                arch: self.host_arch.to_string(),
+            },
+            checks,
+        }
+    }
+}
+
+/// Check that the Docker daemon is reachable, and report its version.
+async fn check_docker(docker: &dyn DoctorDocker) -> DoctorCheck {
+    match docker.server_version().await {
+        Ok(server_version) => DoctorCheck::new(
+            "Docker",
+            CheckStatus::Ok,
+            format!(
+                "Docker {} (API {}) is reachable",
+                server_version.version.unwrap_or_default(),
+                server_version.api_version.unwrap_or_default()
+            ),
+        ),
+        Err(e) if is_docker_unreachable(&e) => {
+            DoctorCheck::new("Docker", CheckStatus::Failed, "Docker is not running")
+                .with_hint("Start Docker, e.g. Docker Desktop, and run doctor again")
+        }
+        Err(e) => DoctorCheck::new(
+            "Docker",
+            CheckStatus::Failed,
+            format!("getting the Docker version failed: {e}"),
+        )
+        .with_hint("Check that your user has permission to use Docker"),
+    }
+}
+
+/// Returns whether the Docker host is on another machine, local sockets and pipes are on this machine.
+fn is_remote_docker_host(host: &str) -> bool {
+    !host.starts_with("unix://") && !host.starts_with("npipe://")
+}
+
+/// Check that there is enough free disk space for the image and the data of a deployment.
+///
+/// Only the disk of this machine is checked. Docker Desktop and Podman machines keep the data in the disk image of a
+/// virtual machine, which can fill up even when this machine has free space.
+async fn check_disk_space(system: &dyn DoctorSystem, path: &Path) -> DoctorCheck {
+    match system.available_disk_space(path).await {
+        Ok(Some(available)) if available < MIN_DISK_SPACE_BYTES => DoctorCheck::new(
+            "Disk space",
+            CheckStatus::Warning,
+            format!("{} available on this machine", format_gib(available)),
+        )
+        .with_hint(format!(
+            "Free up disk space, a deployment needs at least {}, e.g. remove unused images with docker image prune",
+            format_gib(MIN_DISK_SPACE_BYTES)
+        )),
+        Ok(Some(available)) => DoctorCheck::new(
+            "Disk space",
+            CheckStatus::Ok,
+            format!("{} available on this machine", format_gib(available)),
+        ),
+        Ok(None) => DoctorCheck::new(
+            "Disk space",
+            CheckStatus::Skipped,
+            "the available disk space can't be determined on this platform",
+        ),
+        Err(e) => {
+            debug!(error = %e, "getting the available disk space failed");
+            DoctorCheck::new(
+                "Disk space",
+                CheckStatus::Skipped,
+                format!("getting the available disk space failed: {e}"),
+            )
+        }
+    }
+}
+
+/// Check that the port is available, a port used by a local deployment is expected to be in use.
+async fn check_port(
+    system: &dyn DoctorSystem,
+    port: u16,
+    deployments: &[atlas_local::models::Deployment],
+) -> DoctorCheck {
+    let name = format!("Port {port}");
+    if system.is_port_available(port).await {
+        return DoctorCheck::new(name, CheckStatus::Ok, "available");
+    }
+
+    let deployment_name = deployments
+        .iter()
+        .find(|deployment| {
+            deployment
+                .port_bindings
+                .as_ref()
+                .and_then(|bindings| bindings.port)
+                == Some(port)
+        })
+        .and_then(|deployment| deployment.name.clone());
+
+    match deployment_name {
+        Some(deployment_name) => DoctorCheck::new(
+            name,
+            CheckStatus::Ok,
+            format!("used by deployment '{deployment_name}'"),
+        ),
+        // Setup auto-assigns a port by default, so only setup with this port fails
+        None => DoctorCheck::new(name, CheckStatus::Warning, "used by another process").with_hint(
+            format!(
+                "Stop the process that uses port {port}, or pass another port to setup with --port"
+            ),
+        ),
+    }
+}
+
+/// Check whether the deployment image is pulled, setup pulls it when it isn't.
+async fn check_image(docker: &dyn DoctorDocker) -> DoctorCheck {
+    let image = format!("{DEFAULT_IMAGE}:{DEFAULT_IMAGE_TAG}");
+    match docker.image_exists(DEFAULT_IMAGE, DEFAULT_IMAGE_TAG).await {
+        Ok(true) => DoctorCheck::new("Image", CheckStatus::Ok, format!("{image} is pulled")),
+        Ok(false) => DoctorCheck::new(
+            "Image",
+            CheckStatus::Warning,
+            format!("{image} is not pulled"),
+        )
+        .with_hint(format!(
+            "Setup pulls the image, which requires access to Docker Hub. To pull it ahead of time, run docker pull {image}"
+        )),
+        Err(e) => DoctorCheck::new(
+            "Image",
+            CheckStatus::Failed,
+            format!("inspecting {image} failed: {e}"),
+        ),
+    }
+}
+
+/// Check the health of a local deployment.
+async fn check_deployment(
+    docker: &dyn DoctorDocker,
+    deployment: &atlas_local::models::Deployment,
+) -> DoctorCheck {
+    let deployment_name = deployment
+        .name
+        .clone()
+        .unwrap_or_else(|| deployment.container_id.clone());
+    let name = format!("Deployment {deployment_name}");
+
+    match deployment.state {
+        State::Running => {}
+        State::Dead => {
+            return DoctorCheck::new(name, CheckStatus::Failed, "the container is dead")
+                .with_hint(format!(
+                    "Delete the deployment with atlas-local delete {deployment_name} and set it up again"
+                ));
+        }
+        State::Restarting => {
+            return DoctorCheck::new(name, CheckStatus::Warning, "the container is restarting")
+                .with_hint(format!(
+                    "Check the logs with atlas-local logs {deployment_name}"
+                ));
+        }
+        // A stopped deployment is not a problem, it is reported so the report lists every deployment
+        _ => {
+            return DoctorCheck::new(name, CheckStatus::Ok, "not running");
+        }
+    }
+
+    let health = match docker.inspect_container_raw(&deployment_name).await {
+        Ok(container) => container
+            .and_then(|container| container.state)
+            .and_then(|state| state.health)
+            .and_then(|health| health.status),
+        Err(e) => {
+            return DoctorCheck::new(
+                name,
+                CheckStatus::Failed,
+                format!("inspecting the container failed: {e}"),
+            );
+        }
+    };
+
+    match health {
+        Some(HealthStatusEnum::UNHEALTHY) => {
+            DoctorCheck::new(name, CheckStatus::Failed, "running but unhealthy").with_hint(
+                format!("Check the logs with atlas-local logs {deployment_name}, then restart it with atlas-local stop {deployment_name} and atlas-local start {deployment_name}"),
+            )
+        }
+        Some(HealthStatusEnum::STARTING) => DoctorCheck::new(
+            name,
+            CheckStatus::Warning,
+            "running, the health check hasn't passed yet",
+        )
+        .with_hint(format!(
+            "Wait for the deployment to start, or check the logs with atlas-local logs {deployment_name}"
+        )),
+        Some(HealthStatusEnum::HEALTHY) => {
+            DoctorCheck::new(name, CheckStatus::Ok, "running and healthy")
+        }
+        _ => DoctorCheck::new(name, CheckStatus::Ok, "running"),
+    }
+}
+
+/// Format a number of bytes in GiB, with one decimal.
+fn format_gib(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / (1024 * 1024 * 1024) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use atlas_local::models::{BindingType, MongoDBPortBinding};
+    use bollard::models::{ContainerInspectResponse, ContainerState, Health, SystemVersion};
+    use semver::Version;
+
+    use super::*;
+    use crate::commands::test_support;
+    use crate::dependencies::mocks::{MockDocker, MockHostSystem};
+
+    fn create_deployment(name: &str, state: State) -> atlas_local::models::Deployment {
+        atlas_local::models::Deployment {
+            container_id: format!("{name}-container-id"),
+            port_bindings: Some(MongoDBPortBinding::new(Some(27017), BindingType::Loopback)),
+            ..test_support::create_deployment(name, state)
+        }
+    }
+
+    fn container_with_health(status: HealthStatusEnum) -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            state: Some(ContainerState {
+                health: Some(Health {
+                    status: Some(status),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn mock_system(available_disk_space: u64, port_available: bool) -> MockHostSystem {
+        let mut mock_system = MockHostSystem::new();
+        mock_system
+            .expect_available_disk_space()
+            .withf(|path| path == Path::new("/home/user"))
+            .return_once(move |_| Ok(Some(available_disk_space)));
+        mock_system
+            .expect_is_port_available()
+            .withf(|port| *port == 27017)
+            .return_once(move |_| port_available);
+        mock_system
+    }
+
+    fn mock_docker_server_version(mock_docker: &mut MockDocker) {
+        mock_docker.expect_server_version().return_once(|| {
+            Ok(SystemVersion {
+                version: Some("28.0.1".to_string()),
+                api_version: Some("1.48".to_string()),
+                ..Default::default()
+            })
+        });
+    }
+
+    fn create_command(
+        system: MockHostSystem,
+        docker: Result<(MockDocker, MockDocker), String>,
+    ) -> Doctor {
+        Doctor {
+            port: 27017,
+            home_dir: PathBuf::from("/home/user"),
+            remote_docker_host: None,
+            host_os: "macos",
+            host_arch: "aarch64",
+            system: Box::new(system),
+            docker: docker.map(|(docker, deployment_lister)| DoctorDockerClients {
+                docker: Box::new(docker),
+                deployment_lister: Box::new(deployment_lister),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_doctor_all_checks_pass() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker_server_version(&mut mock_docker);
+        mock_docker
+            .expect_image_exists()
+            .withf(|image, tag| image == DEFAULT_IMAGE && tag == DEFAULT_IMAGE_TAG)
+            .return_once(|_, _| Ok(true));
+        mock_docker
+            .expect_inspect_container_raw()
+            .withf(|name| name == "local1")
+            .return_once(|_| Ok(Some(container_with_health(HealthStatusEnum::HEALTHY))));
+
+        let mut mock_lister = MockDocker::new();
+        mock_lister
+            .expect_list()
+            .return_once(|| Ok(vec![create_deployment("local1", State::Running)]));
+
+        // The port is used, but by the deployment itself
+        let report = create_command(
+            mock_system(10 * 1024 * 1024 * 1024, false),
+            Ok((mock_docker, mock_lister)),
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert!(!report.has_failures());
+        assert_eq!(
+            report.checks,
+            vec![
+                DoctorCheck::new(
+                    "Docker",
+                    CheckStatus::Ok,
+                    "Docker 28.0.1 (API 1.48) is reachable"
+                ),
+                DoctorCheck::new(
+                    "Disk space",
+                    CheckStatus::Ok,
+                    "10.0 GiB available on this machine"
+                ),
+                DoctorCheck::new(
+                    "Image",
+                    CheckStatus::Ok,
+                    "mongodb/mongodb-atlas-local:latest is pulled"
+                ),
+                DoctorCheck::new("Port 27017", CheckStatus::Ok, "used by deployment 'local1'"),
+                DoctorCheck::new("Deployment local1", CheckStatus::Ok, "running and healthy"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_doctor_docker_not_running() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker.expect_server_version().return_once(|| {
+            Err(bollard::errors::Error::SocketNotFoundError(
+                "/var/run/docker.sock".to_string(),
+            ))
+        });
+        mock_docker.expect_image_exists().never();
+        let mut mock_lister = MockDocker::new();
+        mock_lister.expect_list().never();
+
+        let report = create_command(
+            mock_system(1024 * 1024 * 1024, true),
+            Ok((mock_docker, mock_lister)),
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert!(report.has_failures());
+        assert_eq!(
+            report.checks,
+            vec![
+                DoctorCheck::new("Docker", CheckStatus::Failed, "Docker is not running")
+                    .with_hint("Start Docker, e.g. Docker Desktop, and run doctor again"),
+                DoctorCheck::new("Disk space", CheckStatus::Warning, "1.0 GiB available on this machine")
+                    .with_hint("Free up disk space, a deployment needs at least 2.0 GiB, e.g. remove unused images with docker image prune"),
+                DoctorCheck::new("Port 27017", CheckStatus::Ok, "available"),
+                DoctorCheck::new("Image", CheckStatus::Skipped, "Docker is not reachable"),
+                DoctorCheck::new("Deployments", CheckStatus::Skipped, "Docker is not reachable"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_doctor_remote_docker_skips_disk_space() {
+        let mut mock_system = MockHostSystem::new();
+        mock_system.expect_available_disk_space().never();
+        mock_system.expect_is_port_available().return_once(|_| true);
+
+        let mut doctor_command = Doctor {
+            remote_docker_host: Some("tcp://10.0.0.5:2376".to_string()),
+            ..create_command(mock_system, Err("connection refused".to_string()))
+        };
+        let report = doctor_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            report.checks[1],
+            DoctorCheck::new(
+                "Disk space",
+                CheckStatus::Skipped,
+                "Docker runs on tcp://10.0.0.5:2376, only the disk of this machine can be checked"
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_remote_docker_host() {
+        assert!(is_remote_docker_host("tcp://10.0.0.5:2376"));
+        assert!(is_remote_docker_host("ssh://user@docker.example.com"));
+        assert!(!is_remote_docker_host("unix:///var/run/docker.sock"));
+        assert!(!is_remote_docker_host("npipe:////./pipe/docker_engine"));
+    }
+
+    #[tokio::test]
+    async fn test_doctor_reports_problems_with_hints() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker_server_version(&mut mock_docker);
+        mock_docker
+            .expect_image_exists()
+            .return_once(|_, _| Ok(false));
+        mock_docker
+            .expect_inspect_container_raw()
+            .withf(|name| name == "local1")
+            .return_once(|_| Ok(Some(container_with_health(HealthStatusEnum::UNHEALTHY))));
+
+        let mut mock_lister = MockDocker::new();
+        mock_lister.expect_list().return_once(|| {
+            Ok(vec![
+                atlas_local::models::Deployment {
+                    port_bindings: Some(MongoDBPortBinding::new(
+                        Some(27018),
+                        BindingType::Loopback,
+                    )),
+                    ..create_deployment("local1", State::Running)
+                },
+                atlas_local::models::Deployment {
+                    port_bindings: None,
+                    ..create_deployment("local2", State::Exited)
+                },
+            ])
+        });
+
+        let report = create_command(
+            mock_system(10 * 1024 * 1024 * 1024, false),
+            Ok((mock_docker, mock_lister)),
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert!(report.has_failures());
+        assert_eq!(
+            report.checks[2..],
+            [
+                DoctorCheck::new(
+                    "Image",
+                    CheckStatus::Warning,
+                    "mongodb/mongodb-atlas-local:latest is not pulled"
+                )
+                .with_hint("Setup pulls the image, which requires access to Docker Hub. To pull it ahead of time, run docker pull mongodb/mongodb-atlas-local:latest"),
+                DoctorCheck::new("Port 27017", CheckStatus::Warning, "used by another process")
+                    .with_hint("Stop the process that uses port 27017, or pass another port to setup with --port"),
+                DoctorCheck::new("Deployment local1", CheckStatus::Failed, "running but unhealthy")
+                    .with_hint("Check the logs with atlas-local logs local1, then restart it with atlas-local stop local1 and atlas-local start local1"),
+                DoctorCheck::new("Deployment local2", CheckStatus::Ok, "not running"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_doctor_report_text() {
+        let report = DoctorReport {
+            version: "0.11.2".to_string(),
+            host: HostInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+            },
+            checks: vec![
+                DoctorCheck::new("Docker", CheckStatus::Failed, "Docker is not running")
+                    .with_hint("Start Docker, e.g. Docker Desktop, and run doctor again"),
+                DoctorCheck::new("Port 27017", CheckStatus::Ok, "available"),
+            ],
+        };
+
+        assert_eq!(
+            report.to_string(),
+            "Version: 0.11.2\n\
+             Host: linux/x86_64\n\
+             \n\
+             [failed] Docker: Docker is not running\n    \
+             Hint: Start Docker, e.g. Docker Desktop, and run doctor again\n\
+             [ok] Port 27017: available\n"
+        );
+    }
+}
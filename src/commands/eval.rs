@@ -0,0 +1,359 @@
+//! Command to evaluate a JavaScript expression against an existing deployment.
+//!
+//! The expression is evaluated with the mongosh in the deployment container, like the .js scripts of the seed command,
+//! so neither mongosh nor a driver has to be installed to query a deployment.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use crate::{
+    args,
+    commands::{CommandWithOutput, connectors::container_database_connection_string},
+    dependencies::{
+        ContainerExecutor, DeploymentGetConnectionString, DeploymentGetDeployment, connect_docker,
+        find_deployment,
+    },
+    settings::Settings,
+};
+
+// Deployment dependencies for the eval command
+pub trait EvalDeploymentManagement:
+    DeploymentGetDeployment + DeploymentGetConnectionString
+{
+}
+impl<T: DeploymentGetDeployment + DeploymentGetConnectionString> EvalDeploymentManagement for T {}
+
+pub struct Eval {
+    deployment_name: String,
+    expression: String,
+    db: Option<String>,
+    json: bool,
+
+    deployment_management: Box<dyn EvalDeploymentManagement + Send + Sync>,
+    executor: Box<dyn ContainerExecutor + Send + Sync>,
+}
+
+impl TryFrom<(args::Eval, &Settings)> for Eval {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Eval, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            expression: args.expression,
+            db: args.db,
+            json: args.json,
+
+            deployment_management: Box::new(Client::new(docker.clone())),
+            executor: Box::new(docker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum EvalResult {
+    Evaluated {
+        deployment_name: String,
+        /// The result as printed by mongosh, or the parsed Extended JSON with --json.
+        result: serde_json::Value,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for EvalResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // The result is printed as is, so scripts can use the output without stripping a message
+            Self::Evaluated {
+                result: serde_json::Value::String(result),
+                ..
+            } => write!(f, "{}", result),
+            Self::Evaluated { result, .. } => write!(f, "{:#}", result),
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Evaluating the expression against deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Eval {
+    type Output = EvalResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(deployment_name = ?self.deployment_name, db = ?self.db, "executing eval command");
+
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found".to_string()));
+        };
+
+        if !matches!(deployment.state, State::Running) {
+            return Ok(self.failed("deployment is not running, start it first".to_string()));
+        }
+
+        let connection_string = self
+            .deployment_management
+            .get_connection_string(deployment.container_id)
+            .await?;
+        // Without --db, db refers to the database of the deployment, like in the seed scripts
+        let database = self
+            .db
+            .as_deref()
+            .or(deployment.mongodb_initdb_database.as_deref());
+        let connection_string = container_database_connection_string(&connection_string, database)?;
+
+        let mut cmd = vec![
+            "mongosh".to_string(),
+            connection_string,
+            "--quiet".to_string(),
+        ];
+        if self.json {
+            cmd.push("--json=relaxed".to_string());
+        }
+        cmd.push("--eval".to_string());
+        cmd.push(self.expression.clone());
+
+        debug!(json = self.json, "evaluating expression");
+        let output = self
+            .executor
+            .exec(&self.deployment_name, cmd, None)
+            .await
+            .context("running mongosh")?;
+
+        if output.exit_code != 0 {
+            let mut error = format!("mongosh exited with code {}", output.exit_code);
+            if let Some(line) = output.last_stderr_line() {
+                error.push_str(": ");
+                error.push_str(line);
+            }
+            return Ok(self.failed(error));
+        }
+
+        let stdout = output.stdout.trim_end();
+        let result = if self.json {
+            serde_json::from_str(stdout).context("parsing the Extended JSON printed by mongosh")?
+        } else {
+            serde_json::Value::String(stdout.to_string())
+        };
+
+        Ok(EvalResult::Evaluated {
+            deployment_name: self.deployment_name.clone(),
+            result,
+        })
+    }
+}
+
+impl Eval {
+    fn failed(&self, error: String) -> EvalResult {
+        EvalResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support;
+    use crate::dependencies::{ExecOutput, mocks::MockDocker};
+    use atlas_local::models::Deployment as AtlasDeployment;
+    use serde_json::json;
+
+    fn create_deployment(name: &str, state: State) -> AtlasDeployment {
+        AtlasDeployment {
+            mongodb_initdb_database: Some("shop".to_string()),
+            ..test_support::create_deployment(name, state)
+        }
+    }
+
+    fn create_deployment_management(state: State) -> MockDocker {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |name| Ok(create_deployment(name, state)));
+        mock_deployment_management
+            .expect_get_connection_string()
+            .returning(|_| Ok("mongodb://localhost:32768/?directConnection=true".to_string()));
+        mock_deployment_management
+    }
+
+    fn create_command(
+        db: Option<&str>,
+        json: bool,
+        deployment_management: MockDocker,
+        executor: MockDocker,
+    ) -> Eval {
+        Eval {
+            deployment_name: "local1".to_string(),
+            expression: "db.users.countDocuments()".to_string(),
+            db: db.map(ToString::to_string),
+            json,
+            deployment_management: Box::new(deployment_management),
+            executor: Box::new(executor),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eval_prints_mongosh_output() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor
+            .expect_exec()
+            .withf(|deployment_name, cmd, stdin| {
+                deployment_name == "local1"
+                    && *cmd
+                        == [
+                            "mongosh",
+                            "mongodb://127.0.0.1:27017/shop?directConnection=true",
+                            "--quiet",
+                            "--eval",
+                            "db.users.countDocuments()",
+                        ]
+                    && stdin.is_none()
+            })
+            .return_once(|_, _, _| {
+                Ok(ExecOutput {
+                    exit_code: 0,
+                    stdout: "42\n".to_string(),
+                    stderr: String::new(),
+                    ..Default::default()
+                })
+            });
+
+        let mut eval_command = create_command(
+            None,
+            false,
+            create_deployment_management(State::Running),
+            mock_executor,
+        );
+
+        let result = eval_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            EvalResult::Evaluated {
+                deployment_name: "local1".to_string(),
+                result: json!("42"),
+            }
+        );
+        assert_eq!(result.to_string(), "42");
+    }
+
+    #[tokio::test]
+    async fn test_eval_json_with_db() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor
+            .expect_exec()
+            .withf(|_, cmd, _| {
+                cmd[1] == "mongodb://127.0.0.1:27017/analytics?directConnection=true"
+                    && cmd[3] == "--json=relaxed"
+            })
+            .return_once(|_, _, _| {
+                Ok(ExecOutput {
+                    exit_code: 0,
+                    stdout: "{\n  \"_id\": {\n    \"$oid\": \"65f0c0ffee0000000000beef\"\n  },\n  \"count\": 3\n}\n".to_string(),
+                    stderr: String::new(),
+                    ..Default::default()
+                })
+            });
+
+        let mut eval_command = create_command(
+            Some("analytics"),
+            true,
+            create_deployment_management(State::Running),
+            mock_executor,
+        );
+
+        let result = eval_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            EvalResult::Evaluated {
+                deployment_name: "local1".to_string(),
+                result: json!({"_id": {"$oid": "65f0c0ffee0000000000beef"}, "count": 3}),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eval_expression_fails() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor.expect_exec().return_once(|_, _, _| {
+            Ok(ExecOutput {
+                exit_code: 1,
+                stdout: String::new(),
+                stderr: "ReferenceError: dbx is not defined\n".to_string(),
+                ..Default::default()
+            })
+        });
+
+        let mut eval_command = create_command(
+            None,
+            false,
+            create_deployment_management(State::Running),
+            mock_executor,
+        );
+
+        let result = eval_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            EvalResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "mongosh exited with code 1: ReferenceError: dbx is not defined".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eval_stopped_deployment() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor.expect_exec().never();
+
+        let mut eval_command = create_command(
+            None,
+            false,
+            create_deployment_management(State::Exited),
+            mock_executor,
+        );
+
+        let result = eval_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            EvalResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "deployment is not running, start it first".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,242 @@
+//! Command to show deployment events.
+//!
+//! This module implements the `events` command which streams the events of local deployments as they occur,
+//! similar to `docker events`.
+
+use std::{fmt::Display, time::SystemTime};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{Command, shutdown::shutdown_signal},
+    dependencies::{DeploymentEvent, DeploymentEventsOptions, DockerEvents, connect_docker},
+    formatting::{Format, Formattable},
+    settings::Settings,
+};
+
+/// Command to show deployment events.
+///
+/// Unlike the other commands, this command streams its output, every event is printed as soon as it is received.
+pub struct Events {
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    format: Format,
+
+    docker_events: Box<dyn DockerEvents + Send + Sync>,
+}
+
+impl TryFrom<(args::Events, &Settings)> for Events {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Events, &Settings)) -> Result<Self> {
+        Ok(Self {
+            since: args.since,
+            until: args.until,
+            format: Format::Text,
+
+            docker_events: Box::new(
+                connect_docker(&settings.docker).context("connecting to Docker")?,
+            ),
+        })
+    }
+}
+
+impl Events {
+    /// Set the format the events are printed in.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Stream the deployment events to the callback until the stream ends or the process is asked to shut down.
+    ///
+    /// Both Ctrl-C and SIGTERM stop the stream, so the command can run under a process manager.
+    async fn stream_events<F>(&self, mut on_event: F) -> Result<()>
+    where
+        F: FnMut(EventOutput) -> Result<()> + Send,
+    {
+        let mut events = self
+            .docker_events
+            .deployment_events(DeploymentEventsOptions {
+                since: self.since,
+                until: self.until,
+            });
+
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                result = &mut shutdown => {
+                    result?;
+                    debug!("stopping the event stream");
+                    return Ok(());
+                }
+                event = events.next() => match event {
+                    Some(event) => on_event(event.context("receiving deployment event")?.into())?,
+                    None => {
+                        debug!("event stream ended");
+                        return Ok(());
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// A single deployment event as printed by the events command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventOutput {
+    /// RFC 3339 timestamp of the event.
+    pub time: String,
+    pub action: String,
+    pub deployment_name: String,
+    pub container_id: String,
+}
+
+impl From<DeploymentEvent> for EventOutput {
+    fn from(event: DeploymentEvent) -> Self {
+        Self {
+            time: humantime::format_rfc3339_seconds(event.time).to_string(),
+            action: event.action,
+            deployment_name: event.deployment_name,
+            container_id: event.container_id,
+        }
+    }
+}
+
+impl Display for EventOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} ({})",
+            self.time, self.action, self.deployment_name, self.container_id
+        )
+    }
+}
+
+#[async_trait]
+impl Command for Events {
+    async fn execute(&mut self) -> Result<()> {
+        let format = self.format;
+
+        // Print every event on its own line, for JSON this results in newline delimited JSON.
+        self.stream_events(|event| {
+            println!("{}", event.format(format)?);
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::mocks::MockDocker;
+    use futures::stream;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn create_event(action: &str) -> DeploymentEvent {
+        DeploymentEvent {
+            time: UNIX_EPOCH + Duration::from_secs(1_767_225_600),
+            action: action.to_string(),
+            deployment_name: "local1234".to_string(),
+            container_id: "abc123".to_string(),
+        }
+    }
+
+    fn create_events_command(docker_events: MockDocker) -> Events {
+        Events {
+            since: Some(UNIX_EPOCH + Duration::from_secs(1_767_225_000)),
+            until: Some(UNIX_EPOCH + Duration::from_secs(1_767_226_000)),
+            format: Format::Text,
+            docker_events: Box::new(docker_events),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_streams_all_events() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker
+            .expect_deployment_events()
+            .withf(|options| {
+                options.since == Some(UNIX_EPOCH + Duration::from_secs(1_767_225_000))
+                    && options.until == Some(UNIX_EPOCH + Duration::from_secs(1_767_226_000))
+            })
+            .return_once(|_| {
+                stream::iter(vec![Ok(create_event("start")), Ok(create_event("die"))]).boxed()
+            });
+
+        let events_command = create_events_command(mock_docker);
+
+        let mut received = Vec::new();
+        events_command
+            .stream_events(|event| {
+                received.push(event);
+                Ok(())
+            })
+            .await
+            .expect("streaming events should succeed");
+
+        assert_eq!(
+            received
+                .iter()
+                .map(|event| event.action.as_str())
+                .collect::<Vec<_>>(),
+            vec!["start", "die"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_events_stream_error() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker.expect_deployment_events().return_once(|_| {
+            stream::iter(vec![
+                Ok(create_event("start")),
+                Err(bollard::errors::Error::RequestTimeoutError),
+            ])
+            .boxed()
+        });
+
+        let events_command = create_events_command(mock_docker);
+
+        let mut received = 0;
+        let result = events_command
+            .stream_events(|_| {
+                received += 1;
+                Ok(())
+            })
+            .await;
+
+        assert_eq!(received, 1);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("receiving deployment event")
+        );
+    }
+
+    #[test]
+    fn test_event_output_display() {
+        let output = EventOutput::from(create_event("start"));
+        assert_eq!(
+            output.to_string(),
+            "2026-01-01T00:00:00Z start local1234 (abc123)"
+        );
+    }
+
+    #[test]
+    fn test_event_output_json() {
+        let output = EventOutput::from(create_event("destroy"));
+        assert_eq!(
+            output.format(Format::JSON).unwrap(),
+            r#"{"time":"2026-01-01T00:00:00Z","action":"destroy","deployment_name":"local1234","container_id":"abc123"}"#
+        );
+    }
+}
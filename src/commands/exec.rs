@@ -0,0 +1,431 @@
+//! Command to run a command inside the container of a deployment.
+//!
+//! This module implements the `exec` command, a `docker exec` that targets a deployment by name. Without a command,
+//! the mongosh inside the container is opened, connected to the deployment. Like the exists command, the outcome is
+//! the exit code, the command prints nothing itself. With `--capture`, the output of the command is captured and
+//! printed as the result instead.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::{
+    args,
+    commands::{
+        Command,
+        connectors::{
+            Connector, ConnectorExitError, ConnectorOptions, ContainerExec, ContainerMongosh,
+            DeploymentParams, InteractiveExec, exit_on_connector_exit,
+        },
+    },
+    dependencies::{
+        ContainerExecutor, DeploymentGetConnectionString, DeploymentGetDeployment, ExecOutput,
+        connect_docker, find_deployment,
+    },
+    formatting::{Format, Formattable},
+    settings::Settings,
+};
+
+// Deployment dependencies for the exec command
+pub trait ExecDeploymentManagement:
+    DeploymentGetDeployment + DeploymentGetConnectionString
+{
+}
+impl<T: DeploymentGetDeployment + DeploymentGetConnectionString> ExecDeploymentManagement for T {}
+
+pub struct Exec {
+    deployment_name: String,
+    command: Vec<String>,
+    capture: bool,
+    max_output: Option<usize>,
+    format: Format,
+
+    deployment_management: Box<dyn ExecDeploymentManagement + Send + Sync>,
+    mongosh: Box<dyn Connector + Send + Sync>,
+    container_exec: Box<dyn InteractiveExec + Send + Sync>,
+    executor: Box<dyn ContainerExecutor + Send + Sync>,
+}
+
+impl TryFrom<(args::Exec, &Settings)> for Exec {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Exec, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            command: args.command,
+            capture: args.capture,
+            max_output: args.max_output,
+            format: Format::Text,
+
+            deployment_management: Box::new(Client::new(docker.clone())),
+            mongosh: Box::new(ContainerMongosh::new(settings.docker.clone())),
+            container_exec: Box::new(ContainerExec::new(settings.docker.clone())),
+            executor: Box::new(docker),
+        })
+    }
+}
+
+/// Output of a command that ran with `--capture`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExecResult {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+    /// Whether output was dropped because stdout or stderr exceeded `--maxOutput`.
+    pub truncated: bool,
+}
+
+impl From<ExecOutput> for ExecResult {
+    fn from(output: ExecOutput) -> Self {
+        Self {
+            exit_code: output.exit_code,
+            stdout: output.stdout,
+            stderr: output.stderr,
+            truncated: output.truncated,
+        }
+    }
+}
+
+impl Display for ExecResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The output is printed as is, like without --capture, stderr is printed separately
+        write!(f, "{}", self.stdout)
+    }
+}
+
+impl Exec {
+    /// Set the format the output of a captured command is printed in.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Run the command in the container, a non-zero exit code is returned as a connector exit error.
+    async fn run(&self) -> Result<()> {
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            bail!("Deployment '{}' does not exist", self.deployment_name)
+        };
+
+        if !matches!(deployment.state, State::Running) {
+            bail!(
+                "Deployment '{}' is not running, start it first",
+                self.deployment_name
+            );
+        }
+
+        if self.capture {
+            let result = self.capture().await?;
+            self.print(&result)?;
+            if result.exit_code != 0 {
+                return Err(ConnectorExitError {
+                    code: i32::try_from(result.exit_code).unwrap_or(1),
+                }
+                .into());
+            }
+            return Ok(());
+        }
+
+        if !self.command.is_empty() {
+            if !self.container_exec.is_available().await {
+                bail!("docker is not installed, exec runs the command with docker exec");
+            }
+
+            debug!(command = ?self.command, "running command in deployment container");
+            return self
+                .container_exec
+                .exec(&self.deployment_name, &self.command)
+                .await;
+        }
+
+        if !self.mongosh.is_available().await {
+            bail!("docker is not installed, exec runs mongosh with docker exec");
+        }
+
+        let connection_string = self
+            .deployment_management
+            .get_connection_string(deployment.container_id)
+            .await?;
+
+        debug!("launching mongosh in deployment container");
+        let params = DeploymentParams::new(&self.deployment_name, connection_string);
+        self.mongosh.launch(&params, &ConnectorOptions::new()).await
+    }
+
+    /// Run the command with its output captured, the output is kept up to --maxOutput bytes.
+    async fn capture(&self) -> Result<ExecResult> {
+        debug!(command = ?self.command, max_output = ?self.max_output, "capturing command in deployment container");
+        let output = match self.max_output {
+            Some(max_output) => {
+                self.executor
+                    .exec_with_max_output(&self.deployment_name, self.command.clone(), max_output)
+                    .await
+            }
+            None => {
+                self.executor
+                    .exec(&self.deployment_name, self.command.clone(), None)
+                    .await
+            }
+        }
+        .context("running the command")?;
+
+        Ok(ExecResult::from(output))
+    }
+
+    /// Print the output of a captured command, text output is printed to stdout and stderr like the command did.
+    fn print(&self, result: &ExecResult) -> Result<()> {
+        match self.format {
+            Format::Text => {
+                print!("{}", result.format(self.format)?);
+                eprint!("{}", result.stderr);
+                if result.truncated {
+                    warn!("the output of the command was truncated to --maxOutput bytes");
+                }
+            }
+            format => println!("{}", result.format(format)?),
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Command for Exec {
+    async fn execute(&mut self) -> Result<()> {
+        // Like docker exec, the exit code of the command is the exit code of the CLI
+        exit_on_connector_exit(self.run().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::create_deployment;
+    use crate::dependencies::mocks::MockDocker;
+    use mockall::mock;
+
+    mock! {
+        pub Connector {}
+
+        #[async_trait]
+        impl Connector for Connector {
+            async fn is_available(&self) -> bool;
+            async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()>;
+        }
+    }
+
+    mock! {
+        pub InteractiveExec {}
+
+        #[async_trait]
+        impl InteractiveExec for InteractiveExec {
+            async fn is_available(&self) -> bool;
+            async fn exec(&self, deployment_name: &str, cmd: &[String]) -> Result<()>;
+        }
+    }
+
+    fn create_command(
+        command: Vec<String>,
+        deployment_management: MockDocker,
+        mongosh: MockConnector,
+        container_exec: MockInteractiveExec,
+    ) -> Exec {
+        Exec {
+            deployment_name: "local1".to_string(),
+            command,
+            capture: false,
+            max_output: None,
+            format: Format::Text,
+            deployment_management: Box::new(deployment_management),
+            mongosh: Box::new(mongosh),
+            container_exec: Box::new(container_exec),
+            executor: Box::new(MockDocker::new()),
+        }
+    }
+
+    fn create_capture_command(max_output: Option<usize>, executor: MockDocker) -> Exec {
+        let mut container_exec = MockInteractiveExec::new();
+        container_exec.expect_exec().never();
+
+        Exec {
+            capture: true,
+            max_output,
+            executor: Box::new(executor),
+            ..create_command(
+                vec!["ls".to_string(), "/data/db".to_string()],
+                MockDocker::new(),
+                MockConnector::new(),
+                container_exec,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exec_without_command_launches_mongosh() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+        mock_deployment_management
+            .expect_get_connection_string()
+            .withf(|container_id| container_id == "container-local1")
+            .return_once(|_| Ok("mongodb://127.0.0.1:32768/?directConnection=true".to_string()));
+
+        let mut mock_mongosh = MockConnector::new();
+        mock_mongosh.expect_is_available().returning(|| true);
+        mock_mongosh
+            .expect_launch()
+            .withf(|params, options| {
+                params.name == "local1"
+                    && params.connection_string
+                        == "mongodb://127.0.0.1:32768/?directConnection=true"
+                    && options.is_empty()
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut mock_container_exec = MockInteractiveExec::new();
+        mock_container_exec.expect_exec().never();
+
+        let exec_command = create_command(
+            vec![],
+            mock_deployment_management,
+            mock_mongosh,
+            mock_container_exec,
+        );
+
+        exec_command.run().await.expect("run should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_exec_command_returns_exit_code() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+
+        let mut mock_mongosh = MockConnector::new();
+        mock_mongosh.expect_launch().never();
+
+        let mut mock_container_exec = MockInteractiveExec::new();
+        mock_container_exec.expect_is_available().returning(|| true);
+        mock_container_exec
+            .expect_exec()
+            .withf(|deployment_name, cmd| deployment_name == "local1" && *cmd == ["ls", "/data/db"])
+            .return_once(|_, _| Err(ConnectorExitError { code: 2 }.into()));
+
+        let exec_command = create_command(
+            vec!["ls".to_string(), "/data/db".to_string()],
+            mock_deployment_management,
+            mock_mongosh,
+            mock_container_exec,
+        );
+
+        let error = exec_command.run().await.expect_err("run should fail");
+        assert!(matches!(
+            error.downcast_ref::<ConnectorExitError>(),
+            Some(ConnectorExitError { code: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exec_stopped_deployment() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Exited)));
+
+        let mut mock_container_exec = MockInteractiveExec::new();
+        mock_container_exec.expect_exec().never();
+
+        let exec_command = create_command(
+            vec!["bash".to_string()],
+            mock_deployment_management,
+            MockConnector::new(),
+            mock_container_exec,
+        );
+
+        let error = exec_command.run().await.expect_err("run should fail");
+        assert_eq!(
+            error.to_string(),
+            "Deployment 'local1' is not running, start it first"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_capture_returns_output() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor
+            .expect_exec()
+            .withf(|deployment_name, cmd, stdin| {
+                deployment_name == "local1" && *cmd == ["ls", "/data/db"] && stdin.is_none()
+            })
+            .return_once(|_, _, _| {
+                Ok(ExecOutput {
+                    exit_code: 2,
+                    stdout: "journal\n".to_string(),
+                    stderr: "ls: cannot access 'WiredTiger.lock'\n".to_string(),
+                    truncated: false,
+                })
+            });
+
+        let exec_command = create_capture_command(None, mock_executor);
+
+        let result = exec_command
+            .capture()
+            .await
+            .expect("capture should succeed");
+        assert_eq!(
+            result,
+            ExecResult {
+                exit_code: 2,
+                stdout: "journal\n".to_string(),
+                stderr: "ls: cannot access 'WiredTiger.lock'\n".to_string(),
+                truncated: false,
+            }
+        );
+        assert_eq!(
+            result
+                .format(Format::Json {
+                    envelope: false,
+                    array: false,
+                })
+                .unwrap(),
+            r#"{"exit_code":2,"stdout":"journal\n","stderr":"ls: cannot access 'WiredTiger.lock'\n","truncated":false}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exec_capture_with_max_output_is_truncated() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor.expect_exec().never();
+        mock_executor
+            .expect_exec_with_max_output()
+            .withf(|deployment_name, cmd, max_output| {
+                deployment_name == "local1" && *cmd == ["ls", "/data/db"] && *max_output == 4
+            })
+            .return_once(|_, _, _| {
+                Ok(ExecOutput {
+                    exit_code: 0,
+                    stdout: "jour".to_string(),
+                    stderr: String::new(),
+                    truncated: true,
+                })
+            });
+
+        let exec_command = create_capture_command(Some(4), mock_executor);
+
+        let result = exec_command
+            .capture()
+            .await
+            .expect("capture should succeed");
+        assert_eq!(result.stdout, "jour");
+        assert!(result.truncated);
+    }
+}
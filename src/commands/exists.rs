@@ -0,0 +1,165 @@
+//! Command to check whether a deployment exists.
+//!
+//! This module implements the `exists` command, a cheap check for scripts.
+//! The answer is the exit code: 0 when the deployment exists and 1 when it doesn't.
+//! When Docker can't be reached, the command fails with the Docker error exit code instead.
+
+use std::process::exit;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::Client;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::Command,
+    dependencies::{DeploymentGetDeployment, connect_docker, find_deployment},
+    formatting::{Format, Formattable},
+    settings::Settings,
+};
+
+/// Exit code used when the deployment doesn't exist.
+const NOT_FOUND_EXIT_CODE: i32 = 1;
+
+/// Command to check whether a deployment exists.
+///
+/// Unlike the other commands, this command reports its outcome with the exit code.
+/// Text output prints nothing, JSON output prints `true` or `false`.
+pub struct Exists {
+    deployment_name: String,
+    format: Format,
+
+    deployment_getter: Box<dyn DeploymentGetDeployment + Send + Sync>,
+}
+
+impl TryFrom<(args::Exists, &Settings)> for Exists {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Exists, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            format: Format::Text,
+
+            deployment_getter: Box::new(Client::new(
+                connect_docker(&settings.docker).context("connecting to Docker")?,
+            )),
+        })
+    }
+}
+
+impl Exists {
+    /// Set the format the outcome is printed in.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Returns whether the deployment exists, errors when Docker failed to answer.
+    async fn exists(&self) -> Result<bool> {
+        Ok(
+            find_deployment(self.deployment_getter.as_ref(), &self.deployment_name)
+                .await?
+                .is_some(),
+        )
+    }
+}
+
+#[async_trait]
+impl Command for Exists {
+    async fn execute(&mut self) -> Result<()> {
+        let exists = self.exists().await?;
+        debug!(
+            deployment_name = %self.deployment_name,
+            exists, "checked whether deployment exists"
+        );
+
+        if self.format != Format::Text {
+            println!("{}", exists.format(self.format)?);
+        }
+
+        if !exists {
+            exit(NOT_FOUND_EXIT_CODE);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::create_deployment;
+    use crate::dependencies::{DockerError, mocks::MockDocker};
+    use atlas_local::{GetDeploymentError, models::State};
+    use bollard::errors::Error as BollardError;
+
+    fn create_exists_command(deployment_getter: MockDocker) -> Exists {
+        Exists {
+            deployment_name: "test-deployment".to_string(),
+            format: Format::Text,
+            deployment_getter: Box::new(deployment_getter),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exists_when_deployment_exists() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter
+            .expect_get_deployment()
+            .withf(|name| name == "test-deployment")
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+
+        let exists_command = create_exists_command(mock_getter);
+
+        assert!(
+            exists_command
+                .exists()
+                .await
+                .expect("exists should succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exists_when_deployment_does_not_exist() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter.expect_get_deployment().return_once(|_| {
+            Err(GetDeploymentError::ContainerInspect(
+                BollardError::DockerResponseServerError {
+                    status_code: 404,
+                    message: "No such container: test-deployment".to_string(),
+                },
+            ))
+        });
+
+        let exists_command = create_exists_command(mock_getter);
+
+        assert!(
+            !exists_command
+                .exists()
+                .await
+                .expect("exists should succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exists_docker_error() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter.expect_get_deployment().return_once(|_| {
+            Err(GetDeploymentError::ContainerInspect(
+                BollardError::DockerResponseServerError {
+                    status_code: 500,
+                    message: "internal server error".to_string(),
+                },
+            ))
+        });
+
+        let exists_command = create_exists_command(mock_getter);
+
+        let error = exists_command
+            .exists()
+            .await
+            .expect_err("exists should fail when Docker fails");
+        assert!(error.chain().any(|cause| cause.is::<DockerError>()));
+    }
+}
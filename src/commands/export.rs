@@ -0,0 +1,307 @@
+//! Command to export the data of a deployment with mongodump.
+//!
+//! mongodump is launched like a connector: the mongodump installed on the host is preferred, the mongodump in the
+//! deployment container is the fallback.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        connectors::{
+            Connector, ConnectorExitError, ConnectorOptions, ContainerMongodump, DeploymentParams,
+            Mongodump, WithFallback,
+        },
+    },
+    dependencies::{
+        DeploymentGetConnectionString, DeploymentGetDeployment, connect_docker, find_deployment,
+    },
+    settings::Settings,
+};
+
+// Deployment dependencies for the export command
+pub trait ExportDeploymentManagement:
+    DeploymentGetDeployment + DeploymentGetConnectionString
+{
+}
+impl<T: DeploymentGetDeployment + DeploymentGetConnectionString> ExportDeploymentManagement for T {}
+
+pub struct Export {
+    deployment_name: String,
+    db: Option<String>,
+    out: PathBuf,
+
+    deployment_management: Box<dyn ExportDeploymentManagement + Send + Sync>,
+    mongodump: Box<dyn Connector + Send + Sync>,
+}
+
+impl TryFrom<(args::Export, &Settings)> for Export {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Export, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            db: args.db,
+            out: args.out,
+
+            deployment_management: Box::new(Client::new(docker)),
+            mongodump: Box::new(WithFallback::new(
+                Box::new(Mongodump::new()),
+                Box::new(ContainerMongodump::new(settings.docker.clone())),
+            )),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ExportResult {
+    Exported {
+        deployment_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        db: Option<String>,
+        out: PathBuf,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for ExportResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exported {
+                deployment_name,
+                db,
+                out,
+            } => match db {
+                Some(db) => write!(
+                    f,
+                    "Database '{}' of deployment '{}' exported to {}",
+                    db,
+                    deployment_name,
+                    out.display()
+                ),
+                None => write!(
+                    f,
+                    "Deployment '{}' exported to {}",
+                    deployment_name,
+                    out.display()
+                ),
+            },
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Exporting deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Export {
+    type Output = ExportResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(
+            deployment_name = ?self.deployment_name,
+            db = ?self.db,
+            out = ?self.out,
+            "executing export command"
+        );
+
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found".to_string()));
+        };
+
+        if !matches!(deployment.state, State::Running) {
+            return Ok(self.failed("deployment is not running, start it first".to_string()));
+        }
+
+        if !self.mongodump.is_available().await {
+            return Ok(self.failed("mongodump is not installed".to_string()));
+        }
+
+        let connection_string = self
+            .deployment_management
+            .get_connection_string(deployment.container_id)
+            .await?;
+
+        let mut options = ConnectorOptions::new();
+        if let Some(db) = &self.db {
+            options.insert("db".to_string(), db.clone());
+        }
+        options.insert("out".to_string(), self.out.display().to_string());
+
+        debug!("launching mongodump");
+        let params = DeploymentParams::new(&self.deployment_name, connection_string);
+        if let Err(e) = self.mongodump.launch(&params, &options).await {
+            // mongodump reports its own errors on stderr, only the exit code is left to report
+            if let Some(ConnectorExitError { code }) = e.downcast_ref::<ConnectorExitError>() {
+                return Ok(self.failed(format!("mongodump exited with code {code}")));
+            }
+            return Err(e).context("launching mongodump");
+        }
+
+        Ok(ExportResult::Exported {
+            deployment_name: self.deployment_name.clone(),
+            db: self.db.clone(),
+            out: self.out.clone(),
+        })
+    }
+}
+
+impl Export {
+    fn failed(&self, error: String) -> ExportResult {
+        ExportResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::create_deployment;
+    use crate::dependencies::mocks::MockDocker;
+    use mockall::mock;
+
+    mock! {
+        pub Connector {}
+
+        #[async_trait]
+        impl Connector for Connector {
+            async fn is_available(&self) -> bool;
+            async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()>;
+        }
+    }
+
+    fn create_command(deployment_management: MockDocker, mongodump: MockConnector) -> Export {
+        Export {
+            deployment_name: "local1".to_string(),
+            db: Some("shop".to_string()),
+            out: PathBuf::from("backup"),
+            deployment_management: Box::new(deployment_management),
+            mongodump: Box::new(mongodump),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_database() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+        mock_deployment_management
+            .expect_get_connection_string()
+            .withf(|container_id| container_id == "container-local1")
+            .return_once(|_| Ok("mongodb://127.0.0.1:27017/?directConnection=true".to_string()));
+
+        let mut mock_mongodump = MockConnector::new();
+        mock_mongodump.expect_is_available().returning(|| true);
+        mock_mongodump
+            .expect_launch()
+            .withf(|params, options| {
+                params.name == "local1"
+                    && params.connection_string
+                        == "mongodb://127.0.0.1:27017/?directConnection=true"
+                    && options.get("db").map(String::as_str) == Some("shop")
+                    && options.get("out").map(String::as_str) == Some("backup")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut export_command = create_command(mock_deployment_management, mock_mongodump);
+
+        let result = export_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ExportResult::Exported {
+                deployment_name: "local1".to_string(),
+                db: Some("shop".to_string()),
+                out: PathBuf::from("backup"),
+            }
+        );
+        assert_eq!(
+            result.to_string(),
+            "Database 'shop' of deployment 'local1' exported to backup"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_stopped_deployment() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Exited)));
+
+        let mut mock_mongodump = MockConnector::new();
+        mock_mongodump.expect_launch().never();
+
+        let mut export_command = create_command(mock_deployment_management, mock_mongodump);
+
+        let result = export_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ExportResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "deployment is not running, start it first".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_mongodump_fails() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(|_| Ok("mongodb://127.0.0.1:27017/?directConnection=true".to_string()));
+
+        let mut mock_mongodump = MockConnector::new();
+        mock_mongodump.expect_is_available().returning(|| true);
+        mock_mongodump
+            .expect_launch()
+            .return_once(|_, _| Err(ConnectorExitError { code: 1 }.into()));
+
+        let mut export_command = create_command(mock_deployment_management, mock_mongodump);
+
+        let result = export_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ExportResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "mongodump exited with code 1".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Command to import a dump into a deployment with mongorestore.
+//!
+//! mongorestore runs in the background like a connector, the mongorestore installed on the host is preferred and the
+//! mongorestore in the deployment container is the fallback. Its log lines are parsed to show the progress in the
+//! spinner and to report the number of restored documents.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        connectors::{
+            BackgroundConnector, BackgroundWithFallback, ConnectorExitError, ConnectorOptions,
+            ContainerMongorestore, DeploymentParams, Mongorestore,
+        },
+    },
+    dependencies::{
+        DeploymentGetConnectionString, DeploymentGetDeployment, PathKind, PathResolver, TokioFs,
+        connect_docker, find_deployment,
+    },
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+    settings::Settings,
+};
+
+// Deployment dependencies for the import command
+pub trait ImportDeploymentManagement:
+    DeploymentGetDeployment + DeploymentGetConnectionString
+{
+}
+impl<T: DeploymentGetDeployment + DeploymentGetConnectionString> ImportDeploymentManagement for T {}
+
+pub struct Import {
+    deployment_name: String,
+    dir: PathBuf,
+    drop: bool,
+
+    interaction: Box<dyn SpinnerInteraction + Send>,
+    deployment_management: Box<dyn ImportDeploymentManagement + Send + Sync>,
+    path_resolver: Box<dyn PathResolver + Send + Sync>,
+    mongorestore: Box<dyn BackgroundConnector + Send + Sync>,
+}
+
+impl TryFrom<(args::Import, &Settings)> for Import {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Import, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            dir: args.dir,
+            drop: args.drop,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker)),
+            path_resolver: Box::new(TokioFs::new()),
+            mongorestore: Box::new(BackgroundWithFallback::new(
+                Box::new(Mongorestore::new()),
+                Box::new(ContainerMongorestore::new(settings.docker.clone())),
+            )),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ImportResult {
+    Imported {
+        deployment_name: String,
+        documents_restored: u64,
+        documents_failed: u64,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for ImportResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Imported {
+                deployment_name,
+                documents_restored,
+                documents_failed,
+            } => {
+                write!(
+                    f,
+                    "{} document(s) imported into deployment '{}'",
+                    documents_restored, deployment_name
+                )?;
+                if *documents_failed > 0 {
+                    write!(f, ", {} document(s) failed to import", documents_failed)?;
+                }
+                Ok(())
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Importing into deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Import {
+    type Output = ImportResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(
+            deployment_name = ?self.deployment_name,
+            dir = ?self.dir,
+            drop = self.drop,
+            "executing import command"
+        );
+
+        if self.path_resolver.path_kind(&self.dir).await? != Some(PathKind::Directory) {
+            return Ok(self.failed(format!(
+                "dump directory {} does not exist",
+                self.dir.display()
+            )));
+        }
+
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found".to_string()));
+        };
+
+        if !matches!(deployment.state, State::Running) {
+            return Ok(self.failed("deployment is not running, start it first".to_string()));
+        }
+
+        if !self.mongorestore.is_available().await {
+            return Ok(self.failed("mongorestore is not installed".to_string()));
+        }
+
+        let connection_string = self
+            .deployment_management
+            .get_connection_string(deployment.container_id)
+            .await?;
+
+        let mut options = ConnectorOptions::new();
+        options.insert("dir".to_string(), self.dir.display().to_string());
+        if self.drop {
+            options.insert("drop".to_string(), "true".to_string());
+        }
+
+        // When spinner goes out of scope, the spinner will be stopped
+        let spinner = self
+            .interaction
+            .start_spinner(message(Message::ImportDump).to_string())?;
+
+        debug!("running mongorestore");
+        let params = DeploymentParams::new(&self.deployment_name, connection_string);
+        let (output, mut lines) = mpsc::unbounded_channel();
+        let mut progress = RestoreProgress::default();
+        let (run_result, ()) =
+            tokio::join!(self.mongorestore.run(&params, &options, output), async {
+                while let Some(line) = lines.recv().await {
+                    trace!(line = %line, "mongorestore output");
+                    if let Some(message) = progress.update(&line) {
+                        spinner.set_message(message);
+                    }
+                }
+            });
+
+        if let Err(e) = run_result {
+            let code = match e.downcast_ref::<ConnectorExitError>() {
+                Some(ConnectorExitError { code }) => *code,
+                None => return Err(e).context("running mongorestore"),
+            };
+            // mongorestore reports the reason it failed in its log
+            let error = match progress.error {
+                Some(error) => format!("mongorestore exited with code {code}: {error}"),
+                None => format!("mongorestore exited with code {code}"),
+            };
+            return Ok(self.failed(error));
+        }
+
+        Ok(ImportResult::Imported {
+            deployment_name: self.deployment_name.clone(),
+            documents_restored: progress.documents_restored,
+            documents_failed: progress.documents_failed,
+        })
+    }
+}
+
+impl Import {
+    fn failed(&self, error: String) -> ImportResult {
+        ImportResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+}
+
+/// Progress of mongorestore, parsed from its log lines.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RestoreProgress {
+    documents_restored: u64,
+    documents_failed: u64,
+    /// The last error mongorestore logged.
+    error: Option<String>,
+}
+
+impl RestoreProgress {
+    /// Update the progress with a log line, returns the new message of the spinner when the line reports progress.
+    ///
+    /// The log lines are prefixed with a timestamp and a tab, progress lines look like
+    /// `[####....]  shop.orders  1.2MB/4.8MB  (25.0%)` after the prefix.
+    fn update(&mut self, line: &str) -> Option<String> {
+        let message = line
+            .split_once('\t')
+            .map_or(line, |(_, message)| message)
+            .trim();
+
+        if let Some(error) = message.strip_prefix("Failed: ") {
+            self.error = Some(error.to_string());
+            return None;
+        }
+
+        if let Some(namespace) = message
+            .strip_prefix("restoring ")
+            .and_then(|rest| rest.split_whitespace().next())
+            // Other restoring lines, e.g. of the indexes, don't start with a namespace
+            .filter(|namespace| namespace.contains('.'))
+        {
+            return Some(format!("Importing {namespace}..."));
+        }
+
+        if let Some((restored, rest)) = message.split_once(" document(s) restored successfully. ") {
+            let failed = rest.strip_suffix(" document(s) failed to restore.")?;
+            self.documents_restored = restored.parse().ok()?;
+            self.documents_failed = failed.parse().ok()?;
+            return None;
+        }
+
+        // Progress bars: the bar, the namespace, the restored size and the percentage
+        let (_, status) = message.strip_prefix('[')?.split_once(']')?;
+        let mut fields = status.split_whitespace();
+        let namespace = fields.next()?;
+        let percentage = fields.next_back()?.strip_prefix('(')?.strip_suffix(')')?;
+
+        Some(format!("Importing {namespace} ({percentage})..."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::commands::test_support::create_deployment;
+    use crate::dependencies::mocks::{MockDocker, MockTokioFs};
+    use crate::interaction::SpinnerHandle;
+    use crate::interaction::mocks::MockInteraction;
+    use mockall::mock;
+    use tokio::sync::mpsc::UnboundedSender;
+
+    mock! {
+        pub BackgroundConnector {}
+
+        #[async_trait]
+        impl BackgroundConnector for BackgroundConnector {
+            async fn is_available(&self) -> bool;
+            async fn run(&self, params: &DeploymentParams, options: &ConnectorOptions, output: UnboundedSender<String>) -> Result<()>;
+        }
+    }
+
+    fn create_running_deployment_management() -> MockDocker {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(|_| Ok("mongodb://127.0.0.1:27017/?directConnection=true".to_string()));
+        mock_deployment_management
+    }
+
+    /// Interaction with a spinner that records its messages.
+    fn create_interaction(messages: Arc<Mutex<Vec<String>>>) -> MockInteraction {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Importing dump...")
+            .return_once(move |_| {
+                Ok(
+                    SpinnerHandle::new(Box::new(|| {})).with_set_message(Box::new(
+                        move |message| messages.lock().unwrap().push(message),
+                    )),
+                )
+            });
+        mock_interaction
+    }
+
+    fn create_path_resolver(kind: Option<PathKind>) -> MockTokioFs {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().returning(move |_| Ok(kind));
+        mock_fs
+    }
+
+    /// mongorestore that logs the lines and exits with the result.
+    fn create_mongorestore(
+        lines: &'static [&'static str],
+        result: Result<()>,
+    ) -> MockBackgroundConnector {
+        let mut mock_mongorestore = MockBackgroundConnector::new();
+        mock_mongorestore.expect_is_available().returning(|| true);
+        mock_mongorestore
+            .expect_run()
+            .withf(|params, options, _| {
+                params.name == "local1"
+                    && options.get("dir").map(String::as_str) == Some("dump")
+                    && options.get("drop").map(String::as_str) == Some("true")
+            })
+            .return_once(move |_, _, output| {
+                for line in lines {
+                    output.send(line.to_string()).unwrap();
+                }
+                result
+            });
+        mock_mongorestore
+    }
+
+    fn create_command(
+        interaction: MockInteraction,
+        deployment_management: MockDocker,
+        path_resolver: MockTokioFs,
+        mongorestore: MockBackgroundConnector,
+    ) -> Import {
+        Import {
+            deployment_name: "local1".to_string(),
+            dir: PathBuf::from("dump"),
+            drop: true,
+            interaction: Box::new(interaction),
+            deployment_management: Box::new(deployment_management),
+            path_resolver: Box::new(path_resolver),
+            mongorestore: Box::new(mongorestore),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_progress() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let mongorestore = create_mongorestore(
+            &[
+                "2026-10-16T12:00:00.000+0000\tpreparing collections to restore from",
+                "2026-10-16T12:00:00.000+0000\trestoring shop.orders from dump/shop/orders.bson",
+                "2026-10-16T12:00:01.000+0000\t[######..................]  shop.orders  1.2MB/4.8MB  (25.0%)",
+                "2026-10-16T12:00:02.000+0000\tfinished restoring shop.orders (1000 documents, 0 failures)",
+                "2026-10-16T12:00:02.000+0000\t1000 document(s) restored successfully. 2 document(s) failed to restore.",
+            ],
+            Ok(()),
+        );
+
+        let mut import_command = create_command(
+            create_interaction(messages.clone()),
+            create_running_deployment_management(),
+            create_path_resolver(Some(PathKind::Directory)),
+            mongorestore,
+        );
+
+        let result = import_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ImportResult::Imported {
+                deployment_name: "local1".to_string(),
+                documents_restored: 1000,
+                documents_failed: 2,
+            }
+        );
+        assert_eq!(
+            *messages.lock().unwrap(),
+            vec![
+                "Importing shop.orders...",
+                "Importing shop.orders (25.0%)...",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_mongorestore_error() {
+        let mongorestore = create_mongorestore(
+            &[
+                "2026-10-16T12:00:00.000+0000\tFailed: error connecting to db server: server selection timeout",
+            ],
+            Err(ConnectorExitError { code: 1 }.into()),
+        );
+
+        let mut import_command = create_command(
+            create_interaction(Arc::new(Mutex::new(Vec::new()))),
+            create_running_deployment_management(),
+            create_path_resolver(Some(PathKind::Directory)),
+            mongorestore,
+        );
+
+        let result = import_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ImportResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "mongorestore exited with code 1: error connecting to db server: server selection timeout".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_missing_dump_directory() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management.expect_get_deployment().never();
+
+        let mut import_command = create_command(
+            MockInteraction::new(),
+            mock_deployment_management,
+            create_path_resolver(None),
+            MockBackgroundConnector::new(),
+        );
+
+        let result = import_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ImportResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "dump directory dump does not exist".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,566 @@
+//! Command to inspect a deployment.
+//!
+//! This module implements the `inspect` command which shows the details of a MongoDB Atlas local deployment.
+//! With `--raw` the unprocessed Docker container inspect output is returned instead, as an escape hatch for debugging.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{
+    Client,
+    models::{CreationSource, Deployment, MongodbType, State},
+};
+use bollard::models::ContainerInspectResponse;
+use semver::Version;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::CommandWithOutput,
+    dependencies::{
+        DeploymentContainerInspector, DeploymentGetDeployment, DockerError, connect_docker,
+        find_deployment,
+    },
+    settings::Settings,
+};
+
+/// Value shown instead of secrets, unless `--showSecrets` is used.
+const REDACTED: &str = "[redacted]";
+
+/// Environment variables with one of these parts in their name contain secrets.
+const SECRET_ENV_VAR_NAME_PARTS: &[&str] = &["PASSWORD", "SECRET", "TOKEN", "API_KEY"];
+
+/// Command to inspect a deployment.
+pub struct Inspect {
+    deployment_name: String,
+    raw: bool,
+    show_secrets: bool,
+
+    deployment_getter: Box<dyn DeploymentGetDeployment + Send + Sync>,
+    container_inspector: Box<dyn DeploymentContainerInspector + Send + Sync>,
+}
+
+impl TryFrom<(args::Inspect, &Settings)> for Inspect {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::Inspect, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            raw: args.raw,
+            show_secrets: args.show_secrets,
+
+            deployment_getter: Box::new(Client::new(docker.clone())),
+            container_inspector: Box::new(docker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum InspectResult {
+    Inspected {
+        deployment: DeploymentDetails,
+    },
+    // The unprocessed Docker inspect output of the container, returned with --raw
+    Raw {
+        container: serde_json::Value,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+/// The details of a deployment, as shown by the inspect command.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeploymentDetails {
+    pub name: String,
+    pub container_id: String,
+    pub mongodb_version: Version,
+    /// Either community or enterprise.
+    pub mongodb_type: String,
+    pub state: State,
+    pub port: Option<u16>,
+    /// The tool that created the deployment, e.g. atlas-local.
+    pub creation_source: Option<String>,
+    pub database: Option<String>,
+    pub username: Option<String>,
+    pub username_file: Option<String>,
+    pub password: Option<String>,
+    pub password_file: Option<String>,
+    pub load_sample_data: Option<bool>,
+    pub local_seed_location: Option<String>,
+    pub mongot_log_file: Option<String>,
+    pub runner_log_file: Option<String>,
+    pub do_not_track: bool,
+    pub telemetry_base_url: Option<String>,
+    pub voyage_api_key: Option<String>,
+}
+
+impl DeploymentDetails {
+    fn new(deployment: Deployment, show_secrets: bool) -> Self {
+        let secret = |value: Option<String>| {
+            value.map(|value| {
+                if show_secrets {
+                    value
+                } else {
+                    REDACTED.to_string()
+                }
+            })
+        };
+
+        Self {
+            name: deployment
+                .name
+                .unwrap_or_else(|| deployment.container_id.clone()),
+            container_id: deployment.container_id,
+            mongodb_version: deployment.mongodb_version,
+            mongodb_type: if matches!(deployment.mongodb_type, MongodbType::Enterprise) {
+                "enterprise".to_string()
+            } else {
+                "community".to_string()
+            },
+            state: deployment.state,
+            port: deployment
+                .port_bindings
+                .as_ref()
+                .and_then(|bindings| bindings.port),
+            creation_source: deployment
+                .creation_source
+                .as_ref()
+                .map(creation_source_name),
+            database: deployment.mongodb_initdb_database,
+            username: deployment.mongodb_initdb_root_username,
+            // The files only contain the paths of the secrets, not the secrets themselves
+            username_file: deployment.mongodb_initdb_root_username_file,
+            password: secret(deployment.mongodb_initdb_root_password),
+            password_file: deployment.mongodb_initdb_root_password_file,
+            load_sample_data: deployment.mongodb_load_sample_data,
+            local_seed_location: deployment.local_seed_location,
+            mongot_log_file: deployment.mongot_log_file,
+            runner_log_file: deployment.runner_log_file,
+            do_not_track: deployment.do_not_track,
+            telemetry_base_url: deployment.telemetry_base_url,
+            voyage_api_key: secret(deployment.voyage_api_key),
+        }
+    }
+}
+
+/// Name of the tool that created a deployment, as shown by inspect and matched by `list --filter source=...`.
+pub(crate) fn creation_source_name(creation_source: &CreationSource) -> String {
+    match creation_source {
+        CreationSource::AtlasLocal => "atlas-local".to_string(),
+        // Deployments created by other tools, e.g. with docker run
+        other => format!("{other:?}").to_lowercase(),
+    }
+}
+
+impl Display for InspectResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inspected { deployment } => {
+                writeln!(f, "Name: {}", deployment.name)?;
+                writeln!(f, "Container ID: {}", deployment.container_id)?;
+                writeln!(f, "MongoDB version: {}", deployment.mongodb_version)?;
+                writeln!(f, "MongoDB type: {}", deployment.mongodb_type)?;
+                writeln!(f, "State: {}", deployment.state)?;
+                if let Some(port) = deployment.port {
+                    writeln!(f, "Port: {port}")?;
+                }
+                if let Some(creation_source) = &deployment.creation_source {
+                    writeln!(f, "Creation source: {creation_source}")?;
+                }
+                if let Some(database) = &deployment.database {
+                    writeln!(f, "Database: {database}")?;
+                }
+                if let Some(username) = &deployment.username {
+                    writeln!(f, "Username: {username}")?;
+                }
+                if let Some(username_file) = &deployment.username_file {
+                    writeln!(f, "Username file: {username_file}")?;
+                }
+                if let Some(password) = &deployment.password {
+                    writeln!(f, "Password: {password}")?;
+                }
+                if let Some(password_file) = &deployment.password_file {
+                    writeln!(f, "Password file: {password_file}")?;
+                }
+                if let Some(load_sample_data) = deployment.load_sample_data {
+                    writeln!(f, "Load sample data: {load_sample_data}")?;
+                }
+                if let Some(local_seed_location) = &deployment.local_seed_location {
+                    writeln!(f, "Initdb folder: {local_seed_location}")?;
+                }
+                if let Some(mongot_log_file) = &deployment.mongot_log_file {
+                    writeln!(f, "Search log file: {mongot_log_file}")?;
+                }
+                if let Some(runner_log_file) = &deployment.runner_log_file {
+                    writeln!(f, "Runner log file: {runner_log_file}")?;
+                }
+                writeln!(f, "Telemetry: {}", !deployment.do_not_track)?;
+                if let Some(telemetry_base_url) = &deployment.telemetry_base_url {
+                    writeln!(f, "Telemetry URL: {telemetry_base_url}")?;
+                }
+                if let Some(voyage_api_key) = &deployment.voyage_api_key {
+                    writeln!(f, "Voyage API key: {voyage_api_key}")?;
+                }
+                Ok(())
+            }
+            Self::Raw { container } => {
+                let pretty =
+                    serde_json::to_string_pretty(container).map_err(|_| std::fmt::Error)?;
+                write!(f, "{pretty}")
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Inspecting deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Inspect {
+    type Output = InspectResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        if self.raw {
+            return self.inspect_raw().await;
+        }
+
+        let Some(deployment) =
+            find_deployment(self.deployment_getter.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.not_found());
+        };
+
+        Ok(InspectResult::Inspected {
+            deployment: DeploymentDetails::new(deployment, self.show_secrets),
+        })
+    }
+}
+
+impl Inspect {
+    async fn inspect_raw(&self) -> Result<InspectResult> {
+        let Some(mut container) = self
+            .container_inspector
+            .inspect_container_raw(&self.deployment_name)
+            .await
+            .map_err(DockerError::from)
+            .context("inspecting container")?
+        else {
+            return Ok(self.not_found());
+        };
+
+        if !self.show_secrets {
+            redact_env_secrets(&mut container);
+        }
+
+        Ok(InspectResult::Raw {
+            container: serde_json::to_value(container)
+                .context("serializing container inspect output")?,
+        })
+    }
+
+    fn not_found(&self) -> InspectResult {
+        InspectResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error: "deployment not found".to_string(),
+        }
+    }
+}
+
+/// Redact the values of environment variables that contain secrets, e.g. the root password.
+fn redact_env_secrets(container: &mut ContainerInspectResponse) {
+    let Some(env) = container
+        .config
+        .as_mut()
+        .and_then(|config| config.env.as_mut())
+    else {
+        return;
+    };
+
+    for variable in env.iter_mut() {
+        let Some((name, _)) = variable.split_once('=') else {
+            continue;
+        };
+
+        let name_upper = name.to_uppercase();
+        if SECRET_ENV_VAR_NAME_PARTS
+            .iter()
+            .any(|part| name_upper.contains(part))
+        {
+            *variable = format!("{name}={REDACTED}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support;
+    use crate::dependencies::mocks::MockDocker;
+    use atlas_local::{
+        GetDeploymentError,
+        models::{BindingType, MongoDBPortBinding},
+    };
+    use bollard::{errors::Error as BollardError, models::ContainerConfig};
+
+    fn create_deployment() -> Deployment {
+        Deployment {
+            port_bindings: Some(MongoDBPortBinding::new(Some(27017), BindingType::Loopback)),
+            mongodb_initdb_root_password: Some("secret-password".to_string()),
+            mongodb_initdb_root_username: Some("admin".to_string()),
+            mongodb_load_sample_data: Some(false),
+            ..test_support::create_deployment("test-deployment", State::Running)
+        }
+    }
+
+    fn create_container() -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            id: Some("container-id".to_string()),
+            config: Some(ContainerConfig {
+                env: Some(vec![
+                    "MONGODB_INITDB_ROOT_USERNAME=admin".to_string(),
+                    "MONGODB_INITDB_ROOT_PASSWORD=secret-password".to_string(),
+                    "VOYAGE_API_KEY=voyage-key".to_string(),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn create_inspect_command(
+        raw: bool,
+        show_secrets: bool,
+        deployment_getter: MockDocker,
+        container_inspector: MockDocker,
+    ) -> Inspect {
+        Inspect {
+            deployment_name: "test-deployment".to_string(),
+            raw,
+            show_secrets,
+            deployment_getter: Box::new(deployment_getter),
+            container_inspector: Box::new(container_inspector),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inspect_redacts_secrets() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter
+            .expect_get_deployment()
+            .withf(|name| name == "test-deployment")
+            .return_once(|_| Ok(create_deployment()));
+
+        let mut inspect_command =
+            create_inspect_command(false, false, mock_getter, MockDocker::new());
+
+        let result = inspect_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let InspectResult::Inspected { deployment } = result else {
+            panic!("expected an inspected result, got {result:?}");
+        };
+        assert_eq!(deployment.name, "test-deployment");
+        assert_eq!(deployment.port, Some(27017));
+        assert_eq!(deployment.username, Some("admin".to_string()));
+        assert_eq!(deployment.password, Some(REDACTED.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_inspect_shows_deployment_settings() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter.expect_get_deployment().return_once(|_| {
+            Ok(Deployment {
+                creation_source: Some(CreationSource::AtlasLocal),
+                mongodb_initdb_database: Some("orders".to_string()),
+                mongot_log_file: Some("/dev/stdout".to_string()),
+                runner_log_file: Some("/dev/stdout".to_string()),
+                ..create_deployment()
+            })
+        });
+
+        let result = create_inspect_command(false, false, mock_getter, MockDocker::new())
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let InspectResult::Inspected { deployment } = &result else {
+            panic!("expected an inspected result, got {result:?}");
+        };
+        assert_eq!(deployment.mongodb_type, "community");
+        assert_eq!(deployment.creation_source, Some("atlas-local".to_string()));
+        assert_eq!(deployment.database, Some("orders".to_string()));
+        assert_eq!(deployment.mongot_log_file, Some("/dev/stdout".to_string()));
+        assert!(deployment.do_not_track);
+
+        let output = result.to_string();
+        assert!(output.contains("Creation source: atlas-local"));
+        assert!(output.contains("Search log file: /dev/stdout"));
+        assert!(output.contains("Telemetry: false"));
+    }
+
+    #[tokio::test]
+    async fn test_inspect_show_secrets() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter
+            .expect_get_deployment()
+            .return_once(|_| Ok(create_deployment()));
+
+        let mut inspect_command =
+            create_inspect_command(false, true, mock_getter, MockDocker::new());
+
+        let result = inspect_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let InspectResult::Inspected { deployment } = result else {
+            panic!("expected an inspected result, got {result:?}");
+        };
+        assert_eq!(deployment.password, Some("secret-password".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_inspect_not_found() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter.expect_get_deployment().return_once(|_| {
+            Err(GetDeploymentError::ContainerInspect(
+                BollardError::DockerResponseServerError {
+                    status_code: 404,
+                    message: "No such container: test-deployment".to_string(),
+                },
+            ))
+        });
+
+        let mut inspect_command =
+            create_inspect_command(false, false, mock_getter, MockDocker::new());
+
+        let result = inspect_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            InspectResult::Failed {
+                deployment_name: "test-deployment".to_string(),
+                error: "deployment not found".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_raw_redacts_env_secrets() {
+        let mut mock_getter = MockDocker::new();
+        mock_getter.expect_get_deployment().never();
+
+        let mut mock_inspector = MockDocker::new();
+        mock_inspector
+            .expect_inspect_container_raw()
+            .withf(|name| name == "test-deployment")
+            .return_once(|_| Ok(Some(create_container())));
+
+        let mut inspect_command = create_inspect_command(true, false, mock_getter, mock_inspector);
+
+        let result = inspect_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let InspectResult::Raw { container } = result else {
+            panic!("expected a raw result, got {result:?}");
+        };
+        assert_eq!(container["Id"], "container-id");
+        assert_eq!(
+            container["Config"]["Env"],
+            serde_json::json!([
+                "MONGODB_INITDB_ROOT_USERNAME=admin",
+                "MONGODB_INITDB_ROOT_PASSWORD=[redacted]",
+                "VOYAGE_API_KEY=[redacted]",
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_raw_show_secrets() {
+        let mut mock_inspector = MockDocker::new();
+        mock_inspector
+            .expect_inspect_container_raw()
+            .return_once(|_| Ok(Some(create_container())));
+
+        let mut inspect_command =
+            create_inspect_command(true, true, MockDocker::new(), mock_inspector);
+
+        let result = inspect_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let InspectResult::Raw { container } = result else {
+            panic!("expected a raw result, got {result:?}");
+        };
+        assert_eq!(
+            container["Config"]["Env"][1],
+            "MONGODB_INITDB_ROOT_PASSWORD=secret-password"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_raw_not_found() {
+        let mut mock_inspector = MockDocker::new();
+        mock_inspector
+            .expect_inspect_container_raw()
+            .return_once(|_| Ok(None));
+
+        let mut inspect_command =
+            create_inspect_command(true, false, MockDocker::new(), mock_inspector);
+
+        let result = inspect_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            InspectResult::Failed {
+                deployment_name: "test-deployment".to_string(),
+                error: "deployment not found".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_raw_docker_error() {
+        let mut mock_inspector = MockDocker::new();
+        mock_inspector
+            .expect_inspect_container_raw()
+            .return_once(|_| {
+                Err(BollardError::DockerResponseServerError {
+                    status_code: 500,
+                    message: "internal server error".to_string(),
+                })
+            });
+
+        let mut inspect_command =
+            create_inspect_command(true, false, MockDocker::new(), mock_inspector);
+
+        let error = inspect_command
+            .execute()
+            .await
+            .expect_err("execute should fail");
+
+        assert!(error.chain().any(|cause| cause.is::<DockerError>()));
+    }
+}
@@ -7,30 +7,46 @@ use std::fmt::Display;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use atlas_local::{Client, models::LogsOptions};
-use bollard::Docker;
+use chrono::{DateTime, Utc};
+use regex::Regex;
 use serde::Serialize;
 
-use crate::{args, commands::CommandWithOutput, dependencies::DeploymentLogsRetriever};
+use crate::{
+    args,
+    commands::CommandWithOutput,
+    dependencies::{DeploymentLogsRetriever, DeploymentStartedAt, connect_docker},
+    settings::Settings,
+};
 
 /// Command to get deployment logs.
 pub struct Logs {
     deployment_name: String,
+    grep: Option<Regex>,
+    invert: bool,
+    since_start: bool,
     deployment_logs_retriever: Box<dyn DeploymentLogsRetriever + Send>,
+    started_at_getter: Box<dyn DeploymentStartedAt + Send>,
 }
 
 /// Convert CLI arguments to command with default dependencies injected.
 ///
 /// This implementation creates a new `Logs` command with the default `atlas_local::Client`
-/// as the logs retriever.
-impl TryFrom<args::Logs> for Logs {
+/// as the logs retriever, and Docker to get when the deployment was last started.
+impl TryFrom<(args::Logs, &Settings)> for Logs {
     type Error = anyhow::Error;
 
-    fn try_from(args: args::Logs) -> std::result::Result<Self, Self::Error> {
+    fn try_from(
+        (args, settings): (args::Logs, &Settings),
+    ) -> std::result::Result<Self, Self::Error> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
         Ok(Logs {
             deployment_name: args.deployment_name,
-            deployment_logs_retriever: Box::new(Client::new(
-                Docker::connect_with_defaults().context("connecting to Docker")?,
-            )),
+            grep: args.grep,
+            invert: args.invert,
+            since_start: args.since_start,
+            deployment_logs_retriever: Box::new(Client::new(docker.clone())),
+            started_at_getter: Box::new(docker),
         })
     }
 }
@@ -55,7 +71,7 @@ impl Display for LogsResult {
 /// Execute the logs command and return the result.
 ///
 /// This implementation calls the client to get logs for the deployment,
-/// filters to only include stdout/stderr logs, and wraps them in a [`LogsResult`].
+/// filters to only include stdout/stderr logs and the lines matching --grep, and wraps them in a [`LogsResult`].
 ///
 #[async_trait]
 impl CommandWithOutput for Logs {
@@ -64,7 +80,23 @@ impl CommandWithOutput for Logs {
     async fn execute(&mut self) -> Result<Self::Output> {
         // Build the logs options.
         // We're only interested in stdout and stderr logs.
-        let logs_options = LogsOptions::builder().stdout(true).stderr(true).build();
+        let mut logs_options = LogsOptions::builder().stdout(true).stderr(true).build();
+
+        // With --sinceStart, the logs from before the last start of the container are skipped
+        if self.since_start {
+            let started_at = self
+                .started_at_getter
+                .started_at(&self.deployment_name)
+                .await
+                .context("getting when the deployment was started")?
+                .with_context(|| {
+                    format!(
+                        "deployment '{}' not found or never started",
+                        self.deployment_name
+                    )
+                })?;
+            logs_options.since = Some(DateTime::<Utc>::from(started_at));
+        }
 
         // Get the logs from the deployment.
         let log_outputs = self
@@ -78,18 +110,30 @@ impl CommandWithOutput for Logs {
         let logs: Vec<String> = log_outputs
             .into_iter()
             .map(|l| l.as_str_lossy().trim_end().to_string())
+            .filter(|line| self.is_shown(line))
             .collect();
 
         Ok(LogsResult(logs))
     }
 }
 
+impl Logs {
+    /// Whether the log line passes the --grep and --invert filters.
+    fn is_shown(&self, line: &str) -> bool {
+        match &self.grep {
+            Some(grep) => grep.is_match(line) != self.invert,
+            None => true,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dependencies::mocks::MockDocker;
     use atlas_local::models::LogOutput;
     use bytes::Bytes;
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[tokio::test]
     async fn test_logs_command() {
@@ -118,7 +162,11 @@ mod tests {
 
         let mut logs_command = Logs {
             deployment_name: "test-deployment".to_string(),
+            grep: None,
+            invert: false,
+            since_start: false,
             deployment_logs_retriever: Box::new(deployment_logs_retriever),
+            started_at_getter: Box::new(MockDocker::new()),
         };
 
         let result = logs_command
@@ -135,4 +183,125 @@ mod tests {
             ])
         );
     }
+
+    fn create_logs_command(grep: &str, invert: bool) -> Logs {
+        let mut deployment_logs_retriever = MockDocker::new();
+        deployment_logs_retriever
+            .expect_get_logs()
+            .return_once(|_, _| {
+                Ok(vec![
+                    LogOutput::StdOut {
+                        message: Bytes::from("Waiting for connections\n"),
+                    },
+                    LogOutput::StdErr {
+                        message: Bytes::from("ERROR: disk almost full\n"),
+                    },
+                    LogOutput::StdOut {
+                        message: Bytes::from("Connection accepted\n"),
+                    },
+                ])
+            });
+
+        Logs {
+            deployment_name: "test-deployment".to_string(),
+            grep: Some(Regex::new(grep).unwrap()),
+            invert,
+            since_start: false,
+            deployment_logs_retriever: Box::new(deployment_logs_retriever),
+            started_at_getter: Box::new(MockDocker::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logs_command_grep() {
+        let result = create_logs_command("(?i)connection", false)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LogsResult(vec![
+                "Waiting for connections".to_string(),
+                "Connection accepted".to_string(),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_command_grep_invert() {
+        let result = create_logs_command("(?i)connection", true)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LogsResult(vec!["ERROR: disk almost full".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_command_since_start() {
+        let started_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let mut started_at_getter = MockDocker::new();
+        started_at_getter
+            .expect_started_at()
+            .withf(|name| name == "test-deployment")
+            .return_once(move |_| Ok(Some(started_at)));
+
+        let mut deployment_logs_retriever = MockDocker::new();
+        deployment_logs_retriever
+            .expect_get_logs()
+            .withf(move |_, options| {
+                options.as_ref().unwrap().since == Some(DateTime::<Utc>::from(started_at))
+            })
+            .return_once(|_, _| {
+                Ok(vec![LogOutput::StdOut {
+                    message: Bytes::from("Waiting for connections\n"),
+                }])
+            });
+
+        let mut logs_command = Logs {
+            deployment_name: "test-deployment".to_string(),
+            grep: None,
+            invert: false,
+            since_start: true,
+            deployment_logs_retriever: Box::new(deployment_logs_retriever),
+            started_at_getter: Box::new(started_at_getter),
+        };
+
+        let result = logs_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LogsResult(vec!["Waiting for connections".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logs_command_since_start_never_started() {
+        let mut started_at_getter = MockDocker::new();
+        started_at_getter
+            .expect_started_at()
+            .return_once(|_| Ok(None));
+
+        let mut deployment_logs_retriever = MockDocker::new();
+        deployment_logs_retriever.expect_get_logs().never();
+
+        let mut logs_command = Logs {
+            deployment_name: "test-deployment".to_string(),
+            grep: None,
+            invert: false,
+            since_start: true,
+            deployment_logs_retriever: Box::new(deployment_logs_retriever),
+            started_at_getter: Box::new(started_at_getter),
+        };
+
+        assert!(logs_command.execute().await.is_err());
+    }
 }
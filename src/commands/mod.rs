@@ -1,29 +1,87 @@
 //! This module contains business logic for the commands for the application.
 //!
 //! The main entry point is the [`command_from_args`] function which converts CLI arguments into a command.
-use anyhow::Result;
+use anyhow::{Result, bail};
 
 use crate::{
-    args::{Indexes, LocalArgs, Search},
+    args::{
+        Indexes, LocalArgs, Search, config::Config, context::Context, profile::Profile,
+        sample_data::SampleData, snapshot::Snapshot,
+    },
     commands::{
-        connect::Connect, delete::Delete, list::List, logs::Logs, setup::Setup, start::Start,
-        stop::Stop, with_mongodb::WithMongodbClientForLocalDeployment,
+        bulk::BulkCommandExt,
+        bulk_setup::BulkSetup,
+        capabilities::Capabilities,
+        clone::CloneDeployment,
+        completion::Completion,
+        config::load_deployment_settings,
+        connect::Connect,
+        delete::Delete,
+        delete_all::DeleteAll,
+        diff::Diff,
+        doctor::Doctor,
+        eval::Eval,
+        events::Events,
+        exec::Exec,
+        exists::Exists,
+        export::Export,
+        import::Import,
+        inspect::Inspect,
+        list::List,
+        logs::Logs,
+        notify::{CompletionNotification, NotifyOnCompletion},
+        profile::load_profile,
+        seed::Seed,
+        setup::{Setup, Summarized},
+        start::Start,
+        stop::Stop,
+        version::Version,
+        with_mongodb::{MongodbClientCache, WithMongodbClientForLocalDeployment},
     },
-    formatting::Format,
+    formatting::{Format, Formattable},
+    settings::Settings,
 };
-pub use core::{Command, CommandWithOutput, CommandWithOutputExt};
+pub use core::{CancelReason, Command, CommandWithOutput, CommandWithOutputExt};
 
+mod bulk;
+pub mod bulk_setup;
+pub mod capabilities;
+pub mod clone;
+pub mod completion;
+pub mod config;
 pub mod connect;
+mod connection_string;
 mod connectors;
+pub mod context;
 mod core;
 pub mod delete;
+pub mod delete_all;
+mod deployment_config;
+pub mod diff;
+pub mod doctor;
+pub mod eval;
+pub mod events;
+pub mod exec;
+pub mod exists;
+pub mod export;
+pub mod import;
+pub mod inspect;
 pub mod list;
 pub mod logs;
+mod notify;
+pub mod profile;
+pub mod sample_data;
 pub mod search;
+pub mod seed;
 pub mod setup;
+mod shutdown;
+pub mod snapshot;
 pub mod start;
 pub mod stop;
+#[cfg(test)]
+mod test_support;
 mod validators;
+pub mod version;
 mod with_mongodb;
 
 /// Convert CLI arguments into a command.
@@ -32,29 +90,276 @@ mod with_mongodb;
 /// It converts the CLI arguments into a command and returns it.
 ///
 /// The output of the command will be formatted using the provided format and printed to stdout.
-pub async fn command_from_args(args: LocalArgs, format: Format) -> Result<Box<dyn Command>> {
+pub async fn command_from_args(
+    args: LocalArgs,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
     match args {
+        // Bulk commands report failed items with the exit code, after printing the outcome of every item.
+        LocalArgs::Delete(delete_args)
+            if delete_args.all || delete_args.deployment_names.len() > 1 =>
+        {
+            DeleteAll::try_from((delete_args, settings))?.with_print_to_stdout_and_exit_code(format)
+        }
         LocalArgs::Delete(delete_args) => {
-            Delete::try_from(delete_args)?.with_print_to_stdout(format)
+            Delete::try_from((delete_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::List(list_args) => {
+            List::try_from((list_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Logs(logs_args) => {
+            Logs::try_from((logs_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Inspect(inspect_args) => {
+            Inspect::try_from((inspect_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Capabilities(capabilities_args) => {
+            Capabilities::try_from((capabilities_args, settings))?.with_print_to_stdout(format)
+        }
+        // The events command streams its output, so it prints the events itself.
+        LocalArgs::Events(events_args) => Ok(Box::new(
+            Events::try_from((events_args, settings))?.with_format(format),
+        )),
+        // The exists command reports its outcome with the exit code, so it prints the outcome itself.
+        LocalArgs::Exists(exists_args) => Ok(Box::new(
+            Exists::try_from((exists_args, settings))?.with_format(format),
+        )),
+        // The diff command also reports its outcome with the exit code, so it prints the outcome itself.
+        LocalArgs::Diff(diff_args) => Ok(Box::new(
+            Diff::try_from((diff_args, settings))?.with_format(format),
+        )),
+        LocalArgs::Clone(clone_args) => {
+            CloneDeployment::try_from((clone_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Export(export_args) => {
+            Export::try_from((export_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Import(import_args) => {
+            Import::try_from((import_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Seed(seed_args) => {
+            Seed::try_from((seed_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::SampleData(sample_data_args) => {
+            sample_data_command_from_args(sample_data_args, settings, format)
+        }
+        // The exec command reports its outcome with the exit code, it only prints the output of a captured command.
+        LocalArgs::Exec(exec_args) => Ok(Box::new(
+            Exec::try_from((exec_args, settings))?.with_format(format),
+        )),
+        LocalArgs::Eval(eval_args) => {
+            Eval::try_from((eval_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Setup(setup_args) if setup_args.from_file.is_some() => {
+            BulkSetup::try_from((setup_args, settings))?.with_print_to_stdout_and_exit_code(format)
+        }
+        LocalArgs::Setup(mut setup_args) => {
+            // Omitted flags use the settings of the profile
+            if let Some(profile_name) = setup_args.from_profile.clone() {
+                load_profile(&profile_name, settings)
+                    .await?
+                    .apply_to_setup(&mut setup_args)?;
+            }
+            // Streamed step outcomes are JSON lines, they can't be mixed with text output
+            if setup_args.stream && format == Format::Text {
+                bail!("--stream requires --output json");
+            }
+            let notify = setup_args.notify;
+            let summary = setup_args.summary;
+            let setup = Setup::try_from((setup_args, settings))?;
+            if summary {
+                with_optional_notification(Summarized(setup), notify, format)
+            } else {
+                with_optional_notification(setup, notify, format)
+            }
+        }
+        LocalArgs::Start(mut start_args) => {
+            // Omitted flags use the saved settings of the deployment
+            load_deployment_settings(&start_args.deployment_name, settings)
+                .await
+                .apply_to_start(&mut start_args);
+            let notify = start_args.notify;
+            with_optional_notification(Start::try_from((start_args, settings))?, notify, format)
+        }
+        LocalArgs::Stop(stop_args) => {
+            Stop::try_from((stop_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Connect(mut connect_args) => {
+            load_deployment_settings(&connect_args.deployment_name, settings)
+                .await
+                .apply_to_connect(&mut connect_args);
+            Connect::try_from((connect_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Attach(attach_args) => {
+            Connect::try_from((attach_args, settings))?.with_print_to_stdout(format)
+        }
+        LocalArgs::Snapshot(snapshot_args) => {
+            snapshot_command_from_args(snapshot_args, settings, format)
+        }
+        LocalArgs::Search(search_args) => {
+            search_command_from_args(search_args, settings, format).await
         }
-        LocalArgs::List(list_args) => List::try_from(list_args)?.with_print_to_stdout(format),
-        LocalArgs::Logs(logs_args) => Logs::try_from(logs_args)?.with_print_to_stdout(format),
-        LocalArgs::Setup(setup_args) => Setup::try_from(setup_args)?.with_print_to_stdout(format),
-        LocalArgs::Start(start_args) => Start::try_from(start_args)?.with_print_to_stdout(format),
-        LocalArgs::Stop(stop_args) => Stop::try_from(stop_args)?.with_print_to_stdout(format),
-        LocalArgs::Connect(connect_args) => {
-            Connect::try_from(connect_args)?.with_print_to_stdout(format)
+        LocalArgs::Context(context_args) => {
+            context_command_from_args(context_args, settings, format)
+        }
+        LocalArgs::Config(config_args) => config_command_from_args(config_args, settings, format),
+        LocalArgs::Profile(profile_args) => {
+            profile_command_from_args(profile_args, settings, format)
+        }
+        LocalArgs::Version(version_args) => {
+            Version::from((version_args, settings)).with_print_to_stdout(format)
+        }
+        // A failed check fails the command, after the outcome of every check is printed.
+        LocalArgs::Doctor(doctor_args) => {
+            Doctor::from((doctor_args, settings)).with_print_to_stdout_and_exit_code(format)
+        }
+        // The completion script is printed as it is, regardless of the output format.
+        LocalArgs::Completion(completion_args) => Ok(Box::new(Completion::from(completion_args))),
+    }
+}
+
+fn context_command_from_args(
+    args: Context,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
+    match args {
+        Context::Set(set_args) => {
+            context::set::Set::try_from((set_args, settings))?.with_print_to_stdout(format)
+        }
+        Context::List(list_args) => {
+            context::list::List::try_from((list_args, settings))?.with_print_to_stdout(format)
+        }
+        Context::Remove(remove_args) => {
+            context::remove::Remove::try_from((remove_args, settings))?.with_print_to_stdout(format)
         }
-        LocalArgs::Search(search_args) => search_command_from_args(search_args, format).await,
     }
 }
 
-async fn search_command_from_args(args: Search, format: Format) -> Result<Box<dyn Command>> {
+fn config_command_from_args(
+    args: Config,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
+    match args {
+        Config::Set(set_args) => {
+            config::set::Set::try_from((set_args, settings))?.with_print_to_stdout(format)
+        }
+        Config::Get(get_args) => {
+            config::get::Get::try_from((get_args, settings))?.with_print_to_stdout(format)
+        }
+        Config::Unset(unset_args) => {
+            config::unset::Unset::try_from((unset_args, settings))?.with_print_to_stdout(format)
+        }
+    }
+}
+
+fn profile_command_from_args(
+    args: Profile,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
+    match args {
+        Profile::Add(add_args) => {
+            profile::add::Add::try_from((add_args, settings))?.with_print_to_stdout(format)
+        }
+        Profile::List(list_args) => {
+            profile::list::List::try_from((list_args, settings))?.with_print_to_stdout(format)
+        }
+        Profile::Remove(remove_args) => {
+            profile::remove::Remove::try_from((remove_args, settings))?.with_print_to_stdout(format)
+        }
+        Profile::Show(show_args) => {
+            profile::show::Show::try_from((show_args, settings))?.with_print_to_stdout(format)
+        }
+    }
+}
+
+fn snapshot_command_from_args(
+    args: Snapshot,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
+    match args {
+        Snapshot::Create(create_args) => {
+            snapshot::create::Create::try_from((create_args, settings))?
+                .with_print_to_stdout(format)
+        }
+        Snapshot::List(list_args) => {
+            snapshot::list::List::try_from((list_args, settings))?.with_print_to_stdout(format)
+        }
+        Snapshot::Delete(delete_args) => {
+            snapshot::delete::Delete::try_from((delete_args, settings))?
+                .with_print_to_stdout(format)
+        }
+        Snapshot::Restore(restore_args) => {
+            snapshot::restore::Restore::try_from((restore_args, settings))?
+                .with_print_to_stdout(format)
+        }
+    }
+}
+
+fn sample_data_command_from_args(
+    args: SampleData,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
+    match args {
+        SampleData::Load(load_args) => {
+            sample_data::load::Load::try_from((load_args, settings))?.with_print_to_stdout(format)
+        }
+        SampleData::Status(status_args) => {
+            sample_data::status::Status::try_from((status_args, settings))?
+                .with_print_to_stdout(format)
+        }
+    }
+}
+
+/// Wrap the command to show a desktop notification on completion when requested, and print its output to stdout.
+fn with_optional_notification<C>(
+    command: C,
+    notify: bool,
+    format: Format,
+) -> Result<Box<dyn Command>>
+where
+    C: CommandWithOutput + Send + 'static,
+    C::Output: CompletionNotification + Formattable + Send + 'static,
+{
+    if notify {
+        NotifyOnCompletion::new(command).with_print_to_stdout(format)
+    } else {
+        command.with_print_to_stdout(format)
+    }
+}
+
+async fn search_command_from_args(
+    args: Search,
+    settings: &Settings,
+    format: Format,
+) -> Result<Box<dyn Command>> {
+    // Clients are created once for the search commands of this invocation and shared between them
+    let mut clients = MongodbClientCache::new(settings.docker.clone());
+
     match args {
         Search::Indexes(indexes_args) => match indexes_args {
+            Indexes::Create(create_args) if create_args.all_collections => {
+                search::create_all::CreateAllCollections::with_mongodb_client_for_local_deployment(
+                    create_args,
+                    settings,
+                    &mut clients,
+                    |args| args.deployment_name.clone(),
+                    |args| args.username.clone(),
+                    |args| args.password.clone(),
+                )
+                .await?
+                .with_print_to_stdout_and_exit_code(format)
+            }
             Indexes::Create(create_args) => {
                 search::create::Create::with_mongodb_client_for_local_deployment(
                     create_args,
+                    settings,
+                    &mut clients,
                     |args| args.deployment_name.clone(),
                     |args| args.username.clone(),
                     |args| args.password.clone(),
@@ -65,6 +370,8 @@ async fn search_command_from_args(args: Search, format: Format) -> Result<Box<dy
             Indexes::Describe(describe_args) => {
                 search::describe::Describe::with_mongodb_client_for_local_deployment(
                     describe_args,
+                    settings,
+                    &mut clients,
                     |args| args.deployment_name.clone(),
                     |args| args.username.clone(),
                     |args| args.password.clone(),
@@ -75,6 +382,8 @@ async fn search_command_from_args(args: Search, format: Format) -> Result<Box<dy
             Indexes::List(list_args) => {
                 search::list::List::with_mongodb_client_for_local_deployment(
                     list_args,
+                    settings,
+                    &mut clients,
                     |args| args.deployment_name.clone(),
                     |args| args.username.clone(),
                     |args| args.password.clone(),
@@ -85,6 +394,32 @@ async fn search_command_from_args(args: Search, format: Format) -> Result<Box<dy
             Indexes::Delete(delete_args) => {
                 search::delete::Delete::with_mongodb_client_for_local_deployment(
                     delete_args,
+                    settings,
+                    &mut clients,
+                    |args| args.deployment_name.clone(),
+                    |args| args.username.clone(),
+                    |args| args.password.clone(),
+                )
+                .await?
+                .with_print_to_stdout(format)
+            }
+            Indexes::Update(update_args) => {
+                search::update::Update::with_mongodb_client_for_local_deployment(
+                    update_args,
+                    settings,
+                    &mut clients,
+                    |args| args.deployment_name.clone(),
+                    |args| args.username.clone(),
+                    |args| args.password.clone(),
+                )
+                .await?
+                .with_print_to_stdout(format)
+            }
+            Indexes::Watch(watch_args) => {
+                search::watch::Watch::with_mongodb_client_for_local_deployment(
+                    watch_args,
+                    settings,
+                    &mut clients,
                     |args| args.deployment_name.clone(),
                     |args| args.username.clone(),
                     |args| args.password.clone(),
@@ -0,0 +1,198 @@
+//! This module contains a wrapper command that shows a desktop notification when a long running command completes.
+//!
+//! Commands like `setup` and `start` can take a while, the notification lets the user know they can get back to it.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::{
+    commands::CommandWithOutput,
+    dependencies::{DesktopNotifier, Notifier},
+};
+
+/// Title of the desktop notification.
+const NOTIFICATION_TITLE: &str = "Atlas Local";
+
+/// Commands that complete faster than this don't show a notification, to avoid noise on quick runs.
+const DEFAULT_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// Trait for command outputs that can be summarized in a desktop notification.
+pub trait CompletionNotification {
+    /// The message to show in the notification.
+    fn completion_notification(&self) -> String;
+}
+
+/// Wrapper command that shows a desktop notification when the wrapped command completes.
+///
+/// The notification is shown for both successful and failed runs, but only when the command took longer than the threshold.
+pub struct NotifyOnCompletion<C> {
+    command: C,
+    threshold: Duration,
+    notifier: Box<dyn Notifier + Send + Sync>,
+}
+
+impl<C> NotifyOnCompletion<C> {
+    pub fn new(command: C) -> Self {
+        Self {
+            command,
+            threshold: DEFAULT_THRESHOLD,
+            notifier: Box::new(DesktopNotifier::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<C, O> CommandWithOutput for NotifyOnCompletion<C>
+where
+    C: CommandWithOutput<Output = O> + Send,
+    O: CompletionNotification + Send,
+{
+    type Output = O;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let started_at = Instant::now();
+        let result = self.command.execute().await;
+
+        let elapsed = started_at.elapsed();
+        if elapsed < self.threshold {
+            debug!(?elapsed, "command completed quickly, skipping notification");
+            return result;
+        }
+
+        let message = match &result {
+            Ok(output) => output.completion_notification(),
+            Err(e) => format!("Failed: {e}"),
+        };
+
+        // A notification is a nice to have, failing to show it should not fail the command.
+        if let Err(e) = self.notifier.notify(NOTIFICATION_TITLE, &message).await {
+            debug!(?e, "failed to show desktop notification");
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::mocks::MockDesktopNotifier;
+    use anyhow::anyhow;
+
+    struct FakeCommand {
+        delay: Duration,
+        result: Option<Result<FakeOutput>>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct FakeOutput(&'static str);
+
+    impl CompletionNotification for FakeOutput {
+        fn completion_notification(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[async_trait]
+    impl CommandWithOutput for FakeCommand {
+        type Output = FakeOutput;
+
+        async fn execute(&mut self) -> Result<Self::Output> {
+            tokio::time::sleep(self.delay).await;
+            self.result
+                .take()
+                .expect("execute should only be called once")
+        }
+    }
+
+    fn create_command(
+        delay: Duration,
+        result: Result<FakeOutput>,
+        threshold: Duration,
+        notifier: MockDesktopNotifier,
+    ) -> NotifyOnCompletion<FakeCommand> {
+        NotifyOnCompletion {
+            command: FakeCommand {
+                delay,
+                result: Some(result),
+            },
+            threshold,
+            notifier: Box::new(notifier),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_on_completion_slow_success() {
+        let mut mock_notifier = MockDesktopNotifier::new();
+        mock_notifier
+            .expect_notify()
+            .withf(|title, message| title == NOTIFICATION_TITLE && message == "Deployment ready")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut cmd = create_command(
+            Duration::from_millis(20),
+            Ok(FakeOutput("Deployment ready")),
+            Duration::from_millis(10),
+            mock_notifier,
+        );
+
+        let result = cmd.execute().await.expect("execute should succeed");
+        assert_eq!(result, FakeOutput("Deployment ready"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_on_completion_slow_failure() {
+        let mut mock_notifier = MockDesktopNotifier::new();
+        mock_notifier
+            .expect_notify()
+            .withf(|_, message| message.contains("Docker not running"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut cmd = create_command(
+            Duration::from_millis(20),
+            Err(anyhow!("Docker not running")),
+            Duration::from_millis(10),
+            mock_notifier,
+        );
+
+        cmd.execute().await.expect_err("execute should fail");
+    }
+
+    #[tokio::test]
+    async fn test_notify_on_completion_fast_command_skips_notification() {
+        let mut mock_notifier = MockDesktopNotifier::new();
+        mock_notifier.expect_notify().never();
+
+        let mut cmd = create_command(
+            Duration::ZERO,
+            Ok(FakeOutput("Deployment ready")),
+            Duration::from_secs(60),
+            mock_notifier,
+        );
+
+        cmd.execute().await.expect("execute should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_notify_on_completion_notifier_error_is_ignored() {
+        let mut mock_notifier = MockDesktopNotifier::new();
+        mock_notifier
+            .expect_notify()
+            .returning(|_, _| Err(anyhow!("no display")));
+
+        let mut cmd = create_command(
+            Duration::from_millis(20),
+            Ok(FakeOutput("Deployment ready")),
+            Duration::from_millis(10),
+            mock_notifier,
+        );
+
+        let result = cmd.execute().await.expect("execute should succeed");
+        assert_eq!(result, FakeOutput("Deployment ready"));
+    }
+}
@@ -0,0 +1,142 @@
+//! Command to save a profile.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        profile::{ProfileSettings, Profiles, profiles_file_path},
+    },
+    config_dir::{ConfigFs, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub struct Add {
+    profile_name: String,
+    settings: ProfileSettings,
+    profiles_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::profile::Add, &Settings)> for Add {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::profile::Add, &Settings)) -> Result<Self> {
+        Ok(Self {
+            profile_name: args.profile_name.clone(),
+            settings: ProfileSettings::from(args),
+            profiles_file: profiles_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum AddResult {
+    Saved { profile_name: String },
+}
+
+impl Display for AddResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Saved { profile_name } => write!(f, "Profile '{}' saved", profile_name),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Add {
+    type Output = AddResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut profiles = load_toml::<Profiles>(self.fs.as_ref(), &self.profiles_file).await?;
+        profiles
+            .profiles
+            .insert(self.profile_name.clone(), self.settings.clone());
+
+        debug!(
+            profile_name = %self.profile_name,
+            path = %self.profiles_file.display(),
+            "saving profile"
+        );
+        save_toml(self.fs.as_ref(), &self.profiles_file, &profiles).await?;
+
+        Ok(AddResult::Saved {
+            profile_name: self.profile_name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{
+        args::ConnectWith,
+        dependencies::{PathKind, mocks::MockTokioFs},
+    };
+
+    #[tokio::test]
+    async fn test_add_replaces_profile_with_same_name() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(concat!(
+                "[profiles.fast]\n",
+                "image = \"mongodb/mongodb-atlas-local\"\n",
+                "[profiles.full]\n",
+                "loadSampleData = true\n",
+            )
+            .to_string())
+        });
+        mock_fs
+            .expect_create_dir_all()
+            .withf(|path| path == Path::new("/config/atlas-local"))
+            .return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/config/atlas-local/profiles.toml")
+                    && contents.contains("[profiles.fast]")
+                    && contents.contains("imageTag = \"8.0\"")
+                    && contents.contains("connectWith = \"mongosh\"")
+                    && !contents.contains("image = ")
+                    && contents.contains("[profiles.full]")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let mut add_command = Add {
+            profile_name: "fast".to_string(),
+            settings: ProfileSettings {
+                image_tag: Some("8.0".to_string()),
+                image: None,
+                load_sample_data: None,
+                connect_with: Some(ConnectWith::Mongosh),
+            },
+            profiles_file: PathBuf::from("/config/atlas-local/profiles.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = add_command.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            AddResult::Saved {
+                profile_name: "fast".to_string()
+            }
+        );
+    }
+}
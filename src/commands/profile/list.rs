@@ -0,0 +1,135 @@
+//! Command to list the saved profiles.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        profile::{ProfileSettings, Profiles, profiles_file_path},
+    },
+    config_dir::{ConfigFs, load_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+    table::Table,
+};
+
+pub struct List {
+    profiles_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::profile::List, &Settings)> for List {
+    type Error = anyhow::Error;
+
+    fn try_from((_, settings): (args::profile::List, &Settings)) -> Result<Self> {
+        Ok(Self {
+            profiles_file: profiles_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+/// A saved profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub settings: ProfileSettings,
+}
+
+/// Result of the profile list command, sorted by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListResult(Vec<ProfileEntry>);
+
+impl From<&ListResult> for Table {
+    fn from(value: &ListResult) -> Self {
+        Table::from_iter(
+            &value.0,
+            &[
+                ("NAME", |p| p.name.clone()),
+                ("SETTINGS", |p| {
+                    p.settings
+                        .entries()
+                        .iter()
+                        .map(|setting| format!("{}={}", setting.key, setting.value))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }),
+            ],
+        )
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Table::from(self).fmt(f)
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for List {
+    type Output = ListResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let profiles = load_toml::<Profiles>(self.fs.as_ref(), &self.profiles_file).await?;
+
+        Ok(ListResult(
+            profiles
+                .profiles
+                .into_iter()
+                .map(|(name, settings)| ProfileEntry { name, settings })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_list_profiles_sorted_by_name() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(concat!(
+                "[profiles.full]\n",
+                "loadSampleData = true\n",
+                "[profiles.fast]\n",
+                "imageTag = \"8.0\"\n",
+                "connectWith = \"mongosh\"\n",
+            )
+            .to_string())
+        });
+
+        let mut list_command = List {
+            profiles_file: PathBuf::from("profiles.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = list_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let names: Vec<_> = result.0.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["fast", "full"]);
+
+        let table = result.to_string();
+        assert!(table.contains("imageTag=8.0 connectWith=mongosh"));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json[0]["name"], "fast");
+        assert_eq!(json[0]["imageTag"], "8.0");
+    }
+}
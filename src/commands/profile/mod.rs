@@ -0,0 +1,217 @@
+//! Commands to manage profiles.
+//!
+//! A profile is a named set of settings for `setup`, e.g. the MongoDB version and the connection method, that is
+//! saved in the configuration directory. The profile to use is selected with `setup --fromProfile`, see
+//! [`load_profile`]. Precedence: the flag, then the setting of the profile, then the default of `setup`.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::{Result, anyhow};
+use atlas_local::models::ImageTag;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    args::{self, ConnectWith},
+    config_dir::{config_dir, load_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub mod add;
+pub mod list;
+pub mod remove;
+pub mod show;
+
+/// Name of the file the profiles are saved in, inside the configuration directory.
+const PROFILES_FILE_NAME: &str = "profiles.toml";
+
+/// The saved profiles, as stored in the profiles file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profiles {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileSettings>,
+}
+
+/// Settings of a profile, a setting that is not saved uses the default of `setup`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSettings {
+    /// Image tag of the deployment, as provided to `profile add --imageTag`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_sample_data: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_with: Option<ConnectWith>,
+}
+
+/// A setting of a profile, as shown by `profile show`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileSetting {
+    pub key: String,
+    pub value: String,
+}
+
+impl From<args::profile::Add> for ProfileSettings {
+    fn from(args: args::profile::Add) -> Self {
+        Self {
+            image_tag: args.image_tag,
+            image: args.image,
+            load_sample_data: args.load_sample_data,
+            connect_with: args.connect_with,
+        }
+    }
+}
+
+impl ProfileSettings {
+    /// The saved settings, named after the flags of `setup` they are used for.
+    pub fn entries(&self) -> Vec<ProfileSetting> {
+        let connect_with = self.connect_with.and_then(|connect_with| {
+            connect_with
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+        });
+
+        [
+            ("imageTag", self.image_tag.clone()),
+            ("image", self.image.clone()),
+            (
+                "loadSampleData",
+                self.load_sample_data.map(|value| value.to_string()),
+            ),
+            ("connectWith", connect_with),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            value.map(|value| ProfileSetting {
+                key: key.to_string(),
+                value,
+            })
+        })
+        .collect()
+    }
+
+    /// Use the settings of the profile for the flags of `setup` that are omitted.
+    pub fn apply_to_setup(&self, args: &mut args::Setup) -> Result<()> {
+        if args.image_tag.is_none() {
+            if let Some(image_tag) = &self.image_tag {
+                // The profiles file can be edited by hand, so the image tag is validated again
+                let image_tag = ImageTag::try_from(image_tag.as_str())
+                    .map_err(|e| anyhow!("invalid image tag '{}' in profile: {}", image_tag, e))?;
+                args.image_tag = Some(image_tag);
+            }
+        }
+        if args.image.is_none() {
+            args.image = self.image.clone();
+        }
+        if args.load_sample_data.is_none() {
+            args.load_sample_data = self.load_sample_data;
+        }
+        if args.connect_with.is_none() {
+            args.connect_with = self.connect_with;
+        }
+
+        Ok(())
+    }
+}
+
+/// Path of the file the profiles are saved in.
+pub fn profiles_file_path(settings: &Settings) -> Result<PathBuf> {
+    Ok(config_dir(settings)?.join(PROFILES_FILE_NAME))
+}
+
+/// Load the settings of the saved profile, used by `setup --fromProfile`.
+pub async fn load_profile(profile_name: &str, settings: &Settings) -> Result<ProfileSettings> {
+    let path = profiles_file_path(settings)?;
+    let mut profiles = load_toml::<Profiles>(&TokioFs::new(), &path).await?;
+
+    let settings = profiles
+        .profiles
+        .remove(profile_name)
+        .ok_or_else(|| anyhow!("profile '{}' does not exist", profile_name))?;
+    debug!(profile_name, ?settings, "using profile");
+
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_load_profiles_from_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[profiles.fast]\nimageTag = \"8.0\"\nconnectWith = \"mongosh\"\n".to_string())
+        });
+
+        let profiles = load_toml::<Profiles>(&mock_fs, Path::new("profiles.toml"))
+            .await
+            .expect("loading profiles should succeed");
+
+        assert_eq!(
+            profiles.profiles.get("fast"),
+            Some(&ProfileSettings {
+                image_tag: Some("8.0".to_string()),
+                image: None,
+                load_sample_data: None,
+                connect_with: Some(ConnectWith::Mongosh),
+            })
+        );
+    }
+
+    #[test]
+    fn test_flags_take_precedence_over_profile() {
+        let settings = ProfileSettings {
+            image_tag: Some("8.0".to_string()),
+            image: None,
+            load_sample_data: Some(true),
+            connect_with: Some(ConnectWith::Mongosh),
+        };
+
+        let mut setup_args = args::Setup::parse_from([
+            "setup",
+            "analytics",
+            "--fromProfile",
+            "fast",
+            "--connectWith",
+            "compass",
+        ]);
+        settings
+            .apply_to_setup(&mut setup_args)
+            .expect("applying the profile should succeed");
+
+        assert_eq!(
+            setup_args.image_tag,
+            Some(ImageTag::try_from("8.0").unwrap())
+        );
+        assert_eq!(setup_args.image, None);
+        assert_eq!(setup_args.load_sample_data, Some(true));
+        assert_eq!(setup_args.connect_with, Some(ConnectWith::Compass));
+    }
+
+    #[test]
+    fn test_apply_invalid_image_tag() {
+        let settings = ProfileSettings {
+            image_tag: Some("not-a-version".to_string()),
+            ..Default::default()
+        };
+
+        let mut setup_args = args::Setup::parse_from(["setup", "analytics"]);
+        settings
+            .apply_to_setup(&mut setup_args)
+            .expect_err("applying an invalid image tag should fail");
+    }
+}
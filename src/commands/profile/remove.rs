@@ -0,0 +1,155 @@
+//! Command to remove a saved profile.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        profile::{Profiles, profiles_file_path},
+    },
+    config_dir::{ConfigFs, load_toml, save_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub struct Remove {
+    profile_name: String,
+    profiles_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::profile::Remove, &Settings)> for Remove {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::profile::Remove, &Settings)) -> Result<Self> {
+        Ok(Self {
+            profile_name: args.profile_name,
+            profiles_file: profiles_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RemoveResult {
+    Removed { profile_name: String },
+    Failed { profile_name: String, error: String },
+}
+
+impl Display for RemoveResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Removed { profile_name } => write!(f, "Profile '{}' removed", profile_name),
+            Self::Failed {
+                profile_name,
+                error,
+            } => write!(f, "Removing profile '{}' failed: {}", profile_name, error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Remove {
+    type Output = RemoveResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut profiles = load_toml::<Profiles>(self.fs.as_ref(), &self.profiles_file).await?;
+
+        if profiles.profiles.remove(&self.profile_name).is_none() {
+            return Ok(RemoveResult::Failed {
+                profile_name: self.profile_name.clone(),
+                error: "profile not found".to_string(),
+            });
+        }
+
+        debug!(
+            profile_name = %self.profile_name,
+            path = %self.profiles_file.display(),
+            "removing profile"
+        );
+        save_toml(self.fs.as_ref(), &self.profiles_file, &profiles).await?;
+
+        Ok(RemoveResult::Removed {
+            profile_name: self.profile_name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    fn create_remove_command(fs: MockTokioFs) -> Remove {
+        Remove {
+            profile_name: "fast".to_string(),
+            profiles_file: PathBuf::from("profiles.toml"),
+            fs: Box::new(fs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_profile() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(concat!(
+                "[profiles.fast]\n",
+                "imageTag = \"8.0\"\n",
+                "[profiles.full]\n",
+                "loadSampleData = true\n",
+            )
+            .to_string())
+        });
+        mock_fs.expect_create_dir_all().return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|_, contents| {
+                !contents.contains("[profiles.fast]") && contents.contains("[profiles.full]")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let result = create_remove_command(mock_fs)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RemoveResult::Removed {
+                profile_name: "fast".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_profile_not_found() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_fs.expect_write_string().never();
+
+        let result = create_remove_command(mock_fs)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RemoveResult::Failed {
+                profile_name: "fast".to_string(),
+                error: "profile not found".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Command to show the settings of a saved profile.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        profile::{ProfileSetting, Profiles, profiles_file_path},
+    },
+    config_dir::{ConfigFs, load_toml},
+    dependencies::TokioFs,
+    settings::Settings,
+    table::Table,
+};
+
+pub struct Show {
+    profile_name: String,
+    profiles_file: PathBuf,
+
+    fs: Box<dyn ConfigFs>,
+}
+
+impl TryFrom<(args::profile::Show, &Settings)> for Show {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::profile::Show, &Settings)) -> Result<Self> {
+        Ok(Self {
+            profile_name: args.profile_name,
+            profiles_file: profiles_file_path(settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ShowResult {
+    Profile {
+        profile_name: String,
+        settings: Vec<ProfileSetting>,
+    },
+    Failed {
+        profile_name: String,
+        error: String,
+    },
+}
+
+impl Display for ShowResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Profile {
+                profile_name,
+                settings,
+            } if settings.is_empty() => {
+                write!(f, "Profile '{}' has no settings", profile_name)
+            }
+            Self::Profile { settings, .. } => Table::from_iter(
+                settings,
+                &[("KEY", |s| s.key.clone()), ("VALUE", |s| s.value.clone())],
+            )
+            .fmt(f),
+            Self::Failed {
+                profile_name,
+                error,
+            } => write!(f, "Showing profile '{}' failed: {}", profile_name, error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Show {
+    type Output = ShowResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let profiles = load_toml::<Profiles>(self.fs.as_ref(), &self.profiles_file).await?;
+
+        Ok(match profiles.profiles.get(&self.profile_name) {
+            Some(settings) => ShowResult::Profile {
+                profile_name: self.profile_name.clone(),
+                settings: settings.entries(),
+            },
+            None => ShowResult::Failed {
+                profile_name: self.profile_name.clone(),
+                error: "profile not found".to_string(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_show_profile_settings() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok("[profiles.fast]\nconnectWith = \"vscode\"\nloadSampleData = false\n".to_string())
+        });
+
+        let mut show_command = Show {
+            profile_name: "fast".to_string(),
+            profiles_file: PathBuf::from("profiles.toml"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = show_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            ShowResult::Profile {
+                profile_name: "fast".to_string(),
+                settings: vec![
+                    ProfileSetting {
+                        key: "loadSampleData".to_string(),
+                        value: "false".to_string(),
+                    },
+                    ProfileSetting {
+                        key: "connectWith".to_string(),
+                        value: "vscode".to_string(),
+                    },
+                ],
+            }
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! Command to load the sample datasets into an existing deployment.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use serde::Serialize;
+use tracing::trace;
+
+use super::{LoadOutcome, load_sample_data};
+use crate::{
+    args,
+    commands::CommandWithOutput,
+    dependencies::{
+        ContainerExecutor, DeploymentGetConnectionString, DeploymentGetDeployment, connect_docker,
+        find_deployment,
+    },
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+    settings::Settings,
+};
+
+// Deployment dependencies for the sample-data load command
+pub trait LoadDeploymentManagement:
+    DeploymentGetDeployment + DeploymentGetConnectionString
+{
+}
+impl<T: DeploymentGetDeployment + DeploymentGetConnectionString> LoadDeploymentManagement for T {}
+
+pub struct Load {
+    deployment_name: String,
+    datasets: Vec<String>,
+
+    interaction: Box<dyn SpinnerInteraction + Send>,
+    deployment_management: Box<dyn LoadDeploymentManagement + Send + Sync>,
+    executor: Box<dyn ContainerExecutor + Send + Sync>,
+}
+
+impl TryFrom<(args::sample_data::Load, &Settings)> for Load {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::sample_data::Load, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            datasets: args.datasets,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker.clone())),
+            executor: Box::new(docker),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum LoadResult {
+    Loaded {
+        deployment_name: String,
+        datasets: Vec<String>,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for LoadResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Loaded {
+                deployment_name,
+                datasets,
+            } => write!(
+                f,
+                "Loaded {} sample dataset(s) into deployment '{}'",
+                datasets.len(),
+                deployment_name
+            ),
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Loading sample data into deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Load {
+    type Output = LoadResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(deployment_name = ?self.deployment_name, datasets = ?self.datasets, "executing sample-data load command");
+
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found".to_string()));
+        };
+
+        if !matches!(deployment.state, State::Running) {
+            return Ok(self.failed("deployment is not running, start it first".to_string()));
+        }
+
+        let connection_string = self
+            .deployment_management
+            .get_connection_string(deployment.container_id)
+            .await?;
+
+        // When spinner goes out of scope, the spinner will be stopped
+        let spinner = self
+            .interaction
+            .start_spinner(message(Message::DownloadSampleData).to_string())?;
+
+        let outcome = load_sample_data(
+            self.executor.as_ref(),
+            &self.deployment_name,
+            &connection_string,
+            &self.datasets,
+            &|msg| spinner.set_message(msg),
+        )
+        .await?;
+
+        Ok(match outcome {
+            LoadOutcome::Loaded(datasets) => LoadResult::Loaded {
+                deployment_name: self.deployment_name.clone(),
+                datasets,
+            },
+            LoadOutcome::Failed(error) => self.failed(error),
+        })
+    }
+}
+
+impl Load {
+    fn failed(&self, error: String) -> LoadResult {
+        LoadResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::args::sample_data::SAMPLE_DATASETS;
+    use crate::commands::sample_data::SAMPLE_DATA_ARCHIVE_URL;
+    use crate::commands::test_support::create_deployment;
+    use crate::dependencies::{ExecOutput, mocks::MockDocker};
+    use crate::interaction::{SpinnerHandle, mocks::MockInteraction};
+
+    fn create_deployment_management(state: State) -> MockDocker {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |name| Ok(create_deployment(name, state)));
+        mock_deployment_management
+            .expect_get_connection_string()
+            .returning(|_| Ok("mongodb://localhost:32768/?directConnection=true".to_string()));
+        mock_deployment_management
+    }
+
+    fn create_command(
+        deployment_management: MockDocker,
+        executor: MockDocker,
+        datasets: Vec<String>,
+    ) -> Load {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        Load {
+            deployment_name: "local1".to_string(),
+            datasets,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(deployment_management),
+            executor: Box::new(executor),
+        }
+    }
+
+    /// Executor that records the commands and fails the commands that start with `failing`.
+    fn create_executor(failing: &'static str) -> (MockDocker, Arc<Mutex<Vec<Vec<String>>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let exec_calls = calls.clone();
+        let mut mock_executor = MockDocker::new();
+        mock_executor
+            .expect_exec()
+            .returning(move |deployment_name, cmd, stdin| {
+                assert_eq!(deployment_name, "local1");
+                assert_eq!(stdin, None);
+                let failed = cmd[0] == failing;
+                exec_calls.lock().unwrap().push(cmd);
+                Ok(if failed {
+                    ExecOutput {
+                        exit_code: 6,
+                        stdout: String::new(),
+                        stderr: "curl: (6) Could not resolve host\n".to_string(),
+                        ..Default::default()
+                    }
+                } else {
+                    ExecOutput::default()
+                })
+            });
+        (mock_executor, calls)
+    }
+
+    #[tokio::test]
+    async fn test_load_downloads_and_restores_archive() {
+        let (mock_executor, calls) = create_executor("none");
+        let mut load_command = create_command(
+            create_deployment_management(State::Running),
+            mock_executor,
+            vec![],
+        );
+
+        let result = load_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LoadResult::Loaded {
+                deployment_name: "local1".to_string(),
+                datasets: SAMPLE_DATASETS.iter().map(ToString::to_string).collect(),
+            }
+        );
+
+        let archive = format!(
+            "/tmp/atlas-local-sample-data-{}.archive",
+            std::process::id()
+        );
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0][0], "bash");
+        assert_eq!(
+            calls[0][4..],
+            [SAMPLE_DATA_ARCHIVE_URL.to_string(), archive.clone()]
+        );
+        assert_eq!(
+            calls[1],
+            vec![
+                "mongorestore".to_string(),
+                "--uri=mongodb://127.0.0.1:27017/?directConnection=true".to_string(),
+                format!("--archive={archive}"),
+                "--drop".to_string(),
+            ]
+        );
+        assert_eq!(calls[2], vec!["rm".to_string(), "-f".to_string(), archive]);
+    }
+
+    #[tokio::test]
+    async fn test_load_selected_datasets() {
+        let (mock_executor, calls) = create_executor("none");
+        let mut load_command = create_command(
+            create_deployment_management(State::Running),
+            mock_executor,
+            vec!["sample_mflix".to_string(), "sample_airbnb".to_string()],
+        );
+
+        let result = load_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LoadResult::Loaded {
+                deployment_name: "local1".to_string(),
+                datasets: vec!["sample_mflix".to_string(), "sample_airbnb".to_string()],
+            }
+        );
+
+        // Only the namespaces of the selected datasets are restored, and dropped
+        let calls = calls.lock().unwrap();
+        assert_eq!(
+            calls[1][3..],
+            [
+                "--drop".to_string(),
+                "--nsInclude=sample_mflix.*".to_string(),
+                "--nsInclude=sample_airbnb.*".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_download_fails() {
+        let (mock_executor, calls) = create_executor("bash");
+        let mut load_command = create_command(
+            create_deployment_management(State::Running),
+            mock_executor,
+            vec![],
+        );
+
+        let result = load_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LoadResult::Failed {
+                deployment_name: "local1".to_string(),
+                error:
+                    "downloading sample data exited with code 6: curl: (6) Could not resolve host"
+                        .to_string(),
+            }
+        );
+
+        // The archive isn't restored, but it is still cleaned up
+        let calls = calls.lock().unwrap();
+        assert_eq!(
+            calls.iter().map(|cmd| cmd[0].as_str()).collect::<Vec<_>>(),
+            vec!["bash", "rm"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_stopped_deployment() {
+        let mut mock_executor = MockDocker::new();
+        mock_executor.expect_exec().never();
+
+        let mut load_command = create_command(
+            create_deployment_management(State::Exited),
+            mock_executor,
+            vec![],
+        );
+
+        let result = load_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            LoadResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "deployment is not running, start it first".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Commands to manage the Atlas sample datasets of deployments.
+//!
+//! The sample datasets are the databases of the sample data archive of Atlas, the archive the deployment image loads
+//! when a deployment is created with `--loadSampleData`. Every dataset is a database, e.g. `sample_mflix`.
+
+use anyhow::{Context, Result};
+use tracing::debug;
+
+use crate::{
+    args::sample_data::SAMPLE_DATASETS,
+    commands::connectors::container_connection_string,
+    dependencies::ContainerExecutor,
+    messages::{Message, message},
+};
+
+pub mod load;
+pub mod status;
+
+/// Archive with all the sample datasets, in the archive format of mongodump.
+const SAMPLE_DATA_ARCHIVE_URL: &str = "https://atlas-education.s3.amazonaws.com/sampledata.archive";
+
+/// Downloads the URL in `$1` to the path in `$2`, the image ships curl or wget depending on its base image.
+const DOWNLOAD_SCRIPT: &str = r#"if command -v curl > /dev/null; then curl -fsSL -o "$2" "$1"; else wget -q -O "$2" "$1"; fi"#;
+
+/// Outcome of loading sample data, a step that fails is reported to the user instead of failing the command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadOutcome {
+    /// The datasets that were loaded.
+    Loaded(Vec<String>),
+    /// The error of the step that failed.
+    Failed(String),
+}
+
+/// Load sample datasets into a running deployment, all datasets when `datasets` is empty.
+///
+/// The archive is downloaded and restored in the deployment container, so nothing has to be installed on the host and
+/// the archive doesn't have to be copied to a remote Docker host. `progress` is called with the message of every step.
+pub async fn load_sample_data(
+    executor: &(dyn ContainerExecutor + Send + Sync),
+    deployment_name: &str,
+    connection_string: &str,
+    datasets: &[String],
+    progress: &(dyn Fn(String) + Send + Sync),
+) -> Result<LoadOutcome> {
+    let connection_string = container_connection_string(connection_string)?;
+
+    // Concurrent loads into the same deployment don't share the archive
+    let archive = format!(
+        "/tmp/atlas-local-sample-data-{}.archive",
+        std::process::id()
+    );
+
+    let load_result = async {
+        progress(message(Message::DownloadSampleData).to_string());
+        debug!(url = SAMPLE_DATA_ARCHIVE_URL, "downloading sample data");
+        let download = vec![
+            "bash".to_string(),
+            "-c".to_string(),
+            DOWNLOAD_SCRIPT.to_string(),
+            "bash".to_string(),
+            SAMPLE_DATA_ARCHIVE_URL.to_string(),
+            archive.clone(),
+        ];
+        if let Some(error) = run_step(executor, deployment_name, "downloading", download).await? {
+            return Ok(Some(error));
+        }
+
+        progress(message(Message::ImportSampleData).to_string());
+        debug!(?datasets, "restoring sample data");
+        // Datasets that are already loaded are dropped first, so loading twice doesn't fail on duplicate keys
+        let mut restore = vec![
+            "mongorestore".to_string(),
+            format!("--uri={connection_string}"),
+            format!("--archive={archive}"),
+            "--drop".to_string(),
+        ];
+        restore.extend(
+            datasets
+                .iter()
+                .map(|dataset| format!("--nsInclude={dataset}.*")),
+        );
+        run_step(executor, deployment_name, "importing", restore).await
+    }
+    .await;
+
+    // The archive is removed from the container, whether it was restored or not
+    let cleanup_result = executor
+        .exec(
+            deployment_name,
+            vec!["rm".to_string(), "-f".to_string(), archive],
+            None,
+        )
+        .await
+        .context("removing the sample data archive");
+
+    if let Some(error) = load_result? {
+        return Ok(LoadOutcome::Failed(error));
+    }
+    cleanup_result?;
+
+    Ok(LoadOutcome::Loaded(if datasets.is_empty() {
+        SAMPLE_DATASETS.iter().map(ToString::to_string).collect()
+    } else {
+        datasets.to_vec()
+    }))
+}
+
+/// Run a step of the load in the container, the error is the last line the step wrote to stderr.
+async fn run_step(
+    executor: &(dyn ContainerExecutor + Send + Sync),
+    deployment_name: &str,
+    step: &str,
+    cmd: Vec<String>,
+) -> Result<Option<String>> {
+    let output = executor
+        .exec(deployment_name, cmd, None)
+        .await
+        .with_context(|| format!("{step} sample data"))?;
+    if output.exit_code == 0 {
+        return Ok(None);
+    }
+
+    let mut error = format!("{step} sample data exited with code {}", output.exit_code);
+    if let Some(line) = output.last_stderr_line() {
+        error.push_str(": ");
+        error.push_str(line);
+    }
+    Ok(Some(error))
+}
@@ -0,0 +1,235 @@
+//! Command to show which sample datasets are loaded in a deployment.
+
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use serde::Serialize;
+use tracing::trace;
+
+use super::SAMPLE_DATASETS;
+use crate::{
+    args,
+    commands::{CommandWithOutput, with_mongodb::LocalDeploymentDatabaseLister},
+    dependencies::{DatabaseLister, DeploymentGetDeployment, connect_docker, find_deployment},
+    settings::Settings,
+};
+
+pub struct Status {
+    deployment_name: String,
+
+    deployment_getter: Box<dyn DeploymentGetDeployment + Send + Sync>,
+    database_lister: Box<dyn DatabaseLister + Send + Sync>,
+}
+
+impl TryFrom<(args::sample_data::Status, &Settings)> for Status {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::sample_data::Status, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+
+            deployment_getter: Box::new(Client::new(docker)),
+            database_lister: Box::new(LocalDeploymentDatabaseLister::new(settings.docker.clone())),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum StatusResult {
+    Status {
+        deployment_name: String,
+        datasets: Vec<SampleDatasetStatus>,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+/// Whether a sample dataset is loaded in the deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SampleDatasetStatus {
+    pub name: String,
+    pub loaded: bool,
+}
+
+impl Display for StatusResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Status {
+                deployment_name,
+                datasets,
+            } => {
+                let loaded = datasets.iter().filter(|dataset| dataset.loaded).count();
+                write!(
+                    f,
+                    "{} of {} sample datasets loaded in deployment '{}'",
+                    loaded,
+                    datasets.len(),
+                    deployment_name
+                )?;
+                for dataset in datasets {
+                    let status = if dataset.loaded {
+                        "loaded"
+                    } else {
+                        "not loaded"
+                    };
+                    write!(f, "\n  {}: {}", dataset.name, status)?;
+                }
+                Ok(())
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Getting the sample data of deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Status {
+    type Output = StatusResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(deployment_name = ?self.deployment_name, "executing sample-data status command");
+
+        let Some(deployment) =
+            find_deployment(self.deployment_getter.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found".to_string()));
+        };
+
+        if !matches!(deployment.state, State::Running) {
+            return Ok(self.failed("deployment is not running, start it first".to_string()));
+        }
+
+        let databases = self
+            .database_lister
+            .list_databases(&self.deployment_name)
+            .await
+            .context("listing databases")?;
+
+        // A dataset counts as loaded when its database exists, the collections are not compared with the archive
+        let datasets = SAMPLE_DATASETS
+            .iter()
+            .map(|dataset| SampleDatasetStatus {
+                name: dataset.to_string(),
+                loaded: databases.iter().any(|database| database.name == *dataset),
+            })
+            .collect();
+
+        Ok(StatusResult::Status {
+            deployment_name: self.deployment_name.clone(),
+            datasets,
+        })
+    }
+}
+
+impl Status {
+    fn failed(&self, error: String) -> StatusResult {
+        StatusResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::create_deployment;
+    use crate::dependencies::{
+        DatabaseInfo,
+        mocks::{MockDocker, MockMongoDB},
+    };
+
+    #[tokio::test]
+    async fn test_status_reports_loaded_datasets() {
+        let mut mock_deployment_getter = MockDocker::new();
+        mock_deployment_getter
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+
+        let mut mock_database_lister = MockMongoDB::new();
+        mock_database_lister
+            .expect_list_databases()
+            .withf(|deployment_name| deployment_name == "local1")
+            .return_once(|_| {
+                Ok(["admin", "sample_mflix", "shop", "sample_airbnb"]
+                    .into_iter()
+                    .map(|name| DatabaseInfo {
+                        name: name.to_string(),
+                        size_on_disk: 4096,
+                    })
+                    .collect())
+            });
+
+        let mut status_command = Status {
+            deployment_name: "local1".to_string(),
+            deployment_getter: Box::new(mock_deployment_getter),
+            database_lister: Box::new(mock_database_lister),
+        };
+
+        let result = status_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let StatusResult::Status { datasets, .. } = &result else {
+            panic!("expected a status, got {result:?}");
+        };
+        assert_eq!(datasets.len(), SAMPLE_DATASETS.len());
+        assert_eq!(
+            datasets
+                .iter()
+                .filter(|dataset| dataset.loaded)
+                .map(|dataset| dataset.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["sample_airbnb", "sample_mflix"]
+        );
+        assert!(
+            result
+                .to_string()
+                .starts_with("2 of 9 sample datasets loaded in deployment 'local1'\n  sample_airbnb: loaded\n  sample_analytics: not loaded\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_stopped_deployment() {
+        let mut mock_deployment_getter = MockDocker::new();
+        mock_deployment_getter
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Exited)));
+
+        let mut mock_database_lister = MockMongoDB::new();
+        mock_database_lister.expect_list_databases().never();
+
+        let mut status_command = Status {
+            deployment_name: "local1".to_string(),
+            deployment_getter: Box::new(mock_deployment_getter),
+            database_lister: Box::new(mock_database_lister),
+        };
+
+        let result = status_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StatusResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "deployment is not running, start it first".to_string(),
+            }
+        );
+    }
+}
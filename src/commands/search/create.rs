@@ -1,5 +1,6 @@
 use std::{
     fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -17,14 +18,18 @@ use crate::{
     args,
     commands::{
         CommandWithOutput,
-        search::file_definition::SearchIndexCreateRequest,
+        search::{
+            file_definition::SearchIndexCreateRequest,
+            watcher::{WatchSchedule, wait_for_search_index_ready},
+        },
         with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
     },
     dependencies::{
-        CreateSearchIndexModel, FileReader, MongoDbSearchIndexStatus, SearchIndexCreator,
-        SearchIndexStatusGetter, SearchIndexType, TokioFs,
+        CreateSearchIndexModel, FileReader, SearchIndexCreator, SearchIndexDefinitionGetter,
+        SearchIndexStatusGetter, SearchIndexType, Sleeper, TokioClock, TokioFs,
     },
     interaction::{InputPrompt, Interaction, SpinnerInteraction},
+    messages::{Message, message},
 };
 
 // Interaction dependencies for the delete command
@@ -32,16 +37,26 @@ pub trait CreateInteraction: InputPrompt + SpinnerInteraction + Send + Sync {}
 impl<T: InputPrompt + SpinnerInteraction + Send + Sync> CreateInteraction for T {}
 
 // MongoDB dependencies for the create command
-pub trait MongoDbClient: SearchIndexCreator + SearchIndexStatusGetter + Send + Sync {}
-impl<T: SearchIndexCreator + SearchIndexStatusGetter + Send + Sync> MongoDbClient for T {}
+pub trait MongoDbClient:
+    SearchIndexCreator + SearchIndexStatusGetter + SearchIndexDefinitionGetter + Send + Sync
+{
+}
+impl<T: SearchIndexCreator + SearchIndexStatusGetter + SearchIndexDefinitionGetter + Send + Sync>
+    MongoDbClient for T
+{
+}
 
 pub struct Create {
-    watch_interval: Duration,
+    watch_schedule: WatchSchedule,
     watch: bool,
+    // Cap on the whole command, creating and watching the index
+    timeout: Option<Duration>,
+    idempotent: bool,
     definition_source: IndexDefinitionSource,
 
     interaction: Box<dyn CreateInteraction>,
     file_reader: Box<dyn FileReader + Send + Sync>,
+    sleeper: Box<dyn Sleeper + Send + Sync>,
     mongodb_client: Result<Box<dyn MongoDbClient + Send + Sync>, TryToGetMongodbClientError>,
 }
 
@@ -54,6 +69,37 @@ struct IndexDefinitionSourceFlags {
     index_name: Option<String>,
     database_name: Option<String>,
     collection: Option<String>,
+    // Set when a vector search index is created, the default search index is created otherwise
+    vector_field: Option<VectorField>,
+}
+
+/// The vector field of a vector search index created from flags.
+struct VectorField {
+    path: String,
+    dimensions: u32,
+    similarity: args::search::VectorSimilarity,
+}
+
+impl VectorField {
+    /// Definition of a vector search index on this field.
+    fn definition(&self) -> bson::Document {
+        let similarity = match self.similarity {
+            args::search::VectorSimilarity::Cosine => "cosine",
+            args::search::VectorSimilarity::Euclidean => "euclidean",
+            args::search::VectorSimilarity::DotProduct => "dotProduct",
+        };
+
+        doc! {
+            "fields": [
+                {
+                    "type": "vector",
+                    "path": &self.path,
+                    "numDimensions": i64::from(self.dimensions),
+                    "similarity": similarity,
+                },
+            ],
+        }
+    }
 }
 
 impl TryFromWithMongodbClient<args::search::Create> for Create {
@@ -71,17 +117,31 @@ impl TryFromWithMongodbClient<args::search::Create> for Create {
                 index_name: args.index_name,
                 database_name: args.database_name,
                 collection: args.collection,
+                vector_field: match args.index_type {
+                    args::search::IndexType::Search => None,
+                    // Clap requires --path, --dimensions and --similarity for vector search indexes
+                    args::search::IndexType::VectorSearch => Some(VectorField {
+                        path: args.path.unwrap_or_default(),
+                        dimensions: args.dimensions.unwrap_or_default(),
+                        similarity: args
+                            .similarity
+                            .unwrap_or(args::search::VectorSimilarity::Cosine),
+                    }),
+                },
             }),
         };
 
         Ok(Self {
             watch: args.watch,
-            watch_interval: Duration::from_secs(1),
+            watch_schedule: WatchSchedule::from_args(&args),
+            timeout: args.timeout,
+            idempotent: args.idempotent,
 
             definition_source,
 
-            interaction: Box::new(Interaction::new()),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
             file_reader: Box::new(TokioFs::new()),
+            sleeper: Box::new(TokioClock::new()),
             // It is possible that the mongodb client is not created successfully, so we need to return a result.
             // We'll handle the result in the `execute` method. This way we can return a `Failed` result if the mongodb client is not created successfully.
             mongodb_client: client_result
@@ -90,19 +150,49 @@ impl TryFromWithMongodbClient<args::search::Create> for Create {
     }
 }
 
+/// Result of the search index create command.
+///
+/// With `--output json` the result is an object tagged by `outcome`:
+/// - `{"outcome":"created","search_index_id":"<id>","unchanged":false}`
+/// - `{"outcome":"skipped","warning":"<message>"}`
+/// - `{"outcome":"failed","error":"<message>"}`
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(test, derive(serde::Deserialize))]
 #[serde(tag = "outcome", rename_all = "snake_case")]
 pub enum CreateResult {
-    Created { search_index_id: String },
-    Failed { error: String },
+    Created {
+        search_index_id: String,
+        /// True when `--idempotent` found an existing index with the same definition.
+        unchanged: bool,
+    },
+    /// Only returned by `--allCollections`, for collections that already have an index with the same name.
+    Skipped {
+        warning: String,
+    },
+    Failed {
+        error: String,
+    },
 }
 
 impl Display for CreateResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Created { search_index_id } => {
+            Self::Created {
+                search_index_id,
+                unchanged: false,
+            } => {
                 write!(f, "Search index created with ID: {search_index_id}")
             }
+            Self::Created {
+                search_index_id,
+                unchanged: true,
+            } => {
+                write!(
+                    f,
+                    "Search index already exists with the same definition, ID: {search_index_id}"
+                )
+            }
+            Self::Skipped { warning } => write!(f, "Warning: {warning}"),
             Self::Failed { error } => write!(f, "Creating index failed: {}", error),
         }
     }
@@ -113,6 +203,28 @@ impl CommandWithOutput for Create {
     type Output = CreateResult;
 
     async fn execute(&mut self) -> Result<Self::Output> {
+        let Some(timeout) = self.timeout else {
+            return self.create().await;
+        };
+
+        // The timeout uses the sleeper, so tests can expire it without waiting.
+        // Expiring drops the create future, the index is left as it is on the server.
+        tokio::select! {
+            biased;
+            result = self.create() => result,
+            _ = self.sleeper.sleep(timeout) => {
+                debug!(?timeout, "search index creation timed out");
+                Ok(CreateResult::Failed {
+                    error: "search index creation timed out".to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl Create {
+    /// Create the search index, and watch it until it is ready when requested.
+    async fn create(&self) -> Result<CreateResult> {
         // Build the index definition
         debug!("building index definition");
         let create_search_index_model_result = match &self.definition_source {
@@ -150,69 +262,74 @@ impl CommandWithOutput for Create {
             }
         };
 
-        debug!("mongodb client available, creating search index");
-
-        // Create the search index.
-        let search_index_id = match mongodb_client
-            .create_search_index(create_search_index_model.clone())
-            .await
-        {
-            Ok(search_index_id) => search_index_id,
-            Err(e) => {
-                return Ok(CreateResult::Failed {
-                    error: format!("failed to create search index: {e}"),
-                });
+        // When idempotent, look for an existing index with the same name before creating a new one.
+        let existing_search_index_id = if self.idempotent {
+            debug!("idempotent enabled, looking for an existing search index");
+
+            match find_identical_search_index(mongodb_client.as_ref(), &create_search_index_model)
+                .await
+            {
+                Ok(existing_search_index_id) => existing_search_index_id,
+                Err(e) => {
+                    return Ok(CreateResult::Failed {
+                        error: format!("{e:#}"),
+                    });
+                }
             }
+        } else {
+            None
         };
 
-        debug!(search_index_id, "search index created");
-
-        if self.watch {
-            debug!("watching enabled, watching search index");
+        let unchanged = existing_search_index_id.is_some();
+        let search_index_id = match existing_search_index_id {
+            Some(search_index_id) => {
+                debug!(
+                    search_index_id,
+                    "identical search index exists, skipping create"
+                );
+                search_index_id
+            }
+            None => {
+                debug!("mongodb client available, creating search index");
 
-            let _watch_spinner_handle = self
-                .interaction
-                .start_spinner("Building search index...".to_string())?;
-
-            loop {
-                match mongodb_client
-                    .get_search_index_status(
-                        create_search_index_model.database_name.clone(),
-                        create_search_index_model.collection_name.clone(),
-                        search_index_id.clone(),
-                    )
+                // Create the search index.
+                let search_index_id = match mongodb_client
+                    .create_search_index(create_search_index_model.clone())
                     .await
                 {
+                    Ok(search_index_id) => search_index_id,
                     Err(e) => {
                         return Ok(CreateResult::Failed {
-                            error: format!(
-                                "failed to get search index status while watching the search index: {e}"
-                            ),
+                            error: format!("failed to create search index: {e}"),
                         });
                     }
-                    Ok(None) => {
-                        return Ok(CreateResult::Failed {
-                            error: "failed to get search index status while watching the search index, the search index does not exist".to_string(),
-                        });
-                    }
-                    Ok(Some(status)) => match status {
-                        MongoDbSearchIndexStatus::Ready => break,
-                        MongoDbSearchIndexStatus::DoesNotExist
-                        | MongoDbSearchIndexStatus::Deleting
-                        | MongoDbSearchIndexStatus::Failed => {
-                            return Ok(CreateResult::Failed {
-                                error: format!(
-                                    "failed to get search index status while watching the search index, the search index is not ready: {status}"
-                                ),
-                            });
-                        }
-                        MongoDbSearchIndexStatus::Pending
-                        | MongoDbSearchIndexStatus::Building
-                        | MongoDbSearchIndexStatus::Stale => {
-                            tokio::time::sleep(self.watch_interval).await;
-                        }
-                    },
-                }
+                };
+
+                debug!(search_index_id, "search index created");
+                search_index_id
+            }
+        };
+
+        if self.watch {
+            debug!("watching enabled, watching search index");
+
+            let _watch_spinner_handle = self
+                .interaction
+                .start_spinner(message(Message::BuildSearchIndex).to_string())?;
+
+            if let Err(e) = wait_for_search_index_ready(
+                mongodb_client.as_ref(),
+                &create_search_index_model.database_name,
+                &create_search_index_model.collection_name,
+                &search_index_id,
+                self.sleeper.as_ref(),
+                self.watch_schedule,
+            )
+            .await
+            {
+                return Ok(CreateResult::Failed {
+                    error: e.to_string(),
+                });
             }
 
             debug!("watch loop exited, search index is ready");
@@ -220,7 +337,110 @@ impl CommandWithOutput for Create {
             debug!("watching disabled, skipping watch");
         }
 
-        Ok(CreateResult::Created { search_index_id })
+        Ok(CreateResult::Created {
+            search_index_id,
+            unchanged,
+        })
+    }
+}
+
+/// Definition of the default search index, which dynamically indexes all fields.
+pub(super) fn default_search_index_definition() -> bson::Document {
+    doc! {
+        "analyzer": "lucene.standard",
+        "searchAnalyzer": "lucene.standard",
+        "mappings": {
+            "dynamic": true,
+        },
+    }
+}
+
+/// Look up an existing search index with the same name as the requested one.
+///
+/// Returns the ID of the existing index when its definition hash matches the requested definition,
+/// `None` when no index with that name exists, and an error when the definitions differ.
+async fn find_identical_search_index(
+    mongodb_client: &(dyn MongoDbClient + Send + Sync),
+    model: &CreateSearchIndexModel,
+) -> Result<Option<String>> {
+    let Some(index_name) = model.name.clone() else {
+        return Ok(None);
+    };
+
+    let Some(existing) = mongodb_client
+        .get_search_index_definition(
+            model.database_name.clone(),
+            model.collection_name.clone(),
+            index_name.clone(),
+        )
+        .await
+        .context("failed to get existing search index definition")?
+    else {
+        return Ok(None);
+    };
+
+    let requested_type =
+        serde_json::to_value(model.index_type.clone().unwrap_or(SearchIndexType::Search))?
+            .as_str()
+            .map(str::to_string);
+    let existing_type = existing.index_type.or(Some("search".to_string()));
+
+    let requested_hash = definition_hash(requested_type.as_deref(), &model.definition);
+    let existing_hash = definition_hash(existing_type.as_deref(), &existing.definition);
+    debug!(
+        requested_hash,
+        existing_hash, "comparing search index definition hashes"
+    );
+
+    if requested_hash != existing_hash {
+        return Err(anyhow!(
+            "search index '{index_name}' already exists with a different definition, use search indexes update to replace its definition"
+        ));
+    }
+
+    Ok(Some(existing.index_id))
+}
+
+/// Hash a search index definition independently of key order.
+///
+/// Empty documents are ignored, because the server fills in defaults such as `"fields": {}` that are not part of the requested definition.
+fn definition_hash(index_type: Option<&str>, definition: &bson::Document) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    index_type.hash(&mut hasher);
+    canonical_json(&bson::Bson::Document(definition.clone()).into_relaxed_extjson())
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Render a JSON value with sorted keys and without empty objects.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map
+                .iter()
+                .filter(
+                    |(_, value)| !matches!(value, serde_json::Value::Object(map) if map.is_empty()),
+                )
+                .map(|(key, value)| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::from(key.as_str()),
+                        canonical_json(value)
+                    )
+                })
+                .collect::<Vec<_>>();
+            entries.sort();
+            format!("{{{}}}", entries.join(","))
+        }
+        serde_json::Value::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(canonical_json)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        value => value.to_string(),
     }
 }
 
@@ -234,20 +454,8 @@ impl Create {
             .read_to_string(path)
             .await
             .with_context(|| format!("failed to read file at path: {path:?}"))?;
-        let index_definition: SearchIndexCreateRequest = serde_json::from_str(&file)
-            .map_err(|e| anyhow!("failed to parse file as search index create request: {e}"))?;
-        let create_search_index_model = CreateSearchIndexModel {
-            database_name: index_definition.database,
-            collection_name: index_definition.collection_name,
-            definition: index_definition
-                .definition
-                .and_then(|value| bson::to_document(&value).ok())
-                .unwrap_or_default(),
-            name: Some(index_definition.name),
-            index_type: index_definition.index_type,
-        };
 
-        Ok(create_search_index_model)
+        Ok(SearchIndexCreateRequest::parse(&file)?.into())
     }
 
     async fn build_index_definition_from_flags(
@@ -267,18 +475,18 @@ impl Create {
             .interaction
             .prompt_if_none(flags.collection.as_deref(), "Collection?")?;
 
+        // Vector search indexes don't use analyzers, they index the vector field instead
+        let (index_type, definition) = match &flags.vector_field {
+            Some(vector_field) => (SearchIndexType::VectorSearch, vector_field.definition()),
+            None => (SearchIndexType::Search, default_search_index_definition()),
+        };
+
         let create_search_index_model = CreateSearchIndexModel {
             database_name,
             collection_name,
-            definition: doc! {
-                "analyzer": "lucene.standard",
-                "searchAnalyzer": "lucene.standard",
-                "mappings": {
-                    "dynamic": true,
-                },
-            },
+            definition,
             name: Some(index_name),
-            index_type: Some(SearchIndexType::Search),
+            index_type: Some(index_type),
         };
 
         Ok(create_search_index_model)
@@ -288,9 +496,12 @@ impl Create {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::test_support::create_spinner_handle;
     use crate::dependencies::mocks::{MockMongoDB, MockTokioFs};
+    use crate::dependencies::{MongoDbSearchIndexStatus, SearchIndexWithDefinition};
+    use crate::formatting::{Format, Formattable};
+    use crate::interaction::InputPromptResult;
     use crate::interaction::mocks::MockInteraction;
-    use crate::interaction::{InputPromptResult, SpinnerHandle};
     use std::path::PathBuf;
     use std::time::Duration;
 
@@ -298,10 +509,6 @@ mod tests {
     // Test Helpers
     // ============================================================================
 
-    fn create_spinner_handle() -> SpinnerHandle {
-        SpinnerHandle::new(Box::new(|| {}))
-    }
-
     fn create_command_from_flags(
         index_name: Option<String>,
         database_name: Option<String>,
@@ -312,15 +519,19 @@ mod tests {
         mongodb_client: Result<MockMongoDB, TryToGetMongodbClientError>,
     ) -> Create {
         Create {
-            watch_interval: Duration::from_millis(10),
+            watch_schedule: WatchSchedule::fixed(Duration::from_millis(10)),
             watch,
+            timeout: None,
+            idempotent: false,
             definition_source: IndexDefinitionSource::Flags(IndexDefinitionSourceFlags {
                 index_name,
                 database_name,
                 collection,
+                vector_field: None,
             }),
             interaction: Box::new(interaction),
             file_reader: Box::new(file_reader),
+            sleeper: Box::new(TokioClock::new()),
             mongodb_client: mongodb_client
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
         }
@@ -334,11 +545,14 @@ mod tests {
         mongodb_client: Result<MockMongoDB, TryToGetMongodbClientError>,
     ) -> Create {
         Create {
-            watch_interval: Duration::from_millis(10),
+            watch_schedule: WatchSchedule::fixed(Duration::from_millis(10)),
             watch,
+            timeout: None,
+            idempotent: false,
             definition_source: IndexDefinitionSource::File(file_path),
             interaction: Box::new(interaction),
             file_reader: Box::new(file_reader),
+            sleeper: Box::new(TokioClock::new()),
             mongodb_client: mongodb_client
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
         }
@@ -378,7 +592,60 @@ mod tests {
         assert_eq!(
             result,
             CreateResult::Created {
-                search_index_id: "index-123".to_string()
+                search_index_id: "index-123".to_string(),
+                unchanged: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_vector_search_index_from_flags() {
+        let mock_interaction = MockInteraction::new();
+        let mock_file_reader = MockTokioFs::new();
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_create_search_index()
+            .withf(|model| {
+                model.index_type == Some(SearchIndexType::VectorSearch)
+                    && model.definition
+                        == doc! {
+                            "fields": [
+                                {
+                                    "type": "vector",
+                                    "path": "embedding",
+                                    "numDimensions": 1536_i64,
+                                    "similarity": "dotProduct",
+                                },
+                            ],
+                        }
+            })
+            .return_once(|_| Ok("index-123".to_string()));
+
+        let mut cmd = create_command_from_flags(
+            Some("vector_index".to_string()),
+            Some("test_db".to_string()),
+            Some("test_collection".to_string()),
+            false,
+            mock_interaction,
+            mock_file_reader,
+            Ok(mock_mongodb),
+        );
+        if let IndexDefinitionSource::Flags(flags) = &mut cmd.definition_source {
+            flags.vector_field = Some(VectorField {
+                path: "embedding".to_string(),
+                dimensions: 1536,
+                similarity: args::search::VectorSimilarity::DotProduct,
+            });
+        }
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateResult::Created {
+                search_index_id: "index-123".to_string(),
+                unchanged: false,
             }
         );
     }
@@ -423,7 +690,8 @@ mod tests {
         assert_eq!(
             result,
             CreateResult::Created {
-                search_index_id: "index-456".to_string()
+                search_index_id: "index-456".to_string(),
+                unchanged: false,
             }
         );
     }
@@ -470,7 +738,93 @@ mod tests {
         assert_eq!(
             result,
             CreateResult::Created {
-                search_index_id: "index-789".to_string()
+                search_index_id: "index-789".to_string(),
+                unchanged: false,
+            }
+        );
+    }
+
+    /// Clock on which the timeout of the command expires right away, while the watch intervals never elapse.
+    struct ExpiringTimeoutClock {
+        timeout: Duration,
+    }
+
+    #[async_trait]
+    impl Sleeper for ExpiringTimeoutClock {
+        async fn sleep(&self, duration: Duration) {
+            if duration != self.timeout {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_watch_timeout_expires() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_create_search_index()
+            .times(1)
+            .return_once(|_| Ok("index-789".to_string()));
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(1)
+            .returning(|_, _, _| Ok(Some(MongoDbSearchIndexStatus::Building)));
+
+        let mut cmd = create_command_from_flags(
+            Some("test_index".to_string()),
+            Some("test_db".to_string()),
+            Some("test_collection".to_string()),
+            true,
+            mock_interaction,
+            MockTokioFs::new(),
+            Ok(mock_mongodb),
+        );
+        cmd.timeout = Some(Duration::from_secs(60));
+        cmd.sleeper = Box::new(ExpiringTimeoutClock {
+            timeout: Duration::from_secs(60),
+        });
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        // The index keeps building, it is not deleted
+        assert_eq!(
+            result,
+            CreateResult::Failed {
+                error: "search index creation timed out".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_completes_before_timeout() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_create_search_index()
+            .return_once(|_| Ok("index-789".to_string()));
+
+        let mut cmd = create_command_from_flags(
+            Some("test_index".to_string()),
+            Some("test_db".to_string()),
+            Some("test_collection".to_string()),
+            false,
+            MockInteraction::new(),
+            MockTokioFs::new(),
+            Ok(mock_mongodb),
+        );
+        cmd.timeout = Some(Duration::from_secs(60));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateResult::Created {
+                search_index_id: "index-789".to_string(),
+                unchanged: false,
             }
         );
     }
@@ -517,7 +871,8 @@ mod tests {
         assert_eq!(
             result,
             CreateResult::Created {
-                search_index_id: "file-index-id".to_string()
+                search_index_id: "file-index-id".to_string(),
+                unchanged: false,
             }
         );
     }
@@ -565,7 +920,8 @@ mod tests {
         assert_eq!(
             result,
             CreateResult::Created {
-                search_index_id: "def-index-id".to_string()
+                search_index_id: "def-index-id".to_string(),
+                unchanged: false,
             }
         );
     }
@@ -993,11 +1349,174 @@ mod tests {
         assert_eq!(
             result,
             CreateResult::Created {
-                search_index_id: "idx-id".to_string()
+                search_index_id: "idx-id".to_string(),
+                unchanged: false,
             }
         );
     }
 
+    // ============================================================================
+    // Idempotent Tests
+    // ============================================================================
+
+    fn existing_search_index(definition: bson::Document) -> SearchIndexWithDefinition {
+        SearchIndexWithDefinition {
+            index_id: "existing-id".to_string(),
+            name: "idx".to_string(),
+            index_type: Some("search".to_string()),
+            definition,
+        }
+    }
+
+    fn create_idempotent_command(mongodb_client: MockMongoDB) -> Create {
+        let mut cmd = create_command_from_flags(
+            Some("idx".to_string()),
+            Some("db".to_string()),
+            Some("col".to_string()),
+            false,
+            MockInteraction::new(),
+            MockTokioFs::new(),
+            Ok(mongodb_client),
+        );
+        cmd.idempotent = true;
+        cmd
+    }
+
+    #[tokio::test]
+    async fn test_create_idempotent_identical_definition_is_unchanged() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .withf(|database_name, collection_name, index_name| {
+                database_name == "db" && collection_name == "col" && index_name == "idx"
+            })
+            .return_once(|_, _, _| {
+                // The server returns keys in a different order and fills in empty defaults.
+                Ok(Some(existing_search_index(doc! {
+                    "mappings": { "fields": {}, "dynamic": true },
+                    "searchAnalyzer": "lucene.standard",
+                    "analyzer": "lucene.standard",
+                })))
+            });
+        mock_mongodb.expect_create_search_index().never();
+
+        let mut cmd = create_idempotent_command(mock_mongodb);
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateResult::Created {
+                search_index_id: "existing-id".to_string(),
+                unchanged: true,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_idempotent_different_definition_fails() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .return_once(|_, _, _| {
+                Ok(Some(existing_search_index(doc! {
+                    "mappings": { "dynamic": false },
+                })))
+            });
+        mock_mongodb.expect_create_search_index().never();
+
+        let mut cmd = create_idempotent_command(mock_mongodb);
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        match result {
+            CreateResult::Failed { error } => {
+                assert!(error.contains("different definition"));
+                assert!(error.contains("search indexes update"));
+            }
+            _ => panic!("Expected Failed result"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_idempotent_no_existing_index_creates() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .return_once(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .return_once(|_| Ok("new-id".to_string()));
+
+        let mut cmd = create_idempotent_command(mock_mongodb);
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateResult::Created {
+                search_index_id: "new-id".to_string(),
+                unchanged: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_definition_hash_differs_by_index_type() {
+        let definition = doc! { "fields": [{ "type": "vector", "path": "embedding" }] };
+        assert_ne!(
+            definition_hash(Some("search"), &definition),
+            definition_hash(Some("vectorSearch"), &definition)
+        );
+    }
+
+    // ============================================================================
+    // JSON Tests
+    // ============================================================================
+
+    fn assert_json_round_trip(result: CreateResult, expected_json: &str) {
+        let json = result
+            .format(Format::JSON)
+            .expect("formatting as JSON should succeed");
+        assert_eq!(json, expected_json);
+
+        let parsed: CreateResult =
+            serde_json::from_str(&json).expect("parsing the JSON output should succeed");
+        assert_eq!(parsed, result);
+    }
+
+    #[test]
+    fn test_create_result_json_created() {
+        assert_json_round_trip(
+            CreateResult::Created {
+                search_index_id: "abc-123".to_string(),
+                unchanged: false,
+            },
+            r#"{"outcome":"created","search_index_id":"abc-123","unchanged":false}"#,
+        );
+    }
+
+    #[test]
+    fn test_create_result_json_unchanged() {
+        assert_json_round_trip(
+            CreateResult::Created {
+                search_index_id: "abc-123".to_string(),
+                unchanged: true,
+            },
+            r#"{"outcome":"created","search_index_id":"abc-123","unchanged":true}"#,
+        );
+    }
+
+    #[test]
+    fn test_create_result_json_failed() {
+        assert_json_round_trip(
+            CreateResult::Failed {
+                error: "something went wrong".to_string(),
+            },
+            r#"{"outcome":"failed","error":"something went wrong"}"#,
+        );
+    }
+
     // ============================================================================
     // Display Tests
     // ============================================================================
@@ -1006,12 +1525,24 @@ mod tests {
     fn test_create_result_display_created() {
         let result = CreateResult::Created {
             search_index_id: "abc-123".to_string(),
+            unchanged: false,
         };
         let output = format!("{}", result);
         assert!(output.contains("abc-123"));
         assert!(output.contains("created"));
     }
 
+    #[test]
+    fn test_create_result_display_unchanged() {
+        let result = CreateResult::Created {
+            search_index_id: "abc-123".to_string(),
+            unchanged: true,
+        };
+        let output = format!("{}", result);
+        assert!(output.contains("abc-123"));
+        assert!(output.contains("already exists"));
+    }
+
     #[test]
     fn test_create_result_display_failed() {
         let result = CreateResult::Failed {
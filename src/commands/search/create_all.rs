@@ -0,0 +1,757 @@
+//! Command to create the default search index on all collections of a database.
+//!
+//! This module implements the `search indexes create --allCollections` command, which lists the collections
+//! of a database and creates the default dynamic search index on each of them.
+
+use std::fmt::Display;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mongodb::Client;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        bulk::{BulkItemResult, BulkOutput, FailurePolicy, run_bulk},
+        search::{
+            create::{CreateResult, default_search_index_definition},
+            watcher::{WatchSchedule, is_search_index_ready, wait_for_search_index_ready},
+        },
+        with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
+    },
+    dependencies::{
+        CollectionLister, CreateSearchIndexModel, SearchIndexCreator, SearchIndexDefinitionGetter,
+        SearchIndexStatusGetter, SearchIndexType, Sleeper, TokioClock,
+    },
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+};
+
+/// Name of the search index when no index name is provided.
+const DEFAULT_INDEX_NAME: &str = "default";
+
+// Interaction dependencies for the create all collections command
+pub trait CreateAllCollectionsInteraction: SpinnerInteraction + Send + Sync {}
+impl<T: SpinnerInteraction + Send + Sync> CreateAllCollectionsInteraction for T {}
+
+// MongoDB dependencies for the create all collections command
+pub trait MongoDbClient:
+    CollectionLister
+    + SearchIndexCreator
+    + SearchIndexStatusGetter
+    + SearchIndexDefinitionGetter
+    + Send
+    + Sync
+{
+}
+impl<
+    T: CollectionLister
+        + SearchIndexCreator
+        + SearchIndexStatusGetter
+        + SearchIndexDefinitionGetter
+        + Send
+        + Sync,
+> MongoDbClient for T
+{
+}
+
+/// When watching the created indexes completes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WatchCompletion {
+    /// Once all indexes are ready, the default and `--watchAll`.
+    #[default]
+    All,
+    /// Once any index is ready, `--watchAny`.
+    Any,
+}
+
+pub struct CreateAllCollections {
+    watch_schedule: WatchSchedule,
+    watch: bool,
+    watch_completion: WatchCompletion,
+    index_name: String,
+    database_name: String,
+    exclude: Vec<String>,
+    concurrency: usize,
+    failure_policy: FailurePolicy,
+
+    interaction: Box<dyn CreateAllCollectionsInteraction>,
+    sleeper: Box<dyn Sleeper + Send + Sync>,
+    mongodb_client: Result<Box<dyn MongoDbClient + Send + Sync>, TryToGetMongodbClientError>,
+}
+
+impl TryFromWithMongodbClient<args::search::Create> for CreateAllCollections {
+    fn try_from_with_mongodb(
+        args: args::search::Create,
+        client_result: Result<Client, TryToGetMongodbClientError>,
+    ) -> Result<Self> {
+        Ok(Self {
+            watch: args.watch,
+            watch_schedule: WatchSchedule::from_args(&args),
+            // --watchAll is the default, clap rejects it together with --watchAny
+            watch_completion: if args.watch_any && !args.watch_all {
+                WatchCompletion::Any
+            } else {
+                WatchCompletion::All
+            },
+            index_name: args
+                .index_name
+                .unwrap_or_else(|| DEFAULT_INDEX_NAME.to_string()),
+            // Clap requires --db when --allCollections is used
+            database_name: args.database_name.unwrap_or_default(),
+            exclude: args.exclude,
+            concurrency: args.concurrency as usize,
+            failure_policy: args.bulk.into(),
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            sleeper: Box::new(TokioClock::new()),
+            mongodb_client: client_result
+                .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
+        })
+    }
+}
+
+/// Result of creating the search index on a single collection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CollectionCreateResult {
+    pub collection_name: String,
+    #[serde(flatten)]
+    pub result: CreateResult,
+    /// Status of the created index when it was watched, `None` without `--watch` or when the index was not created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_status: Option<WatchedIndexStatus>,
+}
+
+/// Status of a created index when watching stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchedIndexStatus {
+    Ready,
+    /// Only with `--watchAny`, when another index was ready first.
+    Building,
+}
+
+/// Result of the create all collections command.
+///
+/// With `--output json` the result is an object tagged by `outcome`:
+/// - `{"outcome":"completed","results":[{"collection_name":"<name>","outcome":"created",...}]}`
+///
+/// With `--watch` every created index has an `index_status`, which is `ready` or, with `--watchAny`, `building`.
+/// - `{"outcome":"failed","error":"<message>"}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum CreateAllCollectionsResult {
+    Completed {
+        results: Vec<CollectionCreateResult>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl BulkItemResult for CollectionCreateResult {
+    fn is_failure(&self) -> bool {
+        matches!(self.result, CreateResult::Failed { .. })
+    }
+}
+
+impl BulkOutput for CreateAllCollectionsResult {
+    fn has_failures(&self) -> bool {
+        match self {
+            Self::Completed { results } => results.iter().any(BulkItemResult::is_failure),
+            Self::Failed { .. } => true,
+        }
+    }
+}
+
+impl Display for CreateAllCollectionsResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Completed { results } if results.is_empty() => {
+                write!(f, "No collections to create search indexes on")
+            }
+            Self::Completed { results } => {
+                for (i, result) in results.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}: {}", result.collection_name, result.result)?;
+                    match result.index_status {
+                        Some(WatchedIndexStatus::Ready) => write!(f, " (ready)")?,
+                        Some(WatchedIndexStatus::Building) => write!(f, " (still building)")?,
+                        None => {}
+                    }
+                }
+                Ok(())
+            }
+            Self::Failed { error } => write!(f, "Creating indexes failed: {}", error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for CreateAllCollections {
+    type Output = CreateAllCollectionsResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        // Get the mongodb client, if it is not available, return a failed result.
+        let mongodb_client = match &self.mongodb_client {
+            Ok(client) => client,
+            Err(e) => {
+                return Ok(CreateAllCollectionsResult::Failed {
+                    error: e.to_string(),
+                });
+            }
+        };
+
+        let collection_names = match mongodb_client
+            .list_collection_names(self.database_name.clone())
+            .await
+        {
+            Ok(collection_names) => collection_names,
+            Err(e) => {
+                return Ok(CreateAllCollectionsResult::Failed {
+                    error: format!("failed to list collections: {e}"),
+                });
+            }
+        };
+
+        let collection_names = collection_names
+            .into_iter()
+            .filter(|collection_name| {
+                let excluded = self.exclude.contains(collection_name);
+                if excluded {
+                    debug!(collection_name, "collection excluded, skipping");
+                }
+                !excluded
+            })
+            .collect::<Vec<_>>();
+
+        // Create up to `concurrency` indexes at the same time, the results are in the order of the collections
+        // By default failures are results so they don't cancel other creations, with --failFast the creation stops at the first failure
+        let this = &*self;
+        let mut results = run_bulk(
+            collection_names,
+            self.failure_policy,
+            self.concurrency,
+            |collection_name| async move {
+                let result = this
+                    .create_search_index(mongodb_client.as_ref(), &collection_name)
+                    .await;
+                CollectionCreateResult {
+                    collection_name,
+                    result,
+                    index_status: None,
+                }
+            },
+        )
+        .await;
+
+        if self.watch {
+            debug!("watching enabled, watching search indexes");
+
+            let _watch_spinner_handle = self
+                .interaction
+                .start_spinner(message(Message::BuildSearchIndexes).to_string())?;
+
+            match self.watch_completion {
+                WatchCompletion::All => self.watch_all(mongodb_client.as_ref(), &mut results).await,
+                WatchCompletion::Any => self.watch_any(mongodb_client.as_ref(), &mut results).await,
+            }
+        }
+
+        Ok(CreateAllCollectionsResult::Completed { results })
+    }
+}
+
+impl CreateAllCollections {
+    /// Wait for every created index to be ready, an index that fails doesn't stop watching the others.
+    async fn watch_all(
+        &self,
+        mongodb_client: &(dyn MongoDbClient + Send + Sync),
+        results: &mut [CollectionCreateResult],
+    ) {
+        for collection_result in results {
+            let CreateResult::Created {
+                search_index_id, ..
+            } = &collection_result.result
+            else {
+                continue;
+            };
+
+            match wait_for_search_index_ready(
+                mongodb_client,
+                &self.database_name,
+                &collection_result.collection_name,
+                search_index_id,
+                self.sleeper.as_ref(),
+                self.watch_schedule,
+            )
+            .await
+            {
+                Ok(()) => collection_result.index_status = Some(WatchedIndexStatus::Ready),
+                Err(e) => {
+                    collection_result.result = CreateResult::Failed {
+                        error: e.to_string(),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wait for any created index to be ready, the other indexes are left building.
+    ///
+    /// Every check polls all indexes that are still building, so all indexes that became ready in the same check are reported ready.
+    async fn watch_any(
+        &self,
+        mongodb_client: &(dyn MongoDbClient + Send + Sync),
+        results: &mut [CollectionCreateResult],
+    ) {
+        for collection_result in results.iter_mut() {
+            if matches!(collection_result.result, CreateResult::Created { .. }) {
+                collection_result.index_status = Some(WatchedIndexStatus::Building);
+            }
+        }
+
+        let mut checks = 0;
+        loop {
+            checks += 1;
+            for collection_result in results.iter_mut() {
+                let CreateResult::Created {
+                    search_index_id, ..
+                } = &collection_result.result
+                else {
+                    continue;
+                };
+                if collection_result.index_status != Some(WatchedIndexStatus::Building) {
+                    continue;
+                }
+
+                match is_search_index_ready(
+                    mongodb_client,
+                    &self.database_name,
+                    &collection_result.collection_name,
+                    search_index_id,
+                )
+                .await
+                {
+                    Ok(true) => collection_result.index_status = Some(WatchedIndexStatus::Ready),
+                    Ok(false) => {}
+                    Err(e) => {
+                        collection_result.result = CreateResult::Failed {
+                            error: e.to_string(),
+                        };
+                        collection_result.index_status = None;
+                    }
+                }
+            }
+
+            // Stop when an index is ready, or when no index is left building because they all failed
+            let has_status = |status| results.iter().any(|r| r.index_status == Some(status));
+            if has_status(WatchedIndexStatus::Ready) || !has_status(WatchedIndexStatus::Building) {
+                debug!(checks, "stopping watch");
+                return;
+            }
+
+            self.sleeper
+                .sleep(self.watch_schedule.interval(checks))
+                .await;
+        }
+    }
+
+    /// Create the default search index on the collection, unless an index with the same name already exists.
+    async fn create_search_index(
+        &self,
+        mongodb_client: &(dyn MongoDbClient + Send + Sync),
+        collection_name: &str,
+    ) -> CreateResult {
+        match mongodb_client
+            .get_search_index_definition(
+                self.database_name.clone(),
+                collection_name.to_string(),
+                self.index_name.clone(),
+            )
+            .await
+        {
+            Ok(Some(_)) => {
+                return CreateResult::Skipped {
+                    warning: format!(
+                        "search index '{}' already exists, skipping collection",
+                        self.index_name
+                    ),
+                };
+            }
+            Ok(None) => {}
+            Err(e) => {
+                return CreateResult::Failed {
+                    error: format!("failed to get existing search index definition: {e}"),
+                };
+            }
+        }
+
+        let model = CreateSearchIndexModel {
+            database_name: self.database_name.clone(),
+            collection_name: collection_name.to_string(),
+            definition: default_search_index_definition(),
+            name: Some(self.index_name.clone()),
+            index_type: Some(SearchIndexType::Search),
+        };
+
+        match mongodb_client.create_search_index(model).await {
+            Ok(search_index_id) => {
+                debug!(collection_name, search_index_id, "search index created");
+                CreateResult::Created {
+                    search_index_id,
+                    unchanged: false,
+                }
+            }
+            Err(e) => CreateResult::Failed {
+                error: format!("failed to create search index: {e}"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::mocks::MockMongoDB;
+    use crate::dependencies::{MongoDbSearchIndexStatus, SearchIndexWithDefinition};
+    use crate::interaction::SpinnerHandle;
+    use crate::interaction::mocks::MockInteraction;
+    use mongodb::bson::Document;
+    use std::time::Duration;
+
+    fn create_command(
+        watch: bool,
+        exclude: Vec<String>,
+        interaction: MockInteraction,
+        mongodb_client: Result<MockMongoDB, TryToGetMongodbClientError>,
+    ) -> CreateAllCollections {
+        CreateAllCollections {
+            watch_schedule: WatchSchedule::fixed(Duration::from_millis(10)),
+            watch,
+            watch_completion: WatchCompletion::All,
+            index_name: DEFAULT_INDEX_NAME.to_string(),
+            database_name: "test_db".to_string(),
+            exclude,
+            concurrency: 1,
+            failure_policy: FailurePolicy::KeepGoing,
+            interaction: Box::new(interaction),
+            sleeper: Box::new(TokioClock::new()),
+            mongodb_client: mongodb_client
+                .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
+        }
+    }
+
+    fn expect_collections(mock_mongodb: &mut MockMongoDB, collections: &[&str]) {
+        let collections = collections.iter().map(|c| c.to_string()).collect();
+        mock_mongodb
+            .expect_list_collection_names()
+            .withf(|database_name| database_name == "test_db")
+            .return_once(move |_| Ok(collections));
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections() {
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies", "users"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .times(2)
+            .returning(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .times(2)
+            .withf(|model| {
+                model.database_name == "test_db"
+                    && model.name == Some("default".to_string())
+                    && model.definition == default_search_index_definition()
+            })
+            .returning(|model| Ok(format!("{}-index", model.collection_name)));
+
+        let mut cmd = create_command(false, vec![], MockInteraction::new(), Ok(mock_mongodb));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateAllCollectionsResult::Completed {
+                results: vec![
+                    CollectionCreateResult {
+                        collection_name: "movies".to_string(),
+                        result: CreateResult::Created {
+                            search_index_id: "movies-index".to_string(),
+                            unchanged: false,
+                        },
+                        index_status: None,
+                    },
+                    CollectionCreateResult {
+                        collection_name: "users".to_string(),
+                        result: CreateResult::Created {
+                            search_index_id: "users-index".to_string(),
+                            unchanged: false,
+                        },
+                        index_status: None,
+                    },
+                ],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_excludes_collections() {
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies", "users", "logs"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .times(1)
+            .withf(|_, collection_name, _| collection_name == "movies")
+            .returning(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .times(1)
+            .returning(|_| Ok("movies-index".to_string()));
+
+        let mut cmd = create_command(
+            false,
+            vec!["users".to_string(), "logs".to_string()],
+            MockInteraction::new(),
+            Ok(mock_mongodb),
+        );
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        let CreateAllCollectionsResult::Completed { results } = result else {
+            panic!("expected completed result, got {result:?}");
+        };
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].collection_name, "movies");
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_skips_existing_index() {
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .return_once(|_, _, _| {
+                Ok(Some(SearchIndexWithDefinition {
+                    index_id: "existing-index".to_string(),
+                    name: "default".to_string(),
+                    index_type: None,
+                    definition: Document::new(),
+                }))
+            });
+        mock_mongodb.expect_create_search_index().never();
+
+        let mut cmd = create_command(false, vec![], MockInteraction::new(), Ok(mock_mongodb));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateAllCollectionsResult::Completed {
+                results: vec![CollectionCreateResult {
+                    collection_name: "movies".to_string(),
+                    result: CreateResult::Skipped {
+                        warning: "search index 'default' already exists, skipping collection"
+                            .to_string(),
+                    },
+                    index_status: None,
+                }],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_create_error_continues() {
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies", "users"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .returning(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .times(2)
+            .returning(|model| {
+                if model.collection_name == "movies" {
+                    Err(anyhow::anyhow!("connection refused"))
+                } else {
+                    Ok("users-index".to_string())
+                }
+            });
+
+        let mut cmd = create_command(false, vec![], MockInteraction::new(), Ok(mock_mongodb));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        let CreateAllCollectionsResult::Completed { results } = result else {
+            panic!("expected completed result, got {result:?}");
+        };
+        assert_eq!(
+            results[0].result,
+            CreateResult::Failed {
+                error: "failed to create search index: connection refused".to_string(),
+            }
+        );
+        assert!(matches!(results[1].result, CreateResult::Created { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_concurrently_keeps_order() {
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies", "users", "logs", "comments"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .times(4)
+            .returning(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .times(4)
+            .returning(|model| {
+                if model.collection_name == "users" {
+                    Err(anyhow::anyhow!("connection refused"))
+                } else {
+                    Ok(format!("{}-index", model.collection_name))
+                }
+            });
+
+        let mut cmd = create_command(false, vec![], MockInteraction::new(), Ok(mock_mongodb));
+        cmd.concurrency = 3;
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        let CreateAllCollectionsResult::Completed { results } = result else {
+            panic!("expected completed result, got {result:?}");
+        };
+        assert_eq!(
+            results
+                .iter()
+                .map(|r| r.collection_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["movies", "users", "logs", "comments"]
+        );
+        assert!(matches!(results[1].result, CreateResult::Failed { .. }));
+        assert_eq!(
+            results
+                .iter()
+                .filter(|r| matches!(r.result, CreateResult::Created { .. }))
+                .count(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_with_watch() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .return_once(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .return_once(|_| Ok("movies-index".to_string()));
+        let mut statuses = vec![
+            MongoDbSearchIndexStatus::Ready,
+            MongoDbSearchIndexStatus::Building,
+        ];
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(2)
+            .withf(|database_name, collection_name, index_id| {
+                database_name == "test_db"
+                    && collection_name == "movies"
+                    && index_id == "movies-index"
+            })
+            .returning(move |_, _, _| Ok(statuses.pop()));
+
+        let mut cmd = create_command(true, vec![], mock_interaction, Ok(mock_mongodb));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        let CreateAllCollectionsResult::Completed { results } = result else {
+            panic!("expected completed result, got {result:?}");
+        };
+        assert!(matches!(results[0].result, CreateResult::Created { .. }));
+        assert_eq!(results[0].index_status, Some(WatchedIndexStatus::Ready));
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_with_watch_any() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_mongodb = MockMongoDB::new();
+        expect_collections(&mut mock_mongodb, &["movies", "users"]);
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .returning(|_, _, _| Ok(None));
+        mock_mongodb
+            .expect_create_search_index()
+            .returning(|model| Ok(format!("{}-index", model.collection_name)));
+        // users becomes ready on the second check, movies keeps building
+        let mut users_statuses = vec![
+            MongoDbSearchIndexStatus::Ready,
+            MongoDbSearchIndexStatus::Building,
+        ];
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(4)
+            .returning(move |_, collection_name, _| {
+                if collection_name == "users" {
+                    Ok(users_statuses.pop())
+                } else {
+                    Ok(Some(MongoDbSearchIndexStatus::Building))
+                }
+            });
+
+        let mut cmd = create_command(true, vec![], mock_interaction, Ok(mock_mongodb));
+        cmd.watch_completion = WatchCompletion::Any;
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        let CreateAllCollectionsResult::Completed { results } = &result else {
+            panic!("expected completed result, got {result:?}");
+        };
+        assert_eq!(results[0].index_status, Some(WatchedIndexStatus::Building));
+        assert_eq!(results[1].index_status, Some(WatchedIndexStatus::Ready));
+        assert_eq!(
+            result.to_string(),
+            concat!(
+                "movies: Search index created with ID: movies-index (still building)\n",
+                "users: Search index created with ID: users-index (ready)",
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_all_collections_list_error() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_list_collection_names()
+            .return_once(|_| Err(anyhow::anyhow!("not authorized")));
+        mock_mongodb.expect_create_search_index().never();
+
+        let mut cmd = create_command(false, vec![], MockInteraction::new(), Ok(mock_mongodb));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateAllCollectionsResult::Failed {
+                error: "failed to list collections: not authorized".to_string(),
+            }
+        );
+    }
+}
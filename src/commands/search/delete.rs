@@ -5,18 +5,22 @@
 
 use std::fmt::Display;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use mongodb::Client;
 use serde::Serialize;
+use tracing::debug;
 
 use crate::{
     args,
     commands::{
-        CommandWithOutput,
+        CancelReason, CommandWithOutput,
+        search::watcher::WATCH_INTERVAL,
         with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
     },
-    dependencies::SearchIndexDeleter,
+    dependencies::{
+        MongoDbSearchIndexStatus, SearchIndexDeleter, SearchIndexStatusGetter, Sleeper, TokioClock,
+    },
     interaction::{
         ConfirmationPrompt, ConfirmationPromptOptions, ConfirmationPromptResult, InputPrompt,
         Interaction, SpinnerInteraction,
@@ -34,8 +38,8 @@ impl<T: ConfirmationPrompt + InputPrompt + SpinnerInteraction + Send + Sync> Del
 }
 
 // MongoDB dependencies for the delete command.
-pub trait MongoDbClient: SearchIndexDeleter + Send + Sync {}
-impl<T: SearchIndexDeleter + Send + Sync> MongoDbClient for T {}
+pub trait MongoDbClient: SearchIndexDeleter + SearchIndexStatusGetter + Send + Sync {}
+impl<T: SearchIndexDeleter + SearchIndexStatusGetter + Send + Sync> MongoDbClient for T {}
 
 /// Command to delete a search index from a local deployment.
 pub struct Delete {
@@ -43,9 +47,11 @@ pub struct Delete {
     database_name: Option<String>,
     collection: Option<String>,
     force: bool,
+    watch: bool,
 
     interaction: Box<dyn DeleteInteraction>,
     mongodb_client: Result<Box<dyn MongoDbClient + Send + Sync>, TryToGetMongodbClientError>,
+    sleeper: Box<dyn Sleeper + Send + Sync>,
 }
 
 impl TryFromWithMongodbClient<args::search::Delete> for Delete {
@@ -58,10 +64,12 @@ impl TryFromWithMongodbClient<args::search::Delete> for Delete {
             database_name: args.database_name,
             collection: args.collection,
             force: args.force,
+            watch: args.watch,
 
-            interaction: Box::new(Interaction::new()),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
             mongodb_client: client_result
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
+            sleeper: Box::new(TokioClock::new()),
         })
     }
 }
@@ -72,7 +80,7 @@ impl TryFromWithMongodbClient<args::search::Delete> for Delete {
 pub enum DeleteResult {
     Deleted { index_name: String },
     Failed { error: String },
-    Canceled,
+    Canceled { reason: CancelReason },
 }
 
 impl Display for DeleteResult {
@@ -80,7 +88,7 @@ impl Display for DeleteResult {
         match self {
             Self::Deleted { index_name } => write!(f, "Index '{}' deleted", index_name),
             Self::Failed { error } => write!(f, "Index not deleted: {}", error),
-            Self::Canceled => write!(f, "Index not deleted"),
+            Self::Canceled { .. } => write!(f, "Index not deleted"),
         }
     }
 }
@@ -148,7 +156,9 @@ impl CommandWithOutput for Delete {
                 confirmation,
                 ConfirmationPromptResult::No | ConfirmationPromptResult::Canceled
             ) {
-                return Ok(DeleteResult::Canceled);
+                return Ok(DeleteResult::Canceled {
+                    reason: CancelReason::Prompt,
+                });
             }
         }
 
@@ -169,7 +179,11 @@ impl CommandWithOutput for Delete {
 
         // Delete the index by name.
         if let Err(e) = mongodb_client
-            .delete_search_index(database_name, collection_name, index_name.clone())
+            .delete_search_index(
+                database_name.clone(),
+                collection_name.clone(),
+                index_name.clone(),
+            )
             .await
         {
             return Ok(DeleteResult::Failed {
@@ -177,25 +191,81 @@ impl CommandWithOutput for Delete {
             });
         }
 
+        if self.watch {
+            debug!("watching enabled, waiting until the search index no longer exists");
+
+            if let Err(e) = wait_for_search_index_deleted(
+                mongodb_client.as_ref(),
+                &database_name,
+                &collection_name,
+                &index_name,
+                self.sleeper.as_ref(),
+            )
+            .await
+            {
+                return Ok(DeleteResult::Failed {
+                    error: e.to_string(),
+                });
+            }
+        }
+
         Ok(DeleteResult::Deleted { index_name })
     }
 }
 
+/// Poll the status of the search index until it no longer exists.
+///
+/// Returns an error when the status can't be retrieved or when the index will not be deleted.
+async fn wait_for_search_index_deleted<C>(
+    mongodb_client: &C,
+    database_name: &str,
+    collection_name: &str,
+    index_name: &str,
+    sleeper: &(dyn Sleeper + Send + Sync),
+) -> Result<()>
+where
+    C: SearchIndexStatusGetter + Send + Sync + ?Sized,
+{
+    loop {
+        match mongodb_client
+            .get_search_index_status(
+                database_name.to_string(),
+                collection_name.to_string(),
+                index_name.to_string(),
+            )
+            .await
+        {
+            Err(e) => {
+                return Err(anyhow!(
+                    "failed to get search index status while watching the search index: {e}"
+                ));
+            }
+            Ok(None | Some(MongoDbSearchIndexStatus::DoesNotExist)) => return Ok(()),
+            Ok(Some(MongoDbSearchIndexStatus::Failed)) => {
+                return Err(anyhow!(
+                    "the search index is not deleted, its status is {}",
+                    MongoDbSearchIndexStatus::Failed
+                ));
+            }
+            Ok(Some(status)) => debug!(%status, "search index is not deleted yet"),
+        }
+
+        sleeper.sleep(WATCH_INTERVAL).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::mocks::MockMongoDB;
+    use crate::commands::test_support::create_spinner_handle;
+    use crate::dependencies::mocks::{MockMongoDB, MockTokioClock};
+    use crate::interaction::InputPromptResult;
     use crate::interaction::mocks::MockInteraction;
-    use crate::interaction::{InputPromptResult, SpinnerHandle};
 
     // ============================================================================
     // Test Helpers
     // ============================================================================
 
-    fn create_spinner_handle() -> SpinnerHandle {
-        SpinnerHandle::new(Box::new(|| {}))
-    }
-
     fn create_command(
         index_name: Option<String>,
         database_name: Option<String>,
@@ -209,9 +279,11 @@ mod tests {
             database_name,
             collection,
             force,
+            watch: false,
             interaction: Box::new(interaction),
             mongodb_client: mongodb_client
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
+            sleeper: Box::new(TokioClock::new()),
         }
     }
 
@@ -348,7 +420,12 @@ mod tests {
 
         let result = cmd.execute().await.expect("execute should succeed");
 
-        assert_eq!(result, DeleteResult::Canceled);
+        assert_eq!(
+            result,
+            DeleteResult::Canceled {
+                reason: CancelReason::Prompt
+            }
+        );
     }
 
     #[tokio::test]
@@ -371,7 +448,12 @@ mod tests {
 
         let result = cmd.execute().await.expect("execute should succeed");
 
-        assert_eq!(result, DeleteResult::Canceled);
+        assert_eq!(
+            result,
+            DeleteResult::Canceled {
+                reason: CancelReason::Prompt
+            }
+        );
     }
 
     #[tokio::test]
@@ -522,6 +604,87 @@ mod tests {
         }
     }
 
+    // ============================================================================
+    // Watch Tests
+    // ============================================================================
+
+    fn create_watch_command(mock_mongodb: MockMongoDB) -> Delete {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_clock = MockTokioClock::new();
+        mock_clock
+            .expect_sleep()
+            .withf(|duration| *duration == WATCH_INTERVAL)
+            .returning(|_| ());
+
+        let mut cmd = create_command(
+            Some("my_index".to_string()),
+            Some("test_db".to_string()),
+            Some("test_collection".to_string()),
+            true,
+            mock_interaction,
+            Ok(mock_mongodb),
+        );
+        cmd.watch = true;
+        cmd.sleeper = Box::new(mock_clock);
+        cmd
+    }
+
+    #[tokio::test]
+    async fn test_delete_watch_until_index_no_longer_exists() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_delete_search_index()
+            .return_once(|_, _, _| Ok(()));
+
+        let mut statuses = vec![None, Some(MongoDbSearchIndexStatus::Deleting)];
+        mock_mongodb
+            .expect_get_search_index_status()
+            .withf(|db, col, name| {
+                db == "test_db" && col == "test_collection" && name == "my_index"
+            })
+            .times(2)
+            .returning(move |_, _, _| Ok(statuses.pop().unwrap()));
+
+        let result = create_watch_command(mock_mongodb)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Deleted {
+                index_name: "my_index".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_watch_index_fails() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_delete_search_index()
+            .return_once(|_, _, _| Ok(()));
+        mock_mongodb
+            .expect_get_search_index_status()
+            .return_once(|_, _, _| Ok(Some(MongoDbSearchIndexStatus::Failed)));
+
+        let result = create_watch_command(mock_mongodb)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Failed {
+                error: "the search index is not deleted, its status is failed".to_string()
+            }
+        );
+    }
+
     // ============================================================================
     // Display Tests
     // ============================================================================
@@ -548,8 +711,21 @@ mod tests {
 
     #[test]
     fn test_delete_result_display_canceled() {
-        let result = DeleteResult::Canceled;
+        let result = DeleteResult::Canceled {
+            reason: CancelReason::Prompt,
+        };
         let output = format!("{}", result);
         assert!(output.contains("not deleted"));
     }
+
+    #[test]
+    fn test_delete_result_json_canceled() {
+        let result = DeleteResult::Canceled {
+            reason: CancelReason::Prompt,
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["outcome"], "canceled");
+        assert_eq!(json["reason"], "prompt");
+    }
 }
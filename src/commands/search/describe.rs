@@ -2,12 +2,13 @@
 //!
 //! This module implements the `search indexes describe` command which retrieves and displays
 //! details about a specific Atlas Search index by its ID in a local deployment.
+//! The latest definition of the index is shown as well, e.g. to verify the mappings of an index created from a file.
 
 use std::fmt::Display;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use mongodb::Client;
+use mongodb::{Client, bson};
 use serde::Serialize;
 
 use crate::{
@@ -16,7 +17,7 @@ use crate::{
         CommandWithOutput,
         with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
     },
-    dependencies::{SearchIndex, SearchIndexDescriber},
+    dependencies::{SearchIndex, SearchIndexDefinitionGetter, SearchIndexDescriber},
     interaction::{InputPrompt, Interaction},
     table::Table,
 };
@@ -29,8 +30,8 @@ pub trait DescribeInteraction: InputPrompt + Send + Sync {}
 impl<T: InputPrompt + Send + Sync> DescribeInteraction for T {}
 
 // MongoDB dependencies for the describe command.
-pub trait MongoDbClient: SearchIndexDescriber + Send + Sync {}
-impl<T: SearchIndexDescriber + Send + Sync> MongoDbClient for T {}
+pub trait MongoDbClient: SearchIndexDescriber + SearchIndexDefinitionGetter + Send + Sync {}
+impl<T: SearchIndexDescriber + SearchIndexDefinitionGetter + Send + Sync> MongoDbClient for T {}
 
 /// Command to describe a search index for a local deployment.
 pub struct Describe {
@@ -48,7 +49,7 @@ impl TryFromWithMongodbClient<args::search::Describe> for Describe {
         Ok(Self {
             index_id: args.index_id,
 
-            interaction: Box::new(Interaction::new()),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
             mongodb_client: client_result
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
         })
@@ -59,8 +60,15 @@ impl TryFromWithMongodbClient<args::search::Describe> for Describe {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(tag = "outcome", rename_all = "snake_case")]
 pub enum DescribeResult {
-    Success { index: SearchIndex },
-    Failed { error: String },
+    Success {
+        index: SearchIndex,
+        // The latest definition as JSON, None when the index was deleted while describing it
+        #[serde(skip_serializing_if = "Option::is_none")]
+        definition: Option<serde_json::Value>,
+    },
+    Failed {
+        error: String,
+    },
 }
 
 /// Convert the describe result into a table for display.
@@ -70,7 +78,7 @@ pub enum DescribeResult {
 impl From<&DescribeResult> for Table {
     fn from(value: &DescribeResult) -> Self {
         match value {
-            DescribeResult::Success { index } => Table::from_iter(
+            DescribeResult::Success { index, .. } => Table::from_iter(
                 std::iter::once(index),
                 &[
                     ("ID", |i: &SearchIndex| i.index_id.clone()),
@@ -96,7 +104,15 @@ impl From<&DescribeResult> for Table {
 impl Display for DescribeResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            DescribeResult::Success { .. } => Table::from(self).fmt(f),
+            DescribeResult::Success { definition, .. } => {
+                Table::from(self).fmt(f)?;
+                if let Some(definition) = definition {
+                    let pretty =
+                        serde_json::to_string_pretty(definition).map_err(|_| std::fmt::Error)?;
+                    write!(f, "\nDefinition:\n{pretty}")?;
+                }
+                Ok(())
+            }
             DescribeResult::Failed { error } => write!(f, "Describing index failed: {}", error),
         }
     }
@@ -131,26 +147,52 @@ impl CommandWithOutput for Describe {
         };
 
         // Describe the search index.
-        match mongodb_client.describe_search_index(index_id.clone()).await {
-            Ok(Some(index)) => Ok(DescribeResult::Success { index }),
-            Ok(None) => Ok(DescribeResult::Failed {
-                error: format!("search index with ID '{}' not found", index_id),
-            }),
-            Err(e) => Ok(DescribeResult::Failed {
-                error: format!("failed to describe search index: {e}"),
+        let index = match mongodb_client.describe_search_index(index_id.clone()).await {
+            Ok(Some(index)) => index,
+            Ok(None) => {
+                return Ok(DescribeResult::Failed {
+                    error: format!("search index with ID '{}' not found", index_id),
+                });
+            }
+            Err(e) => {
+                return Ok(DescribeResult::Failed {
+                    error: format!("failed to describe search index: {e}"),
+                });
+            }
+        };
+
+        // The definition is only available by name, within the collection of the index
+        let definition = match mongodb_client
+            .get_search_index_definition(
+                index.database.clone(),
+                index.collection_name.clone(),
+                index.name.clone(),
+            )
+            .await
+        {
+            Ok(definition) => definition.map(|definition| {
+                bson::Bson::Document(definition.definition).into_relaxed_extjson()
             }),
-        }
+            Err(e) => {
+                return Ok(DescribeResult::Failed {
+                    error: format!("failed to get search index definition: {e}"),
+                });
+            }
+        };
+
+        Ok(DescribeResult::Success { index, definition })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::MongoDbSearchIndexStatus;
     use crate::dependencies::mocks::MockMongoDB;
+    use crate::dependencies::{MongoDbSearchIndexStatus, SearchIndexWithDefinition};
     use crate::interaction::InputPromptResult;
     use crate::interaction::mocks::MockInteraction;
     use anyhow::anyhow;
+    use mongodb::bson::doc;
 
     // ============================================================================
     // Test Helpers
@@ -193,6 +235,19 @@ mod tests {
             .expect_describe_search_index()
             .withf(|id| id == "idx-123")
             .return_once(|_| Ok(Some(create_sample_search_index("idx-123", "my_index"))));
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .withf(|db, col, name| {
+                db == "test_db" && col == "test_collection" && name == "my_index"
+            })
+            .return_once(|_, _, name| {
+                Ok(Some(SearchIndexWithDefinition {
+                    index_id: "idx-123".to_string(),
+                    name,
+                    index_type: Some("search".to_string()),
+                    definition: doc! { "mappings": { "dynamic": true } },
+                }))
+            });
 
         let mut cmd = create_command(
             Some("idx-123".to_string()),
@@ -203,9 +258,13 @@ mod tests {
         let result = cmd.execute().await.expect("execute should succeed");
 
         match result {
-            DescribeResult::Success { index } => {
+            DescribeResult::Success { index, definition } => {
                 assert_eq!(index.index_id, "idx-123");
                 assert_eq!(index.name, "my_index");
+                assert_eq!(
+                    definition,
+                    Some(serde_json::json!({ "mappings": { "dynamic": true } }))
+                );
             }
             DescribeResult::Failed { error } => panic!("Expected success, got error: {}", error),
         }
@@ -227,13 +286,16 @@ mod tests {
             .expect_describe_search_index()
             .withf(|id| id == "prompted-id")
             .return_once(|_| Ok(Some(create_sample_search_index("prompted-id", "my_index"))));
+        mock_mongodb
+            .expect_get_search_index_definition()
+            .return_once(|_, _, _| Ok(None));
 
         let mut cmd = create_command(None, mock_interaction, Ok(mock_mongodb));
 
         let result = cmd.execute().await.expect("execute should succeed");
 
         match result {
-            DescribeResult::Success { index } => {
+            DescribeResult::Success { index, .. } => {
                 assert_eq!(index.index_id, "prompted-id");
             }
             DescribeResult::Failed { error } => panic!("Expected success, got error: {}", error),
@@ -346,6 +408,7 @@ mod tests {
     fn test_describe_result_display_success() {
         let result = DescribeResult::Success {
             index: create_sample_search_index("idx-1", "my_index"),
+            definition: Some(serde_json::json!({ "mappings": { "dynamic": true } })),
         };
         let output = format!("{}", result);
         assert!(output.contains("idx-1"));
@@ -354,6 +417,7 @@ mod tests {
         assert!(output.contains("test_collection"));
         assert!(output.contains("READY"));
         assert!(output.contains("search"));
+        assert!(output.contains("Definition:\n{\n  \"mappings\": {\n    \"dynamic\": true"));
     }
 
     #[test]
@@ -371,7 +435,10 @@ mod tests {
         let mut index = create_sample_search_index("idx-1", "my_index");
         index.index_type = None;
 
-        let result = DescribeResult::Success { index };
+        let result = DescribeResult::Success {
+            index,
+            definition: None,
+        };
         let output = format!("{}", result);
         // Should show default type "search"
         assert!(output.contains("search"));
@@ -1,7 +1,9 @@
+use anyhow::{Result, anyhow};
+use mongodb::bson;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::dependencies::SearchIndexType;
+use crate::dependencies::{CreateSearchIndexModel, SearchIndexType};
 
 /// Request to create an Atlas Search index.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -20,3 +22,31 @@ pub struct SearchIndexCreateRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub definition: Option<Value>,
 }
+
+impl SearchIndexCreateRequest {
+    /// Parse the contents of a JSON index configuration file.
+    pub fn parse(contents: &str) -> Result<Self> {
+        serde_json::from_str(contents)
+            .map_err(|e| anyhow!("failed to parse file as search index create request: {e}"))
+    }
+
+    /// The index definition as a BSON document, empty when the file has no definition.
+    pub fn definition_document(&self) -> bson::Document {
+        self.definition
+            .as_ref()
+            .and_then(|value| bson::to_document(value).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl From<SearchIndexCreateRequest> for CreateSearchIndexModel {
+    fn from(request: SearchIndexCreateRequest) -> Self {
+        Self {
+            definition: request.definition_document(),
+            database_name: request.database,
+            collection_name: request.collection_name,
+            name: Some(request.name),
+            index_type: request.index_type,
+        }
+    }
+}
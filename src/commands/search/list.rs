@@ -2,11 +2,14 @@
 //!
 //! This module implements the `search indexes list` command which retrieves and displays
 //! all Atlas Search indexes for a specified database and collection in a local deployment.
+//! With `--status` or `--notReady`, the current status of every index is queried and only the matching indexes are
+//! listed.
 
 use std::fmt::Display;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt, stream};
 use mongodb::Client;
 use serde::Serialize;
 
@@ -16,7 +19,9 @@ use crate::{
         CommandWithOutput,
         with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
     },
-    dependencies::{SearchIndex, SearchIndexLister},
+    dependencies::{
+        MongoDbSearchIndexStatus, SearchIndex, SearchIndexLister, SearchIndexStatusGetter,
+    },
     interaction::{InputPrompt, Interaction},
     table::Table,
 };
@@ -24,18 +29,62 @@ use crate::{
 /// Default search index type when none is specified.
 const DEFAULT_INDEX_TYPE: &str = "search";
 
+/// Maximum number of search index statuses to query at the same time when filtering on the status.
+const STATUS_CONCURRENCY: usize = 8;
+
 // Interaction dependencies for the list command.
 pub trait ListInteraction: InputPrompt + Send + Sync {}
 impl<T: InputPrompt + Send + Sync> ListInteraction for T {}
 
 // MongoDB dependencies for the list command.
-pub trait MongoDbClient: SearchIndexLister + Send + Sync {}
-impl<T: SearchIndexLister + Send + Sync> MongoDbClient for T {}
+pub trait MongoDbClient: SearchIndexLister + SearchIndexStatusGetter + Send + Sync {}
+impl<T: SearchIndexLister + SearchIndexStatusGetter + Send + Sync> MongoDbClient for T {}
+
+/// Filter on the status of the listed search indexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFilter {
+    /// Only indexes with this status.
+    Status(MongoDbSearchIndexStatus),
+    /// Only indexes that are not ready.
+    NotReady,
+}
+
+impl StatusFilter {
+    fn from_args(status: Option<args::search::IndexStatus>, not_ready: bool) -> Option<Self> {
+        match (status, not_ready) {
+            (Some(status), _) => Some(Self::Status(status.into())),
+            (None, true) => Some(Self::NotReady),
+            (None, false) => None,
+        }
+    }
+
+    fn matches(&self, status: MongoDbSearchIndexStatus) -> bool {
+        match self {
+            Self::Status(expected) => status == *expected,
+            Self::NotReady => status != MongoDbSearchIndexStatus::Ready,
+        }
+    }
+}
+
+impl From<args::search::IndexStatus> for MongoDbSearchIndexStatus {
+    fn from(status: args::search::IndexStatus) -> Self {
+        match status {
+            args::search::IndexStatus::Building => Self::Building,
+            args::search::IndexStatus::Deleting => Self::Deleting,
+            args::search::IndexStatus::DoesNotExist => Self::DoesNotExist,
+            args::search::IndexStatus::Failed => Self::Failed,
+            args::search::IndexStatus::Pending => Self::Pending,
+            args::search::IndexStatus::Ready => Self::Ready,
+            args::search::IndexStatus::Stale => Self::Stale,
+        }
+    }
+}
 
 /// Command to list all search indexes for a local deployment.
 pub struct List {
     database_name: Option<String>,
     collection: Option<String>,
+    status_filter: Option<StatusFilter>,
 
     interaction: Box<dyn ListInteraction>,
     mongodb_client: Result<Box<dyn MongoDbClient + Send + Sync>, TryToGetMongodbClientError>,
@@ -49,8 +98,9 @@ impl TryFromWithMongodbClient<args::search::List> for List {
         Ok(Self {
             database_name: args.database_name,
             collection: args.collection,
+            status_filter: StatusFilter::from_args(args.status, args.not_ready),
 
-            interaction: Box::new(Interaction::new()),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
             mongodb_client: client_result
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
         })
@@ -149,22 +199,67 @@ impl CommandWithOutput for List {
         };
 
         // List the search indexes.
-        match mongodb_client
+        let indexes = match mongodb_client
             .list_search_indexes(database_name, collection_name)
             .await
         {
-            Ok(indexes) => Ok(ListResult::Success { indexes }),
+            Ok(indexes) => indexes,
+            Err(e) => {
+                return Ok(ListResult::Failed {
+                    error: format!("failed to list search indexes: {e}"),
+                });
+            }
+        };
+
+        let Some(status_filter) = self.status_filter else {
+            return Ok(ListResult::Success { indexes });
+        };
+
+        // Query the current status of the indexes, and only keep the ones that match the filter.
+        match with_current_status(mongodb_client.as_ref(), indexes).await {
+            Ok(indexes) => Ok(ListResult::Success {
+                indexes: indexes
+                    .into_iter()
+                    .filter(|index| status_filter.matches(index.status))
+                    .collect(),
+            }),
             Err(e) => Ok(ListResult::Failed {
-                error: format!("failed to list search indexes: {e}"),
+                error: format!("failed to get search index status: {e:#}"),
             }),
         }
     }
 }
 
+/// Update the status of every index with its current status, up to [`STATUS_CONCURRENCY`] indexes at the same time.
+///
+/// The indexes stay in the order in which they were listed.
+async fn with_current_status(
+    mongodb_client: &(dyn MongoDbClient + Send + Sync),
+    indexes: Vec<SearchIndex>,
+) -> Result<Vec<SearchIndex>> {
+    stream::iter(indexes)
+        .map(|mut index| async move {
+            let status = mongodb_client
+                .get_search_index_status(
+                    index.database.clone(),
+                    index.collection_name.clone(),
+                    index.name.clone(),
+                )
+                .await
+                .with_context(|| format!("getting the status of search index {}", index.name))?;
+
+            // The index can be deleted between listing it and getting its status
+            index.status = status.unwrap_or(MongoDbSearchIndexStatus::DoesNotExist);
+            Ok(index)
+        })
+        .buffered(STATUS_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::MongoDbSearchIndexStatus;
     use crate::dependencies::mocks::MockMongoDB;
     use crate::interaction::InputPromptResult;
     use crate::interaction::mocks::MockInteraction;
@@ -183,6 +278,7 @@ mod tests {
         List {
             database_name,
             collection,
+            status_filter: None,
             interaction: Box::new(interaction),
             mongodb_client: mongodb_client
                 .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
@@ -357,6 +453,107 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_list_filters_on_current_status() {
+        let mock_interaction = MockInteraction::new();
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_list_search_indexes()
+            .return_once(|_, _| {
+                Ok(vec![
+                    create_sample_search_index("idx-1", "index_a"),
+                    create_sample_search_index("idx-2", "index_b"),
+                    create_sample_search_index("idx-3", "index_c"),
+                ])
+            });
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(3)
+            .returning(|db, col, name| {
+                assert_eq!(db, "test_db");
+                assert_eq!(col, "test_collection");
+                Ok(match name.as_str() {
+                    "index_a" => Some(MongoDbSearchIndexStatus::Ready),
+                    "index_b" => Some(MongoDbSearchIndexStatus::Failed),
+                    _ => Some(MongoDbSearchIndexStatus::Building),
+                })
+            });
+
+        let mut cmd = create_command(
+            Some("test_db".to_string()),
+            Some("test_collection".to_string()),
+            mock_interaction,
+            Ok(mock_mongodb),
+        );
+        cmd.status_filter = Some(StatusFilter::Status(MongoDbSearchIndexStatus::Failed));
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        match result {
+            ListResult::Success { indexes } => {
+                assert_eq!(indexes.len(), 1);
+                assert_eq!(indexes[0].name, "index_b");
+                assert_eq!(indexes[0].status, MongoDbSearchIndexStatus::Failed);
+            }
+            ListResult::Failed { error } => panic!("Expected success, got error: {}", error),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_not_ready_keeps_order() {
+        let mock_interaction = MockInteraction::new();
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_list_search_indexes()
+            .return_once(|_, _| {
+                Ok(vec![
+                    create_sample_search_index("idx-1", "index_a"),
+                    create_sample_search_index("idx-2", "index_b"),
+                    create_sample_search_index("idx-3", "index_c"),
+                ])
+            });
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(3)
+            .returning(|_, _, name| {
+                Ok(match name.as_str() {
+                    "index_a" => Some(MongoDbSearchIndexStatus::Pending),
+                    "index_b" => Some(MongoDbSearchIndexStatus::Ready),
+                    // Deleted after it was listed
+                    _ => None,
+                })
+            });
+
+        let mut cmd = create_command(
+            Some("test_db".to_string()),
+            Some("test_collection".to_string()),
+            mock_interaction,
+            Ok(mock_mongodb),
+        );
+        cmd.status_filter = Some(StatusFilter::NotReady);
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        match result {
+            ListResult::Success { indexes } => {
+                let statuses: Vec<_> = indexes
+                    .iter()
+                    .map(|i| (i.name.as_str(), i.status))
+                    .collect();
+                assert_eq!(
+                    statuses,
+                    vec![
+                        ("index_a", MongoDbSearchIndexStatus::Pending),
+                        ("index_c", MongoDbSearchIndexStatus::DoesNotExist),
+                    ]
+                );
+            }
+            ListResult::Failed { error } => panic!("Expected success, got error: {}", error),
+        }
+    }
+
     // ============================================================================
     // Error Path Tests
     // ============================================================================
@@ -410,6 +607,37 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_list_status_query_fails() {
+        let mock_interaction = MockInteraction::new();
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_list_search_indexes()
+            .return_once(|_, _| Ok(vec![create_sample_search_index("idx-1", "my_index")]));
+        mock_mongodb
+            .expect_get_search_index_status()
+            .return_once(|_, _, _| Err(anyhow!("connection lost")));
+
+        let mut cmd = create_command(
+            Some("db".to_string()),
+            Some("col".to_string()),
+            mock_interaction,
+            Ok(mock_mongodb),
+        );
+        cmd.status_filter = Some(StatusFilter::NotReady);
+
+        let result = cmd.execute().await.expect("execute should succeed");
+
+        match result {
+            ListResult::Failed { error } => {
+                assert!(error.contains("getting the status of search index my_index"));
+                assert!(error.contains("connection lost"));
+            }
+            ListResult::Success { .. } => panic!("Expected failure"),
+        }
+    }
+
     #[tokio::test]
     async fn test_list_user_cancels_database_prompt() {
         let mut mock_interaction = MockInteraction::new();
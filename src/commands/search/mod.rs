@@ -1,5 +1,9 @@
 pub mod create;
+pub mod create_all;
 pub mod delete;
 pub mod describe;
 mod file_definition;
 pub mod list;
+pub mod update;
+pub mod watch;
+mod watcher;
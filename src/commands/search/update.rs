@@ -0,0 +1,395 @@
+//! Command to update a search index of a local deployment.
+//!
+//! This module implements the `search indexes update` command which replaces the definition of an
+//! existing search index with the definition of a JSON index configuration file, or of stdin.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use mongodb::Client;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        search::{
+            file_definition::SearchIndexCreateRequest,
+            watcher::{WATCH_INTERVAL, WatchSchedule, wait_for_search_index_ready},
+        },
+        with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
+    },
+    dependencies::{
+        FileReader, SearchIndexStatusGetter, SearchIndexUpdater, Sleeper, StdinReader, TokioClock,
+        TokioFs,
+    },
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+};
+
+/// Value of --file that reads the index configuration from stdin.
+const STDIN_FILE: &str = "-";
+
+// Interaction dependencies for the update command.
+pub trait UpdateInteraction: SpinnerInteraction + Send + Sync {}
+impl<T: SpinnerInteraction + Send + Sync> UpdateInteraction for T {}
+
+// Filesystem dependencies for the update command.
+pub trait UpdateFs: FileReader + StdinReader + Send + Sync {}
+impl<T: FileReader + StdinReader + Send + Sync> UpdateFs for T {}
+
+// MongoDB dependencies for the update command.
+pub trait MongoDbClient: SearchIndexUpdater + SearchIndexStatusGetter + Send + Sync {}
+impl<T: SearchIndexUpdater + SearchIndexStatusGetter + Send + Sync> MongoDbClient for T {}
+
+/// Command to update a search index of a local deployment.
+pub struct Update {
+    // None reads the index configuration from stdin
+    file: Option<PathBuf>,
+    watch: bool,
+
+    interaction: Box<dyn UpdateInteraction>,
+    fs: Box<dyn UpdateFs>,
+    sleeper: Box<dyn Sleeper + Send + Sync>,
+    mongodb_client: Result<Box<dyn MongoDbClient + Send + Sync>, TryToGetMongodbClientError>,
+}
+
+impl TryFromWithMongodbClient<args::search::Update> for Update {
+    fn try_from_with_mongodb(
+        args: args::search::Update,
+        client_result: Result<Client, TryToGetMongodbClientError>,
+    ) -> Result<Self> {
+        Ok(Self {
+            file: args
+                .file
+                .filter(|file| file != STDIN_FILE)
+                .map(PathBuf::from),
+            watch: args.watch,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            fs: Box::new(TokioFs::new()),
+            sleeper: Box::new(TokioClock::new()),
+            mongodb_client: client_result
+                .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
+        })
+    }
+}
+
+/// Result of the update command.
+///
+/// With `--output json` the result is an object tagged by `outcome`:
+/// - `{"outcome":"updated","index_name":"<name>","database":"<db>","collection_name":"<collection>"}`
+/// - `{"outcome":"failed","error":"<message>"}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum UpdateResult {
+    Updated {
+        index_name: String,
+        database: String,
+        collection_name: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl Display for UpdateResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Updated {
+                index_name,
+                database,
+                collection_name,
+            } => write!(
+                f,
+                "Index '{}' updated in {}.{}",
+                index_name, database, collection_name
+            ),
+            Self::Failed { error } => write!(f, "Updating index failed: {}", error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Update {
+    type Output = UpdateResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        // Read and parse the index configuration.
+        let request = match self.read_request().await {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(UpdateResult::Failed {
+                    error: format!("{e:#}"),
+                });
+            }
+        };
+
+        // Unlike create, there is no default definition to fall back on
+        if request.definition.is_none() {
+            return Ok(UpdateResult::Failed {
+                error: "the index configuration has no definition".to_string(),
+            });
+        }
+
+        // Get the mongodb client.
+        let mongodb_client = match &self.mongodb_client {
+            Ok(client) => client,
+            Err(e) => {
+                return Ok(UpdateResult::Failed {
+                    error: e.to_string(),
+                });
+            }
+        };
+
+        // Start spinner while updating the index.
+        let _spinner = self
+            .interaction
+            .start_spinner(message(Message::UpdateSearchIndex).to_string())?;
+
+        // Update the index by name.
+        if let Err(e) = mongodb_client
+            .update_search_index(
+                request.database.clone(),
+                request.collection_name.clone(),
+                request.name.clone(),
+                request.definition_document(),
+            )
+            .await
+        {
+            return Ok(UpdateResult::Failed {
+                error: format!("failed to update search index: {e}"),
+            });
+        }
+
+        if self.watch {
+            debug!("watching enabled, waiting until the search index is ready");
+
+            if let Err(e) = wait_for_search_index_ready(
+                mongodb_client.as_ref(),
+                &request.database,
+                &request.collection_name,
+                &request.name,
+                self.sleeper.as_ref(),
+                WatchSchedule::fixed(WATCH_INTERVAL),
+            )
+            .await
+            {
+                return Ok(UpdateResult::Failed {
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        Ok(UpdateResult::Updated {
+            index_name: request.name,
+            database: request.database,
+            collection_name: request.collection_name,
+        })
+    }
+}
+
+impl Update {
+    /// Read the index configuration from the file, or from stdin when no file is set.
+    async fn read_request(&self) -> Result<SearchIndexCreateRequest> {
+        let contents = match &self.file {
+            Some(path) => self
+                .fs
+                .read_to_string(path)
+                .await
+                .with_context(|| format!("failed to read file at path: {path:?}"))?,
+            None => self.fs.read_stdin_to_string().await?,
+        };
+
+        if contents.trim().is_empty() {
+            return Err(anyhow!("the index configuration is empty"));
+        }
+
+        SearchIndexCreateRequest::parse(&contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::commands::test_support::create_spinner_handle;
+    use crate::dependencies::MongoDbSearchIndexStatus;
+    use crate::dependencies::mocks::{MockMongoDB, MockTokioClock, MockTokioFs};
+    use crate::interaction::mocks::MockInteraction;
+    use mongodb::bson::doc;
+
+    const INDEX_CONFIGURATION: &str = r#"{
+        "collectionName": "movies",
+        "database": "sample_mflix",
+        "name": "default",
+        "definition": { "mappings": { "dynamic": false } }
+    }"#;
+
+    fn create_command(
+        file: Option<&str>,
+        watch: bool,
+        fs: MockTokioFs,
+        sleeper: MockTokioClock,
+        mongodb_client: MockMongoDB,
+    ) -> Update {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Updating search index...")
+            .returning(|_| Ok(create_spinner_handle()));
+
+        Update {
+            file: file.map(PathBuf::from),
+            watch,
+            interaction: Box::new(mock_interaction),
+            fs: Box::new(fs),
+            sleeper: Box::new(sleeper),
+            mongodb_client: Ok(Box::new(mongodb_client)),
+        }
+    }
+
+    fn updated_result() -> UpdateResult {
+        UpdateResult::Updated {
+            index_name: "default".to_string(),
+            database: "sample_mflix".to_string(),
+            collection_name: "movies".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_from_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_read_to_string()
+            .withf(|path| path == Path::new("index.json"))
+            .return_once(|_| Ok(INDEX_CONFIGURATION.to_string()));
+        mock_fs.expect_read_stdin_to_string().never();
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_update_search_index()
+            .withf(|db, col, name, definition| {
+                db == "sample_mflix"
+                    && col == "movies"
+                    && name == "default"
+                    && *definition == doc! { "mappings": { "dynamic": false } }
+            })
+            .return_once(|_, _, _, _| Ok(()));
+
+        let result = create_command(
+            Some("index.json"),
+            false,
+            mock_fs,
+            MockTokioClock::new(),
+            mock_mongodb,
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert_eq!(result, updated_result());
+        assert_eq!(
+            result.to_string(),
+            "Index 'default' updated in sample_mflix.movies"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_from_stdin_and_watch() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_read_to_string().never();
+        mock_fs
+            .expect_read_stdin_to_string()
+            .return_once(|| Ok(INDEX_CONFIGURATION.to_string()));
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_update_search_index()
+            .return_once(|_, _, _, _| Ok(()));
+        let mut statuses = vec![
+            MongoDbSearchIndexStatus::Ready,
+            MongoDbSearchIndexStatus::Building,
+        ];
+        mock_mongodb
+            .expect_get_search_index_status()
+            .withf(|db, col, name| db == "sample_mflix" && col == "movies" && name == "default")
+            .times(2)
+            .returning(move |_, _, _| Ok(statuses.pop()));
+
+        let mut mock_sleeper = MockTokioClock::new();
+        mock_sleeper
+            .expect_sleep()
+            .withf(|duration| *duration == WATCH_INTERVAL)
+            .times(1)
+            .returning(|_| ());
+
+        let result = create_command(None, true, mock_fs, mock_sleeper, mock_mongodb)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(result, updated_result());
+    }
+
+    #[tokio::test]
+    async fn test_update_without_definition_fails() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_read_stdin_to_string().return_once(|| {
+            Ok(
+                r#"{"collectionName": "movies", "database": "sample_mflix", "name": "default"}"#
+                    .to_string(),
+            )
+        });
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb.expect_update_search_index().never();
+
+        let result = create_command(None, false, mock_fs, MockTokioClock::new(), mock_mongodb)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            UpdateResult::Failed {
+                error: "the index configuration has no definition".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_server_error() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_read_to_string()
+            .return_once(|_| Ok(INDEX_CONFIGURATION.to_string()));
+
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_update_search_index()
+            .return_once(|_, _, _, _| Err(anyhow!("Search index default not found")));
+        mock_mongodb.expect_get_search_index_status().never();
+
+        let result = create_command(
+            Some("index.json"),
+            true,
+            mock_fs,
+            MockTokioClock::new(),
+            mock_mongodb,
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            UpdateResult::Failed {
+                error: "failed to update search index: Search index default not found".to_string(),
+            }
+        );
+    }
+}
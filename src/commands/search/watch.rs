@@ -0,0 +1,325 @@
+//! Command to watch a search index of a local deployment.
+//!
+//! This module implements the `search indexes watch` command which polls the status of a search index,
+//! found by its ID, until it is ready. It is used to monitor an index build that was started elsewhere.
+
+use std::{fmt::Display, time::Duration};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use mongodb::Client;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        search::watcher::{WatchSchedule, wait_for_search_index_ready},
+        with_mongodb::{TryFromWithMongodbClient, TryToGetMongodbClientError},
+    },
+    dependencies::{SearchIndexDescriber, SearchIndexStatusGetter, Sleeper, TokioClock},
+    interaction::{InputPrompt, Interaction, SpinnerInteraction},
+    messages::{Message, message},
+};
+
+// Interaction dependencies for the watch command.
+pub trait WatchInteraction: InputPrompt + SpinnerInteraction + Send + Sync {}
+impl<T: InputPrompt + SpinnerInteraction + Send + Sync> WatchInteraction for T {}
+
+// MongoDB dependencies for the watch command.
+pub trait MongoDbClient: SearchIndexDescriber + SearchIndexStatusGetter + Send + Sync {}
+impl<T: SearchIndexDescriber + SearchIndexStatusGetter + Send + Sync> MongoDbClient for T {}
+
+/// Command to watch a search index of a local deployment.
+pub struct Watch {
+    index_id: Option<String>,
+    interval: Duration,
+    timeout: Option<Duration>,
+
+    interaction: Box<dyn WatchInteraction>,
+    sleeper: Box<dyn Sleeper + Send + Sync>,
+    mongodb_client: Result<Box<dyn MongoDbClient + Send + Sync>, TryToGetMongodbClientError>,
+}
+
+impl TryFromWithMongodbClient<args::search::Watch> for Watch {
+    fn try_from_with_mongodb(
+        args: args::search::Watch,
+        client_result: Result<Client, TryToGetMongodbClientError>,
+    ) -> Result<Self> {
+        Ok(Self {
+            index_id: args.index_id,
+            interval: args.interval,
+            timeout: args.timeout,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            sleeper: Box::new(TokioClock::new()),
+            mongodb_client: client_result
+                .map(|client| Box::new(client) as Box<dyn MongoDbClient + Send + Sync>),
+        })
+    }
+}
+
+/// Result of the watch command.
+///
+/// With `--output json` the result is an object tagged by `outcome`:
+/// - `{"outcome":"ready","index_id":"<id>","index_name":"<name>"}`
+/// - `{"outcome":"failed","error":"<message>"}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum WatchResult {
+    Ready {
+        index_id: String,
+        index_name: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl Display for WatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ready { index_name, .. } => write!(f, "Index '{}' is ready", index_name),
+            Self::Failed { error } => write!(f, "Watching index failed: {}", error),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Watch {
+    type Output = WatchResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let Some(timeout) = self.timeout else {
+            return self.watch().await;
+        };
+
+        // The timeout uses the sleeper, so tests can expire it without waiting
+        tokio::select! {
+            biased;
+            result = self.watch() => result,
+            _ = self.sleeper.sleep(timeout) => {
+                debug!(?timeout, "watching the search index timed out");
+                Ok(WatchResult::Failed {
+                    error: "watching the search index timed out".to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl Watch {
+    /// Look up the search index and poll its status until it is ready.
+    async fn watch(&self) -> Result<WatchResult> {
+        // Prompt for index ID if not provided.
+        let index_id = match self
+            .interaction
+            .prompt_if_none(self.index_id.as_deref(), "Search Index ID?")
+        {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(WatchResult::Failed {
+                    error: e.to_string(),
+                });
+            }
+        };
+
+        // Get the mongodb client, if it is not available, return a failed result.
+        let mongodb_client = match &self.mongodb_client {
+            Ok(client) => client,
+            Err(e) => {
+                return Ok(WatchResult::Failed {
+                    error: e.to_string(),
+                });
+            }
+        };
+
+        // The status is only available by name, within the collection of the index
+        let index = match mongodb_client.describe_search_index(index_id.clone()).await {
+            Ok(Some(index)) => index,
+            Ok(None) => {
+                return Ok(WatchResult::Failed {
+                    error: format!("search index with ID '{}' not found", index_id),
+                });
+            }
+            Err(e) => {
+                return Ok(WatchResult::Failed {
+                    error: format!("failed to describe search index: {e}"),
+                });
+            }
+        };
+
+        let _spinner = self
+            .interaction
+            .start_spinner(message(Message::BuildSearchIndex).to_string())?;
+
+        if let Err(e) = wait_for_search_index_ready(
+            mongodb_client.as_ref(),
+            &index.database,
+            &index.collection_name,
+            &index.name,
+            self.sleeper.as_ref(),
+            WatchSchedule::fixed(self.interval),
+        )
+        .await
+        {
+            return Ok(WatchResult::Failed {
+                error: e.to_string(),
+            });
+        }
+
+        Ok(WatchResult::Ready {
+            index_id: index.index_id,
+            index_name: index.name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::create_spinner_handle;
+    use crate::dependencies::mocks::{MockMongoDB, MockTokioClock};
+    use crate::dependencies::{MongoDbSearchIndexStatus, SearchIndex};
+    use crate::interaction::mocks::MockInteraction;
+
+    fn create_command(
+        timeout: Option<Duration>,
+        sleeper: Box<dyn Sleeper + Send + Sync>,
+        mongodb_client: MockMongoDB,
+    ) -> Watch {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Building search index...")
+            .returning(|_| Ok(create_spinner_handle()));
+
+        Watch {
+            index_id: Some("idx-123".to_string()),
+            interval: Duration::from_secs(5),
+            timeout,
+            interaction: Box::new(mock_interaction),
+            sleeper,
+            mongodb_client: Ok(Box::new(mongodb_client)),
+        }
+    }
+
+    fn mock_mongodb_with_index() -> MockMongoDB {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_describe_search_index()
+            .withf(|id| id == "idx-123")
+            .return_once(|_| {
+                Ok(Some(SearchIndex {
+                    index_id: "idx-123".to_string(),
+                    name: "my_index".to_string(),
+                    database: "test_db".to_string(),
+                    collection_name: "test_collection".to_string(),
+                    status: MongoDbSearchIndexStatus::Building,
+                    index_type: Some("search".to_string()),
+                }))
+            });
+        mock_mongodb
+    }
+
+    #[tokio::test]
+    async fn test_watch_until_ready() {
+        let mut mock_mongodb = mock_mongodb_with_index();
+        let mut statuses = vec![
+            MongoDbSearchIndexStatus::Ready,
+            MongoDbSearchIndexStatus::Building,
+            MongoDbSearchIndexStatus::Pending,
+        ];
+        mock_mongodb
+            .expect_get_search_index_status()
+            .withf(|db, col, name| {
+                db == "test_db" && col == "test_collection" && name == "my_index"
+            })
+            .times(3)
+            .returning(move |_, _, _| Ok(statuses.pop()));
+
+        let mut mock_sleeper = MockTokioClock::new();
+        mock_sleeper
+            .expect_sleep()
+            .withf(|duration| *duration == Duration::from_secs(5))
+            .times(2)
+            .returning(|_| ());
+
+        let result = create_command(None, Box::new(mock_sleeper), mock_mongodb)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            WatchResult::Ready {
+                index_id: "idx-123".to_string(),
+                index_name: "my_index".to_string(),
+            }
+        );
+        assert_eq!(result.to_string(), "Index 'my_index' is ready");
+    }
+
+    #[tokio::test]
+    async fn test_watch_index_not_found() {
+        let mut mock_mongodb = MockMongoDB::new();
+        mock_mongodb
+            .expect_describe_search_index()
+            .return_once(|_| Ok(None));
+        mock_mongodb.expect_get_search_index_status().never();
+
+        let result = create_command(None, Box::new(MockTokioClock::new()), mock_mongodb)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            WatchResult::Failed {
+                error: "search index with ID 'idx-123' not found".to_string(),
+            }
+        );
+    }
+
+    /// Clock on which the timeout of the command expires right away, while the watch intervals never elapse.
+    struct ExpiringTimeoutClock {
+        timeout: Duration,
+    }
+
+    #[async_trait]
+    impl Sleeper for ExpiringTimeoutClock {
+        async fn sleep(&self, duration: Duration) {
+            if duration != self.timeout {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_timeout_expires() {
+        let mut mock_mongodb = mock_mongodb_with_index();
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(1)
+            .returning(|_, _, _| Ok(Some(MongoDbSearchIndexStatus::Building)));
+
+        let result = create_command(
+            Some(Duration::from_secs(60)),
+            Box::new(ExpiringTimeoutClock {
+                timeout: Duration::from_secs(60),
+            }),
+            mock_mongodb,
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            WatchResult::Failed {
+                error: "watching the search index timed out".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Watching the status of a search index.
+//!
+//! Search indexes are built in the background after they are created or updated. The commands that wait for an index
+//! to become ready, `search indexes create --watch`, `search indexes update --watch` and `search indexes watch`, poll
+//! the status of the index with the functions of this module.
+
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+
+use crate::{
+    args,
+    dependencies::{MongoDbSearchIndexStatus, SearchIndexStatusGetter, Sleeper},
+};
+
+/// Interval between index status checks, the first interval when backing off.
+pub(super) const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum interval between index status checks when backing off and --watchMaxInterval is not set.
+const DEFAULT_WATCH_MAX_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Intervals between the index status checks while watching a search index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct WatchSchedule {
+    interval: Duration,
+    // The interval doubles after every check up to this maximum, without it the interval is fixed
+    max_interval: Option<Duration>,
+}
+
+impl WatchSchedule {
+    /// Check the status at a fixed interval.
+    pub(super) fn fixed(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_interval: None,
+        }
+    }
+
+    /// Start checking the status at the interval, doubling it after every check up to the maximum interval.
+    pub(super) fn backoff(interval: Duration, max_interval: Duration) -> Self {
+        Self {
+            interval,
+            max_interval: Some(max_interval),
+        }
+    }
+
+    /// The schedule selected with the --watchBackoff and --watchMaxInterval flags.
+    pub(super) fn from_args(args: &args::search::Create) -> Self {
+        if args.watch_backoff {
+            Self::backoff(
+                WATCH_INTERVAL,
+                args.watch_max_interval
+                    .unwrap_or(DEFAULT_WATCH_MAX_INTERVAL),
+            )
+        } else {
+            Self::fixed(WATCH_INTERVAL)
+        }
+    }
+
+    /// Interval to wait after the given number of checks that found the index not ready yet.
+    pub(super) fn interval(&self, checks: u32) -> Duration {
+        match self.max_interval {
+            Some(max_interval) => self
+                .interval
+                .saturating_mul(2u32.saturating_pow(checks.saturating_sub(1)))
+                .min(max_interval),
+            None => self.interval,
+        }
+    }
+}
+
+/// Poll the status of the search index until it is ready.
+///
+/// Returns an error when the status can't be retrieved or when the index will not become ready.
+pub(super) async fn wait_for_search_index_ready<C>(
+    mongodb_client: &C,
+    database_name: &str,
+    collection_name: &str,
+    search_index_id: &str,
+    sleeper: &(dyn Sleeper + Send + Sync),
+    watch_schedule: WatchSchedule,
+) -> Result<()>
+where
+    C: SearchIndexStatusGetter + Send + Sync + ?Sized,
+{
+    let mut checks = 0;
+    loop {
+        checks += 1;
+        if is_search_index_ready(
+            mongodb_client,
+            database_name,
+            collection_name,
+            search_index_id,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+
+        sleeper.sleep(watch_schedule.interval(checks)).await;
+    }
+}
+
+/// Check the status of the search index once.
+///
+/// Returns whether the index is ready, or an error when the status can't be retrieved or when the index will not become ready.
+pub(super) async fn is_search_index_ready<C>(
+    mongodb_client: &C,
+    database_name: &str,
+    collection_name: &str,
+    search_index_id: &str,
+) -> Result<bool>
+where
+    C: SearchIndexStatusGetter + Send + Sync + ?Sized,
+{
+    match mongodb_client
+        .get_search_index_status(
+            database_name.to_string(),
+            collection_name.to_string(),
+            search_index_id.to_string(),
+        )
+        .await
+    {
+        Err(e) => Err(anyhow!(
+            "failed to get search index status while watching the search index: {e}"
+        )),
+        Ok(None) => Err(anyhow!(
+            "failed to get search index status while watching the search index, the search index does not exist"
+        )),
+        Ok(Some(status)) => match status {
+            MongoDbSearchIndexStatus::Ready => Ok(true),
+            MongoDbSearchIndexStatus::DoesNotExist
+            | MongoDbSearchIndexStatus::Deleting
+            | MongoDbSearchIndexStatus::Failed => Err(anyhow!(
+                "failed to get search index status while watching the search index, the search index is not ready: {status}"
+            )),
+            MongoDbSearchIndexStatus::Pending
+            | MongoDbSearchIndexStatus::Building
+            | MongoDbSearchIndexStatus::Stale => Ok(false),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::mocks::{MockMongoDB, MockTokioClock};
+
+    #[tokio::test]
+    async fn test_watch_backoff_schedule() {
+        let mut mock_mongodb = MockMongoDB::new();
+        // Simulate: Pending -> Building x4 -> Ready
+        let status_sequence = std::sync::atomic::AtomicUsize::new(0);
+        mock_mongodb
+            .expect_get_search_index_status()
+            .times(6)
+            .returning(move |_, _, _| {
+                let count = status_sequence.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match count {
+                    0 => Ok(Some(MongoDbSearchIndexStatus::Pending)),
+                    1..=4 => Ok(Some(MongoDbSearchIndexStatus::Building)),
+                    _ => Ok(Some(MongoDbSearchIndexStatus::Ready)),
+                }
+            });
+
+        // Record the sleeps instead of waiting
+        let sleeps = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sleeps_clone = sleeps.clone();
+        let mut mock_clock = MockTokioClock::new();
+        mock_clock.expect_sleep().returning(move |duration| {
+            sleeps_clone.lock().unwrap().push(duration);
+        });
+
+        wait_for_search_index_ready(
+            &mock_mongodb,
+            "test_db",
+            "test_collection",
+            "index-123",
+            &mock_clock,
+            WatchSchedule::backoff(Duration::from_secs(1), Duration::from_secs(5)),
+        )
+        .await
+        .expect("the index should become ready");
+
+        assert_eq!(
+            *sleeps.lock().unwrap(),
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(4),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            ]
+        );
+    }
+}
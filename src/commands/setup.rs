@@ -1,47 +1,129 @@
-use std::{collections::HashMap, fmt::Display, path::PathBuf};
+use std::{collections::HashMap, fmt::Display, io::Write, path::PathBuf, time::Duration};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use atlas_local::{
     Client, CreateDeploymentError,
     client::CreateDeploymentStepOutcome,
-    models::{BindingType, CreateDeploymentOptions, CreationSource, ImageTag, MongoDBPortBinding},
+    models::{
+        BindingType, CreateDeploymentOptions, CreationSource, Deployment, ImageTag,
+        MongoDBPortBinding, State, WatchOptions,
+    },
 };
-use bollard::Docker;
 use semver::Version;
 use serde::Serialize;
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{
-    args::{self, ConnectWith},
+    args::{self, ConnectWith, PullPolicy},
     commands::{
-        CommandWithOutput,
-        connectors::{Compass, Connector, DeploymentParams, Mongosh, VsCode},
+        CancelReason, CommandWithOutput,
+        clone::volumes_by_destination,
+        config::{deployments_file_path, save_default_db},
+        connection_string::ConnectionStringOptions,
+        connectors::{
+            Compass, Connector, ConnectorOptions, DeploymentParams, Mongosh, VsCode,
+            default_connector, exit_on_connector_exit,
+        },
+        deployment_config::split_image_reference,
+        notify::CompletionNotification,
+        sample_data::{LoadOutcome, load_sample_data},
+        shutdown::shutdown_signal,
         validators,
+        with_mongodb::{LocalDeploymentPrimaryWaiter, WAIT_FOR_PRIMARY_TIMEOUT},
+    },
+    config_dir::ConfigFs,
+    dependencies::{
+        ContainerExecutor, DeploymentContainerInspector, DeploymentCreator, DeploymentDeleter,
+        DeploymentGetConnectionString, DeploymentGetDeployment, DeploymentImageExists,
+        DeploymentStarter, DeploymentStopper, DeploymentWaiter, DockerError, DockerSettings,
+        DockerVolumeExists, FileWriter, PathKind, PathResolver, PrimaryWaiter, TokioFs,
+        VolumeCopier, connect_docker, find_deployment, wait_for_healthy,
     },
-    dependencies::{DeploymentCreator, DeploymentGetConnectionString},
     env,
     interaction::{
-        InputPrompt, InputPromptOptions, InputPromptResult, InputPromptValidator, InputValidator,
-        Interaction, MultiStepSpinnerInteraction, MultiStepSpinnerOutcome, MultiStepSpinnerStep,
-        SelectPrompt, SelectPromptOptions, SelectPromptResult, SpinnerInteraction,
+        ConfirmationPrompt, ConfirmationPromptOptions, ConfirmationPromptResult, InputPrompt,
+        InputPromptOptions, InputPromptResult, InputPromptValidator, InputValidator,
+        InputValidatorResult, Interaction, JsonLinesStepReporter, MultiStepSpinner,
+        MultiStepSpinnerInteraction, MultiStepSpinnerOutcome, MultiStepSpinnerStep,
+        ProgressReporter, SelectPrompt, SelectPromptOptions, SelectPromptResult,
+        SpinnerInteraction,
     },
+    messages::{Message, message},
+    settings::Settings,
 };
 
+/// Warning shown when the deployment is bound to all interfaces with --bindIpAll.
+const BIND_IP_ALL_WARNING: &str =
+    "the deployment is bound to all interfaces and can be reached from the network";
+
+/// Maximum time to wait for a resumed deployment, or one with a reused volume, to become healthy once it is started.
+const RESUME_WAIT_FOR_HEALTHY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Path of the MongoDB data directory in the container, the volume of --reuseVolume is copied into it.
+const DATA_DIRECTORY: &str = "/data/db";
+
+/// Image used when no image is specified.
+pub(super) const DEFAULT_IMAGE: &str = "mongodb/mongodb-atlas-local";
+
+/// Image tag used when no image tag is specified.
+pub(super) const DEFAULT_IMAGE_TAG: &str = "latest";
+
+/// Names of the setup steps in the streamed step outcomes, in the order of the spinner steps.
+const SETUP_STEP_NAMES: [&str; 4] = [
+    "pull_image",
+    "create_container",
+    "start_container",
+    "wait_for_healthy",
+];
+
 // Setup dependencies for the setup command
 pub trait SetupDeploymentManagement:
-    DeploymentCreator + DeploymentGetConnectionString + Sync
+    DeploymentCreator
+    + DeploymentDeleter
+    + DeploymentGetConnectionString
+    + DeploymentGetDeployment
+    + DeploymentStarter
+    + DeploymentStopper
+    + DeploymentWaiter
+    + Sync
+{
+}
+impl<
+    T: DeploymentCreator
+        + DeploymentDeleter
+        + DeploymentGetConnectionString
+        + DeploymentGetDeployment
+        + DeploymentStarter
+        + DeploymentStopper
+        + DeploymentWaiter
+        + Sync,
+> SetupDeploymentManagement for T
 {
 }
-impl<T: DeploymentCreator + DeploymentGetConnectionString + Sync> SetupDeploymentManagement for T {}
+
+// Docker dependencies to copy the volume of --reuseVolume into the new deployment
+pub trait SetupVolumeManagement: DockerVolumeExists + VolumeCopier {}
+impl<T: DockerVolumeExists + VolumeCopier> SetupVolumeManagement for T {}
 
 // Interaction dependencies for the setup command
 pub trait SetupInteraction:
-    SpinnerInteraction + SelectPrompt + InputPrompt + MultiStepSpinnerInteraction + Sync
+    SpinnerInteraction
+    + SelectPrompt
+    + InputPrompt
+    + ConfirmationPrompt
+    + MultiStepSpinnerInteraction
+    + Sync
 {
 }
-impl<T: SpinnerInteraction + SelectPrompt + InputPrompt + MultiStepSpinnerInteraction + Sync>
-    SetupInteraction for T
+impl<
+    T: SpinnerInteraction
+        + SelectPrompt
+        + InputPrompt
+        + ConfirmationPrompt
+        + MultiStepSpinnerInteraction
+        + Sync,
+> SetupInteraction for T
 {
 }
 
@@ -67,52 +149,187 @@ fn bool_from_env(key: &str) -> Result<Option<bool>> {
         .map_err(|e| anyhow::anyhow!("invalid value for {}: {}", key, e))
 }
 
+/// Parses a string as a port, validated with the same rules as the port prompt.
+/// Returns `None` when the port should be auto-assigned (empty or "auto-assign").
+fn parse_port(s: &str) -> Result<Option<u16>> {
+    if let InputValidatorResult::Invalid(message) = validators::PortValidator.validate(s)? {
+        anyhow::bail!("{message}, got '{s}'");
+    }
+
+    if s.is_empty() || s == "auto-assign" {
+        return Ok(None);
+    }
+
+    Ok(Some(s.parse::<u16>().context("converting port to u16")?))
+}
+
+/// Reads an environment variable as a port.
+/// Returns `None` if unset or auto-assign, `Some(port)` if set to a valid port, error if invalid.
+fn port_from_env(key: &str) -> Result<Option<u16>> {
+    let v = match std::env::var(key) {
+        Ok(s) => s,
+        Err(std::env::VarError::NotPresent) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    parse_port(&v).map_err(|e| anyhow::anyhow!("invalid value for {}: {}", key, e))
+}
+
+/// Reads an environment variable as an image registry.
+/// Returns `None` if unset, `Some(registry)` if set to a valid registry, error if invalid.
+fn registry_from_env(key: &str) -> Result<Option<String>> {
+    let v = match std::env::var(key) {
+        Ok(s) => s,
+        Err(std::env::VarError::NotPresent) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    args::parse_registry(&v)
+        .map(Some)
+        .map_err(|e| anyhow::anyhow!("invalid value for {}: {}", key, e))
+}
+
+/// Reference of the image in the registry, replacing the registry of the image when it has one.
+fn image_in_registry(image: &str, registry: &str) -> String {
+    // Like Docker, the first component is a registry when it is localhost or has a dot or a port
+    let repository = match image.split_once('/') {
+        Some((first, rest)) if first.contains(['.', ':']) || first == "localhost" => rest,
+        _ => image,
+    };
+
+    format!("{registry}/{repository}")
+}
+
+/// Whether a deployment running MongoDB `version`, from an image with tag `container_tag`, matches `image_tag`.
+///
+/// Version tags match the MongoDB version of the deployment, e.g. 8.2 matches 8.2.2. Other tags, like preview or a
+/// timestamped version, have to match the tag of the container.
+fn image_tag_matches(image_tag: &ImageTag, version: &Version, container_tag: Option<&str>) -> bool {
+    let tag = image_tag.to_string();
+    if container_tag == Some(tag.as_str()) {
+        return true;
+    }
+
+    match image_tag {
+        ImageTag::Semver(_) => {
+            let version = version.to_string();
+            version == tag || version.starts_with(&format!("{tag}."))
+        }
+        _ => false,
+    }
+}
+
 pub struct Setup {
     deployment_name: Option<String>,
     image_tag: Option<ImageTag>,
+    fail_on_eol: bool,
     use_preview: Option<bool>,
     voyage_api_key: Option<String>,
     port: Option<u16>,
     bind_ip_all: bool,
+    // Set with --iUnderstand, --bindIpAll is allowed without authentication
+    i_understand: bool,
     initdb: Option<PathBuf>,
     force: bool,
     load_sample_data: Option<bool>,
+    // Set with --dataset, only these sample datasets are loaded once the deployment is healthy
+    datasets: Vec<String>,
     username: Option<String>,
     password: Option<String>,
 
     image: Option<String>,
-    skip_pull_image: bool,
+    // Set with --registry, the image is pulled from this registry
+    registry: Option<String>,
+    pull_policy: PullPolicy,
     connect_with: Option<ConnectWith>,
+    // Used when --connectWith is omitted, pre-selected in the prompt and used as is with --force
+    default_connector: Option<ConnectWith>,
+    connection_string_options: ConnectionStringOptions,
+    connector_options: ConnectorOptions,
+    wait_for_primary: bool,
+    resume: bool,
+    // Set with --reuseVolume, the data of this volume is copied into the new deployment
+    reuse_volume: Option<String>,
+    // Set with --stream, the outcome of every step is written to it as a JSON line
+    step_stream: Option<Box<dyn Write + Send + Sync>>,
+    // Set with --outputConnectionStringFile, the connection string is written to it
+    connection_string_file: Option<PathBuf>,
+    // Set with --defaultDb, the database is saved as a setting of the deployment in this file
+    deployments_file: Option<PathBuf>,
 
     interaction: Box<dyn SetupInteraction + Send>,
-    deployment_management: Box<dyn SetupDeploymentManagement + Send>,
+    deployment_management: Box<dyn SetupDeploymentManagement + Send + Sync>,
+    // How to reconnect to Docker while waiting for the deployment, with --reconnectOnDrop
+    docker_settings: DockerSettings,
     connectors: HashMap<ConnectWith, Box<dyn Connector + Send + Sync>>,
+    primary_waiter: Box<dyn PrimaryWaiter + Send + Sync>,
+    path_resolver: Box<dyn PathResolver + Send + Sync>,
+    file_writer: Box<dyn FileWriter + Send + Sync>,
+    image_checker: Box<dyn DeploymentImageExists + Send + Sync>,
+    container_inspector: Box<dyn DeploymentContainerInspector + Send + Sync>,
+    executor: Box<dyn ContainerExecutor + Send + Sync>,
+    volume_management: Box<dyn SetupVolumeManagement + Send + Sync>,
+    config_fs: Box<dyn ConfigFs>,
 }
 
-impl TryFrom<args::Setup> for Setup {
+impl TryFrom<(args::Setup, &Settings)> for Setup {
     type Error = anyhow::Error;
 
-    fn try_from(args: args::Setup) -> Result<Self> {
+    fn try_from((args, settings): (args::Setup, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
         Ok(Self {
             deployment_name: args.deployment_name,
             image_tag: args.image_tag,
+            fail_on_eol: args.fail_on_eol,
             use_preview: bool_from_env(env::MONGODB_ATLAS_LOCAL_PREVIEW)?,
             voyage_api_key: std::env::var(env::MONGODB_ATLAS_LOCAL_VOYAGE_API_KEY).ok(),
-            port: args.port,
+            // Precedence: --port flag, then the environment variable, otherwise auto-assign.
+            port: match args.port {
+                Some(port) => Some(port),
+                None => port_from_env(env::MONGODB_ATLAS_LOCAL_PORT)?,
+            },
             bind_ip_all: args.bind_ip_all,
+            i_understand: args.i_understand,
             initdb: args.initdb,
             force: args.force,
             load_sample_data: args.load_sample_data,
+            datasets: args.datasets,
             username: args.username,
             password: args.password,
             image: args.image,
-            skip_pull_image: args.skip_pull_image,
+            // Precedence: --registry flag, then the environment variable
+            registry: match args.registry {
+                Some(registry) => Some(registry),
+                None => registry_from_env(env::MONGODB_ATLAS_LOCAL_REGISTRY)?,
+            },
+            // The deprecated --skipPullImage flag maps to never
+            pull_policy: match args.pull_policy {
+                Some(pull_policy) => pull_policy,
+                None if args.skip_pull_image => PullPolicy::Never,
+                None => PullPolicy::default(),
+            },
             connect_with: args.connect_with,
+            // Precedence: --defaultConnector flag, then the environment variable
+            default_connector: default_connector(args.default_connector)?,
+            connection_string_options: ConnectionStringOptions {
+                host: args.connect_host,
+                ..args.connection_string.into()
+            },
+            connector_options: args.connector_args.into_iter().collect(),
+            wait_for_primary: args.wait_for_primary,
+            resume: args.resume,
+            reuse_volume: args.reuse_volume,
+            step_stream: args
+                .stream
+                .then(|| Box::new(std::io::stdout()) as Box<dyn Write + Send + Sync>),
+            connection_string_file: args.output_connection_string_file,
+            deployments_file: match args.connection_string.default_db {
+                Some(_) => Some(deployments_file_path(settings)?),
+                None => None,
+            },
 
-            interaction: Box::new(Interaction::new()),
-            deployment_management: Box::new(Client::new(
-                Docker::connect_with_defaults().context("connecting to Docker")?,
-            )),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker.clone())),
+            docker_settings: settings.docker.clone(),
             connectors: HashMap::from([
                 (
                     ConnectWith::Compass,
@@ -121,6 +338,14 @@ impl TryFrom<args::Setup> for Setup {
                 (ConnectWith::Mongosh, Box::new(Mongosh::new())),
                 (ConnectWith::VsCode, Box::new(VsCode::new())),
             ]),
+            primary_waiter: Box::new(LocalDeploymentPrimaryWaiter::new(settings.docker.clone())),
+            path_resolver: Box::new(TokioFs::new()),
+            file_writer: Box::new(TokioFs::new()),
+            image_checker: Box::new(docker.clone()),
+            container_inspector: Box::new(docker.clone()),
+            executor: Box::new(docker.clone()),
+            volume_management: Box::new(docker),
+            config_fs: Box::new(TokioFs::new()),
         })
     }
 }
@@ -133,6 +358,14 @@ pub enum SetupResult {
         mongodb_version: Version,
         port: u16,
         load_sample_data: bool,
+        // True when --resume started an existing deployment instead of creating a new one
+        resumed: bool,
+        // The image pulled from the registry selected with --registry
+        #[serde(skip_serializing_if = "Option::is_none")]
+        image: Option<String>,
+        // E.g. the requested MongoDB version reached its end of life
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         connect_result: Option<ConnectResult>,
     },
@@ -140,6 +373,11 @@ pub enum SetupResult {
         deployment_name: Option<String>,
         error: String,
     },
+    // The user canceled a prompt, or the setup was interrupted while creating the deployment
+    Canceled {
+        deployment_name: Option<String>,
+        reason: CancelReason,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -159,12 +397,28 @@ impl Display for SetupResult {
                 mongodb_version,
                 port,
                 load_sample_data,
+                resumed,
+                image,
+                warnings,
                 connect_result,
             } => {
-                writeln!(f, "Successfully setup deployment '{deployment_name}'")?;
+                if *resumed {
+                    writeln!(
+                        f,
+                        "Successfully resumed setup of deployment '{deployment_name}'"
+                    )?;
+                } else {
+                    writeln!(f, "Successfully setup deployment '{deployment_name}'")?;
+                }
                 writeln!(f, "MongoDB version: {mongodb_version}")?;
                 writeln!(f, "Port: {port}")?;
                 writeln!(f, "Load sample data: {load_sample_data}")?;
+                if let Some(image) = image {
+                    writeln!(f, "Image: {image}")?;
+                }
+                for warning in warnings {
+                    writeln!(f, "Warning: {warning}")?;
+                }
 
                 // Display connection result if present
                 if let Some(connect_result) = connect_result {
@@ -202,10 +456,95 @@ impl Display for SetupResult {
                     "Setting up deployment {quoted_deployment_name}failed: {error}"
                 )
             }
+            Self::Canceled {
+                deployment_name,
+                reason,
+            } => {
+                let quoted_deployment_name = deployment_name
+                    .as_deref()
+                    .map(|name| format!("'{}' ", name))
+                    .unwrap_or_default();
+
+                match reason {
+                    CancelReason::Prompt => write!(
+                        f,
+                        "Setting up deployment {quoted_deployment_name}failed: User canceled the setup"
+                    ),
+                    CancelReason::Signal => write!(
+                        f,
+                        "Setting up deployment {quoted_deployment_name}was interrupted, use --resume to continue the setup"
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl CompletionNotification for SetupResult {
+    fn completion_notification(&self) -> String {
+        match self {
+            Self::Setup {
+                deployment_name, ..
+            } => format!("Deployment '{deployment_name}' is ready"),
+            Self::Failed { .. } | Self::Canceled { .. } => self.to_string(),
+        }
+    }
+}
+
+/// Setup result printed as a single line with --summary, e.g. `name=x version=8.2.2 port=27017 uri=mongodb://...`.
+///
+/// The JSON output is the same as the one of [`SetupResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct SetupSummary(SetupResult);
+
+impl Display for SetupSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            SetupResult::Setup {
+                deployment_name,
+                mongodb_version,
+                port,
+                connect_result,
+                ..
+            } => {
+                write!(
+                    f,
+                    "name={deployment_name} version={mongodb_version} port={port}"
+                )?;
+                if let Some(ConnectResult::ConnectionString { connection_string }) = connect_result
+                {
+                    write!(f, " uri={connection_string}")?;
+                }
+                Ok(())
+            }
+            // A failure is already a single line
+            SetupResult::Failed { .. } | SetupResult::Canceled { .. } => self.0.fmt(f),
         }
     }
 }
 
+impl CompletionNotification for SetupSummary {
+    fn completion_notification(&self) -> String {
+        self.0.completion_notification()
+    }
+}
+
+/// Wrapper command that returns the result of the setup command as a [`SetupSummary`].
+pub struct Summarized<C>(pub C);
+
+#[async_trait]
+impl<C> CommandWithOutput for Summarized<C>
+where
+    C: CommandWithOutput<Output = SetupResult> + Send,
+{
+    type Output = SetupSummary;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        self.0.execute().await.map(SetupSummary)
+    }
+}
+
 #[async_trait]
 impl CommandWithOutput for Setup {
     type Output = SetupResult;
@@ -222,6 +561,24 @@ impl CommandWithOutput for Setup {
             self.image_tag = Some(ImageTag::Preview);
         }
 
+        // End-of-life versions only get a warning in the result, unless --failOnEol is set
+        if self.fail_on_eol {
+            if let Some(warning) = self.end_of_life_warning() {
+                return Ok(SetupResult::Failed {
+                    deployment_name: self.deployment_name.clone(),
+                    error: warning,
+                });
+            }
+        }
+
+        // Validate the initdb path before prompting, so the user isn't asked for settings of a setup that can't succeed
+        if let Some(setup_result) = self.resolve_initdb().await? {
+            return Ok(setup_result);
+        }
+        if let Some(setup_result) = self.check_reuse_volume().await? {
+            return Ok(setup_result);
+        }
+
         // If the force flag is not set, prompt the user for the settings
         if !self.force {
             // If the user canceled the setup, setup_result will be Some
@@ -231,19 +588,95 @@ impl CommandWithOutput for Setup {
             }
         }
 
-        // Create the deployment
+        // Exposing a deployment without authentication to the network must be intentional
+        if let Some(setup_result) = self.confirm_network_exposure()? {
+            return Ok(setup_result);
+        }
+
+        // With --resume, start a deployment of which a previous setup was interrupted instead of creating it again
+        if self.resume {
+            if let Some(setup_result) = self.resume_deployment().await? {
+                return Ok(setup_result);
+            }
+        }
+
+        // Apply the pull policy, only an image that is available locally can skip the pull
+        let skip_pull_image = match self.image_available_locally().await? {
+            Some(true) => true,
+            Some(false) if self.pull_policy == PullPolicy::Never => {
+                let (image, tag) = self.image_reference();
+                return Ok(SetupResult::Failed {
+                    deployment_name: self.deployment_name.clone(),
+                    error: format!(
+                        "image {image}:{tag} is not available locally and the pull policy is never"
+                    ),
+                });
+            }
+            Some(false) | None => false,
+        };
+
+        // Ctrl-C or SIGTERM stops creating the deployment, a deployment that was already created can be started with --resume
+        let deployment_name = self.deployment_name.clone();
+        tokio::select! {
+            result = self.create_deployment(skip_pull_image) => result,
+            result = shutdown_signal() => {
+                result?;
+                Ok(SetupResult::Canceled {
+                    deployment_name,
+                    reason: CancelReason::Signal,
+                })
+            }
+        }
+    }
+}
+
+/// Docker error messages that mean the image is not built for the architecture of the host.
+const ARCHITECTURE_MISMATCH_ERRORS: &[&str] = &["exec format error", "no matching manifest"];
+
+/// Check whether creating the deployment failed because the image is not built for the architecture of the host.
+///
+/// Docker only reports this in the error message, so the messages of the whole error chain are checked.
+fn is_architecture_mismatch(error: &CreateDeploymentError) -> bool {
+    std::iter::successors(Some(error as &dyn std::error::Error), |e| e.source()).any(|e| {
+        let message = e.to_string().to_lowercase();
+        ARCHITECTURE_MISMATCH_ERRORS
+            .iter()
+            .any(|mismatch| message.contains(mismatch))
+    })
+}
+
+fn deployment_outcome_to_multi_step_spinner_outcome(
+    outcome: CreateDeploymentStepOutcome,
+) -> MultiStepSpinnerOutcome {
+    match outcome {
+        CreateDeploymentStepOutcome::Success => MultiStepSpinnerOutcome::Success,
+        CreateDeploymentStepOutcome::Failure => MultiStepSpinnerOutcome::Failure,
+        CreateDeploymentStepOutcome::Skipped => MultiStepSpinnerOutcome::Skipped,
+    }
+}
+
+impl Setup {
+    /// Create the deployment, reporting the progress of every step.
+    async fn create_deployment(&mut self, skip_pull_image: bool) -> Result<SetupResult> {
         let create_deployment_options = CreateDeploymentOptions {
             name: self.deployment_name.clone(),
             image_tag: self.image_tag.clone(),
             creation_source: Some(CreationSource::AtlasLocal),
             wait_until_healthy: Some(true),
+            // With --reuseVolume the data comes from the volume, the deployment isn't seeded
             local_seed_location: self
                 .initdb
                 .as_ref()
+                .filter(|_| self.reuse_volume.is_none())
                 .map(|path| path.to_string_lossy().to_string()),
             mongodb_initdb_root_username: self.username.clone(),
             mongodb_initdb_root_password: self.password.clone(),
-            load_sample_data: self.load_sample_data,
+            // The image can only load all sample datasets, selected datasets are loaded by finish_setup
+            load_sample_data: if self.datasets.is_empty() && self.reuse_volume.is_none() {
+                self.load_sample_data
+            } else {
+                Some(false)
+            },
             mongodb_port_binding: if self.bind_ip_all {
                 Some(MongoDBPortBinding::new(
                     self.port,
@@ -252,20 +685,33 @@ impl CommandWithOutput for Setup {
             } else {
                 Some(MongoDBPortBinding::new(self.port, BindingType::Loopback))
             },
-            image: self.image.clone(),
-            skip_pull_image: Some(self.skip_pull_image),
+            image: self.effective_image(),
+            skip_pull_image: Some(skip_pull_image),
             voyage_api_key: self.voyage_api_key.clone(),
             ..Default::default()
         };
 
         // Start the multi-step spinner
-        let mut multi_step_spinner = self.interaction.start_multi_step_spinner(vec![
-            MultiStepSpinnerStep::new("Pulling the latest version of the MongoDB image..."),
-            MultiStepSpinnerStep::new("Creating the deployment..."),
-            MultiStepSpinnerStep::new("Starting the deployment..."),
-            MultiStepSpinnerStep::new("Waiting for the deployment to be healthy..."),
+        let spinner = self.interaction.start_multi_step_spinner(vec![
+            MultiStepSpinnerStep::new(message(Message::SetupPullImage)),
+            MultiStepSpinnerStep::new(message(Message::SetupCreateDeployment)),
+            MultiStepSpinnerStep::new(message(Message::SetupStartDeployment)),
+            MultiStepSpinnerStep::new(message(Message::SetupWaitForHealthy)),
         ])?;
 
+        // With --stream, step outcomes are also written as JSON lines as soon as they complete
+        let mut multi_step_spinner: Box<dyn MultiStepSpinner + Send + Sync> =
+            match self.step_stream.take() {
+                Some(step_stream) => Box::new(ProgressReporter::new(vec![
+                    spinner,
+                    Box::new(JsonLinesStepReporter::new(
+                        SETUP_STEP_NAMES.to_vec(),
+                        step_stream,
+                    )),
+                ])),
+                None => spinner,
+            };
+
         let mut create_deployment_progress = self
             .deployment_management
             .create_deployment(create_deployment_options);
@@ -310,109 +756,632 @@ impl CommandWithOutput for Setup {
             .wait_for_deployment_outcome()
             .await
         {
-            Ok(deployment) => {
-                let deployment_name = deployment.name.clone().unwrap_or("unknown".to_string());
-                let mongodb_version = deployment.mongodb_version.clone();
-                let port = deployment
-                    .port_bindings
-                    .as_ref()
-                    .and_then(|bindings| bindings.port)
-                    .unwrap_or(0);
-                let load_sample_data = deployment.mongodb_load_sample_data.unwrap_or(false);
-
-                // Prompt for connection method and connect if requested
-                let connect_result = self
-                    .prompt_and_connect(&deployment.container_id, &deployment_name)
-                    .await?;
-
-                Ok(SetupResult::Setup {
-                    deployment_name,
-                    mongodb_version,
-                    port,
-                    load_sample_data,
-                    connect_result,
-                })
-            }
+            Ok(deployment) => match self.reuse_volume.clone() {
+                Some(volume) => self.finish_reused_volume_setup(deployment, &volume).await,
+                None => self.finish_setup(deployment, false).await,
+            },
             Err(CreateDeploymentError::ReceiveDeployment(error)) => {
                 Err(error).context("receiving deployment outcome")
             }
+            // Without emulation, an image built for another architecture fails to pull or start with a cryptic Docker error
+            Err(e) if is_architecture_mismatch(&e) => Ok(SetupResult::Failed {
+                deployment_name: self.deployment_name.clone(),
+                error: format!(
+                    "{e}: the image is not built for the {arch} architecture of this host. Use an image that supports {arch} with --image or --mdbVersion, or enable emulation of other architectures in Docker",
+                    arch = std::env::consts::ARCH
+                ),
+            }),
             Err(e) => Ok(SetupResult::Failed {
                 deployment_name: self.deployment_name.clone(),
                 error: e.to_string(),
             }),
         }
     }
-}
 
-fn deployment_outcome_to_multi_step_spinner_outcome(
-    outcome: CreateDeploymentStepOutcome,
-) -> MultiStepSpinnerOutcome {
-    match outcome {
-        CreateDeploymentStepOutcome::Success => MultiStepSpinnerOutcome::Success,
-        CreateDeploymentStepOutcome::Failure => MultiStepSpinnerOutcome::Failure,
-        CreateDeploymentStepOutcome::Skipped => MultiStepSpinnerOutcome::Skipped,
+    /// Wait for a primary if requested and connect to the deployment, once it is healthy.
+    async fn finish_setup(&self, deployment: Deployment, resumed: bool) -> Result<SetupResult> {
+        let deployment_name = deployment.name.clone().unwrap_or("unknown".to_string());
+        let mongodb_version = deployment.mongodb_version.clone();
+        let port = deployment
+            .port_bindings
+            .as_ref()
+            .and_then(|bindings| bindings.port)
+            .unwrap_or(0);
+        let mut load_sample_data = deployment.mongodb_load_sample_data.unwrap_or(false);
+
+        // A healthy container doesn't guarantee that the replica set has elected a primary yet
+        if self.wait_for_primary {
+            let _wait_for_primary_spinner = self
+                .interaction
+                .start_spinner(message(Message::WaitForPrimary).to_string())?;
+
+            let has_primary = self
+                .primary_waiter
+                .wait_for_primary(&deployment_name, WAIT_FOR_PRIMARY_TIMEOUT)
+                .await
+                .context("waiting for primary")?;
+
+            if !has_primary {
+                return Ok(SetupResult::Failed {
+                    deployment_name: Some(deployment_name),
+                    error: "Waiting for deployment to have a primary timed out".to_string(),
+                });
+            }
+        }
+
+        // A resumed deployment already has the datasets it was created with, a reused volume has its own data
+        if !self.datasets.is_empty() && !resumed && self.reuse_volume.is_none() {
+            let spinner = self
+                .interaction
+                .start_spinner(message(Message::DownloadSampleData).to_string())?;
+
+            let connection_string = self
+                .deployment_management
+                .get_connection_string(deployment.container_id.clone())
+                .await
+                .context("getting connection string")?;
+            let outcome = load_sample_data(
+                self.executor.as_ref(),
+                &deployment_name,
+                &connection_string,
+                &self.datasets,
+                &|msg| spinner.set_message(msg),
+            )
+            .await
+            .context("loading sample data")?;
+            drop(spinner);
+
+            if let LoadOutcome::Failed(error) = outcome {
+                return Ok(SetupResult::Failed {
+                    deployment_name: Some(deployment_name),
+                    error: format!("Loading sample data failed: {error}"),
+                });
+            }
+            load_sample_data = true;
+        }
+
+        // Connect adds the database to the connection string of the deployment from now on
+        if let (Some(deployments_file), Some(default_db)) = (
+            &self.deployments_file,
+            &self.connection_string_options.default_database,
+        ) {
+            // The deployment is ready, so it is not failed when only the setting can't be saved
+            if let Err(e) = save_default_db(
+                self.config_fs.as_ref(),
+                deployments_file,
+                &deployment_name,
+                default_db,
+            )
+            .await
+            {
+                warn!(
+                    error = format!("{e:#}"),
+                    "saving the default database failed, save it with config set {deployment_name} defaultDb={default_db}"
+                );
+            }
+        }
+
+        // Prompt for connection method and connect if requested
+        let connect_result = self
+            .prompt_and_connect(&deployment.container_id, &deployment_name)
+            .await?;
+
+        Ok(SetupResult::Setup {
+            deployment_name,
+            mongodb_version,
+            port,
+            load_sample_data,
+            resumed,
+            image: self.registry.as_ref().map(|_| {
+                let (image, tag) = self.image_reference();
+                format!("{image}:{tag}")
+            }),
+            warnings: self.warnings(),
+            connect_result,
+        })
     }
-}
 
-impl Setup {
-    fn prompt_settings(&mut self) -> Result<Option<SetupResult>> {
-        // Prompt the user for the setup type
-        // There are three options: default, custom, and cancel
-        // - Default: Use default settings, no need to prompt for missing settings, we can pick the defaults
-        // - Custom: Prompt for missing settings, we need to prompt for the missing settings
-        // - Cancel: Cancel the setup
+    /// Warnings about the settings of the deployment, shown with the result.
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = self.end_of_life_warning().into_iter().collect();
+        if self.bind_ip_all {
+            warnings.push(BIND_IP_ALL_WARNING.to_string());
+        }
+        if let Some(warning) = self.ignored_seed_settings_warning() {
+            warnings.push(warning);
+        }
+        warnings
+    }
 
-        // Build the select options
-        let select_options = SelectPromptOptions::builder()
-            .message("How do you want to set up your local Atlas deployment?")
-            .options(vec![
-                SETUP_TYPE_DEFAULT,
-                SETUP_TYPE_CUSTOM,
-                SETUP_TYPE_CANCEL,
-            ])
-            .build();
+    /// Warning when settings that seed the data of the deployment are set together with --reuseVolume.
+    fn ignored_seed_settings_warning(&self) -> Option<String> {
+        let volume = self.reuse_volume.as_ref()?;
 
-        // Create a closure to return the cancelled message
-        let cancelled_message = |deployment_name: Option<String>| -> Result<Option<SetupResult>> {
-            Ok(Some(SetupResult::Failed {
-                deployment_name,
-                error: "User canceled the setup".to_string(),
-            }))
+        let mut ignored = vec![];
+        if self.initdb.is_some() {
+            ignored.push("--initdb");
+        }
+        if self.load_sample_data == Some(true) {
+            ignored.push("--loadSampleData");
+        }
+        if !self.datasets.is_empty() {
+            ignored.push("--dataset");
+        }
+
+        (!ignored.is_empty()).then(|| {
+            format!(
+                "{} ignored, the data of the deployment is copied from volume '{volume}'",
+                ignored.join(", ")
+            )
+        })
+    }
+
+    /// Verify that the volume of --reuseVolume exists.
+    ///
+    /// Returns Some(SetupResult::Failed) when it doesn't, so no deployment is created.
+    async fn check_reuse_volume(&self) -> Result<Option<SetupResult>> {
+        let Some(volume) = &self.reuse_volume else {
+            return Ok(None);
         };
 
-        // Prompt the user for the setup type and handle the result
-        match self
+        let exists = self
+            .volume_management
+            .volume_exists(volume)
+            .await
+            .map_err(DockerError::from)
+            .context("checking whether the volume exists")?;
+        debug!(volume, exists, "checked whether the volume to reuse exists");
+
+        if exists {
+            return Ok(None);
+        }
+
+        Ok(Some(SetupResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error: format!("volume '{volume}' does not exist"),
+        }))
+    }
+
+    /// Copy the volume of --reuseVolume into the new deployment, then wait for it to be healthy and finish the setup.
+    ///
+    /// A new deployment that could not be copied into is deleted, it doesn't have the requested data.
+    async fn finish_reused_volume_setup(
+        &self,
+        deployment: Deployment,
+        volume: &str,
+    ) -> Result<SetupResult> {
+        let deployment_name = deployment
+            .name
+            .clone()
+            .context("the new deployment has no name")?;
+
+        let spinner = self
             .interaction
-            .select(select_options)
-            .context("failed to prompt for setup type")?
-        {
-            SelectPromptResult::Selected(value) if value == SETUP_TYPE_DEFAULT => {
-                // Nothing to do here, default settings will be used when creating the deployment
-                debug!("Using default settings");
-            }
-            SelectPromptResult::Selected(value) if value == SETUP_TYPE_CUSTOM => {
-                // Prompt for the custom settings (deployment name, MongoDB version, port) if one of the fields is not provided
-                if self.deployment_name.is_none() || self.image_tag.is_none() || self.port.is_none()
-                {
-                    // Prompt for the custom settings
-                    // If the user canceled the prompt, return a failed result
-                    if let PromptCustomSettingsResult::Canceled = self
-                        .prompt_custom_settings()
-                        .context("prompting for custom settings")?
-                    {
-                        return cancelled_message(self.deployment_name.clone());
-                    }
-                }
-            }
-            SelectPromptResult::Canceled | SelectPromptResult::Selected(_) => {
-                // User canceled the setup
-                // Return a failed result
-                return cancelled_message(self.deployment_name.clone());
+            .start_spinner(message(Message::CopyVolume).replace("{volume}", volume))?;
+        let copy_result = self.copy_reused_volume(&deployment_name, volume).await;
+        drop(spinner);
+
+        if let Err(e) = copy_result {
+            let mut error = format!("failed to copy volume '{volume}': {e:#}");
+            debug!(deployment_name, "deleting the new deployment");
+            if let Err(e) = self.deployment_management.delete(&deployment_name).await {
+                error.push_str(&format!(
+                    ", the new deployment '{deployment_name}' could not be deleted: {e}"
+                ));
             }
+            return Ok(SetupResult::Failed {
+                deployment_name: Some(deployment_name),
+                error,
+            });
         }
 
-        Ok(None)
-    }
+        if let Err(e) = wait_for_healthy(
+            self.deployment_management.as_ref(),
+            &self.docker_settings,
+            &deployment_name,
+            WatchOptions::builder()
+                .allow_unhealthy_initial_state(false)
+                .timeout_duration(RESUME_WAIT_FOR_HEALTHY_TIMEOUT)
+                .build(),
+        )
+        .await
+        {
+            return Ok(SetupResult::Failed {
+                deployment_name: Some(deployment_name),
+                error: e.to_string(),
+            });
+        }
+
+        // Get the deployment again, an auto-assigned port is only known once the container is running
+        let deployment = self
+            .deployment_management
+            .get_deployment(&deployment_name)
+            .await
+            .context("getting the deployment with the reused volume")?;
+
+        self.finish_setup(deployment, false).await
+    }
+
+    /// Replace the data directory of the deployment with the files of the volume, and start the deployment again.
+    ///
+    /// MongoDB must not write to the data directory while it is replaced, so the deployment is stopped during the copy.
+    async fn copy_reused_volume(&self, deployment_name: &str, volume: &str) -> Result<()> {
+        self.deployment_management
+            .stop(deployment_name)
+            .await
+            .context("stopping the deployment")?;
+
+        let container = self
+            .container_inspector
+            .inspect_container_raw(deployment_name)
+            .await
+            .map_err(DockerError::from)
+            .context("inspecting the container of the deployment")?
+            .with_context(|| format!("deployment '{deployment_name}' not found"))?;
+
+        // The helper container runs the image of the deployment, it is pulled already
+        let image = container
+            .config
+            .as_ref()
+            .and_then(|config| config.image.clone())
+            .context("the container of the deployment has no image")?;
+        let data_volume = volumes_by_destination(&container)
+            .remove(DATA_DIRECTORY)
+            .with_context(|| format!("the deployment has no volume mounted on {DATA_DIRECTORY}"))?;
+
+        debug!(volume, data_volume, "copying the volume to reuse");
+        self.volume_management
+            .copy_volume(&image, volume, &data_volume)
+            .await
+            .map_err(DockerError::from)
+            .with_context(|| format!("copying the volume into {DATA_DIRECTORY}"))?;
+
+        self.deployment_management
+            .start(deployment_name)
+            .await
+            .context("starting the deployment")?;
+
+        Ok(())
+    }
+
+    /// Make sure that binding an unauthenticated deployment to all interfaces is intentional.
+    ///
+    /// Returns a failed result when it isn't confirmed, with --force it has to be confirmed with --iUnderstand.
+    fn confirm_network_exposure(&self) -> Result<Option<SetupResult>> {
+        if !self.bind_ip_all || self.username.is_some() || self.i_understand {
+            return Ok(None);
+        }
+
+        if self.force {
+            return Ok(Some(SetupResult::Failed {
+                deployment_name: self.deployment_name.clone(),
+                error: "--bindIpAll without --username exposes a deployment without authentication to the network, use --iUnderstand to proceed anyway".to_string(),
+            }));
+        }
+
+        let confirmation = self
+            .interaction
+            .confirm(
+                ConfirmationPromptOptions::builder()
+                    .pre_confirmation_help_text(
+                        "The deployment is reachable from the network and has no authentication, anyone who can reach this machine can access its data.".to_string(),
+                    )
+                    .message("Are you sure you want to bind the deployment to all interfaces?".to_string())
+                    .default(false)
+                    .build(),
+            )
+            .context("confirming binding to all interfaces")?;
+
+        match confirmation {
+            ConfirmationPromptResult::Yes => Ok(None),
+            ConfirmationPromptResult::No | ConfirmationPromptResult::Canceled => {
+                Ok(Some(SetupResult::Canceled {
+                    deployment_name: self.deployment_name.clone(),
+                    reason: CancelReason::Prompt,
+                }))
+            }
+        }
+    }
+
+    /// Warning when the requested MongoDB version reached its end of life.
+    fn end_of_life_warning(&self) -> Option<String> {
+        self.image_tag
+            .as_ref()
+            .and_then(validators::end_of_life_warning)
+    }
+
+    /// Resume the setup of a deployment that was interrupted after its container was created.
+    ///
+    /// Returns `None` when there is no created or exited deployment with the requested name, the deployment is then created as usual.
+    async fn resume_deployment(&self) -> Result<Option<SetupResult>> {
+        let Some(deployment_name) = self.deployment_name.clone() else {
+            return Ok(None);
+        };
+
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &deployment_name).await?
+        else {
+            debug!(deployment_name, "no deployment to resume");
+            return Ok(None);
+        };
+
+        // Only deployments that never became healthy are resumed, other states keep the default behavior
+        if !matches!(deployment.state, State::Created | State::Exited) {
+            debug!(deployment_name, state = ?deployment.state, "deployment can't be resumed");
+            return Ok(None);
+        }
+
+        // The image of the container is compared with the requested image and tag
+        let container_image = self
+            .container_inspector
+            .inspect_container_raw(&deployment_name)
+            .await
+            .map_err(DockerError::from)
+            .context("inspecting the container of the deployment")?
+            .and_then(|container| container.config)
+            .and_then(|config| config.image);
+
+        if let Some(setting) = self.resume_config_mismatch(&deployment, container_image.as_deref())
+        {
+            return Ok(Some(SetupResult::Failed {
+                deployment_name: Some(deployment_name),
+                error: format!(
+                    "Existing deployment can't be resumed, its {setting} does not match the requested {setting}"
+                ),
+            }));
+        }
+
+        let mut multi_step_spinner = self.interaction.start_multi_step_spinner(vec![
+            MultiStepSpinnerStep::new(message(Message::SetupStartDeployment)),
+            MultiStepSpinnerStep::new(message(Message::SetupWaitForHealthy)),
+        ])?;
+
+        if let Err(e) = self.deployment_management.start(&deployment_name).await {
+            multi_step_spinner.set_step_outcome(0, MultiStepSpinnerOutcome::Failure)?;
+            return Ok(Some(SetupResult::Failed {
+                deployment_name: Some(deployment_name),
+                error: e.to_string(),
+            }));
+        }
+        multi_step_spinner.set_step_outcome(0, MultiStepSpinnerOutcome::Success)?;
+
+        if let Err(e) = wait_for_healthy(
+            self.deployment_management.as_ref(),
+            &self.docker_settings,
+            &deployment_name,
+            WatchOptions::builder()
+                .allow_unhealthy_initial_state(false)
+                .timeout_duration(RESUME_WAIT_FOR_HEALTHY_TIMEOUT)
+                .build(),
+        )
+        .await
+        {
+            multi_step_spinner.set_step_outcome(1, MultiStepSpinnerOutcome::Failure)?;
+            return Ok(Some(SetupResult::Failed {
+                deployment_name: Some(deployment_name),
+                error: e.to_string(),
+            }));
+        }
+        multi_step_spinner.set_step_outcome(1, MultiStepSpinnerOutcome::Success)?;
+
+        // Get the deployment again, an auto-assigned port is only known once the container is running
+        let deployment = self
+            .deployment_management
+            .get_deployment(&deployment_name)
+            .await
+            .context("getting resumed deployment")?;
+
+        Ok(Some(self.finish_setup(deployment, true).await?))
+    }
+
+    /// Returns the first setting of the existing deployment that doesn't match the requested settings.
+    ///
+    /// Settings that were not provided are not compared, the deployment is created with defaults for them.
+    /// `container_image` is the image reference of the container of the deployment.
+    fn resume_config_mismatch(
+        &self,
+        deployment: &Deployment,
+        container_image: Option<&str>,
+    ) -> Option<&'static str> {
+        let (container_image, container_tag) = match container_image.map(split_image_reference) {
+            Some((image, tag)) => (Some(image), tag),
+            None => (None, None),
+        };
+
+        if (self.image.is_some() || self.registry.is_some())
+            && self.effective_image().as_deref() != container_image
+        {
+            return Some("image");
+        }
+
+        // --mdbVersion and the preview setting both select the image tag
+        if self.image_tag.as_ref().is_some_and(|image_tag| {
+            !image_tag_matches(image_tag, &deployment.mongodb_version, container_tag)
+        }) {
+            return Some("MongoDB version");
+        }
+
+        let port_binding = deployment.port_bindings.as_ref();
+
+        if self.port.is_some() && self.port != port_binding.and_then(|binding| binding.port) {
+            return Some("port");
+        }
+
+        let binds_all_interfaces = port_binding
+            .is_some_and(|binding| matches!(binding.binding_type, BindingType::AnyInterface));
+        if self.bind_ip_all != binds_all_interfaces {
+            return Some("bind address");
+        }
+
+        if self.username.is_some() && self.username != deployment.mongodb_initdb_root_username {
+            return Some("username");
+        }
+
+        if self
+            .load_sample_data
+            .is_some_and(|load| load != deployment.mongodb_load_sample_data.unwrap_or(false))
+        {
+            return Some("load sample data setting");
+        }
+
+        let initdb = self
+            .initdb
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string());
+        if initdb.is_some() && initdb != deployment.local_seed_location {
+            return Some("initdb folder");
+        }
+
+        None
+    }
+
+    /// The image the deployment is created from, in the registry selected with --registry.
+    ///
+    /// Returns None to use the default image.
+    fn effective_image(&self) -> Option<String> {
+        match &self.registry {
+            Some(registry) => Some(image_in_registry(
+                self.image.as_deref().unwrap_or(DEFAULT_IMAGE),
+                registry,
+            )),
+            None => self.image.clone(),
+        }
+    }
+
+    /// The image and tag the deployment is created from.
+    fn image_reference(&self) -> (String, String) {
+        (
+            self.effective_image()
+                .unwrap_or_else(|| DEFAULT_IMAGE.to_string()),
+            self.image_tag
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| DEFAULT_IMAGE_TAG.to_string()),
+        )
+    }
+
+    /// Check whether the image is available locally
+    ///
+    /// Returns None when the pull policy is always, the image is pulled regardless.
+    async fn image_available_locally(&self) -> Result<Option<bool>> {
+        if self.pull_policy == PullPolicy::Always {
+            return Ok(None);
+        }
+
+        let (image, tag) = self.image_reference();
+        let exists = self
+            .image_checker
+            .image_exists(&image, &tag)
+            .await
+            .map_err(DockerError::from)
+            .context("checking whether the image is available locally")?;
+        debug!(
+            image,
+            tag, exists, "checked whether the image is available locally"
+        );
+
+        Ok(Some(exists))
+    }
+
+    /// Verify that the initdb path exists and resolve it to an absolute path
+    ///
+    /// Docker only accepts absolute paths for bind mounts, so relative paths are resolved against the current directory.
+    /// Returns Some(SetupResult::Failed) when the path can't be used.
+    async fn resolve_initdb(&mut self) -> Result<Option<SetupResult>> {
+        let Some(initdb) = self.initdb.clone() else {
+            return Ok(None);
+        };
+
+        let error = match self
+            .path_resolver
+            .path_kind(&initdb)
+            .await
+            .context("checking the initdb path")?
+        {
+            Some(PathKind::File | PathKind::Directory) => None,
+            Some(PathKind::Other) => Some(format!(
+                "initdb path {} is not a file or directory",
+                initdb.display()
+            )),
+            None => Some(format!("initdb path {} does not exist", initdb.display())),
+        };
+
+        if let Some(error) = error {
+            return Ok(Some(SetupResult::Failed {
+                deployment_name: self.deployment_name.clone(),
+                error,
+            }));
+        }
+
+        let resolved = self
+            .path_resolver
+            .canonicalize(&initdb)
+            .await
+            .context("resolving the initdb path")?;
+        debug!(initdb = %resolved.display(), "resolved initdb path");
+        self.initdb = Some(resolved);
+
+        Ok(None)
+    }
+
+    fn prompt_settings(&mut self) -> Result<Option<SetupResult>> {
+        // Prompt the user for the setup type
+        // There are three options: default, custom, and cancel
+        // - Default: Use default settings, no need to prompt for missing settings, we can pick the defaults
+        // - Custom: Prompt for missing settings, we need to prompt for the missing settings
+        // - Cancel: Cancel the setup
+
+        // Build the select options
+        let select_options = SelectPromptOptions::builder()
+            .message("How do you want to set up your local Atlas deployment?")
+            .options(vec![
+                SETUP_TYPE_DEFAULT,
+                SETUP_TYPE_CUSTOM,
+                SETUP_TYPE_CANCEL,
+            ])
+            .build();
+
+        // Create a closure to return the cancelled message
+        let cancelled_message = |deployment_name: Option<String>| -> Result<Option<SetupResult>> {
+            Ok(Some(SetupResult::Canceled {
+                deployment_name,
+                reason: CancelReason::Prompt,
+            }))
+        };
+
+        // Prompt the user for the setup type and handle the result
+        match self
+            .interaction
+            .select(select_options)
+            .context("failed to prompt for setup type")?
+        {
+            SelectPromptResult::Selected(value) if value == SETUP_TYPE_DEFAULT => {
+                // Nothing to do here, default settings will be used when creating the deployment
+                debug!("Using default settings");
+            }
+            SelectPromptResult::Selected(value) if value == SETUP_TYPE_CUSTOM => {
+                // Prompt for the custom settings (deployment name, MongoDB version, port) if one of the fields is not provided
+                if self.deployment_name.is_none() || self.image_tag.is_none() || self.port.is_none()
+                {
+                    // Prompt for the custom settings
+                    // If the user canceled the prompt, return a failed result
+                    if let PromptCustomSettingsResult::Canceled = self
+                        .prompt_custom_settings()
+                        .context("prompting for custom settings")?
+                    {
+                        return cancelled_message(self.deployment_name.clone());
+                    }
+                }
+            }
+            SelectPromptResult::Canceled | SelectPromptResult::Selected(_) => {
+                // User canceled the setup
+                // Return a failed result
+                return cancelled_message(self.deployment_name.clone());
+            }
+        }
+
+        Ok(None)
+    }
 
     /// Prompt for the custom settings
     /// These custom settings are:
@@ -550,9 +1519,12 @@ impl Setup {
         let connect_with = if let Some(connect_with) = &self.connect_with {
             // If connect_with was provided via CLI, use it directly
             Some(*connect_with)
+        } else if self.connection_string_file.is_some() {
+            // The connection string is needed for the file, return it instead of prompting
+            Some(ConnectWith::ConnectionString)
         } else if self.force {
-            // If force flag is set and no connect_with provided, skip connection
-            None
+            // If force flag is set and no connect_with provided, use the default connector or skip connection
+            self.default_connector
         } else {
             // Prompt the user to select a connection method
             self.prompt_connection_method()?
@@ -570,9 +1542,35 @@ impl Setup {
             .await
             .context("getting connection string")?;
 
+        // Tailor the connection string using the --connectHost, --uriTemplate, --appendOption and --defaultDb flags
+        let connection_string = match self.connection_string_options.apply(&connection_string) {
+            Ok(connection_string) => connection_string,
+            Err(e) => {
+                return Ok(Some(ConnectResult::Failed {
+                    error: format!("{e:#}"),
+                }));
+            }
+        };
+
+        if let Some(connection_string_file) = &self.connection_string_file {
+            debug!(path = %connection_string_file.display(), "writing connection string to file");
+            self.file_writer
+                .write_string(connection_string_file, &connection_string)
+                .await
+                .with_context(|| {
+                    format!(
+                        "writing connection string to {}",
+                        connection_string_file.display()
+                    )
+                })?;
+        }
+
         // If the connector is ConnectionString, return the connection string
+        // With --hideConnectionString, connectors and the file still get the real connection string
         if connect_with == ConnectWith::ConnectionString {
-            return Ok(Some(ConnectResult::ConnectionString { connection_string }));
+            return Ok(Some(ConnectResult::ConnectionString {
+                connection_string: self.connection_string_options.shown(&connection_string),
+            }));
         }
 
         // Get the connector
@@ -594,11 +1592,16 @@ impl Setup {
             }));
         }
 
-        // Launch the connector
-        connector
-            .launch(&DeploymentParams::new(deployment_name, &connection_string))
-            .await
-            .context("launching connector")?;
+        // Launch the connector, if the connector exits with a non-zero exit code, exit with the same code
+        exit_on_connector_exit(
+            connector
+                .launch(
+                    &DeploymentParams::new(deployment_name, &connection_string),
+                    &self.connector_options,
+                )
+                .await,
+        )
+        .context("launching connector")?;
 
         let method = match connect_with {
             ConnectWith::Compass => "Compass",
@@ -614,15 +1617,30 @@ impl Setup {
 
     /// Prompt the user to select a connection method
     fn prompt_connection_method(&self) -> Result<Option<ConnectWith>> {
+        let options = vec![
+            CONNECT_WITH_COMPASS,
+            CONNECT_WITH_MONGOSH,
+            CONNECT_WITH_VSCODE,
+            CONNECT_WITH_CONNECTION_STRING,
+            CONNECT_WITH_SKIP,
+        ];
+
+        // Pre-select the default connector, the first option otherwise
+        let starting_cursor = self
+            .default_connector
+            .map(|connect_with| match connect_with {
+                ConnectWith::Compass => CONNECT_WITH_COMPASS,
+                ConnectWith::Mongosh => CONNECT_WITH_MONGOSH,
+                ConnectWith::VsCode => CONNECT_WITH_VSCODE,
+                ConnectWith::ConnectionString => CONNECT_WITH_CONNECTION_STRING,
+            })
+            .and_then(|default_option| options.iter().position(|o| *o == default_option))
+            .unwrap_or_default();
+
         let select_options = SelectPromptOptions::builder()
             .message("How do you want to connect to your local Atlas deployment?")
-            .options(vec![
-                CONNECT_WITH_COMPASS,
-                CONNECT_WITH_MONGOSH,
-                CONNECT_WITH_VSCODE,
-                CONNECT_WITH_CONNECTION_STRING,
-                CONNECT_WITH_SKIP,
-            ])
+            .options(options)
+            .starting_cursor(starting_cursor)
             .build();
 
         match self
@@ -673,18 +1691,25 @@ const CONNECT_WITH_SKIP: &str = "Skip";
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::mocks::MockDocker;
+    use crate::dependencies::ExecOutput;
+    use crate::dependencies::mocks::{MockDocker, MockMongoDB, MockTokioFs};
     use crate::interaction::mocks::MockInteraction;
-    use crate::interaction::{MultiStepSpinner, MultiStepSpinnerOutcome};
+    use crate::interaction::{MultiStepSpinner, MultiStepSpinnerOutcome, SpinnerHandle};
     use atlas_local::{
+        GetDeploymentError,
         client::{CreateDeploymentProgress, CreateDeploymentStepOutcome},
         models::{
             BindingType, CreationSource, Deployment as AtlasDeployment, ImageTag, MongodbType,
         },
     };
+    use bollard::errors::Error as BollardError;
+    use bollard::models::{
+        ContainerConfig, ContainerInspectResponse, MountPoint, MountPointTypeEnum,
+    };
     use futures_util::FutureExt;
     use mockall::mock;
     use semver::Version;
+    use std::path::Path;
     use std::sync::Arc;
 
     mock! {
@@ -693,7 +1718,7 @@ mod tests {
         #[async_trait]
         impl Connector for Connector {
             async fn is_available(&self) -> bool;
-            async fn launch(&self, params: &DeploymentParams) -> Result<()>;
+            async fn launch(&self, params: &DeploymentParams, options: &ConnectorOptions) -> Result<()>;
         }
     }
 
@@ -806,7 +1831,7 @@ mod tests {
         username: Option<String>,
         password: Option<String>,
         interaction: Box<dyn SetupInteraction + Send>,
-        deployment_management: Box<dyn SetupDeploymentManagement + Send>,
+        deployment_management: Box<dyn SetupDeploymentManagement + Send + Sync>,
     ) -> Setup {
         create_setup_command_with_connectors(
             deployment_name,
@@ -842,27 +1867,49 @@ mod tests {
         connect_with: Option<ConnectWith>,
         voyage_api_key: Option<String>,
         interaction: Box<dyn SetupInteraction + Send>,
-        deployment_management: Box<dyn SetupDeploymentManagement + Send>,
+        deployment_management: Box<dyn SetupDeploymentManagement + Send + Sync>,
         connectors: HashMap<ConnectWith, Box<dyn Connector + Send + Sync>>,
     ) -> Setup {
         Setup {
             deployment_name,
             image_tag,
+            fail_on_eol: false,
             use_preview,
             voyage_api_key,
             port,
             bind_ip_all,
+            i_understand: false,
             initdb,
             force,
             load_sample_data,
+            datasets: vec![],
             username,
             password,
             image: None,
-            skip_pull_image: false,
+            registry: None,
+            pull_policy: PullPolicy::Always,
             connect_with,
+            default_connector: None,
+            connection_string_options: ConnectionStringOptions::default(),
+            connector_options: ConnectorOptions::new(),
+            wait_for_primary: false,
+            resume: false,
+            reuse_volume: None,
+            step_stream: None,
+            connection_string_file: None,
+            deployments_file: None,
             interaction,
             deployment_management,
+            docker_settings: DockerSettings::default(),
             connectors,
+            primary_waiter: Box::new(MockMongoDB::new()),
+            path_resolver: Box::new(MockTokioFs::new()),
+            file_writer: Box::new(MockTokioFs::new()),
+            image_checker: Box::new(MockDocker::new()),
+            container_inspector: Box::new(MockDocker::new()),
+            executor: Box::new(MockDocker::new()),
+            volume_management: Box::new(MockDocker::new()),
+            config_fs: Box::new(MockTokioFs::new()),
         }
     }
 
@@ -964,35 +2011,36 @@ mod tests {
                 mongodb_version: version,
                 port: 27017,
                 load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
                 connect_result: Some(ConnectResult::Skipped),
             }
         );
         verify_all_steps_succeeded(&outcomes);
     }
 
+    /// Writer that keeps the written bytes, so the streamed step outcomes can be inspected.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
     #[tokio::test]
-    async fn test_setup_without_force_flag_selects_default_settings() {
+    async fn test_setup_with_stream_writes_step_outcomes() {
         let deployment_name = "test-deployment".to_string();
         let version = Version::parse("8.2.2").unwrap();
 
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mut mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
-        // Two selects: setup type and connection method
-        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
-        mock_interaction
-            .expect_select()
-            .times(2)
-            .returning(move |_| {
-                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                match count {
-                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
-                        SETUP_TYPE_DEFAULT.to_string(),
-                    )),
-                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
-                        CONNECT_WITH_SKIP.to_string(),
-                    )),
-                }
-            });
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
@@ -1001,35 +2049,23 @@ mod tests {
             Some(27017),
             Some(false),
         );
-        let progress = create_successful_progress(deployment);
-        let expected_name = deployment_name.clone();
+        let progress = create_mock_progress(
+            CreateDeploymentStepOutcome::Skipped,
+            CreateDeploymentStepOutcome::Success,
+            CreateDeploymentStepOutcome::Success,
+            CreateDeploymentStepOutcome::Success,
+            Ok(deployment),
+        );
         mock_deployment_management
             .expect_create_deployment()
-            .return_once(move |options| {
-                assert_eq!(options.name, Some(expected_name));
-                assert_eq!(options.image_tag, None);
-                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
-                assert_eq!(options.wait_until_healthy, Some(true));
-                assert_eq!(options.local_seed_location, None);
-                assert_eq!(options.mongodb_initdb_root_username, None);
-                assert_eq!(options.mongodb_initdb_root_password, None);
-                assert_eq!(options.load_sample_data, None);
-                assert!(options.mongodb_port_binding.is_some());
-                if let Some(binding) = &options.mongodb_port_binding {
-                    assert_eq!(binding.port, None);
-                    assert!(matches!(binding.binding_type, BindingType::Loopback));
-                }
-                assert_eq!(options.image, None);
-                assert_eq!(options.skip_pull_image, Some(false));
-                progress
-            });
+            .return_once(move |_| progress);
 
         let mut setup_command = create_setup_command(
             Some(deployment_name.clone()),
-            None,
-            None,
-            false,
-            None,
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
             false,
             None,
             None,
@@ -1037,228 +2073,126 @@ mod tests {
             Box::new(mock_interaction),
             Box::new(mock_deployment_management),
         );
+        let step_stream = SharedBuffer::default();
+        setup_command.step_stream = Some(Box::new(step_stream.clone()));
 
-        let result = setup_command
+        setup_command
             .execute()
             .await
             .expect("execute should succeed");
 
+        // The steps are reported to the spinner and streamed
         assert_eq!(
-            result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27017,
-                load_sample_data: false,
-                connect_result: Some(ConnectResult::Skipped),
-            }
+            outcomes.lock().unwrap().len(),
+            4,
+            "Expected 4 spinner steps"
         );
-    }
+        assert_eq!(
+            String::from_utf8(step_stream.0.lock().unwrap().clone()).unwrap(),
+            concat!(
+                "{\"step\":\"pull_image\",\"outcome\":\"skipped\"}\n",
+                "{\"step\":\"create_container\",\"outcome\":\"success\"}\n",
+                "{\"step\":\"start_container\",\"outcome\":\"success\"}\n",
+                "{\"step\":\"wait_for_healthy\",\"outcome\":\"success\"}\n",
+            )
+        );
+    }
 
     #[tokio::test]
-    async fn test_setup_without_force_flag_prompts_for_custom_settings() {
-        let deployment_name = "custom-deployment".to_string();
-        let version = Version::parse("8.0.0").unwrap();
-
-        let mut mock_interaction = MockInteraction::new();
-        // Two selects: setup type and connection method
-        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
-        mock_interaction
-            .expect_select()
-            .times(2)
-            .returning(move |_| {
-                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                match count {
-                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
-                        SETUP_TYPE_CUSTOM.to_string(),
-                    )),
-                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
-                        CONNECT_WITH_SKIP.to_string(),
-                    )),
-                }
-            });
-
-        mock_interaction
-            .expect_input()
-            .times(4)
-            .returning(|options| match options.message.as_str() {
-                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "custom-deployment".to_string(),
-                )),
-                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "8.0".to_string(),
-                )),
-                "Port?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "27018".to_string(),
-                )),
-                "Would you like to load sample data? (y/N)" => Ok(
-                    crate::interaction::InputPromptResult::Input("y".to_string()),
-                ),
-                _ => panic!("Unexpected prompt: {}", options.message),
-            });
+    async fn test_setup_with_initdb_resolves_absolute_path() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
 
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let outcomes_clone = outcomes.clone();
-        mock_interaction
-            .expect_start_multi_step_spinner()
-            .return_once(move |_| {
-                Ok(Box::new(MockMultiStepSpinner {
-                    outcomes: outcomes_clone,
-                }))
-            });
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_path_resolver = MockTokioFs::new();
+        mock_path_resolver
+            .expect_path_kind()
+            .withf(|path| path == Path::new("./seed"))
+            .return_once(|_| Ok(Some(PathKind::Directory)));
+        mock_path_resolver
+            .expect_canonicalize()
+            .withf(|path| path == Path::new("./seed"))
+            .return_once(|_| Ok(PathBuf::from("/home/user/project/seed")));
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
             Some(deployment_name.clone()),
             version.clone(),
-            Some(27018),
-            Some(true),
+            Some(27017),
+            Some(false),
         );
         let progress = create_successful_progress(deployment);
-        let expected_name = deployment_name.clone();
-        let expected_version = ImageTag::try_from("8.0").unwrap();
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
-                assert_eq!(options.name, Some(expected_name));
-                assert_eq!(options.image_tag, Some(expected_version.clone()));
-                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
-                assert_eq!(options.wait_until_healthy, Some(true));
-                assert_eq!(options.local_seed_location, None);
-                assert_eq!(options.mongodb_initdb_root_username, None);
-                assert_eq!(options.mongodb_initdb_root_password, None);
-                assert_eq!(options.load_sample_data, Some(true));
-                assert!(options.mongodb_port_binding.is_some());
-                if let Some(binding) = &options.mongodb_port_binding {
-                    assert_eq!(binding.port, Some(27018));
-                    assert!(matches!(binding.binding_type, BindingType::Loopback));
-                }
-                assert_eq!(options.image, None);
-                assert_eq!(options.skip_pull_image, Some(false));
+                assert_eq!(
+                    options.local_seed_location,
+                    Some("/home/user/project/seed".to_string())
+                );
                 progress
             });
 
         let mut setup_command = create_setup_command(
-            None,
-            None,
-            None,
-            false,
-            None,
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
             false,
-            None,
+            Some(PathBuf::from("./seed")),
             None,
             None,
             Box::new(mock_interaction),
             Box::new(mock_deployment_management),
         );
+        setup_command.path_resolver = Box::new(mock_path_resolver);
 
         let result = setup_command
             .execute()
             .await
             .expect("execute should succeed");
 
-        assert_eq!(
-            result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27018,
-                load_sample_data: true,
-                connect_result: Some(ConnectResult::Skipped),
-            }
-        );
+        assert!(matches!(result, SetupResult::Setup { .. }));
+        verify_all_steps_succeeded(&outcomes);
     }
 
     #[tokio::test]
-    async fn test_setup_without_force_flag_custom_settings_with_partial_fields_provided() {
-        let deployment_name = "partial-deployment".to_string();
-        let version = Version::parse("8.1.0").unwrap();
-
-        let mut mock_interaction = MockInteraction::new();
-        // Two selects: setup type and connection method
-        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
-        mock_interaction
-            .expect_select()
-            .times(2)
-            .returning(move |_| {
-                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                match count {
-                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
-                        SETUP_TYPE_CUSTOM.to_string(),
-                    )),
-                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
-                        CONNECT_WITH_SKIP.to_string(),
-                    )),
-                }
-            });
-
-        mock_interaction
-            .expect_input()
-            .times(4)
-            .returning(|options| match options.message.as_str() {
-                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "partial-deployment".to_string(),
-                )),
-                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "8.1".to_string(),
-                )),
-                "Port?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "27019".to_string(),
-                )),
-                "Would you like to load sample data? (y/N)" => Ok(
-                    crate::interaction::InputPromptResult::Input("n".to_string()),
-                ),
-                _ => panic!("Unexpected prompt: {}", options.message),
-            });
+    async fn test_setup_with_pull_policy_missing_skips_pull_of_local_image() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
 
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let outcomes_clone = outcomes.clone();
-        mock_interaction
-            .expect_start_multi_step_spinner()
-            .return_once(move |_| {
-                Ok(Box::new(MockMultiStepSpinner {
-                    outcomes: outcomes_clone,
-                }))
-            });
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_image_checker = MockDocker::new();
+        mock_image_checker
+            .expect_image_exists()
+            .withf(|image, tag| image == DEFAULT_IMAGE && tag == "8.2.2")
+            .return_once(|_, _| Ok(true));
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
             Some(deployment_name.clone()),
             version.clone(),
-            Some(27019),
+            Some(27017),
             Some(false),
         );
         let progress = create_successful_progress(deployment);
-        let expected_name = deployment_name.clone();
-        let expected_version = ImageTag::try_from("8.1").unwrap();
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
-                assert_eq!(options.name, Some(expected_name));
-                assert_eq!(options.image_tag, Some(expected_version.clone()));
-                assert_eq!(options.voyage_api_key, None);
-                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
-                assert_eq!(options.wait_until_healthy, Some(true));
-                assert_eq!(options.local_seed_location, None);
-                assert_eq!(options.mongodb_initdb_root_username, None);
-                assert_eq!(options.mongodb_initdb_root_password, None);
-                assert_eq!(options.load_sample_data, Some(false));
-                assert!(options.mongodb_port_binding.is_some());
-                if let Some(binding) = &options.mongodb_port_binding {
-                    assert_eq!(binding.port, Some(27019));
-                    assert!(matches!(binding.binding_type, BindingType::Loopback));
-                }
-                assert_eq!(options.image, None);
-                assert_eq!(options.skip_pull_image, Some(false));
+                assert_eq!(options.skip_pull_image, Some(true));
                 progress
             });
 
         let mut setup_command = create_setup_command(
             Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.1").unwrap()),
-            None,
-            false,
-            None,
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
             false,
             None,
             None,
@@ -1266,79 +2200,46 @@ mod tests {
             Box::new(mock_interaction),
             Box::new(mock_deployment_management),
         );
+        setup_command.pull_policy = PullPolicy::Missing;
+        setup_command.image_checker = Box::new(mock_image_checker);
 
         let result = setup_command
             .execute()
             .await
             .expect("execute should succeed");
 
-        assert_eq!(
-            result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27019,
-                load_sample_data: false,
-                connect_result: Some(ConnectResult::Skipped),
-            }
-        );
+        assert!(matches!(result, SetupResult::Setup { .. }));
     }
 
     #[tokio::test]
-    async fn test_setup_voyage_api_key_passed_to_create_deployment() {
-        let deployment_name = "voyage-test".to_string();
-        let version = Version::parse("8.0.0").unwrap();
-        let voyage_api_key = "super-secret".to_string();
-
-        let mut mock_interaction = MockInteraction::new();
-        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let outcomes_clone = outcomes.clone();
-        mock_interaction
-            .expect_start_multi_step_spinner()
-            .return_once(move |_| {
-                Ok(Box::new(MockMultiStepSpinner {
-                    outcomes: outcomes_clone,
-                }))
-            });
+    async fn test_setup_with_pull_policy_never_fails_without_local_image() {
+        let mut mock_image_checker = MockDocker::new();
+        mock_image_checker
+            .expect_image_exists()
+            .withf(|image, tag| image == "custom/atlas-local" && tag == DEFAULT_IMAGE_TAG)
+            .return_once(|_, _| Ok(false));
 
         let mut mock_deployment_management = MockDocker::new();
-        let deployment = create_deployment(
-            Some(deployment_name.clone()),
-            version.clone(),
-            Some(27017),
-            Some(false),
-        );
-        let progress = create_successful_progress(deployment);
-        let expected_voyage_key = voyage_api_key.clone();
         mock_deployment_management
             .expect_create_deployment()
-            .return_once(move |options| {
-                assert_eq!(
-                    options.voyage_api_key,
-                    Some(expected_voyage_key),
-                    "voyage_api_key from {} must be passed to CreateDeploymentOptions",
-                    env::MONGODB_ATLAS_LOCAL_VOYAGE_API_KEY
-                );
-                progress
-            });
+            .never();
 
-        let mut setup_command = create_setup_command_with_connectors(
-            Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8").unwrap()),
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            None,
             None,
-            Some(27017),
             true,
-            Some(false),
-            false,
             None,
+            false,
             None,
             None,
             None,
-            Some(voyage_api_key),
-            Box::new(mock_interaction),
+            Box::new(MockInteraction::new()),
             Box::new(mock_deployment_management),
-            HashMap::new(),
         );
+        setup_command.image = Some("custom/atlas-local".to_string());
+        setup_command.pull_policy = PullPolicy::Never;
+        setup_command.image_checker = Box::new(mock_image_checker);
 
         let result = setup_command
             .execute()
@@ -1347,93 +2248,45 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27017,
-                load_sample_data: false,
-                connect_result: Some(ConnectResult::Skipped),
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "image custom/atlas-local:latest is not available locally and the pull policy is never".to_string(),
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_without_force_flag_custom_settings_with_all_fields_provided() {
-        let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.0.0").unwrap();
-
-        let mut mock_interaction = MockInteraction::new();
-        // Two selects: setup type and connection method
-        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
-        mock_interaction
-            .expect_select()
-            .times(2)
-            .returning(move |_| {
-                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                match count {
-                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
-                        SETUP_TYPE_CUSTOM.to_string(),
-                    )),
-                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
-                        CONNECT_WITH_SKIP.to_string(),
-                    )),
-                }
-            });
-
-        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let outcomes_clone = outcomes.clone();
-        mock_interaction
-            .expect_start_multi_step_spinner()
-            .return_once(move |_| {
-                Ok(Box::new(MockMultiStepSpinner {
-                    outcomes: outcomes_clone,
-                }))
-            });
+    async fn test_setup_with_registry_uses_image_from_registry() {
+        let mut mock_image_checker = MockDocker::new();
+        mock_image_checker
+            .expect_image_exists()
+            .withf(|image, tag| {
+                image == "mirror.example.com:5000/mongodb/mongodb-atlas-local"
+                    && tag == DEFAULT_IMAGE_TAG
+            })
+            .return_once(|_, _| Ok(false));
 
         let mut mock_deployment_management = MockDocker::new();
-        let deployment = create_deployment(
-            Some(deployment_name.clone()),
-            version.clone(),
-            Some(27017),
-            Some(false),
-        );
-        let progress = create_successful_progress(deployment);
-        let expected_name = deployment_name.clone();
-        let expected_version = ImageTag::try_from("8").unwrap();
         mock_deployment_management
             .expect_create_deployment()
-            .return_once(move |options| {
-                assert_eq!(options.name, Some(expected_name));
-                assert_eq!(options.image_tag, Some(expected_version.clone()));
-                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
-                assert_eq!(options.wait_until_healthy, Some(true));
-                assert_eq!(options.local_seed_location, None);
-                assert_eq!(options.mongodb_initdb_root_username, None);
-                assert_eq!(options.mongodb_initdb_root_password, None);
-                assert_eq!(options.load_sample_data, Some(false));
-                assert!(options.mongodb_port_binding.is_some());
-                if let Some(binding) = &options.mongodb_port_binding {
-                    assert_eq!(binding.port, Some(27017));
-                    assert!(matches!(binding.binding_type, BindingType::Loopback));
-                }
-                assert_eq!(options.image, None);
-                assert_eq!(options.skip_pull_image, Some(false));
-                progress
-            });
+            .never();
 
         let mut setup_command = create_setup_command(
-            Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8").unwrap()),
-            Some(27017),
-            false,
-            Some(false),
+            Some("test-deployment".to_string()),
+            None,
+            None,
+            true,
+            None,
             false,
             None,
             None,
             None,
-            Box::new(mock_interaction),
+            Box::new(MockInteraction::new()),
             Box::new(mock_deployment_management),
         );
+        setup_command.registry = Some("mirror.example.com:5000".to_string());
+        setup_command.pull_policy = PullPolicy::Never;
+        setup_command.image_checker = Box::new(mock_image_checker);
 
         let result = setup_command
             .execute()
@@ -1442,40 +2295,60 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27017,
-                load_sample_data: false,
-                connect_result: Some(ConnectResult::Skipped),
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "image mirror.example.com:5000/mongodb/mongodb-atlas-local:latest is not available locally and the pull policy is never".to_string(),
             }
         );
     }
 
-    // ============================================================================
-    // Cancellation Tests
-    // ============================================================================
+    #[test]
+    fn test_image_in_registry() {
+        assert_eq!(
+            image_in_registry("mongodb/mongodb-atlas-local", "mirror.example.com"),
+            "mirror.example.com/mongodb/mongodb-atlas-local"
+        );
+        // The registry of the image is replaced
+        assert_eq!(
+            image_in_registry(
+                "registry.example.com/team/atlas-local",
+                "localhost:5000/cache"
+            ),
+            "localhost:5000/cache/team/atlas-local"
+        );
+        assert_eq!(
+            image_in_registry("atlas-local", "mirror.example.com"),
+            "mirror.example.com/atlas-local"
+        );
+    }
 
     #[tokio::test]
-    async fn test_setup_user_cancels_at_initial_select_prompt() {
-        let mut mock_interaction = MockInteraction::new();
-        mock_interaction
-            .expect_select()
-            .return_once(|_| Ok(crate::interaction::SelectPromptResult::Canceled));
+    async fn test_setup_with_missing_initdb_path_fails() {
+        let mut mock_path_resolver = MockTokioFs::new();
+        mock_path_resolver
+            .expect_path_kind()
+            .return_once(|_| Ok(None));
+        mock_path_resolver.expect_canonicalize().never();
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
 
         let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
             None,
             None,
+            true,
             None,
             false,
-            None,
-            false,
+            Some(PathBuf::from("./missing")),
             None,
             None,
-            None,
-            Box::new(mock_interaction),
-            Box::new(MockDocker::new()),
+            Box::new(MockInteraction::new()),
+            Box::new(mock_deployment_management),
         );
+        setup_command.path_resolver = Box::new(mock_path_resolver);
 
         let result = setup_command
             .execute()
@@ -1485,37 +2358,33 @@ mod tests {
         assert_eq!(
             result,
             SetupResult::Failed {
-                deployment_name: None,
-                error: "User canceled the setup".to_string(),
+                deployment_name: Some("test-deployment".to_string()),
+                error: "initdb path ./missing does not exist".to_string(),
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_user_cancels_at_deployment_name_prompt() {
-        let mut mock_interaction = MockInteraction::new();
-        mock_interaction.expect_select().return_once(|_| {
-            Ok(crate::interaction::SelectPromptResult::Selected(
-                SETUP_TYPE_CUSTOM.to_string(),
-            ))
-        });
-        mock_interaction
-            .expect_input()
-            .return_once(|_| Ok(crate::interaction::InputPromptResult::Canceled));
+    async fn test_setup_fail_on_eol_version() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
 
         let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            Some(ImageTag::try_from("6.0").unwrap()),
             None,
-            None,
-            None,
-            false,
+            true,
             None,
             false,
             None,
             None,
             None,
-            Box::new(mock_interaction),
-            Box::new(MockDocker::new()),
+            Box::new(MockInteraction::new()),
+            Box::new(mock_deployment_management),
         );
+        setup_command.fail_on_eol = true;
 
         let result = setup_command
             .execute()
@@ -1525,43 +2394,58 @@ mod tests {
         assert_eq!(
             result,
             SetupResult::Failed {
-                deployment_name: None,
-                error: "User canceled the setup".to_string(),
+                deployment_name: Some("test-deployment".to_string()),
+                error: "MongoDB 6 reached its end of life and no longer receives fixes, consider using MongoDB 8 instead".to_string(),
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_user_cancels_at_mongodb_version_prompt() {
-        let mut mock_interaction = MockInteraction::new();
-        mock_interaction.expect_select().return_once(|_| {
-            Ok(crate::interaction::SelectPromptResult::Selected(
-                SETUP_TYPE_CUSTOM.to_string(),
-            ))
-        });
-        mock_interaction.expect_input().times(1).return_once(|_| {
-            Ok(crate::interaction::InputPromptResult::Input(
-                "test".to_string(),
-            ))
-        });
+    async fn test_setup_wait_for_primary_timeout() {
+        let deployment_name = "test-deployment".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
         mock_interaction
-            .expect_input()
-            .times(1)
-            .return_once(|_| Ok(crate::interaction::InputPromptResult::Canceled));
+            .expect_start_spinner()
+            .withf(|msg| msg == "Waiting for deployment to have a primary...")
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            Version::parse("8.2.2").unwrap(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let mut mock_primary_waiter = MockMongoDB::new();
+        mock_primary_waiter
+            .expect_wait_for_primary()
+            .withf(|name, timeout| {
+                name == "test-deployment" && *timeout == WAIT_FOR_PRIMARY_TIMEOUT
+            })
+            .return_once(|_, _| Ok(false));
 
         let mut setup_command = create_setup_command(
-            None,
-            None,
-            None,
-            false,
-            None,
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
             false,
             None,
             None,
             None,
             Box::new(mock_interaction),
-            Box::new(MockDocker::new()),
+            Box::new(mock_deployment_management),
         );
+        setup_command.wait_for_primary = true;
+        setup_command.primary_waiter = Box::new(mock_primary_waiter);
 
         let result = setup_command
             .execute()
@@ -1571,47 +2455,73 @@ mod tests {
         assert_eq!(
             result,
             SetupResult::Failed {
-                deployment_name: Some("test".to_string()),
-                error: "User canceled the setup".to_string(),
+                deployment_name: Some(deployment_name.clone()),
+                error: "Waiting for deployment to have a primary timed out".to_string(),
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_user_cancels_at_port_prompt() {
-        let mut mock_interaction = MockInteraction::new();
-        mock_interaction.expect_select().return_once(|_| {
-            Ok(crate::interaction::SelectPromptResult::Selected(
-                SETUP_TYPE_CUSTOM.to_string(),
-            ))
-        });
+    async fn test_setup_loads_selected_datasets() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
         mock_interaction
-            .expect_input()
+            .expect_start_spinner()
+            .withf(|msg| msg == "Downloading sample data...")
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                // The image doesn't load all datasets, only the selected ones are restored afterwards
+                assert_eq!(options.load_sample_data, Some(false));
+                progress
+            });
+        mock_deployment_management
+            .expect_get_connection_string()
+            .withf(|container_id| container_id == "test-container-id")
+            .return_once(|_| Ok("mongodb://127.0.0.1:27017/?directConnection=true".to_string()));
+
+        let restored = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let exec_restored = restored.clone();
+        let mut mock_executor = MockDocker::new();
+        mock_executor
+            .expect_exec()
             .times(3)
-            .returning(|options| match options.message.as_str() {
-                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "test".to_string(),
-                )),
-                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "8".to_string(),
-                )),
-                "Port?" => Ok(crate::interaction::InputPromptResult::Canceled),
-                _ => panic!("Unexpected prompt: {}", options.message),
+            .returning(move |name, cmd, _| {
+                assert_eq!(name, "test-deployment");
+                if cmd[0] == "mongorestore" {
+                    exec_restored.lock().unwrap().extend(cmd);
+                }
+                Ok(ExecOutput::default())
             });
 
         let mut setup_command = create_setup_command(
-            None,
-            None,
-            None,
-            false,
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
             None,
             false,
             None,
             None,
             None,
             Box::new(mock_interaction),
-            Box::new(MockDocker::new()),
+            Box::new(mock_deployment_management),
         );
+        setup_command.datasets = vec!["sample_mflix".to_string()];
+        setup_command.executor = Box::new(mock_executor);
 
         let result = setup_command
             .execute()
@@ -1620,42 +2530,82 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Failed {
-                deployment_name: Some("test".to_string()),
-                error: "User canceled the setup".to_string(),
+            SetupResult::Setup {
+                deployment_name,
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: true,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
             }
         );
+        assert!(
+            restored
+                .lock()
+                .unwrap()
+                .contains(&"--nsInclude=sample_mflix.*".to_string())
+        );
+        verify_all_steps_succeeded(&outcomes);
     }
 
     #[tokio::test]
-    async fn test_setup_user_cancels_at_load_sample_data_prompt() {
-        let mut mock_interaction = MockInteraction::new();
-        mock_interaction.expect_select().return_once(|_| {
-            Ok(crate::interaction::SelectPromptResult::Selected(
-                SETUP_TYPE_CUSTOM.to_string(),
-            ))
-        });
+    async fn test_setup_without_force_flag_selects_default_settings() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        // Two selects: setup type and connection method
+        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
         mock_interaction
-            .expect_input()
-            .times(4)
-            .returning(|options| match options.message.as_str() {
-                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "test".to_string(),
-                )),
-                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "8".to_string(),
-                )),
-                "Port?" => Ok(crate::interaction::InputPromptResult::Input(
-                    "27017".to_string(),
-                )),
-                "Would you like to load sample data? (y/N)" => {
-                    Ok(crate::interaction::InputPromptResult::Canceled)
+            .expect_select()
+            .times(2)
+            .returning(move |_| {
+                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match count {
+                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
+                        SETUP_TYPE_DEFAULT.to_string(),
+                    )),
+                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
+                        CONNECT_WITH_SKIP.to_string(),
+                    )),
                 }
-                _ => panic!("Unexpected prompt: {}", options.message),
+            });
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        let expected_name = deployment_name.clone();
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert_eq!(options.name, Some(expected_name));
+                assert_eq!(options.image_tag, None);
+                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
+                assert_eq!(options.wait_until_healthy, Some(true));
+                assert_eq!(options.local_seed_location, None);
+                assert_eq!(options.mongodb_initdb_root_username, None);
+                assert_eq!(options.mongodb_initdb_root_password, None);
+                assert_eq!(options.load_sample_data, None);
+                assert!(options.mongodb_port_binding.is_some());
+                if let Some(binding) = &options.mongodb_port_binding {
+                    assert_eq!(binding.port, None);
+                    assert!(matches!(binding.binding_type, BindingType::Loopback));
+                }
+                assert_eq!(options.image, None);
+                assert_eq!(options.skip_pull_image, Some(false));
+                progress
             });
 
         let mut setup_command = create_setup_command(
-            None,
+            Some(deployment_name.clone()),
             None,
             None,
             false,
@@ -1665,7 +2615,7 @@ mod tests {
             None,
             None,
             Box::new(mock_interaction),
-            Box::new(MockDocker::new()),
+            Box::new(mock_deployment_management),
         );
 
         let result = setup_command
@@ -1675,52 +2625,107 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Failed {
-                deployment_name: Some("test".to_string()),
-                error: "User canceled the setup".to_string(),
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
             }
         );
     }
 
-    // ============================================================================
-    // Error Handling Tests
-    // ============================================================================
-
     #[tokio::test]
-    async fn test_setup_handles_deployment_failure() {
-        let deployment_name = "test-deployment".to_string();
+    async fn test_setup_without_force_flag_prompts_for_custom_settings() {
+        let deployment_name = "custom-deployment".to_string();
+        let version = Version::parse("8.0.0").unwrap();
+
+        let mut mock_interaction = MockInteraction::new();
+        // Two selects: setup type and connection method
+        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
+        mock_interaction
+            .expect_select()
+            .times(2)
+            .returning(move |_| {
+                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match count {
+                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
+                        SETUP_TYPE_CUSTOM.to_string(),
+                    )),
+                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
+                        CONNECT_WITH_SKIP.to_string(),
+                    )),
+                }
+            });
+
+        mock_interaction
+            .expect_input()
+            .times(4)
+            .returning(|options| match options.message.as_str() {
+                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "custom-deployment".to_string(),
+                )),
+                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "8.0".to_string(),
+                )),
+                "Port?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "27018".to_string(),
+                )),
+                "Would you like to load sample data? (y/N)" => Ok(
+                    crate::interaction::InputPromptResult::Input("y".to_string()),
+                ),
+                _ => panic!("Unexpected prompt: {}", options.message),
+            });
 
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        let outcomes_clone = outcomes.clone();
+        mock_interaction
+            .expect_start_multi_step_spinner()
+            .return_once(move |_| {
+                Ok(Box::new(MockMultiStepSpinner {
+                    outcomes: outcomes_clone,
+                }))
+            });
 
         let mut mock_deployment_management = MockDocker::new();
-        let expected_name = deployment_name.clone();
-        let progress = create_mock_progress(
-            CreateDeploymentStepOutcome::Failure,
-            CreateDeploymentStepOutcome::Skipped,
-            CreateDeploymentStepOutcome::Skipped,
-            CreateDeploymentStepOutcome::Skipped,
-            Err(CreateDeploymentError::PullImage(
-                atlas_local::client::PullImageError::from(bollard::errors::Error::from(
-                    std::io::Error::new(std::io::ErrorKind::Other, "Failed to pull image"),
-                )),
-            )),
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27018),
+            Some(true),
         );
+        let progress = create_successful_progress(deployment);
+        let expected_name = deployment_name.clone();
+        let expected_version = ImageTag::try_from("8.0").unwrap();
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
                 assert_eq!(options.name, Some(expected_name));
-                assert_eq!(options.image_tag, None);
+                assert_eq!(options.image_tag, Some(expected_version.clone()));
                 assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
                 assert_eq!(options.wait_until_healthy, Some(true));
+                assert_eq!(options.local_seed_location, None);
+                assert_eq!(options.mongodb_initdb_root_username, None);
+                assert_eq!(options.mongodb_initdb_root_password, None);
+                assert_eq!(options.load_sample_data, Some(true));
+                assert!(options.mongodb_port_binding.is_some());
+                if let Some(binding) = &options.mongodb_port_binding {
+                    assert_eq!(binding.port, Some(27018));
+                    assert!(matches!(binding.binding_type, BindingType::Loopback));
+                }
+                assert_eq!(options.image, None);
+                assert_eq!(options.skip_pull_image, Some(false));
                 progress
             });
 
         let mut setup_command = create_setup_command(
-            Some(deployment_name.clone()),
             None,
             None,
-            true,
+            None,
+            false,
             None,
             false,
             None,
@@ -1735,62 +2740,110 @@ mod tests {
             .await
             .expect("execute should succeed");
 
-        match result {
-            SetupResult::Failed {
-                deployment_name: name,
-                error,
-            } => {
-                assert_eq!(name, Some(deployment_name));
-                assert!(!error.is_empty());
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27018,
+                load_sample_data: true,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
             }
-            _ => panic!("Expected Failed result, got {:?}", result),
-        }
+        );
     }
 
-    // This test should never happen, but we test it for completeness
     #[tokio::test]
-    async fn test_setup_handles_receive_deployment_error() {
-        let deployment_name = "test-deployment".to_string();
+    async fn test_setup_without_force_flag_custom_settings_with_partial_fields_provided() {
+        let deployment_name = "partial-deployment".to_string();
+        let version = Version::parse("8.1.0").unwrap();
+
+        let mut mock_interaction = MockInteraction::new();
+        // Two selects: setup type and connection method
+        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
+        mock_interaction
+            .expect_select()
+            .times(2)
+            .returning(move |_| {
+                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match count {
+                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
+                        SETUP_TYPE_CUSTOM.to_string(),
+                    )),
+                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
+                        CONNECT_WITH_SKIP.to_string(),
+                    )),
+                }
+            });
+
+        mock_interaction
+            .expect_input()
+            .times(4)
+            .returning(|options| match options.message.as_str() {
+                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "partial-deployment".to_string(),
+                )),
+                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "8.1".to_string(),
+                )),
+                "Port?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "27019".to_string(),
+                )),
+                "Would you like to load sample data? (y/N)" => Ok(
+                    crate::interaction::InputPromptResult::Input("n".to_string()),
+                ),
+                _ => panic!("Unexpected prompt: {}", options.message),
+            });
 
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        let outcomes_clone = outcomes.clone();
+        mock_interaction
+            .expect_start_multi_step_spinner()
+            .return_once(move |_| {
+                Ok(Box::new(MockMultiStepSpinner {
+                    outcomes: outcomes_clone,
+                }))
+            });
 
         let mut mock_deployment_management = MockDocker::new();
-        use tokio::sync::oneshot;
-        let (pull_s, pull_r) = oneshot::channel();
-        let _ = pull_s.send(CreateDeploymentStepOutcome::Success);
-        let (create_s, create_r) = oneshot::channel();
-        let _ = create_s.send(CreateDeploymentStepOutcome::Success);
-        let (start_s, start_r) = oneshot::channel();
-        let _ = start_s.send(CreateDeploymentStepOutcome::Success);
-        let (healthy_s, healthy_r) = oneshot::channel();
-        let _ = healthy_s.send(CreateDeploymentStepOutcome::Success);
-        let (deploy_s, deploy_r) = oneshot::channel();
-        drop(deploy_s); // Simulate error by dropping sender
-
-        let progress = CreateDeploymentProgress {
-            pull_image_finished: pull_r.fuse(),
-            create_container_finished: create_r.fuse(),
-            start_container_finished: start_r.fuse(),
-            wait_for_healthy_deployment_finished: healthy_r.fuse(),
-            deployment: deploy_r.fuse(),
-        };
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27019),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
         let expected_name = deployment_name.clone();
+        let expected_version = ImageTag::try_from("8.1").unwrap();
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
                 assert_eq!(options.name, Some(expected_name));
-                assert_eq!(options.image_tag, None);
+                assert_eq!(options.image_tag, Some(expected_version.clone()));
+                assert_eq!(options.voyage_api_key, None);
                 assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
                 assert_eq!(options.wait_until_healthy, Some(true));
+                assert_eq!(options.local_seed_location, None);
+                assert_eq!(options.mongodb_initdb_root_username, None);
+                assert_eq!(options.mongodb_initdb_root_password, None);
+                assert_eq!(options.load_sample_data, Some(false));
+                assert!(options.mongodb_port_binding.is_some());
+                if let Some(binding) = &options.mongodb_port_binding {
+                    assert_eq!(binding.port, Some(27019));
+                    assert!(matches!(binding.binding_type, BindingType::Loopback));
+                }
+                assert_eq!(options.image, None);
+                assert_eq!(options.skip_pull_image, Some(false));
                 progress
             });
 
         let mut setup_command = create_setup_command(
-            Some(deployment_name),
-            None,
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.1").unwrap()),
             None,
-            true,
+            false,
             None,
             false,
             None,
@@ -1800,33 +2853,43 @@ mod tests {
             Box::new(mock_deployment_management),
         );
 
-        let result = setup_command.execute().await;
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
 
-        assert!(
-            result.is_err(),
-            "Expected error when deployment receiver is dropped"
-        );
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("receiving deployment outcome"),
-            "Error message should mention receiving deployment outcome"
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27019,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
+            }
         );
     }
 
-    // ============================================================================
-    // Option-Specific Tests
-    // ============================================================================
-
     #[tokio::test]
-    async fn test_setup_with_initdb_path() {
-        let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.2.2").unwrap();
-        let initdb_path = std::path::PathBuf::from("/tmp/test-initdb");
+    async fn test_setup_port_from_env_passed_to_port_binding() {
+        let deployment_name = "port-env-test".to_string();
+        let version = Version::parse("8.0.0").unwrap();
+        // The value as it would be read from MONGODB_ATLAS_LOCAL_PORT when --port is absent.
+        let port = parse_port("27017").expect("port should be valid");
 
+        let mut mock_interaction = MockInteraction::new();
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        let outcomes_clone = outcomes.clone();
+        mock_interaction
+            .expect_start_multi_step_spinner()
+            .return_once(move |_| {
+                Ok(Box::new(MockMultiStepSpinner {
+                    outcomes: outcomes_clone,
+                }))
+            });
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
@@ -1839,25 +2902,35 @@ mod tests {
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
+                let binding = options
+                    .mongodb_port_binding
+                    .as_ref()
+                    .expect("port binding should be set");
                 assert_eq!(
-                    options.local_seed_location,
-                    Some("/tmp/test-initdb".to_string())
+                    binding.port,
+                    Some(27017),
+                    "port from {} must be passed to MongoDBPortBinding",
+                    env::MONGODB_ATLAS_LOCAL_PORT
                 );
                 progress
             });
 
-        let mut setup_command = create_setup_command(
+        let mut setup_command = create_setup_command_with_connectors(
             Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
-            Some(27017),
+            Some(ImageTag::try_from("8").unwrap()),
+            None,
+            port,
             true,
             Some(false),
             false,
-            Some(initdb_path),
+            None,
+            None,
+            None,
             None,
             None,
             Box::new(mock_interaction),
             Box::new(mock_deployment_management),
+            HashMap::new(),
         );
 
         let result = setup_command
@@ -1872,18 +2945,30 @@ mod tests {
                 mongodb_version: version,
                 port: 27017,
                 load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
                 connect_result: Some(ConnectResult::Skipped),
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_with_username_and_password() {
-        let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.2.2").unwrap();
+    async fn test_setup_voyage_api_key_passed_to_create_deployment() {
+        let deployment_name = "voyage-test".to_string();
+        let version = Version::parse("8.0.0").unwrap();
+        let voyage_api_key = "super-secret".to_string();
 
+        let mut mock_interaction = MockInteraction::new();
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        let outcomes_clone = outcomes.clone();
+        mock_interaction
+            .expect_start_multi_step_spinner()
+            .return_once(move |_| {
+                Ok(Box::new(MockMultiStepSpinner {
+                    outcomes: outcomes_clone,
+                }))
+            });
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
@@ -1893,32 +2978,35 @@ mod tests {
             Some(false),
         );
         let progress = create_successful_progress(deployment);
+        let expected_voyage_key = voyage_api_key.clone();
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
                 assert_eq!(
-                    options.mongodb_initdb_root_username,
-                    Some("admin".to_string())
-                );
-                assert_eq!(
-                    options.mongodb_initdb_root_password,
-                    Some("password".to_string())
+                    options.voyage_api_key,
+                    Some(expected_voyage_key),
+                    "voyage_api_key from {} must be passed to CreateDeploymentOptions",
+                    env::MONGODB_ATLAS_LOCAL_VOYAGE_API_KEY
                 );
                 progress
             });
 
-        let mut setup_command = create_setup_command(
+        let mut setup_command = create_setup_command_with_connectors(
             Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(ImageTag::try_from("8").unwrap()),
+            None,
             Some(27017),
             true,
             Some(false),
             false,
             None,
-            Some("admin".to_string()),
-            Some("password".to_string()),
+            None,
+            None,
+            None,
+            Some(voyage_api_key),
             Box::new(mock_interaction),
             Box::new(mock_deployment_management),
+            HashMap::new(),
         );
 
         let result = setup_command
@@ -1933,18 +3021,46 @@ mod tests {
                 mongodb_version: version,
                 port: 27017,
                 load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
                 connect_result: Some(ConnectResult::Skipped),
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_with_bind_ip_all() {
+    async fn test_setup_without_force_flag_custom_settings_with_all_fields_provided() {
         let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.2.2").unwrap();
+        let version = Version::parse("8.0.0").unwrap();
+
+        let mut mock_interaction = MockInteraction::new();
+        // Two selects: setup type and connection method
+        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
+        mock_interaction
+            .expect_select()
+            .times(2)
+            .returning(move |_| {
+                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match count {
+                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
+                        SETUP_TYPE_CUSTOM.to_string(),
+                    )),
+                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
+                        CONNECT_WITH_SKIP.to_string(),
+                    )),
+                }
+            });
 
         let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        let outcomes_clone = outcomes.clone();
+        mock_interaction
+            .expect_start_multi_step_spinner()
+            .return_once(move |_| {
+                Ok(Box::new(MockMultiStepSpinner {
+                    outcomes: outcomes_clone,
+                }))
+            });
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
@@ -1954,26 +3070,36 @@ mod tests {
             Some(false),
         );
         let progress = create_successful_progress(deployment);
+        let expected_name = deployment_name.clone();
+        let expected_version = ImageTag::try_from("8").unwrap();
         mock_deployment_management
             .expect_create_deployment()
             .return_once(move |options| {
+                assert_eq!(options.name, Some(expected_name));
+                assert_eq!(options.image_tag, Some(expected_version.clone()));
+                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
+                assert_eq!(options.wait_until_healthy, Some(true));
+                assert_eq!(options.local_seed_location, None);
+                assert_eq!(options.mongodb_initdb_root_username, None);
+                assert_eq!(options.mongodb_initdb_root_password, None);
+                assert_eq!(options.load_sample_data, Some(false));
                 assert!(options.mongodb_port_binding.is_some());
-                if let Some(binding) = options.mongodb_port_binding {
-                    assert!(matches!(
-                        binding.binding_type,
-                        atlas_local::models::BindingType::AnyInterface
-                    ));
+                if let Some(binding) = &options.mongodb_port_binding {
+                    assert_eq!(binding.port, Some(27017));
+                    assert!(matches!(binding.binding_type, BindingType::Loopback));
                 }
+                assert_eq!(options.image, None);
+                assert_eq!(options.skip_pull_image, Some(false));
                 progress
             });
 
         let mut setup_command = create_setup_command(
             Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(ImageTag::try_from("8").unwrap()),
             Some(27017),
-            true,
+            false,
             Some(false),
-            true,
+            false,
             None,
             None,
             None,
@@ -1993,57 +3119,37 @@ mod tests {
                 mongodb_version: version,
                 port: 27017,
                 load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
                 connect_result: Some(ConnectResult::Skipped),
             }
         );
     }
 
     // ============================================================================
-    // Connect After Setup Tests
+    // Cancellation Tests
     // ============================================================================
 
     #[tokio::test]
-    async fn test_setup_with_connect_with_connection_string() {
-        let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.2.2").unwrap();
-        let connection_string = "mongodb://localhost:27017".to_string();
-
-        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
-
-        let mut mock_deployment_management = MockDocker::new();
-        let deployment = create_deployment(
-            Some(deployment_name.clone()),
-            version.clone(),
-            Some(27017),
-            Some(false),
-        );
-        let progress = create_successful_progress(deployment);
-        mock_deployment_management
-            .expect_create_deployment()
-            .return_once(move |_| progress);
-
-        let connection_string_clone = connection_string.clone();
-        mock_deployment_management
-            .expect_get_connection_string()
-            .return_once(move |_| Ok(connection_string_clone));
+    async fn test_setup_user_cancels_at_initial_select_prompt() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_select()
+            .return_once(|_| Ok(crate::interaction::SelectPromptResult::Canceled));
 
-        let mut setup_command = create_setup_command_with_connectors(
-            Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
+        let mut setup_command = create_setup_command(
+            None,
+            None,
             None,
-            Some(27017),
-            true,
-            Some(false),
             false,
             None,
+            false,
             None,
             None,
-            Some(ConnectWith::ConnectionString),
             None,
             Box::new(mock_interaction),
-            Box::new(mock_deployment_management),
-            HashMap::new(),
+            Box::new(MockDocker::new()),
         );
 
         let result = setup_command
@@ -2053,65 +3159,37 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27017,
-                load_sample_data: false,
-                connect_result: Some(ConnectResult::ConnectionString { connection_string }),
+            SetupResult::Canceled {
+                deployment_name: None,
+                reason: CancelReason::Prompt,
             }
         );
     }
 
     #[tokio::test]
-    async fn test_setup_with_connect_with_compass() {
-        let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.2.2").unwrap();
-        let connection_string = "mongodb://localhost:27017".to_string();
-
-        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
-
-        let mut mock_deployment_management = MockDocker::new();
-        let deployment = create_deployment(
-            Some(deployment_name.clone()),
-            version.clone(),
-            Some(27017),
-            Some(false),
-        );
-        let progress = create_successful_progress(deployment);
-        mock_deployment_management
-            .expect_create_deployment()
-            .return_once(move |_| progress);
-
-        let connection_string_clone = connection_string.clone();
-        mock_deployment_management
-            .expect_get_connection_string()
-            .return_once(move |_| Ok(connection_string_clone));
-
-        let mut mock_connector = MockConnector::new();
-        mock_connector.expect_is_available().returning(|| true);
-        mock_connector.expect_launch().returning(|_| Ok(()));
-
-        let mut connectors: HashMap<ConnectWith, Box<dyn Connector + Send + Sync>> = HashMap::new();
-        connectors.insert(ConnectWith::Compass, Box::new(mock_connector));
+    async fn test_setup_user_cancels_at_deployment_name_prompt() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction.expect_select().return_once(|_| {
+            Ok(crate::interaction::SelectPromptResult::Selected(
+                SETUP_TYPE_CUSTOM.to_string(),
+            ))
+        });
+        mock_interaction
+            .expect_input()
+            .return_once(|_| Ok(crate::interaction::InputPromptResult::Canceled));
 
-        let mut setup_command = create_setup_command_with_connectors(
-            Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
+        let mut setup_command = create_setup_command(
+            None,
+            None,
             None,
-            Some(27017),
-            true,
-            Some(false),
             false,
             None,
+            false,
             None,
             None,
-            Some(ConnectWith::Compass),
             None,
             Box::new(mock_interaction),
-            Box::new(mock_deployment_management),
-            connectors,
+            Box::new(MockDocker::new()),
         );
 
         let result = setup_command
@@ -2121,67 +3199,1826 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Setup {
+            SetupResult::Canceled {
+                deployment_name: None,
+                reason: CancelReason::Prompt,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_user_cancels_at_mongodb_version_prompt() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction.expect_select().return_once(|_| {
+            Ok(crate::interaction::SelectPromptResult::Selected(
+                SETUP_TYPE_CUSTOM.to_string(),
+            ))
+        });
+        mock_interaction.expect_input().times(1).return_once(|_| {
+            Ok(crate::interaction::InputPromptResult::Input(
+                "test".to_string(),
+            ))
+        });
+        mock_interaction
+            .expect_input()
+            .times(1)
+            .return_once(|_| Ok(crate::interaction::InputPromptResult::Canceled));
+
+        let mut setup_command = create_setup_command(
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(MockDocker::new()),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Canceled {
+                deployment_name: Some("test".to_string()),
+                reason: CancelReason::Prompt,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_user_cancels_at_port_prompt() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction.expect_select().return_once(|_| {
+            Ok(crate::interaction::SelectPromptResult::Selected(
+                SETUP_TYPE_CUSTOM.to_string(),
+            ))
+        });
+        mock_interaction
+            .expect_input()
+            .times(3)
+            .returning(|options| match options.message.as_str() {
+                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "test".to_string(),
+                )),
+                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "8".to_string(),
+                )),
+                "Port?" => Ok(crate::interaction::InputPromptResult::Canceled),
+                _ => panic!("Unexpected prompt: {}", options.message),
+            });
+
+        let mut setup_command = create_setup_command(
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(MockDocker::new()),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Canceled {
+                deployment_name: Some("test".to_string()),
+                reason: CancelReason::Prompt,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_user_cancels_at_load_sample_data_prompt() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction.expect_select().return_once(|_| {
+            Ok(crate::interaction::SelectPromptResult::Selected(
+                SETUP_TYPE_CUSTOM.to_string(),
+            ))
+        });
+        mock_interaction
+            .expect_input()
+            .times(4)
+            .returning(|options| match options.message.as_str() {
+                "Deployment Name?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "test".to_string(),
+                )),
+                "Major MongoDB Version?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "8".to_string(),
+                )),
+                "Port?" => Ok(crate::interaction::InputPromptResult::Input(
+                    "27017".to_string(),
+                )),
+                "Would you like to load sample data? (y/N)" => {
+                    Ok(crate::interaction::InputPromptResult::Canceled)
+                }
+                _ => panic!("Unexpected prompt: {}", options.message),
+            });
+
+        let mut setup_command = create_setup_command(
+            None,
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(MockDocker::new()),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Canceled {
+                deployment_name: Some("test".to_string()),
+                reason: CancelReason::Prompt,
+            }
+        );
+    }
+
+    // ============================================================================
+    // Error Handling Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_setup_handles_deployment_failure() {
+        let deployment_name = "test-deployment".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let expected_name = deployment_name.clone();
+        let progress = create_mock_progress(
+            CreateDeploymentStepOutcome::Failure,
+            CreateDeploymentStepOutcome::Skipped,
+            CreateDeploymentStepOutcome::Skipped,
+            CreateDeploymentStepOutcome::Skipped,
+            Err(CreateDeploymentError::PullImage(
+                atlas_local::client::PullImageError::from(bollard::errors::Error::from(
+                    std::io::Error::new(std::io::ErrorKind::Other, "Failed to pull image"),
+                )),
+            )),
+        );
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert_eq!(options.name, Some(expected_name));
+                assert_eq!(options.image_tag, None);
+                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
+                assert_eq!(options.wait_until_healthy, Some(true));
+                progress
+            });
+
+        let mut setup_command = create_setup_command(
+            Some(deployment_name.clone()),
+            None,
+            None,
+            true,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        match result {
+            SetupResult::Failed {
+                deployment_name: name,
+                error,
+            } => {
+                assert_eq!(name, Some(deployment_name));
+                assert!(!error.is_empty());
+            }
+            _ => panic!("Expected Failed result, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_setup_handles_architecture_mismatch() {
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let progress = create_mock_progress(
+            CreateDeploymentStepOutcome::Failure,
+            CreateDeploymentStepOutcome::Skipped,
+            CreateDeploymentStepOutcome::Skipped,
+            CreateDeploymentStepOutcome::Skipped,
+            Err(CreateDeploymentError::PullImage(
+                atlas_local::client::PullImageError::from(bollard::errors::Error::from(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "no matching manifest for linux/arm64/v8 in the manifest list entries",
+                    ),
+                )),
+            )),
+        );
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            None,
+            None,
+            true,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        match result {
+            SetupResult::Failed { error, .. } => {
+                assert!(
+                    error.contains("not built for the"),
+                    "Error should explain the architecture mismatch, got: {error}"
+                );
+                assert!(error.contains("enable emulation"));
+            }
+            _ => panic!("Expected Failed result, got {:?}", result),
+        }
+    }
+
+    // This test should never happen, but we test it for completeness
+    #[tokio::test]
+    async fn test_setup_handles_receive_deployment_error() {
+        let deployment_name = "test-deployment".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        use tokio::sync::oneshot;
+        let (pull_s, pull_r) = oneshot::channel();
+        let _ = pull_s.send(CreateDeploymentStepOutcome::Success);
+        let (create_s, create_r) = oneshot::channel();
+        let _ = create_s.send(CreateDeploymentStepOutcome::Success);
+        let (start_s, start_r) = oneshot::channel();
+        let _ = start_s.send(CreateDeploymentStepOutcome::Success);
+        let (healthy_s, healthy_r) = oneshot::channel();
+        let _ = healthy_s.send(CreateDeploymentStepOutcome::Success);
+        let (deploy_s, deploy_r) = oneshot::channel();
+        drop(deploy_s); // Simulate error by dropping sender
+
+        let progress = CreateDeploymentProgress {
+            pull_image_finished: pull_r.fuse(),
+            create_container_finished: create_r.fuse(),
+            start_container_finished: start_r.fuse(),
+            wait_for_healthy_deployment_finished: healthy_r.fuse(),
+            deployment: deploy_r.fuse(),
+        };
+        let expected_name = deployment_name.clone();
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert_eq!(options.name, Some(expected_name));
+                assert_eq!(options.image_tag, None);
+                assert_eq!(options.creation_source, Some(CreationSource::AtlasLocal));
+                assert_eq!(options.wait_until_healthy, Some(true));
+                progress
+            });
+
+        let mut setup_command = create_setup_command(
+            Some(deployment_name),
+            None,
+            None,
+            true,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command.execute().await;
+
+        assert!(
+            result.is_err(),
+            "Expected error when deployment receiver is dropped"
+        );
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("receiving deployment outcome"),
+            "Error message should mention receiving deployment outcome"
+        );
+    }
+
+    // ============================================================================
+    // Option-Specific Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_setup_with_initdb_path() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+        let initdb_path = std::path::PathBuf::from("/tmp/test-initdb");
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert_eq!(
+                    options.local_seed_location,
+                    Some("/tmp/test-initdb".to_string())
+                );
+                progress
+            });
+
+        let mut setup_command = create_setup_command(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            Some(initdb_path),
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_with_username_and_password() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert_eq!(
+                    options.mongodb_initdb_root_username,
+                    Some("admin".to_string())
+                );
+                assert_eq!(
+                    options.mongodb_initdb_root_password,
+                    Some("password".to_string())
+                );
+                progress
+            });
+
+        let mut setup_command = create_setup_command(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            Some("admin".to_string()),
+            Some("password".to_string()),
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_with_bind_ip_all() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert!(options.mongodb_port_binding.is_some());
+                if let Some(binding) = options.mongodb_port_binding {
+                    assert!(matches!(
+                        binding.binding_type,
+                        atlas_local::models::BindingType::AnyInterface
+                    ));
+                }
+                progress
+            });
+
+        let mut setup_command = create_setup_command(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
+            true,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+        // Without authentication, binding to all interfaces has to be confirmed with --iUnderstand
+        setup_command.i_understand = true;
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![BIND_IP_ALL_WARNING.to_string()],
+                connect_result: Some(ConnectResult::Skipped),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_bind_ip_all_without_auth_requires_i_understand() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
+            true,
+            None,
+            None,
+            None,
+            Box::new(MockInteraction::new()),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "--bindIpAll without --username exposes a deployment without authentication to the network, use --iUnderstand to proceed anyway".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_confirm_network_exposure() {
+        let create_command = |username: Option<String>, interaction: MockInteraction| {
+            create_setup_command(
+                Some("test-deployment".to_string()),
+                None,
+                None,
+                false,
+                None,
+                true,
+                None,
+                username,
+                None,
+                Box::new(interaction),
+                Box::new(MockDocker::new()),
+            )
+        };
+
+        // Authenticated deployments are not prompted for
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction.expect_confirm().never();
+        let setup_command = create_command(Some("admin".to_string()), mock_interaction);
+        assert_eq!(setup_command.confirm_network_exposure().unwrap(), None);
+
+        // Declining the prompt cancels the setup
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_confirm()
+            .return_once(|_| Ok(ConfirmationPromptResult::No));
+        let setup_command = create_command(None, mock_interaction);
+        assert_eq!(
+            setup_command.confirm_network_exposure().unwrap(),
+            Some(SetupResult::Canceled {
+                deployment_name: Some("test-deployment".to_string()),
+                reason: CancelReason::Prompt,
+            })
+        );
+    }
+
+    // ============================================================================
+    // Connect After Setup Tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_setup_with_connect_with_connection_string() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+        let connection_string = "mongodb://localhost:27017".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let connection_string_clone = connection_string.clone();
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(move |_| Ok(connection_string_clone));
+
+        let mut setup_command = create_setup_command_with_connectors(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            None,
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            Some(ConnectWith::ConnectionString),
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+            HashMap::new(),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::ConnectionString { connection_string }),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_force_uses_default_connector() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+        let connection_string = "mongodb://localhost:27017".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let connection_string_clone = connection_string.clone();
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(move |_| Ok(connection_string_clone));
+
+        let mut setup_command = create_setup_command_with_connectors(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            None,
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+            HashMap::new(),
+        );
+        setup_command.default_connector = Some(ConnectWith::ConnectionString);
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name,
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::ConnectionString { connection_string }),
+            }
+        );
+    }
+
+    fn create_connection_string_file_setup_command(file_writer: MockTokioFs) -> Setup {
+        let deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            Version::parse("8.2.2").unwrap(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(|_| Ok("mongodb://localhost:27017".to_string()));
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            Box::new(create_mock_interaction_with_spinner(outcomes)),
+            Box::new(mock_deployment_management),
+        );
+        setup_command.connection_string_file = Some(PathBuf::from("/tmp/connection-string"));
+        setup_command.file_writer = Box::new(file_writer);
+        setup_command
+    }
+
+    #[tokio::test]
+    async fn test_setup_writes_connection_string_file() {
+        let mut mock_file_writer = MockTokioFs::new();
+        mock_file_writer
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/tmp/connection-string")
+                    && contents == "mongodb://localhost:27017"
+            })
+            .return_once(|_, _| Ok(()));
+
+        let result = create_connection_string_file_setup_command(mock_file_writer)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        // Without --connectWith, the connection string is returned instead of skipping the connection
+        let SetupResult::Setup { connect_result, .. } = result else {
+            panic!("Expected Setup result, got {:?}", result);
+        };
+        assert_eq!(
+            connect_result,
+            Some(ConnectResult::ConnectionString {
+                connection_string: "mongodb://localhost:27017".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_hide_connection_string() {
+        // The file still gets the real connection string
+        let mut mock_file_writer = MockTokioFs::new();
+        mock_file_writer
+            .expect_write_string()
+            .withf(|_, contents| contents == "mongodb://localhost:27017")
+            .return_once(|_, _| Ok(()));
+
+        let mut setup_command = create_connection_string_file_setup_command(mock_file_writer);
+        setup_command.connection_string_options.hide = true;
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let SetupResult::Setup { connect_result, .. } = result else {
+            panic!("Expected Setup result, got {:?}", result);
+        };
+        assert_eq!(
+            connect_result,
+            Some(ConnectResult::ConnectionString {
+                connection_string: "[hidden]".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_default_db_saved_as_deployment_setting() {
+        let deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            Version::parse("8.2.2").unwrap(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+
+        // The database of the --initdb scripts is left alone
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_create_deployment()
+            .withf(|options| options.mongodb_initdb_database.is_none())
+            .return_once(move |_| progress);
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(|_| Ok("mongodb://localhost:27017/?directConnection=true".to_string()));
+
+        let mut mock_config_fs = MockTokioFs::new();
+        mock_config_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_config_fs
+            .expect_create_dir_all()
+            .return_once(|_| Ok(()));
+        mock_config_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/config/deployments.toml")
+                    && contents.contains("[deployments.test-deployment]")
+                    && contents.contains("defaultDb = \"inventory\"")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            Box::new(create_mock_interaction_with_spinner(outcomes)),
+            Box::new(mock_deployment_management),
+        );
+        setup_command.connect_with = Some(ConnectWith::ConnectionString);
+        setup_command.connection_string_options.default_database = Some("inventory".to_string());
+        setup_command.deployments_file = Some(PathBuf::from("/config/deployments.toml"));
+        setup_command.config_fs = Box::new(mock_config_fs);
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let SetupResult::Setup { connect_result, .. } = result else {
+            panic!("Expected Setup result, got {:?}", result);
+        };
+        assert_eq!(
+            connect_result,
+            Some(ConnectResult::ConnectionString {
+                connection_string: "mongodb://localhost:27017/inventory?directConnection=true"
+                    .to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_connect_host() {
+        // The file gets the connection string for the client machine as well
+        let mut mock_file_writer = MockTokioFs::new();
+        mock_file_writer
+            .expect_write_string()
+            .withf(|_, contents| contents == "mongodb://10.0.0.5:27017")
+            .return_once(|_, _| Ok(()));
+
+        let mut setup_command = create_connection_string_file_setup_command(mock_file_writer);
+        setup_command.connection_string_options.host = Some("10.0.0.5".to_string());
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let SetupResult::Setup { connect_result, .. } = result else {
+            panic!("Expected Setup result, got {:?}", result);
+        };
+        assert_eq!(
+            connect_result,
+            Some(ConnectResult::ConnectionString {
+                connection_string: "mongodb://10.0.0.5:27017".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_fails_when_connection_string_file_cant_be_written() {
+        let mut mock_file_writer = MockTokioFs::new();
+        mock_file_writer
+            .expect_write_string()
+            .return_once(|_, _| Err(anyhow::anyhow!("permission denied")));
+
+        let result = create_connection_string_file_setup_command(mock_file_writer)
+            .execute()
+            .await;
+
+        assert!(result.is_err(), "Expected an error, got {:?}", result.ok());
+    }
+
+    #[test]
+    fn test_prompt_connection_method_preselects_default_connector() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_select()
+            .withf(|options| {
+                *options
+                    == SelectPromptOptions::builder()
+                        .message("How do you want to connect to your local Atlas deployment?")
+                        .options(vec![
+                            CONNECT_WITH_COMPASS,
+                            CONNECT_WITH_MONGOSH,
+                            CONNECT_WITH_VSCODE,
+                            CONNECT_WITH_CONNECTION_STRING,
+                            CONNECT_WITH_SKIP,
+                        ])
+                        .starting_cursor(1)
+                        .build()
+            })
+            .return_once(|_| {
+                Ok(SelectPromptResult::Selected(
+                    CONNECT_WITH_MONGOSH.to_string(),
+                ))
+            });
+
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            None,
+            None,
+            false,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(MockDocker::new()),
+        );
+        setup_command.default_connector = Some(ConnectWith::Mongosh);
+
+        assert_eq!(
+            setup_command.prompt_connection_method().unwrap(),
+            Some(ConnectWith::Mongosh)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_with_connect_with_compass() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+        let connection_string = "mongodb://localhost:27017".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let connection_string_clone = connection_string.clone();
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(move |_| Ok(connection_string_clone));
+
+        let mut mock_connector = MockConnector::new();
+        mock_connector.expect_is_available().returning(|| true);
+        mock_connector.expect_launch().returning(|_, _| Ok(()));
+
+        let mut connectors: HashMap<ConnectWith, Box<dyn Connector + Send + Sync>> = HashMap::new();
+        connectors.insert(ConnectWith::Compass, Box::new(mock_connector));
+
+        let mut setup_command = create_setup_command_with_connectors(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            None,
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            Some(ConnectWith::Compass),
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+            connectors,
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Connected {
+                    method: "Compass".to_string(),
+                }),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_with_connect_with_compass_not_installed() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+        let connection_string = "mongodb://localhost:27017".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let connection_string_clone = connection_string.clone();
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(move |_| Ok(connection_string_clone));
+
+        let mut mock_connector = MockConnector::new();
+        mock_connector.expect_is_available().returning(|| false);
+
+        let mut connectors: HashMap<ConnectWith, Box<dyn Connector + Send + Sync>> = HashMap::new();
+        connectors.insert(ConnectWith::Compass, Box::new(mock_connector));
+
+        let mut setup_command = create_setup_command_with_connectors(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            None,
+            Some(27017),
+            true,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            Some(ConnectWith::Compass),
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+            connectors,
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: deployment_name.clone(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Failed {
+                    error: "Compass is not installed".to_string(),
+                }),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_prompts_for_connection_and_selects_connection_string() {
+        let deployment_name = "test-deployment".to_string();
+        let version = Version::parse("8.2.2").unwrap();
+        let connection_string = "mongodb://localhost:27017".to_string();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        // Two selects: setup type (default) and connection method (connection string)
+        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
+        mock_interaction
+            .expect_select()
+            .times(2)
+            .returning(move |_| {
+                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match count {
+                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
+                        SETUP_TYPE_DEFAULT.to_string(),
+                    )),
+                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
+                        CONNECT_WITH_CONNECTION_STRING.to_string(),
+                    )),
+                }
+            });
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment = create_deployment(
+            Some(deployment_name.clone()),
+            version.clone(),
+            Some(27017),
+            Some(false),
+        );
+        let progress = create_successful_progress(deployment);
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+
+        let connection_string_clone = connection_string.clone();
+        mock_deployment_management
+            .expect_get_connection_string()
+            .return_once(move |_| Ok(connection_string_clone));
+
+        let mut setup_command = create_setup_command(
+            Some(deployment_name.clone()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            false,
+            Some(false),
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(mock_deployment_management),
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
                 deployment_name: deployment_name.clone(),
                 mongodb_version: version,
                 port: 27017,
                 load_sample_data: false,
-                connect_result: Some(ConnectResult::Connected {
-                    method: "Compass".to_string(),
-                }),
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::ConnectionString { connection_string }),
+            }
+        );
+    }
+
+    // ============================================================================
+    // Display Tests
+    // ============================================================================
+
+    #[test]
+    fn test_setup_result_display_failed_with_deployment_name() {
+        let result = SetupResult::Failed {
+            deployment_name: Some("test-deployment".to_string()),
+            error: "test error".to_string(),
+        };
+        let output = format!("{}", result);
+        assert!(
+            output.contains("test-deployment"),
+            "Output should contain deployment name"
+        );
+        assert!(
+            output.contains("test error"),
+            "Output should contain error message"
+        );
+        assert!(output.contains("failed"), "Output should indicate failure");
+    }
+
+    #[test]
+    fn test_setup_result_display_failed_without_deployment_name() {
+        let result = SetupResult::Failed {
+            deployment_name: None,
+            error: "test error".to_string(),
+        };
+        let output = format!("{}", result);
+        assert!(
+            output.contains("test error"),
+            "Output should contain error message"
+        );
+        assert!(output.contains("failed"), "Output should indicate failure");
+        assert!(
+            !output.contains("'"),
+            "Output should not contain quotes when name is missing"
+        );
+    }
+
+    #[test]
+    fn test_setup_result_display_canceled() {
+        let prompt = SetupResult::Canceled {
+            deployment_name: Some("test-deployment".to_string()),
+            reason: CancelReason::Prompt,
+        };
+        assert_eq!(
+            prompt.to_string(),
+            "Setting up deployment 'test-deployment' failed: User canceled the setup"
+        );
+
+        let signal = SetupResult::Canceled {
+            deployment_name: Some("test-deployment".to_string()),
+            reason: CancelReason::Signal,
+        };
+        assert_eq!(
+            signal.to_string(),
+            "Setting up deployment 'test-deployment' was interrupted, use --resume to continue the setup"
+        );
+
+        let json = serde_json::to_value(&signal).unwrap();
+        assert_eq!(json["outcome"], "canceled");
+        assert_eq!(json["reason"], "signal");
+    }
+
+    #[test]
+    fn test_setup_result_display_setup() {
+        let result = SetupResult::Setup {
+            deployment_name: "test-deployment".to_string(),
+            mongodb_version: Version::parse("8.2.2").unwrap(),
+            port: 27017,
+            load_sample_data: true,
+            resumed: false,
+            image: None,
+            warnings: vec![],
+            connect_result: None,
+        };
+        let output = format!("{}", result);
+        assert!(
+            output.contains("test-deployment"),
+            "Output should contain deployment name"
+        );
+        assert!(
+            output.contains("8.2.2"),
+            "Output should contain MongoDB version"
+        );
+        assert!(output.contains("27017"), "Output should contain port");
+        assert!(
+            output.contains("true"),
+            "Output should contain load_sample_data value"
+        );
+    }
+
+    #[test]
+    fn test_setup_summary_display() {
+        let summary = SetupSummary(SetupResult::Setup {
+            deployment_name: "test-deployment".to_string(),
+            mongodb_version: Version::parse("8.2.2").unwrap(),
+            port: 27017,
+            load_sample_data: true,
+            resumed: false,
+            image: None,
+            warnings: vec![],
+            connect_result: Some(ConnectResult::ConnectionString {
+                connection_string: "mongodb://127.0.0.1:27017/?directConnection=true".to_string(),
+            }),
+        });
+
+        assert_eq!(
+            summary.to_string(),
+            "name=test-deployment version=8.2.2 port=27017 uri=mongodb://127.0.0.1:27017/?directConnection=true"
+        );
+        // The JSON output is unchanged
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::to_value(&summary.0).unwrap()
+        );
+    }
+
+    // ============================================================================
+    // Utility Function Tests
+    // ============================================================================
+
+    #[test]
+    fn test_deployment_outcome_to_multi_step_spinner_outcome_success() {
+        let outcome = CreateDeploymentStepOutcome::Success;
+        let result = deployment_outcome_to_multi_step_spinner_outcome(outcome);
+        assert!(matches!(result, MultiStepSpinnerOutcome::Success));
+    }
+
+    #[test]
+    fn test_deployment_outcome_to_multi_step_spinner_outcome_skipped() {
+        let outcome = CreateDeploymentStepOutcome::Skipped;
+        let result = deployment_outcome_to_multi_step_spinner_outcome(outcome);
+        assert!(matches!(result, MultiStepSpinnerOutcome::Skipped));
+    }
+
+    #[test]
+    fn test_deployment_outcome_to_multi_step_spinner_outcome_failure() {
+        let outcome = CreateDeploymentStepOutcome::Failure;
+        let result = deployment_outcome_to_multi_step_spinner_outcome(outcome);
+        assert!(matches!(result, MultiStepSpinnerOutcome::Failure));
+    }
+
+    #[test]
+    fn test_parse_bool_true_values() {
+        assert_eq!(parse_bool("true").unwrap(), true);
+        assert_eq!(parse_bool("TRUE").unwrap(), true);
+        assert_eq!(parse_bool("True").unwrap(), true);
+    }
+
+    #[test]
+    fn test_parse_bool_false_values() {
+        assert_eq!(parse_bool("false").unwrap(), false);
+        assert_eq!(parse_bool("FALSE").unwrap(), false);
+        assert_eq!(parse_bool("False").unwrap(), false);
+    }
+
+    #[test]
+    fn test_parse_bool_errors_on_invalid_value() {
+        let err = parse_bool("invalid").expect_err("parse_bool should error on invalid value");
+        let msg = err.to_string();
+        assert!(
+            msg.contains("expected true or false"),
+            "error message should mention expected values: {}",
+            msg
+        );
+        assert!(
+            msg.contains("invalid"),
+            "error message should mention the invalid value: {}",
+            msg
+        );
+    }
+
+    #[test]
+    fn test_parse_bool_errors_on_empty_string() {
+        parse_bool("").expect_err("parse_bool should error on empty string");
+    }
+
+    #[test]
+    fn test_parse_port_valid_value() {
+        assert_eq!(parse_port("27017").unwrap(), Some(27017));
+    }
+
+    #[test]
+    fn test_parse_port_auto_assign_values() {
+        assert_eq!(parse_port("").unwrap(), None);
+        assert_eq!(parse_port("auto-assign").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_port_errors_on_invalid_value() {
+        let err = parse_port("0").expect_err("parse_port should error on port 0");
+        assert!(
+            err.to_string().contains("between 1 and 65535"),
+            "error message should mention the valid range: {}",
+            err
+        );
+        parse_port("not-a-port").expect_err("parse_port should error on non-numeric value");
+        parse_port("65536").expect_err("parse_port should error on out of range value");
+    }
+    // ============================================================================
+    // Resume Tests
+    // ============================================================================
+
+    fn create_resume_command(
+        port: Option<u16>,
+        interaction: MockInteraction,
+        deployment_management: MockDocker,
+    ) -> Setup {
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            port,
+            true,
+            None,
+            false,
+            None,
+            None,
+            None,
+            Box::new(interaction),
+            Box::new(deployment_management),
+        );
+        setup_command.resume = true;
+        setup_command.container_inspector = Box::new(create_container_inspector(
+            "mongodb/mongodb-atlas-local:8.2.2",
+        ));
+        setup_command
+    }
+
+    fn create_container_inspector(image: &'static str) -> MockDocker {
+        let mut mock_container_inspector = MockDocker::new();
+        mock_container_inspector
+            .expect_inspect_container_raw()
+            .returning(move |_| {
+                Ok(Some(ContainerInspectResponse {
+                    config: Some(ContainerConfig {
+                        image: Some(image.to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }))
+            });
+        mock_container_inspector
+    }
+
+    #[tokio::test]
+    async fn test_setup_resume_exited_deployment() {
+        let version = Version::parse("8.2.2").unwrap();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut exited_deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            version.clone(),
+            Some(27017),
+            None,
+        );
+        exited_deployment.state = State::Exited;
+        let running_deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            version.clone(),
+            Some(27017),
+            None,
+        );
+
+        let mut mock_deployment_management = MockDocker::new();
+        let mut deployments = vec![running_deployment, exited_deployment];
+        mock_deployment_management
+            .expect_get_deployment()
+            .times(2)
+            .withf(|name| name == "test-deployment")
+            .returning(move |_| Ok(deployments.pop().unwrap()));
+        mock_deployment_management
+            .expect_start()
+            .withf(|name| name == "test-deployment")
+            .return_once(|_| Ok(()));
+        mock_deployment_management
+            .expect_wait_for_healthy_deployment()
+            .withf(|name, _| name == "test-deployment")
+            .return_once(|_, _| Ok(()));
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut setup_command =
+            create_resume_command(Some(27017), mock_interaction, mock_deployment_management);
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: "test-deployment".to_string(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: true,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
+            }
+        );
+        let outcomes = outcomes.lock().unwrap();
+        assert_eq!(outcomes.len(), 2, "Expected 2 spinner steps");
+        assert!(
+            outcomes
+                .iter()
+                .all(|(_, outcome)| matches!(outcome, MultiStepSpinnerOutcome::Success))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_resume_without_existing_deployment_creates_deployment() {
+        let version = Version::parse("8.2.2").unwrap();
+
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|_| {
+                Err(GetDeploymentError::ContainerInspect(
+                    bollard::errors::Error::DockerResponseServerError {
+                        status_code: 404,
+                        message: "No such container: test-deployment".to_string(),
+                    },
+                ))
+            });
+        mock_deployment_management.expect_start().never();
+        let deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            version.clone(),
+            Some(27017),
+            None,
+        );
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| create_successful_progress(deployment));
+
+        let mut setup_command =
+            create_resume_command(Some(27017), mock_interaction, mock_deployment_management);
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Setup {
+                deployment_name: "test-deployment".to_string(),
+                mongodb_version: version,
+                port: 27017,
+                load_sample_data: false,
+                resumed: false,
+                image: None,
+                warnings: vec![],
+                connect_result: Some(ConnectResult::Skipped),
+            }
+        );
+        verify_all_steps_succeeded(&outcomes);
+    }
+
+    #[tokio::test]
+    async fn test_setup_resume_config_mismatch() {
+        let mut exited_deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            Version::parse("8.2.2").unwrap(),
+            Some(27017),
+            None,
+        );
+        exited_deployment.state = State::Exited;
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(exited_deployment));
+        mock_deployment_management.expect_start().never();
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut setup_command = create_resume_command(
+            Some(27018),
+            MockInteraction::new(),
+            mock_deployment_management,
+        );
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "Existing deployment can't be resumed, its port does not match the requested port"
+                    .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_resume_version_mismatch() {
+        let mut exited_deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            Version::parse("8.0.4").unwrap(),
+            Some(27017),
+            None,
+        );
+        exited_deployment.state = State::Exited;
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(exited_deployment));
+        mock_deployment_management.expect_start().never();
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut setup_command =
+            create_resume_command(None, MockInteraction::new(), mock_deployment_management);
+        setup_command.container_inspector = Box::new(create_container_inspector(
+            "mongodb/mongodb-atlas-local:8.0.4",
+        ));
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "Existing deployment can't be resumed, its MongoDB version does not match the requested MongoDB version"
+                    .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_setup_resume_image_mismatch() {
+        let mut exited_deployment = create_deployment(
+            Some("test-deployment".to_string()),
+            Version::parse("8.2.2").unwrap(),
+            Some(27017),
+            None,
+        );
+        exited_deployment.state = State::Exited;
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(exited_deployment));
+        mock_deployment_management.expect_start().never();
+
+        let mut setup_command =
+            create_resume_command(None, MockInteraction::new(), mock_deployment_management);
+        setup_command.registry = Some("registry.example.com".to_string());
+
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "Existing deployment can't be resumed, its image does not match the requested image"
+                    .to_string(),
             }
         );
     }
 
+    // ============================================================================
+    // Reuse Volume Tests
+    // ============================================================================
+
+    fn create_reuse_volume_command(
+        deployment_management: MockDocker,
+        volume_management: MockDocker,
+    ) -> Setup {
+        let mut mock_interaction =
+            create_mock_interaction_with_spinner(Arc::new(std::sync::Mutex::new(Vec::new())));
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Copying the data of volume 'golden'...")
+            .return_once(|_| Ok(SpinnerHandle::new(Box::new(|| {}))));
+
+        let mut container_inspector = MockDocker::new();
+        container_inspector
+            .expect_inspect_container_raw()
+            .withf(|name| name == "test-deployment")
+            .returning(|_| {
+                Ok(Some(ContainerInspectResponse {
+                    config: Some(ContainerConfig {
+                        image: Some("mongodb/mongodb-atlas-local:8.2.2".to_string()),
+                        ..Default::default()
+                    }),
+                    mounts: Some(vec![MountPoint {
+                        typ: Some(MountPointTypeEnum::VOLUME),
+                        name: Some("test-deployment-db".to_string()),
+                        destination: Some("/data/db".to_string()),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }))
+            });
+
+        let mut setup_command = create_setup_command(
+            Some("test-deployment".to_string()),
+            Some(ImageTag::try_from("8.2.2").unwrap()),
+            Some(27017),
+            true,
+            Some(true),
+            false,
+            None,
+            None,
+            None,
+            Box::new(mock_interaction),
+            Box::new(deployment_management),
+        );
+        setup_command.reuse_volume = Some("golden".to_string());
+        setup_command.container_inspector = Box::new(container_inspector);
+        setup_command.volume_management = Box::new(volume_management);
+        setup_command
+    }
+
     #[tokio::test]
-    async fn test_setup_with_connect_with_compass_not_installed() {
-        let deployment_name = "test-deployment".to_string();
+    async fn test_setup_reuse_volume_copies_volume_into_data_directory() {
         let version = Version::parse("8.2.2").unwrap();
-        let connection_string = "mongodb://localhost:27017".to_string();
-
-        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
 
         let mut mock_deployment_management = MockDocker::new();
         let deployment = create_deployment(
-            Some(deployment_name.clone()),
+            Some("test-deployment".to_string()),
             version.clone(),
             Some(27017),
             Some(false),
         );
-        let progress = create_successful_progress(deployment);
+        let progress = create_successful_progress(deployment.clone());
         mock_deployment_management
             .expect_create_deployment()
-            .return_once(move |_| progress);
-
-        let connection_string_clone = connection_string.clone();
+            .return_once(move |options| {
+                // The data comes from the volume, the sample data isn't loaded
+                assert_eq!(options.load_sample_data, Some(false));
+                progress
+            });
+        let stop_calls = calls.clone();
         mock_deployment_management
-            .expect_get_connection_string()
-            .return_once(move |_| Ok(connection_string_clone));
-
-        let mut mock_connector = MockConnector::new();
-        mock_connector.expect_is_available().returning(|| false);
-
-        let mut connectors: HashMap<ConnectWith, Box<dyn Connector + Send + Sync>> = HashMap::new();
-        connectors.insert(ConnectWith::Compass, Box::new(mock_connector));
+            .expect_stop()
+            .return_once(move |name| {
+                stop_calls.lock().unwrap().push(format!("stop {name}"));
+                Ok(())
+            });
+        let start_calls = calls.clone();
+        mock_deployment_management
+            .expect_start()
+            .return_once(move |name| {
+                start_calls.lock().unwrap().push(format!("start {name}"));
+                Ok(())
+            });
+        mock_deployment_management
+            .expect_wait_for_healthy_deployment()
+            .withf(|name, _| name == "test-deployment")
+            .return_once(|_, _| Ok(()));
+        mock_deployment_management
+            .expect_get_deployment()
+            .withf(|name| name == "test-deployment")
+            .return_once(move |_| Ok(deployment));
+        mock_deployment_management.expect_delete().never();
+
+        let mut mock_volume_management = MockDocker::new();
+        mock_volume_management
+            .expect_volume_exists()
+            .withf(|volume| volume == "golden")
+            .return_once(|_| Ok(true));
+        let copy_calls = calls.clone();
+        mock_volume_management
+            .expect_copy_volume()
+            .return_once(move |image, source, target| {
+                assert_eq!(image, "mongodb/mongodb-atlas-local:8.2.2");
+                copy_calls
+                    .lock()
+                    .unwrap()
+                    .push(format!("copy {source} {target}"));
+                Ok(())
+            });
 
-        let mut setup_command = create_setup_command_with_connectors(
-            Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
-            None,
-            Some(27017),
-            true,
-            Some(false),
-            false,
-            None,
-            None,
-            None,
-            Some(ConnectWith::Compass),
-            None,
-            Box::new(mock_interaction),
-            Box::new(mock_deployment_management),
-            connectors,
-        );
+        let mut setup_command =
+            create_reuse_volume_command(mock_deployment_management, mock_volume_management);
 
         let result = setup_command
             .execute()
@@ -2191,73 +5028,57 @@ mod tests {
         assert_eq!(
             result,
             SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
+                deployment_name: "test-deployment".to_string(),
                 mongodb_version: version,
                 port: 27017,
                 load_sample_data: false,
-                connect_result: Some(ConnectResult::Failed {
-                    error: "Compass is not installed".to_string(),
-                }),
+                resumed: false,
+                image: None,
+                warnings: vec![
+                    "--loadSampleData ignored, the data of the deployment is copied from volume 'golden'"
+                        .to_string()
+                ],
+                connect_result: Some(ConnectResult::Skipped),
             }
         );
+        // The volume is only copied while the deployment is stopped
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "stop test-deployment",
+                "copy golden test-deployment-db",
+                "start test-deployment",
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn test_setup_prompts_for_connection_and_selects_connection_string() {
-        let deployment_name = "test-deployment".to_string();
-        let version = Version::parse("8.2.2").unwrap();
-        let connection_string = "mongodb://localhost:27017".to_string();
-
-        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let mut mock_interaction = create_mock_interaction_with_spinner(outcomes.clone());
-
-        // Two selects: setup type (default) and connection method (connection string)
-        let select_call_count = std::sync::atomic::AtomicUsize::new(0);
-        mock_interaction
-            .expect_select()
-            .times(2)
-            .returning(move |_| {
-                let count = select_call_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                match count {
-                    0 => Ok(crate::interaction::SelectPromptResult::Selected(
-                        SETUP_TYPE_DEFAULT.to_string(),
-                    )),
-                    _ => Ok(crate::interaction::SelectPromptResult::Selected(
-                        CONNECT_WITH_CONNECTION_STRING.to_string(),
-                    )),
-                }
-            });
-
+    async fn test_setup_reuse_volume_missing_volume_fails() {
         let mut mock_deployment_management = MockDocker::new();
-        let deployment = create_deployment(
-            Some(deployment_name.clone()),
-            version.clone(),
-            Some(27017),
-            Some(false),
-        );
-        let progress = create_successful_progress(deployment);
         mock_deployment_management
             .expect_create_deployment()
-            .return_once(move |_| progress);
+            .never();
 
-        let connection_string_clone = connection_string.clone();
-        mock_deployment_management
-            .expect_get_connection_string()
-            .return_once(move |_| Ok(connection_string_clone));
+        let mut mock_volume_management = MockDocker::new();
+        mock_volume_management
+            .expect_volume_exists()
+            .return_once(|_| Ok(false));
 
         let mut setup_command = create_setup_command(
-            Some(deployment_name.clone()),
-            Some(ImageTag::try_from("8.2.2").unwrap()),
-            Some(27017),
-            false,
-            Some(false),
+            Some("test-deployment".to_string()),
+            None,
+            None,
+            true,
+            None,
             false,
             None,
             None,
             None,
-            Box::new(mock_interaction),
+            Box::new(MockInteraction::new()),
             Box::new(mock_deployment_management),
         );
+        setup_command.reuse_volume = Some("golden".to_string());
+        setup_command.volume_management = Box::new(mock_volume_management);
 
         let result = setup_command
             .execute()
@@ -2266,140 +5087,99 @@ mod tests {
 
         assert_eq!(
             result,
-            SetupResult::Setup {
-                deployment_name: deployment_name.clone(),
-                mongodb_version: version,
-                port: 27017,
-                load_sample_data: false,
-                connect_result: Some(ConnectResult::ConnectionString { connection_string }),
+            SetupResult::Failed {
+                deployment_name: Some("test-deployment".to_string()),
+                error: "volume 'golden' does not exist".to_string(),
             }
         );
     }
 
-    // ============================================================================
-    // Display Tests
-    // ============================================================================
+    #[tokio::test]
+    async fn test_setup_reuse_volume_copy_failure_deletes_deployment() {
+        let mut mock_deployment_management = MockDocker::new();
+        let progress = create_successful_progress(create_deployment(
+            Some("test-deployment".to_string()),
+            Version::parse("8.2.2").unwrap(),
+            Some(27017),
+            Some(false),
+        ));
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |_| progress);
+        mock_deployment_management
+            .expect_stop()
+            .return_once(|_| Ok(()));
+        mock_deployment_management.expect_start().never();
+        mock_deployment_management
+            .expect_delete()
+            .withf(|name| name == "test-deployment")
+            .return_once(|_| Ok(()));
+
+        let mut mock_volume_management = MockDocker::new();
+        mock_volume_management
+            .expect_volume_exists()
+            .return_once(|_| Ok(true));
+        mock_volume_management
+            .expect_copy_volume()
+            .return_once(|_, _, _| {
+                Err(BollardError::DockerResponseServerError {
+                    status_code: 500,
+                    message: "no space left on device".to_string(),
+                })
+            });
 
-    #[test]
-    fn test_setup_result_display_failed_with_deployment_name() {
-        let result = SetupResult::Failed {
-            deployment_name: Some("test-deployment".to_string()),
-            error: "test error".to_string(),
-        };
-        let output = format!("{}", result);
-        assert!(
-            output.contains("test-deployment"),
-            "Output should contain deployment name"
-        );
-        assert!(
-            output.contains("test error"),
-            "Output should contain error message"
-        );
-        assert!(output.contains("failed"), "Output should indicate failure");
-    }
+        let mut setup_command =
+            create_reuse_volume_command(mock_deployment_management, mock_volume_management);
 
-    #[test]
-    fn test_setup_result_display_failed_without_deployment_name() {
-        let result = SetupResult::Failed {
-            deployment_name: None,
-            error: "test error".to_string(),
-        };
-        let output = format!("{}", result);
-        assert!(
-            output.contains("test error"),
-            "Output should contain error message"
-        );
-        assert!(output.contains("failed"), "Output should indicate failure");
-        assert!(
-            !output.contains("'"),
-            "Output should not contain quotes when name is missing"
-        );
-    }
+        let result = setup_command
+            .execute()
+            .await
+            .expect("execute should succeed");
 
-    #[test]
-    fn test_setup_result_display_setup() {
-        let result = SetupResult::Setup {
-            deployment_name: "test-deployment".to_string(),
-            mongodb_version: Version::parse("8.2.2").unwrap(),
-            port: 27017,
-            load_sample_data: true,
-            connect_result: None,
+        let SetupResult::Failed {
+            deployment_name,
+            error,
+        } = result
+        else {
+            panic!("expected the setup to fail, got {result:?}");
         };
-        let output = format!("{}", result);
-        assert!(
-            output.contains("test-deployment"),
-            "Output should contain deployment name"
-        );
+        assert_eq!(deployment_name, Some("test-deployment".to_string()));
         assert!(
-            output.contains("8.2.2"),
-            "Output should contain MongoDB version"
-        );
-        assert!(output.contains("27017"), "Output should contain port");
-        assert!(
-            output.contains("true"),
-            "Output should contain load_sample_data value"
+            error.starts_with("failed to copy volume 'golden': copying the volume into /data/db")
         );
     }
 
-    // ============================================================================
-    // Utility Function Tests
-    // ============================================================================
-
-    #[test]
-    fn test_deployment_outcome_to_multi_step_spinner_outcome_success() {
-        let outcome = CreateDeploymentStepOutcome::Success;
-        let result = deployment_outcome_to_multi_step_spinner_outcome(outcome);
-        assert!(matches!(result, MultiStepSpinnerOutcome::Success));
-    }
-
-    #[test]
-    fn test_deployment_outcome_to_multi_step_spinner_outcome_skipped() {
-        let outcome = CreateDeploymentStepOutcome::Skipped;
-        let result = deployment_outcome_to_multi_step_spinner_outcome(outcome);
-        assert!(matches!(result, MultiStepSpinnerOutcome::Skipped));
-    }
-
-    #[test]
-    fn test_deployment_outcome_to_multi_step_spinner_outcome_failure() {
-        let outcome = CreateDeploymentStepOutcome::Failure;
-        let result = deployment_outcome_to_multi_step_spinner_outcome(outcome);
-        assert!(matches!(result, MultiStepSpinnerOutcome::Failure));
-    }
-
     #[test]
-    fn test_parse_bool_true_values() {
-        assert_eq!(parse_bool("true").unwrap(), true);
-        assert_eq!(parse_bool("TRUE").unwrap(), true);
-        assert_eq!(parse_bool("True").unwrap(), true);
-    }
-
-    #[test]
-    fn test_parse_bool_false_values() {
-        assert_eq!(parse_bool("false").unwrap(), false);
-        assert_eq!(parse_bool("FALSE").unwrap(), false);
-        assert_eq!(parse_bool("False").unwrap(), false);
-    }
+    fn test_image_tag_matches() {
+        let version = Version::parse("8.2.2").unwrap();
 
-    #[test]
-    fn test_parse_bool_errors_on_invalid_value() {
-        let err = parse_bool("invalid").expect_err("parse_bool should error on invalid value");
-        let msg = err.to_string();
-        assert!(
-            msg.contains("expected true or false"),
-            "error message should mention expected values: {}",
-            msg
-        );
-        assert!(
-            msg.contains("invalid"),
-            "error message should mention the invalid value: {}",
-            msg
-        );
+        assert!(image_tag_matches(
+            &ImageTag::try_from("8.2").unwrap(),
+            &version,
+            Some("8.2.2")
+        ));
+        assert!(image_tag_matches(
+            &ImageTag::try_from("8").unwrap(),
+            &version,
+            None
+        ));
+        assert!(!image_tag_matches(
+            &ImageTag::try_from("8.0").unwrap(),
+            &version,
+            Some("8.2.2")
+        ));
+        assert!(image_tag_matches(
+            &ImageTag::Preview,
+            &version,
+            Some("preview")
+        ));
+        assert!(!image_tag_matches(
+            &ImageTag::Preview,
+            &version,
+            Some("8.2.2")
+        ));
     }
 
-    #[test]
-    fn test_parse_bool_errors_on_empty_string() {
-        parse_bool("").expect_err("parse_bool should error on empty string");
-    }
     // ============================================================================
     // TryFrom Tests
     // ============================================================================
@@ -2412,20 +5192,39 @@ mod tests {
         // But we're testing that the conversion logic works
         let args = args::Setup {
             deployment_name: Some("test".to_string()),
+            from_file: None,
+            from_profile: None,
+            bulk: Default::default(),
             image_tag: Some(ImageTag::Latest),
+            fail_on_eol: false,
             port: Some(27017),
             bind_ip_all: false,
+            i_understand: false,
             initdb: None,
             force: true,
             load_sample_data: Some(false),
+            datasets: vec![],
             username: Some("admin".to_string()),
             password: Some("password".to_string()),
             image: Some("test-image".to_string()),
+            registry: None,
+            pull_policy: None,
             skip_pull_image: true,
             connect_with: Some(ConnectWith::Compass),
+            default_connector: None,
+            notify: false,
+            connection_string: Default::default(),
+            connect_host: None,
+            connector_args: vec![],
+            wait_for_primary: false,
+            resume: false,
+            reuse_volume: None,
+            stream: false,
+            summary: false,
+            output_connection_string_file: None,
         };
 
-        let result = Setup::try_from(args);
+        let result = Setup::try_from((args, &Settings::default()));
         // The result may be Ok or Err depending on Docker availability
         // But we're testing that the code path is executed
         match result {
@@ -2438,7 +5237,7 @@ mod tests {
                 assert_eq!(setup.username, Some("admin".to_string()));
                 assert_eq!(setup.password, Some("password".to_string()));
                 assert_eq!(setup.image, Some("test-image".to_string()));
-                assert_eq!(setup.skip_pull_image, true);
+                assert_eq!(setup.pull_policy, PullPolicy::Never);
                 assert_eq!(setup.connect_with, Some(ConnectWith::Compass));
             }
             Err(_) => {
@@ -0,0 +1,39 @@
+//! Shutdown signal handling for long-running commands.
+//!
+//! Long-running commands stop in an orderly way when the user presses Ctrl-C (SIGINT),
+//! or when a process manager such as systemd or a container runtime sends SIGTERM.
+
+use anyhow::{Context, Result};
+use tracing::debug;
+
+/// Wait until the process is asked to shut down.
+#[cfg(unix)]
+pub async fn shutdown_signal() -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut terminate =
+        signal(SignalKind::terminate()).context("listening for the terminate signal")?;
+
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => {
+            result.context("listening for Ctrl-C")?;
+            debug!("received Ctrl-C");
+        }
+        _ = terminate.recv() => {
+            debug!("received SIGTERM");
+        }
+    }
+
+    Ok(())
+}
+
+/// Wait until the process is asked to shut down.
+#[cfg(not(unix))]
+pub async fn shutdown_signal() -> Result<()> {
+    tokio::signal::ctrl_c()
+        .await
+        .context("listening for Ctrl-C")?;
+    debug!("received Ctrl-C");
+
+    Ok(())
+}
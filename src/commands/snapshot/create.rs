@@ -0,0 +1,514 @@
+//! Command to create a snapshot of a deployment.
+//!
+//! The data volumes are archived with the archive API of Docker, so the deployment doesn't need a shell or a helper
+//! container and remote Docker hosts work the same. A running deployment is locked for writes while its volumes are
+//! archived, the archives are consistent because MongoDB flushes its data files before the lock is granted.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{Client, models::State};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use tracing::{debug, trace, warn};
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        clone::volumes_by_destination,
+        deployment_config::DeploymentConfig,
+        snapshot::{
+            SnapshotMetadata, SnapshotVolume, SnapshotsFs, format_size, save_snapshot,
+            snapshot_dir, snapshots_dir,
+        },
+        with_mongodb::LocalDeploymentFsyncLocker,
+    },
+    dependencies::{
+        DeploymentArchiver, DeploymentContainerInspector, DeploymentGetDeployment, DockerError,
+        FsyncLocker, TokioFs, connect_docker, find_deployment,
+    },
+    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+    settings::Settings,
+};
+
+// Docker dependencies to archive the data volumes of a deployment
+pub trait SnapshotDocker: DeploymentContainerInspector + DeploymentArchiver {}
+impl<T: DeploymentContainerInspector + DeploymentArchiver> SnapshotDocker for T {}
+
+pub struct Create {
+    deployment_name: String,
+    snapshots_dir: PathBuf,
+    created_at: DateTime<Utc>,
+
+    interaction: Box<dyn SpinnerInteraction + Send>,
+    deployment_management: Box<dyn DeploymentGetDeployment + Send + Sync>,
+    docker: Box<dyn SnapshotDocker + Send + Sync>,
+    fsync_locker: Box<dyn FsyncLocker + Send + Sync>,
+    fs: Box<dyn SnapshotsFs>,
+}
+
+impl TryFrom<(args::snapshot::Create, &Settings)> for Create {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::snapshot::Create, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            snapshots_dir: snapshots_dir(args.snapshots_dir.snapshots_dir, settings)?,
+            created_at: Utc::now(),
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker.clone())),
+            docker: Box::new(docker),
+            fsync_locker: Box::new(LocalDeploymentFsyncLocker::new(settings.docker.clone())),
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum CreateResult {
+    Created {
+        deployment_name: String,
+        snapshot_id: String,
+        size_bytes: u64,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+}
+
+impl Display for CreateResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Created {
+                deployment_name,
+                snapshot_id,
+                size_bytes,
+            } => write!(
+                f,
+                "Snapshot '{}' of deployment '{}' created ({})",
+                snapshot_id,
+                deployment_name,
+                format_size(*size_bytes)
+            ),
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Creating a snapshot of deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Create {
+    type Output = CreateResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(deployment_name = ?self.deployment_name, "executing snapshot create command");
+
+        // When spinner goes out of scope, the spinner will be stopped
+        let _spinner = self
+            .interaction
+            .start_spinner(message(Message::CreateSnapshot).to_string())?;
+
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(self.failed("deployment not found"));
+        };
+
+        // The data files of a running deployment are only consistent while its writes are locked
+        let lock = match deployment.state {
+            State::Running => true,
+            State::Created | State::Exited => false,
+            State::Paused => return Ok(self.failed("Deployment is paused, start it first")),
+            State::Restarting => {
+                return Ok(self.failed("Deployment is restarting, wait until it is running"));
+            }
+            State::Dead => return Ok(self.failed("Deployment is dead")),
+            State::Removing => return Ok(self.failed("Deployment is in removing state")),
+        };
+
+        let container = self
+            .docker
+            .inspect_container_raw(&self.deployment_name)
+            .await
+            .map_err(DockerError::from)
+            .context("inspecting deployment")?
+            .context("deployment not found")?;
+        let mut paths = volumes_by_destination(&container)
+            .into_keys()
+            .collect::<Vec<_>>();
+        paths.sort();
+        if paths.is_empty() {
+            return Ok(self.failed("Deployment has no data volumes"));
+        }
+
+        let snapshot_id = self.created_at.format("%Y%m%dT%H%M%SZ").to_string();
+        let snapshot_dir = snapshot_dir(&self.snapshots_dir, &self.deployment_name, &snapshot_id)?;
+        if self.fs.path_kind(&snapshot_dir).await?.is_some() {
+            return Ok(self.failed(&format!("snapshot '{}' already exists", snapshot_id)));
+        }
+        // The archives and the description hold the data and the credentials of the deployment
+        self.fs.create_private_dir_all(&snapshot_dir).await?;
+
+        if lock {
+            debug!("locking the writes of the deployment");
+            self.fsync_locker
+                .fsync_lock(&self.deployment_name)
+                .await
+                .context("locking the writes of the deployment")?;
+        }
+
+        let archive_result = self.archive_volumes(&snapshot_dir, &paths).await;
+
+        // The deployment is unlocked, even when archiving failed
+        if lock {
+            self.fsync_locker
+                .fsync_unlock(&self.deployment_name)
+                .await
+                .context("unlocking the writes of the deployment")?;
+        }
+
+        let volumes = match archive_result {
+            Ok(volumes) => volumes,
+            Err(e) => {
+                // Don't leave an incomplete snapshot behind
+                if let Err(remove_error) = self.fs.remove_dir_all(&snapshot_dir).await {
+                    warn!(error = %remove_error, "removing the incomplete snapshot failed");
+                }
+                return Err(e);
+            }
+        };
+
+        let metadata = SnapshotMetadata {
+            id: snapshot_id.clone(),
+            deployment_name: self.deployment_name.clone(),
+            created_at: self.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            mongodb_version: deployment.mongodb_version.to_string(),
+            size_bytes: volumes.iter().map(|(_, size)| size).sum(),
+            volumes: volumes.into_iter().map(|(volume, _)| volume).collect(),
+            // Restoring into a new deployment doesn't need the deployment, it may be deleted by then
+            deployment: Some(DeploymentConfig::new(
+                &deployment,
+                container.config.and_then(|config| config.image),
+            )),
+        };
+        save_snapshot(self.fs.as_ref(), &snapshot_dir, &metadata).await?;
+
+        Ok(CreateResult::Created {
+            deployment_name: self.deployment_name.clone(),
+            snapshot_id,
+            size_bytes: metadata.size_bytes,
+        })
+    }
+}
+
+impl Create {
+    fn failed(&self, error: &str) -> CreateResult {
+        CreateResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error: error.to_string(),
+        }
+    }
+
+    /// Archive the volumes mounted on the paths, returns the archived volumes with the size of their archive.
+    async fn archive_volumes(
+        &self,
+        snapshot_dir: &std::path::Path,
+        paths: &[String],
+    ) -> Result<Vec<(SnapshotVolume, u64)>> {
+        let mut volumes = Vec::new();
+        for path in paths {
+            let archive = archive_name(path);
+            debug!(path, archive, "archiving volume");
+
+            let size = self
+                .docker
+                .archive_path(&self.deployment_name, path, &snapshot_dir.join(&archive))
+                .await
+                .with_context(|| format!("archiving the volume mounted on {path}"))?;
+
+            volumes.push((
+                SnapshotVolume {
+                    path: path.clone(),
+                    archive,
+                },
+                size,
+            ));
+        }
+
+        Ok(volumes)
+    }
+}
+
+/// File name of the archive of the volume mounted on the path, e.g. data-db.tar for /data/db.
+fn archive_name(path: &str) -> String {
+    format!("{}.tar", path.trim_matches('/').replace('/', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+    use crate::commands::test_support::{create_deployment, create_spinner_handle};
+    use crate::dependencies::mocks::{MockDocker, MockMongoDB, MockTokioFs};
+    use crate::interaction::mocks::MockInteraction;
+    use bollard::models::{ContainerInspectResponse, MountPoint, MountPointTypeEnum};
+    use chrono::TimeZone;
+
+    fn create_container() -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            mounts: Some(vec![
+                MountPoint {
+                    typ: Some(MountPointTypeEnum::VOLUME),
+                    name: Some("local1-db".to_string()),
+                    destination: Some("/data/db".to_string()),
+                    ..Default::default()
+                },
+                MountPoint {
+                    typ: Some(MountPointTypeEnum::VOLUME),
+                    name: Some("local1-configdb".to_string()),
+                    destination: Some("/data/configdb".to_string()),
+                    ..Default::default()
+                },
+                // Bind mounts, like a seed directory, are not part of the data of the deployment
+                MountPoint {
+                    typ: Some(MountPointTypeEnum::BIND),
+                    source: Some("/home/user/seed".to_string()),
+                    destination: Some("/docker-entrypoint-initdb.d".to_string()),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn create_interaction() -> MockInteraction {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Creating snapshot...")
+            .return_once(|_| Ok(create_spinner_handle()));
+        mock_interaction
+    }
+
+    fn create_command(
+        deployment_management: MockDocker,
+        docker: MockDocker,
+        fsync_locker: MockMongoDB,
+        fs: MockTokioFs,
+    ) -> Create {
+        Create {
+            deployment_name: "local1".to_string(),
+            snapshots_dir: PathBuf::from("/snapshots"),
+            created_at: Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap(),
+            interaction: Box::new(create_interaction()),
+            deployment_management: Box::new(deployment_management),
+            docker: Box::new(docker),
+            fsync_locker: Box::new(fsync_locker),
+            fs: Box::new(fs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_snapshot_of_running_deployment() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running)));
+        let mut mock_docker = MockDocker::new();
+        mock_docker
+            .expect_inspect_container_raw()
+            .return_once(|_| Ok(Some(create_container())));
+        let archive_calls = calls.clone();
+        mock_docker
+            .expect_archive_path()
+            .times(2)
+            .returning(move |_, path, archive| {
+                archive_calls
+                    .lock()
+                    .unwrap()
+                    .push(format!("archive {path} {}", archive.display()));
+                Ok(1024 * 1024)
+            });
+
+        let mut mock_fsync_locker = MockMongoDB::new();
+        let lock_calls = calls.clone();
+        mock_fsync_locker.expect_fsync_lock().return_once(move |_| {
+            lock_calls.lock().unwrap().push("lock".to_string());
+            Ok(())
+        });
+        let unlock_calls = calls.clone();
+        mock_fsync_locker
+            .expect_fsync_unlock()
+            .return_once(move |_| {
+                unlock_calls.lock().unwrap().push("unlock".to_string());
+                Ok(())
+            });
+
+        let snapshot_dir = Path::new("/snapshots/local1/20261016T120000Z");
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .withf(move |path| path == snapshot_dir)
+            .return_once(|_| Ok(None));
+        mock_fs
+            .expect_create_private_dir_all()
+            .withf(move |path| path == snapshot_dir)
+            .return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_private_string()
+            .withf(move |path, contents| {
+                path == snapshot_dir.join("snapshot.json")
+                    && serde_json::from_str::<SnapshotMetadata>(contents).unwrap()
+                        == SnapshotMetadata {
+                            id: "20261016T120000Z".to_string(),
+                            deployment_name: "local1".to_string(),
+                            created_at: "2026-10-16T12:00:00Z".to_string(),
+                            mongodb_version: "8.2.2".to_string(),
+                            size_bytes: 2 * 1024 * 1024,
+                            volumes: vec![
+                                SnapshotVolume {
+                                    path: "/data/configdb".to_string(),
+                                    archive: "data-configdb.tar".to_string(),
+                                },
+                                SnapshotVolume {
+                                    path: "/data/db".to_string(),
+                                    archive: "data-db.tar".to_string(),
+                                },
+                            ],
+                            deployment: Some(DeploymentConfig::new(
+                                &create_deployment("local1", State::Running),
+                                None,
+                            )),
+                        }
+            })
+            .return_once(|_, _| Ok(()));
+
+        let result = create_command(
+            mock_deployment_management,
+            mock_docker,
+            mock_fsync_locker,
+            mock_fs,
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateResult::Created {
+                deployment_name: "local1".to_string(),
+                snapshot_id: "20261016T120000Z".to_string(),
+                size_bytes: 2 * 1024 * 1024,
+            }
+        );
+        // The volumes are archived while the writes are locked
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "lock".to_string(),
+                format!(
+                    "archive /data/configdb {}",
+                    snapshot_dir.join("data-configdb.tar").display()
+                ),
+                format!(
+                    "archive /data/db {}",
+                    snapshot_dir.join("data-db.tar").display()
+                ),
+                "unlock".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_snapshot_removes_incomplete_snapshot() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Exited)));
+        let mut mock_docker = MockDocker::new();
+        mock_docker
+            .expect_inspect_container_raw()
+            .return_once(|_| Ok(Some(create_container())));
+        mock_docker
+            .expect_archive_path()
+            .return_once(|_, _, _| Err(anyhow::anyhow!("no space left on device")));
+
+        // A stopped deployment is archived without locking it
+        let mut mock_fsync_locker = MockMongoDB::new();
+        mock_fsync_locker.expect_fsync_lock().never();
+
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_fs
+            .expect_create_private_dir_all()
+            .return_once(|_| Ok(()));
+        mock_fs
+            .expect_remove_dir_all()
+            .withf(|path| path == Path::new("/snapshots/local1/20261016T120000Z"))
+            .return_once(|_| Ok(()));
+        mock_fs.expect_write_private_string().never();
+
+        let error = create_command(
+            mock_deployment_management,
+            mock_docker,
+            mock_fsync_locker,
+            mock_fs,
+        )
+        .execute()
+        .await
+        .expect_err("execute should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "archiving the volume mounted on /data/configdb"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_snapshot_of_paused_deployment() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Paused)));
+        let mut mock_docker = MockDocker::new();
+        mock_docker.expect_archive_path().never();
+
+        let result = create_command(
+            mock_deployment_management,
+            mock_docker,
+            MockMongoDB::new(),
+            MockTokioFs::new(),
+        )
+        .execute()
+        .await
+        .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            CreateResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "Deployment is paused, start it first".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Command to delete a saved snapshot.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        snapshot::{SnapshotsFs, load_snapshot, snapshot_dir, snapshots_dir},
+    },
+    dependencies::TokioFs,
+    settings::Settings,
+};
+
+pub struct Delete {
+    deployment_name: String,
+    snapshot_id: String,
+    snapshots_dir: PathBuf,
+
+    fs: Box<dyn SnapshotsFs>,
+}
+
+impl TryFrom<(args::snapshot::Delete, &Settings)> for Delete {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::snapshot::Delete, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            snapshot_id: args.snapshot,
+            snapshots_dir: snapshots_dir(args.snapshots_dir.snapshots_dir, settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum DeleteResult {
+    Deleted {
+        deployment_name: String,
+        snapshot_id: String,
+    },
+    Failed {
+        deployment_name: String,
+        snapshot_id: String,
+        error: String,
+    },
+}
+
+impl Display for DeleteResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deleted {
+                deployment_name,
+                snapshot_id,
+            } => write!(
+                f,
+                "Snapshot '{}' of deployment '{}' deleted",
+                snapshot_id, deployment_name
+            ),
+            Self::Failed {
+                deployment_name,
+                snapshot_id,
+                error,
+            } => write!(
+                f,
+                "Deleting snapshot '{}' of deployment '{}' failed: {}",
+                snapshot_id, deployment_name, error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Delete {
+    type Output = DeleteResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let snapshot_dir = snapshot_dir(
+            &self.snapshots_dir,
+            &self.deployment_name,
+            &self.snapshot_id,
+        )?;
+
+        // Only directories with a snapshot are deleted, never an arbitrary directory
+        if load_snapshot(self.fs.as_ref(), &snapshot_dir)
+            .await?
+            .is_none()
+        {
+            return Ok(DeleteResult::Failed {
+                deployment_name: self.deployment_name.clone(),
+                snapshot_id: self.snapshot_id.clone(),
+                error: "snapshot not found".to_string(),
+            });
+        }
+
+        debug!(path = %snapshot_dir.display(), "deleting snapshot");
+        self.fs.remove_dir_all(&snapshot_dir).await?;
+
+        Ok(DeleteResult::Deleted {
+            deployment_name: self.deployment_name.clone(),
+            snapshot_id: self.snapshot_id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    fn create_delete_command(fs: MockTokioFs) -> Delete {
+        Delete {
+            deployment_name: "local1".to_string(),
+            snapshot_id: "20261016T120000Z".to_string(),
+            snapshots_dir: PathBuf::from("/snapshots"),
+            fs: Box::new(fs),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_snapshot() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(r#"{"id":"20261016T120000Z","deploymentName":"local1","createdAt":"2026-10-16T12:00:00Z","mongodbVersion":"8.2.2","sizeBytes":1024,"volumes":[]}"#.to_string())
+        });
+        mock_fs
+            .expect_remove_dir_all()
+            .withf(|path| path == Path::new("/snapshots/local1/20261016T120000Z"))
+            .return_once(|_| Ok(()));
+
+        let result = create_delete_command(mock_fs)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Deleted {
+                deployment_name: "local1".to_string(),
+                snapshot_id: "20261016T120000Z".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_snapshot_not_found() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_fs.expect_remove_dir_all().never();
+
+        let result = create_delete_command(mock_fs)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            DeleteResult::Failed {
+                deployment_name: "local1".to_string(),
+                snapshot_id: "20261016T120000Z".to_string(),
+                error: "snapshot not found".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Command to list the saved snapshots.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::{
+    args,
+    commands::{
+        CommandWithOutput,
+        snapshot::{SnapshotMetadata, SnapshotsFs, format_size, list_snapshots, snapshots_dir},
+    },
+    dependencies::TokioFs,
+    settings::Settings,
+    table::Table,
+};
+
+pub struct List {
+    deployment_name: Option<String>,
+    snapshots_dir: PathBuf,
+
+    fs: Box<dyn SnapshotsFs>,
+}
+
+impl TryFrom<(args::snapshot::List, &Settings)> for List {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::snapshot::List, &Settings)) -> Result<Self> {
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            snapshots_dir: snapshots_dir(args.snapshots_dir.snapshots_dir, settings)?,
+
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+/// Result of the snapshot list command, sorted by deployment and creation time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListResult(Vec<SnapshotMetadata>);
+
+impl From<&ListResult> for Table {
+    fn from(value: &ListResult) -> Self {
+        Table::from_iter(
+            &value.0,
+            &[
+                ("DEPLOYMENT", |s| s.deployment_name.clone()),
+                ("SNAPSHOT ID", |s| s.id.clone()),
+                ("CREATED", |s| s.created_at.clone()),
+                ("MDB VER", |s| s.mongodb_version.clone()),
+                ("SIZE", |s| format_size(s.size_bytes)),
+            ],
+        )
+    }
+}
+
+impl Display for ListResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Table::from(self).fmt(f)
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for List {
+    type Output = ListResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let mut snapshots = list_snapshots(
+            self.fs.as_ref(),
+            &self.snapshots_dir,
+            self.deployment_name.as_deref(),
+        )
+        .await?;
+
+        // The configuration of the deployment includes its credentials, it is only used to restore the snapshot
+        for snapshot in &mut snapshots {
+            snapshot.deployment = None;
+        }
+
+        Ok(ListResult(snapshots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[tokio::test]
+    async fn test_list_snapshots_of_deployment() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().returning(|path| {
+            Ok(Some(if path.ends_with("snapshot.json") {
+                PathKind::File
+            } else {
+                PathKind::Directory
+            }))
+        });
+        // Only the directory of the deployment is read
+        mock_fs
+            .expect_read_dir()
+            .withf(|path| path == PathBuf::from("/snapshots").join("local1"))
+            .return_once(|path| Ok(vec![path.join("20261016T120000Z")]));
+        mock_fs.expect_read_to_string().return_once(|_| {
+            Ok(r#"{"id":"20261016T120000Z","deploymentName":"local1","createdAt":"2026-10-16T12:00:00Z","mongodbVersion":"8.2.2","sizeBytes":1572864,"volumes":[{"path":"/data/db","archive":"data-db.tar"}]}"#.to_string())
+        });
+
+        let mut list_command = List {
+            deployment_name: Some("local1".to_string()),
+            snapshots_dir: PathBuf::from("/snapshots"),
+            fs: Box::new(mock_fs),
+        };
+
+        let result = list_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result
+                .0
+                .iter()
+                .map(|snapshot| (snapshot.deployment_name.as_str(), snapshot.id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("local1", "20261016T120000Z")]
+        );
+        assert!(result.to_string().contains("1.5 MiB"));
+    }
+}
@@ -0,0 +1,339 @@
+//! Commands to manage snapshots of deployments.
+//!
+//! A snapshot is a point-in-time copy of the data volumes of a deployment, saved as tar archives on the machine the
+//! CLI runs on. Every snapshot is a directory in the snapshots directory, `<deployment name>/<snapshot id>`, with an
+//! archive per volume and a `snapshot.json` file that describes the snapshot. The description is written last, so
+//! directories without one are incomplete snapshots and are ignored. The description also has the configuration of
+//! the deployment, so a snapshot can be restored into a new deployment after the deployment is deleted.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result, bail};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    commands::deployment_config::DeploymentConfig,
+    config_dir::config_dir,
+    dependencies::{
+        DirectoryLister, DirectoryRemover, FileReader, FileWriter, PathKind, PathResolver,
+    },
+    env,
+    settings::Settings,
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod restore;
+
+/// Name of the snapshots directory inside the configuration directory.
+const SNAPSHOTS_DIR_NAME: &str = "snapshots";
+
+/// Name of the file that describes a snapshot, inside the directory of the snapshot.
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+
+// Filesystem dependencies to save, list and delete snapshots
+pub trait SnapshotsFs:
+    FileReader + FileWriter + PathResolver + DirectoryLister + DirectoryRemover + Send + Sync
+{
+}
+impl<T: FileReader + FileWriter + PathResolver + DirectoryLister + DirectoryRemover + Send + Sync>
+    SnapshotsFs for T
+{
+}
+
+/// Description of a snapshot, as stored in the `snapshot.json` file of the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotMetadata {
+    pub id: String,
+    pub deployment_name: String,
+    /// Time the snapshot was created, in RFC 3339 format.
+    pub created_at: String,
+    /// MongoDB version of the deployment, the data files can only be used with the same version.
+    pub mongodb_version: String,
+    /// Total size of the archives.
+    pub size_bytes: u64,
+    pub volumes: Vec<SnapshotVolume>,
+    /// Configuration of the deployment, to create a new deployment for the snapshot with `snapshot restore --into`.
+    ///
+    /// It includes the credentials of the deployment, so `snapshot list` doesn't show it and the snapshot is only
+    /// readable by the current user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<DeploymentConfig>,
+}
+
+/// Archive of a data volume of the deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotVolume {
+    /// Path the volume is mounted on in the container, e.g. /data/db.
+    pub path: String,
+    /// File name of the archive, in the directory of the snapshot.
+    pub archive: String,
+}
+
+/// Directory where the snapshots are stored.
+pub fn snapshots_dir(override_dir: Option<PathBuf>, settings: &Settings) -> Result<PathBuf> {
+    resolve_snapshots_dir(
+        override_dir,
+        |name| std::env::var_os(name),
+        || config_dir(settings),
+    )
+}
+
+/// Resolve the snapshots directory, `var` looks up environment variables.
+fn resolve_snapshots_dir(
+    override_dir: Option<PathBuf>,
+    var: impl Fn(&str) -> Option<OsString>,
+    config_dir: impl FnOnce() -> Result<PathBuf>,
+) -> Result<PathBuf> {
+    // Precedence: --snapshotsDir, then the environment variable, then the configuration directory
+    if let Some(dir) =
+        override_dir.or_else(|| var(env::MONGODB_ATLAS_LOCAL_SNAPSHOTS_DIR).map(PathBuf::from))
+    {
+        return Ok(dir);
+    }
+
+    Ok(config_dir()?.join(SNAPSHOTS_DIR_NAME))
+}
+
+/// Directory of a snapshot.
+///
+/// The names are validated, so a name like `../..` can't select a directory outside the snapshots directory.
+pub fn snapshot_dir(
+    snapshots_dir: &Path,
+    deployment_name: &str,
+    snapshot_id: &str,
+) -> Result<PathBuf> {
+    Ok(snapshots_dir
+        .join(path_component(deployment_name, "deployment name")?)
+        .join(path_component(snapshot_id, "snapshot ID")?))
+}
+
+fn path_component<'a>(name: &'a str, kind: &str) -> Result<&'a str> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        bail!("invalid {}: '{}'", kind, name);
+    }
+
+    Ok(name)
+}
+
+/// Load the description of a snapshot, `None` when the snapshot doesn't exist.
+pub async fn load_snapshot(
+    fs: &dyn SnapshotsFs,
+    snapshot_dir: &Path,
+) -> Result<Option<SnapshotMetadata>> {
+    let path = snapshot_dir.join(SNAPSHOT_FILE_NAME);
+    if fs.path_kind(&path).await? != Some(PathKind::File) {
+        return Ok(None);
+    }
+
+    let contents = fs.read_to_string(&path).await?;
+    let metadata = serde_json::from_str(&contents)
+        .with_context(|| format!("parsing snapshot {}", path.display()))?;
+
+    Ok(Some(metadata))
+}
+
+/// Save the description of a snapshot, this completes the snapshot.
+///
+/// The description has the credentials of the deployment, so only the current user can read it.
+pub async fn save_snapshot(
+    fs: &dyn SnapshotsFs,
+    snapshot_dir: &Path,
+    metadata: &SnapshotMetadata,
+) -> Result<()> {
+    let contents = serde_json::to_string_pretty(metadata).context("serializing snapshot")?;
+    fs.write_private_string(&snapshot_dir.join(SNAPSHOT_FILE_NAME), &contents)
+        .await
+}
+
+/// List the snapshots of a deployment, or of all deployments, sorted by deployment and creation time.
+pub async fn list_snapshots(
+    fs: &dyn SnapshotsFs,
+    snapshots_dir: &Path,
+    deployment_name: Option<&str>,
+) -> Result<Vec<SnapshotMetadata>> {
+    let deployment_dirs = match deployment_name {
+        Some(deployment_name) => {
+            vec![snapshots_dir.join(path_component(deployment_name, "deployment name")?)]
+        }
+        None => list_dirs(fs, snapshots_dir).await?,
+    };
+
+    let mut snapshots = Vec::new();
+    for deployment_dir in deployment_dirs {
+        for snapshot_dir in list_dirs(fs, &deployment_dir).await? {
+            match load_snapshot(fs, &snapshot_dir).await? {
+                Some(metadata) => snapshots.push(metadata),
+                None => debug!(path = %snapshot_dir.display(), "incomplete snapshot, skipping"),
+            }
+        }
+    }
+
+    // The IDs are creation times that sort chronologically
+    snapshots.sort_by(|a, b| {
+        a.deployment_name
+            .cmp(&b.deployment_name)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    Ok(snapshots)
+}
+
+/// The directories in the directory, none when the directory doesn't exist.
+async fn list_dirs(fs: &dyn SnapshotsFs, dir: &Path) -> Result<Vec<PathBuf>> {
+    if fs.path_kind(dir).await? != Some(PathKind::Directory) {
+        return Ok(Vec::new());
+    }
+
+    let mut dirs = Vec::new();
+    for path in fs.read_dir(dir).await? {
+        if fs.path_kind(&path).await? == Some(PathKind::Directory) {
+            dirs.push(path);
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Format a size in bytes in MiB, with one decimal.
+fn format_size(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024 * 1024) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use anyhow::anyhow;
+
+    use super::*;
+    use crate::dependencies::mocks::MockTokioFs;
+
+    fn vars(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars = HashMap::<_, _>::from_iter(vars.iter().copied());
+        move |name| vars.get(name).map(OsString::from)
+    }
+
+    #[test]
+    fn test_resolve_snapshots_dir() {
+        let config_dir = || Ok(PathBuf::from("/config"));
+
+        assert_eq!(
+            resolve_snapshots_dir(
+                Some(PathBuf::from("/flag")),
+                vars(&[(env::MONGODB_ATLAS_LOCAL_SNAPSHOTS_DIR, "/env")]),
+                config_dir,
+            )
+            .unwrap(),
+            PathBuf::from("/flag")
+        );
+        assert_eq!(
+            resolve_snapshots_dir(
+                None,
+                vars(&[(env::MONGODB_ATLAS_LOCAL_SNAPSHOTS_DIR, "/env")]),
+                || Err(anyhow!("the configuration directory is not used")),
+            )
+            .unwrap(),
+            PathBuf::from("/env")
+        );
+        assert_eq!(
+            resolve_snapshots_dir(None, vars(&[]), config_dir).unwrap(),
+            PathBuf::from("/config").join("snapshots")
+        );
+    }
+
+    #[test]
+    fn test_snapshot_dir_rejects_paths() {
+        let snapshots_dir = Path::new("/snapshots");
+
+        assert_eq!(
+            snapshot_dir(snapshots_dir, "local1", "20261016T120000Z").unwrap(),
+            snapshots_dir.join("local1").join("20261016T120000Z")
+        );
+        for snapshot_id in ["", "..", "../local2", "a\\b"] {
+            assert!(
+                snapshot_dir(snapshots_dir, "local1", snapshot_id).is_err(),
+                "{snapshot_id}"
+            );
+        }
+        assert!(snapshot_dir(snapshots_dir, "..", "20261016T120000Z").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_snapshots_skips_incomplete_snapshots() {
+        let snapshots_dir = PathBuf::from("/snapshots");
+        let dirs = HashMap::from([
+            (
+                snapshots_dir.clone(),
+                vec![snapshots_dir.join("local2"), snapshots_dir.join("local1")],
+            ),
+            (
+                snapshots_dir.join("local1"),
+                vec![
+                    snapshots_dir.join("local1/20261016T120000Z"),
+                    snapshots_dir.join("local1/20261015T080000Z"),
+                ],
+            ),
+            (
+                snapshots_dir.join("local2"),
+                vec![snapshots_dir.join("local2/20261016T090000Z")],
+            ),
+        ]);
+        let snapshot_files = HashMap::from([
+            (
+                snapshots_dir.join("local1/20261016T120000Z/snapshot.json"),
+                r#"{"id":"20261016T120000Z","deploymentName":"local1","createdAt":"2026-10-16T12:00:00Z","mongodbVersion":"8.2.2","sizeBytes":1024,"volumes":[{"path":"/data/db","archive":"data-db.tar"}]}"#,
+            ),
+            (
+                snapshots_dir.join("local1/20261015T080000Z/snapshot.json"),
+                r#"{"id":"20261015T080000Z","deploymentName":"local1","createdAt":"2026-10-15T08:00:00Z","mongodbVersion":"8.2.2","sizeBytes":512,"volumes":[]}"#,
+            ),
+        ]);
+
+        let mut mock_fs = MockTokioFs::new();
+        let kind_dirs = dirs.clone();
+        let kind_files = snapshot_files.clone();
+        mock_fs.expect_path_kind().returning(move |path| {
+            Ok(if kind_files.contains_key(path) {
+                Some(PathKind::File)
+            } else if kind_dirs.contains_key(path) || path.starts_with("/snapshots/local2/") {
+                // The snapshot of local2 has no snapshot.json, it wasn't completed
+                Some(PathKind::Directory)
+            } else {
+                None
+            })
+        });
+        mock_fs
+            .expect_read_dir()
+            .returning(move |path| Ok(dirs.get(path).cloned().unwrap_or_default()));
+        mock_fs
+            .expect_read_to_string()
+            .returning(move |path| Ok(snapshot_files[path].to_string()));
+
+        let snapshots = list_snapshots(&mock_fs, &snapshots_dir, None)
+            .await
+            .expect("listing snapshots should succeed");
+
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|snapshot| snapshot.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["20261015T080000Z", "20261016T120000Z"]
+        );
+        assert_eq!(
+            snapshots[1].volumes,
+            vec![SnapshotVolume {
+                path: "/data/db".to_string(),
+                archive: "data-db.tar".to_string(),
+            }]
+        );
+    }
+}
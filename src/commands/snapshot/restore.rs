@@ -0,0 +1,912 @@
+//! Command to restore a snapshot of a deployment.
+//!
+//! The data volumes of the deployment are emptied and the archives of the snapshot are extracted into them, while the
+//! deployment is stopped. With `--into`, a new deployment is created with the configuration of the deployment saved
+//! in the snapshot and the snapshot is restored into the new deployment instead, so the deployment may be deleted.
+
+use std::{fmt::Display, path::PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use atlas_local::{
+    Client,
+    models::{Deployment, State},
+};
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use crate::{
+    args,
+    commands::{
+        CancelReason, CommandWithOutput,
+        clone::{copy_deployment_options, volumes_by_destination},
+        deployment_config::DeploymentConfig,
+        snapshot::{
+            SnapshotMetadata, SnapshotsFs, load_snapshot, path_component, snapshot_dir,
+            snapshots_dir,
+        },
+    },
+    dependencies::{
+        DeploymentContainerInspector, DeploymentCreator, DeploymentDeleter,
+        DeploymentGetDeployment, DeploymentStarter, DeploymentStopper, DockerError, TokioFs,
+        VolumeArchiveRestorer, connect_docker, find_deployment,
+    },
+    interaction::{
+        ConfirmationPrompt, ConfirmationPromptOptions, ConfirmationPromptResult, Interaction,
+        SpinnerInteraction,
+    },
+    messages::{Message, message},
+    settings::Settings,
+};
+
+// Interaction dependencies for the snapshot restore command
+pub trait RestoreInteraction: ConfirmationPrompt + SpinnerInteraction + Send {}
+impl<T: ConfirmationPrompt + SpinnerInteraction + Send> RestoreInteraction for T {}
+
+// Deployment dependencies for the snapshot restore command
+pub trait RestoreDeploymentManagement:
+    DeploymentGetDeployment
+    + DeploymentCreator
+    + DeploymentDeleter
+    + DeploymentStarter
+    + DeploymentStopper
+{
+}
+impl<
+    T: DeploymentGetDeployment
+        + DeploymentCreator
+        + DeploymentDeleter
+        + DeploymentStarter
+        + DeploymentStopper,
+> RestoreDeploymentManagement for T
+{
+}
+
+// Docker dependencies to restore the data volumes of a deployment
+pub trait RestoreDocker: DeploymentContainerInspector + VolumeArchiveRestorer {}
+impl<T: DeploymentContainerInspector + VolumeArchiveRestorer> RestoreDocker for T {}
+
+pub struct Restore {
+    deployment_name: String,
+    snapshot_id: String,
+    into: Option<String>,
+    force: bool,
+    snapshots_dir: PathBuf,
+
+    interaction: Box<dyn RestoreInteraction>,
+    deployment_management: Box<dyn RestoreDeploymentManagement + Send + Sync>,
+    docker: Box<dyn RestoreDocker + Send + Sync>,
+    fs: Box<dyn SnapshotsFs>,
+}
+
+impl TryFrom<(args::snapshot::Restore, &Settings)> for Restore {
+    type Error = anyhow::Error;
+
+    fn try_from((args, settings): (args::snapshot::Restore, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
+        Ok(Self {
+            deployment_name: args.deployment_name,
+            snapshot_id: args.snapshot,
+            into: args.into,
+            force: args.force,
+            snapshots_dir: snapshots_dir(args.snapshots_dir.snapshots_dir, settings)?,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker.clone())),
+            docker: Box::new(docker),
+            fs: Box::new(TokioFs::new()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RestoreResult {
+    Restored {
+        deployment_name: String,
+        snapshot_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+    },
+    Failed {
+        deployment_name: String,
+        error: String,
+    },
+    Canceled {
+        deployment_name: String,
+        reason: CancelReason,
+    },
+}
+
+impl Display for RestoreResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Restored {
+                deployment_name,
+                snapshot_id,
+                port,
+            } => {
+                write!(
+                    f,
+                    "Snapshot '{}' restored into deployment '{}'",
+                    snapshot_id, deployment_name
+                )?;
+                if let Some(port) = port {
+                    write!(f, " on port {}", port)?;
+                }
+                Ok(())
+            }
+            Self::Failed {
+                deployment_name,
+                error,
+            } => write!(
+                f,
+                "Restoring a snapshot into deployment '{}' failed: {}",
+                deployment_name, error
+            ),
+            Self::Canceled { .. } => write!(f, "Snapshot not restored"),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Restore {
+    type Output = RestoreResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        trace!(
+            deployment_name = ?self.deployment_name,
+            snapshot_id = ?self.snapshot_id,
+            into = ?self.into,
+            "executing snapshot restore command"
+        );
+
+        let snapshot_dir = snapshot_dir(
+            &self.snapshots_dir,
+            &self.deployment_name,
+            &self.snapshot_id,
+        )?;
+        let Some(snapshot) = load_snapshot(self.fs.as_ref(), &snapshot_dir).await? else {
+            return Ok(self.failed("snapshot not found".to_string()));
+        };
+
+        // Restoring into a new deployment doesn't touch existing data, so it doesn't need a confirmation
+        if self.into.is_none() && !self.force {
+            let confirmation = self
+                .interaction
+                .confirm(
+                    ConfirmationPromptOptions::builder()
+                        .pre_confirmation_help_text(
+                            "This operation will replace all of the data of the deployment with the data of the snapshot. This action cannot be undone.".to_string(),
+                        )
+                        .message(format!(
+                            "Are you sure you want to restore snapshot '{}' into '{}'?",
+                            self.snapshot_id, self.deployment_name
+                        ))
+                        .default(false)
+                        .build(),
+                )
+                .context("confirming restore")?;
+
+            if matches!(
+                confirmation,
+                ConfirmationPromptResult::No | ConfirmationPromptResult::Canceled
+            ) {
+                // Operation cancelled by user.
+                return Ok(RestoreResult::Canceled {
+                    deployment_name: self.deployment_name.clone(),
+                    reason: CancelReason::Prompt,
+                });
+            }
+        }
+
+        // When spinner goes out of scope, the spinner will be stopped
+        let _spinner = self
+            .interaction
+            .start_spinner(message(Message::RestoreSnapshot).to_string())?;
+
+        let start = match &self.into {
+            Some(into) => {
+                if find_deployment(self.deployment_management.as_ref(), into)
+                    .await?
+                    .is_some()
+                {
+                    return Ok(self.failed(format!("deployment '{}' already exists", into)));
+                }
+
+                let config = match snapshot.deployment.clone() {
+                    Some(config) => config,
+                    // Snapshots without the configuration use the configuration of the deployment
+                    None => match self.find_snapshot_deployment(&snapshot).await? {
+                        Ok(deployment) => DeploymentConfig::new(&deployment, None),
+                        Err(error) => return Ok(self.failed(error)),
+                    },
+                };
+
+                debug!("creating the new deployment");
+                if let Err(e) = self
+                    .deployment_management
+                    .create_deployment(copy_deployment_options(config, into)?)
+                    .wait_for_deployment_outcome()
+                    .await
+                {
+                    return Ok(self.failed(format!("failed to create the deployment: {e}")));
+                }
+
+                self.deployment_management
+                    .stop(into)
+                    .await
+                    .context("stopping the new deployment")?;
+                true
+            }
+            None => {
+                let deployment = match self.find_snapshot_deployment(&snapshot).await? {
+                    Ok(deployment) => deployment,
+                    Err(error) => return Ok(self.failed(error)),
+                };
+
+                let running = matches!(
+                    deployment.state,
+                    State::Running | State::Restarting | State::Paused
+                );
+                if running {
+                    debug!("stopping the deployment while its volumes are restored");
+                    self.deployment_management
+                        .stop(&self.deployment_name)
+                        .await
+                        .context("stopping the deployment")?;
+                }
+                running
+            }
+        };
+
+        if let Err(e) = self.restore_volumes(&snapshot_dir, &snapshot).await {
+            let Some(into) = &self.into else {
+                // The volumes may be partly emptied, so the deployment is not started with incomplete data
+                return Ok(self.failed(format!(
+                    "failed to restore the data volumes: {e:#}. Deployment '{}' is stopped and its data may be incomplete, restore the snapshot again",
+                    self.deployment_name
+                )));
+            };
+
+            let mut error = format!("failed to restore the data volumes: {e:#}");
+            // The new deployment has incomplete data, so it is not kept, like a failed clone
+            debug!("deleting the new deployment");
+            if let Err(e) = self.deployment_management.delete(into).await {
+                error.push_str(&format!(
+                    ", the new deployment '{}' could not be deleted: {e}",
+                    into
+                ));
+            }
+            return Ok(self.failed(error));
+        }
+
+        let target = self.target();
+        let mut port = None;
+        if start {
+            self.deployment_management
+                .start(target)
+                .await
+                .context("starting the deployment")?;
+
+            // The free port of a new deployment is picked by Docker when the deployment starts
+            if self.into.is_some() {
+                port = find_deployment(self.deployment_management.as_ref(), target)
+                    .await?
+                    .and_then(|deployment| deployment.port_bindings)
+                    .and_then(|binding| binding.port);
+            }
+        }
+
+        Ok(RestoreResult::Restored {
+            deployment_name: target.to_string(),
+            snapshot_id: self.snapshot_id.clone(),
+            port,
+        })
+    }
+}
+
+impl Restore {
+    /// The deployment the snapshot is restored into.
+    fn target(&self) -> &str {
+        self.into.as_deref().unwrap_or(&self.deployment_name)
+    }
+
+    fn failed(&self, error: String) -> RestoreResult {
+        RestoreResult::Failed {
+            deployment_name: self.target().to_string(),
+            error,
+        }
+    }
+
+    /// Get the deployment of the snapshot, the inner error is the failure when it can't restore the snapshot.
+    async fn find_snapshot_deployment(
+        &self,
+        snapshot: &SnapshotMetadata,
+    ) -> Result<Result<Deployment, String>> {
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(Err(format!(
+                "deployment '{}' not found",
+                self.deployment_name
+            )));
+        };
+
+        // The data files can only be read by the MongoDB version that wrote them
+        let mongodb_version = deployment.mongodb_version.to_string();
+        if mongodb_version != snapshot.mongodb_version {
+            return Ok(Err(format!(
+                "the snapshot was created with MongoDB {}, the deployment runs MongoDB {}",
+                snapshot.mongodb_version, mongodb_version
+            )));
+        }
+
+        Ok(Ok(deployment))
+    }
+
+    /// Replace the data volumes of the target deployment with the archives of the snapshot.
+    ///
+    /// The volumes are matched on the path they are mounted on in the container, e.g. /data/db. Every archive must
+    /// have a volume before any volume is touched.
+    async fn restore_volumes(
+        &self,
+        snapshot_dir: &std::path::Path,
+        snapshot: &SnapshotMetadata,
+    ) -> Result<()> {
+        let target = self.target();
+        let container = self
+            .docker
+            .inspect_container_raw(target)
+            .await
+            .map_err(DockerError::from)
+            .with_context(|| format!("inspecting deployment '{target}'"))?
+            .with_context(|| format!("deployment '{target}' not found"))?;
+
+        // The helper container runs the image of the deployment, it is pulled already
+        let image = container
+            .config
+            .as_ref()
+            .and_then(|config| config.image.clone())
+            .context("the container of the deployment has no image")?;
+
+        let volumes = volumes_by_destination(&container);
+        let mut restores = Vec::new();
+        for snapshot_volume in &snapshot.volumes {
+            let volume = volumes.get(&snapshot_volume.path).with_context(|| {
+                format!(
+                    "the deployment has no volume mounted on {}",
+                    snapshot_volume.path
+                )
+            })?;
+            // The snapshot description can be edited, its archives must stay in the snapshot directory
+            let archive =
+                snapshot_dir.join(path_component(&snapshot_volume.archive, "archive name")?);
+            restores.push((snapshot_volume.path.as_str(), volume, archive));
+        }
+
+        for (path, volume, archive) in restores {
+            debug!(path, volume, archive = %archive.display(), "restoring volume");
+            self.docker
+                .restore_volume(&image, volume, path, &archive)
+                .await
+                .with_context(|| format!("restoring the volume mounted on {path}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+    use crate::commands::test_support::{self, create_spinner_handle, create_successful_progress};
+    use crate::dependencies::{
+        PathKind,
+        mocks::{MockDocker, MockTokioFs},
+    };
+    use crate::interaction::mocks::MockInteraction;
+    use atlas_local::{
+        GetDeploymentError,
+        models::{BindingType, Deployment as AtlasDeployment, MongoDBPortBinding},
+    };
+    use bollard::errors::Error as BollardError;
+    use bollard::models::{
+        ContainerConfig, ContainerInspectResponse, MountPoint, MountPointTypeEnum,
+    };
+    use semver::Version;
+
+    const SNAPSHOT: &str = r#"{"id":"20261016T120000Z","deploymentName":"local1","createdAt":"2026-10-16T12:00:00Z","mongodbVersion":"8.2.2","sizeBytes":2048,"volumes":[{"path":"/data/configdb","archive":"data-configdb.tar"},{"path":"/data/db","archive":"data-db.tar"}]}"#;
+
+    fn create_deployment(name: &str, state: State, version: &str, port: u16) -> AtlasDeployment {
+        AtlasDeployment {
+            mongodb_version: Version::parse(version).unwrap(),
+            port_bindings: Some(MongoDBPortBinding::new(Some(port), BindingType::Loopback)),
+            ..test_support::create_deployment(name, state)
+        }
+    }
+
+    fn create_container(name: &str) -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            config: Some(ContainerConfig {
+                image: Some("mongodb/mongodb-atlas-local:8.2.2".to_string()),
+                ..Default::default()
+            }),
+            mounts: Some(
+                ["db", "configdb"]
+                    .iter()
+                    .map(|dir| MountPoint {
+                        typ: Some(MountPointTypeEnum::VOLUME),
+                        name: Some(format!("{name}-{dir}")),
+                        destination: Some(format!("/data/{dir}")),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn not_found() -> GetDeploymentError {
+        GetDeploymentError::ContainerInspect(BollardError::DockerResponseServerError {
+            status_code: 404,
+            message: "No such container".to_string(),
+        })
+    }
+
+    /// Filesystem with the snapshot 20261016T120000Z of local1.
+    fn create_fs() -> MockTokioFs {
+        create_fs_with_snapshot(SNAPSHOT.to_string())
+    }
+
+    /// Filesystem with the snapshot 20261016T120000Z of local1, described by the snapshot JSON.
+    fn create_fs_with_snapshot(snapshot: String) -> MockTokioFs {
+        let snapshot_file = Path::new("/snapshots/local1/20261016T120000Z/snapshot.json");
+
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .returning(move |path| Ok((path == snapshot_file).then_some(PathKind::File)));
+        mock_fs
+            .expect_read_to_string()
+            .returning(move |_| Ok(snapshot.clone()));
+        mock_fs
+    }
+
+    fn create_interaction() -> MockInteraction {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .withf(|msg| msg == "Restoring snapshot...")
+            .return_once(|_| Ok(create_spinner_handle()));
+        mock_interaction
+    }
+
+    fn create_command(
+        into: Option<&str>,
+        interaction: MockInteraction,
+        deployment_management: MockDocker,
+        docker: MockDocker,
+    ) -> Restore {
+        Restore {
+            deployment_name: "local1".to_string(),
+            snapshot_id: "20261016T120000Z".to_string(),
+            into: into.map(str::to_string),
+            force: false,
+            snapshots_dir: PathBuf::from("/snapshots"),
+            interaction: Box::new(interaction),
+            deployment_management: Box::new(deployment_management),
+            docker: Box::new(docker),
+            fs: Box::new(create_fs()),
+        }
+    }
+
+    /// Docker that records the volumes that are restored.
+    fn create_docker(calls: Arc<Mutex<Vec<String>>>) -> MockDocker {
+        let mut mock_docker = MockDocker::new();
+        mock_docker
+            .expect_inspect_container_raw()
+            .returning(|name| Ok(Some(create_container(name))));
+        mock_docker.expect_restore_volume().times(2).returning(
+            move |image, volume, path, archive| {
+                assert_eq!(image, "mongodb/mongodb-atlas-local:8.2.2");
+                calls
+                    .lock()
+                    .unwrap()
+                    .push(format!("restore {volume} {path} {}", archive.display()));
+                Ok(())
+            },
+        );
+        mock_docker
+    }
+
+    #[tokio::test]
+    async fn test_restore_running_deployment() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut mock_interaction = create_interaction();
+        mock_interaction
+            .expect_confirm()
+            .return_once(|_| Ok(ConfirmationPromptResult::Yes));
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running, "8.2.2", 27017)));
+        let stop_calls = calls.clone();
+        mock_deployment_management
+            .expect_stop()
+            .return_once(move |name| {
+                stop_calls.lock().unwrap().push(format!("stop {name}"));
+                Ok(())
+            });
+        let start_calls = calls.clone();
+        mock_deployment_management
+            .expect_start()
+            .return_once(move |name| {
+                start_calls.lock().unwrap().push(format!("start {name}"));
+                Ok(())
+            });
+
+        let mut restore_command = create_command(
+            None,
+            mock_interaction,
+            mock_deployment_management,
+            create_docker(calls.clone()),
+        );
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Restored {
+                deployment_name: "local1".to_string(),
+                snapshot_id: "20261016T120000Z".to_string(),
+                port: None,
+            }
+        );
+
+        // The deployment is stopped while its volumes are restored
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "stop local1",
+                "restore local1-configdb /data/configdb /snapshots/local1/20261016T120000Z/data-configdb.tar",
+                "restore local1-db /data/db /snapshots/local1/20261016T120000Z/data-db.tar",
+                "start local1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_into_new_deployment() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let mut mock_interaction = create_interaction();
+        // The deployment isn't touched, so there is nothing to confirm
+        mock_interaction.expect_confirm().never();
+
+        let mut mock_deployment_management = MockDocker::new();
+        let copy_gets = Arc::new(Mutex::new(0));
+        mock_deployment_management
+            .expect_get_deployment()
+            .returning(move |name| match name {
+                "local1" => Ok(create_deployment("local1", State::Running, "8.2.2", 27017)),
+                // The new deployment doesn't exist until it is created
+                _ => {
+                    let mut copy_gets = copy_gets.lock().unwrap();
+                    *copy_gets += 1;
+                    if *copy_gets == 1 {
+                        Err(not_found())
+                    } else {
+                        Ok(create_deployment("copy", State::Running, "8.2.2", 32768))
+                    }
+                }
+            });
+        mock_deployment_management
+            .expect_create_deployment()
+            .withf(|options| options.name.as_deref() == Some("copy"))
+            .return_once(|_| {
+                create_successful_progress(create_deployment(
+                    "copy",
+                    State::Running,
+                    "8.2.2",
+                    32768,
+                ))
+            });
+        mock_deployment_management
+            .expect_stop()
+            .withf(|name| name == "copy")
+            .return_once(|_| Ok(()));
+        mock_deployment_management
+            .expect_start()
+            .withf(|name| name == "copy")
+            .return_once(|_| Ok(()));
+
+        let mut restore_command = create_command(
+            Some("copy"),
+            mock_interaction,
+            mock_deployment_management,
+            create_docker(calls.clone()),
+        );
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Restored {
+                deployment_name: "copy".to_string(),
+                snapshot_id: "20261016T120000Z".to_string(),
+                port: Some(32768),
+            }
+        );
+        assert!(
+            calls
+                .lock()
+                .unwrap()
+                .iter()
+                .all(|call| call.starts_with("restore copy-"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_into_new_deployment_after_deployment_deleted() {
+        let mut snapshot: SnapshotMetadata = serde_json::from_str(SNAPSHOT).unwrap();
+        snapshot.deployment = Some(DeploymentConfig::new(
+            &AtlasDeployment {
+                mongodb_initdb_root_username: Some("admin".to_string()),
+                ..create_deployment("local1", State::Running, "8.2.2", 27017)
+            },
+            Some("mongodb/mongodb-atlas-local:8.2.2".to_string()),
+        ));
+
+        let mut mock_interaction = create_interaction();
+        mock_interaction.expect_confirm().never();
+
+        let mut mock_deployment_management = MockDocker::new();
+        let copy_gets = Arc::new(Mutex::new(0));
+        mock_deployment_management
+            .expect_get_deployment()
+            .returning(move |name| match name {
+                // The deployment of the snapshot was deleted
+                "local1" => Err(not_found()),
+                _ => {
+                    let mut copy_gets = copy_gets.lock().unwrap();
+                    *copy_gets += 1;
+                    if *copy_gets == 1 {
+                        Err(not_found())
+                    } else {
+                        Ok(create_deployment("copy", State::Running, "8.2.2", 32768))
+                    }
+                }
+            });
+        mock_deployment_management
+            .expect_create_deployment()
+            .withf(|options| {
+                options.name.as_deref() == Some("copy")
+                    && options.image.as_deref() == Some("mongodb/mongodb-atlas-local")
+                    && options.mongodb_initdb_root_username.as_deref() == Some("admin")
+            })
+            .return_once(|_| {
+                create_successful_progress(create_deployment(
+                    "copy",
+                    State::Running,
+                    "8.2.2",
+                    32768,
+                ))
+            });
+        mock_deployment_management
+            .expect_stop()
+            .withf(|name| name == "copy")
+            .return_once(|_| Ok(()));
+        mock_deployment_management
+            .expect_start()
+            .withf(|name| name == "copy")
+            .return_once(|_| Ok(()));
+
+        let mut restore_command = create_command(
+            Some("copy"),
+            mock_interaction,
+            mock_deployment_management,
+            create_docker(Arc::new(Mutex::new(Vec::new()))),
+        );
+        restore_command.fs = Box::new(create_fs_with_snapshot(
+            serde_json::to_string(&snapshot).unwrap(),
+        ));
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Restored {
+                deployment_name: "copy".to_string(),
+                snapshot_id: "20261016T120000Z".to_string(),
+                port: Some(32768),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_failure_reports_stopped_deployment() {
+        let mut mock_interaction = create_interaction();
+        mock_interaction
+            .expect_confirm()
+            .return_once(|_| Ok(ConfirmationPromptResult::Yes));
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running, "8.2.2", 27017)));
+        mock_deployment_management
+            .expect_stop()
+            .return_once(|_| Ok(()));
+        // The volumes may be partly emptied, so the deployment is not started
+        mock_deployment_management.expect_start().never();
+
+        let mut mock_docker = MockDocker::new();
+        mock_docker
+            .expect_inspect_container_raw()
+            .returning(|name| Ok(Some(create_container(name))));
+        mock_docker
+            .expect_restore_volume()
+            .return_once(|_, _, _, _| Err(anyhow::anyhow!("no space left on device")));
+
+        let mut restore_command = create_command(
+            None,
+            mock_interaction,
+            mock_deployment_management,
+            mock_docker,
+        );
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Failed {
+                deployment_name: "local1".to_string(),
+                error: "failed to restore the data volumes: restoring the volume mounted on /data/configdb: no space left on device. Deployment 'local1' is stopped and its data may be incomplete, restore the snapshot again".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_into_failure_deletes_new_deployment() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .returning(|name| match name {
+                "local1" => Ok(create_deployment(name, State::Running, "8.2.2", 27017)),
+                _ => Err(not_found()),
+            });
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(|_| {
+                create_successful_progress(create_deployment(
+                    "copy",
+                    State::Running,
+                    "8.2.2",
+                    32768,
+                ))
+            });
+        mock_deployment_management
+            .expect_stop()
+            .withf(|name| name == "copy")
+            .return_once(|_| Ok(()));
+        // The new deployment has incomplete data, so it is deleted instead of started
+        mock_deployment_management
+            .expect_delete()
+            .withf(|name| name == "copy")
+            .return_once(|_| Ok(()));
+        mock_deployment_management.expect_start().never();
+
+        let mut mock_docker = MockDocker::new();
+        mock_docker
+            .expect_inspect_container_raw()
+            .returning(|name| Ok(Some(create_container(name))));
+        mock_docker
+            .expect_restore_volume()
+            .return_once(|_, _, _, _| Err(anyhow::anyhow!("no space left on device")));
+
+        let mut restore_command = create_command(
+            Some("copy"),
+            create_interaction(),
+            mock_deployment_management,
+            mock_docker,
+        );
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Failed {
+                deployment_name: "copy".to_string(),
+                error: "failed to restore the data volumes: restoring the volume mounted on /data/configdb: no space left on device".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_other_mongodb_version() {
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|name| Ok(create_deployment(name, State::Running, "8.0.4", 27017)));
+        mock_deployment_management.expect_stop().never();
+
+        let mut restore_command = Restore {
+            force: true,
+            ..create_command(
+                None,
+                create_interaction(),
+                mock_deployment_management,
+                MockDocker::new(),
+            )
+        };
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Failed {
+                deployment_name: "local1".to_string(),
+                error:
+                    "the snapshot was created with MongoDB 8.2.2, the deployment runs MongoDB 8.0.4"
+                        .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_canceled() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_confirm()
+            .return_once(|_| Ok(ConfirmationPromptResult::No));
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management.expect_get_deployment().never();
+
+        let mut restore_command = create_command(
+            None,
+            mock_interaction,
+            mock_deployment_management,
+            MockDocker::new(),
+        );
+
+        let result = restore_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            RestoreResult::Canceled {
+                deployment_name: "local1".to_string(),
+                reason: CancelReason::Prompt,
+            }
+        );
+    }
+}
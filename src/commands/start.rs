@@ -5,54 +5,102 @@ use async_trait::async_trait;
 use atlas_local::{
     Client,
     client::WatchDeploymentError,
-    models::{State, WatchOptions},
+    models::{Deployment, State, WatchOptions},
 };
-use bollard::Docker;
 use serde::Serialize;
 use tracing::{debug, info, trace};
 
 use crate::{
-    args,
-    commands::CommandWithOutput,
+    args::{self, TimeoutAction},
+    commands::{
+        CancelReason, CommandWithOutput,
+        deployment_config::DeploymentConfig,
+        notify::CompletionNotification,
+        with_mongodb::{LocalDeploymentPrimaryWaiter, WAIT_FOR_PRIMARY_TIMEOUT},
+    },
     dependencies::{
+        DeploymentContainerInspector, DeploymentContainerRemover, DeploymentCreator,
         DeploymentGetDeployment, DeploymentStarter, DeploymentUnpauser, DeploymentWaiter,
+        DockerError, DockerSettings, PrimaryWaiter, connect_docker, find_deployment,
+        wait_for_healthy,
+    },
+    interaction::{
+        ConfirmationPrompt, ConfirmationPromptOptions, ConfirmationPromptResult, Interaction,
+        SpinnerInteraction,
     },
-    interaction::{Interaction, SpinnerInteraction},
+    messages::{Message, message},
+    settings::Settings,
 };
 
 // Start dependencies for the start command
 pub trait StartDeploymentManagement:
-    DeploymentStarter + DeploymentGetDeployment + DeploymentUnpauser + DeploymentWaiter
+    DeploymentStarter
+    + DeploymentGetDeployment
+    + DeploymentUnpauser
+    + DeploymentWaiter
+    + DeploymentCreator
+{
+}
+impl<
+    T: DeploymentStarter
+        + DeploymentGetDeployment
+        + DeploymentUnpauser
+        + DeploymentWaiter
+        + DeploymentCreator,
+> StartDeploymentManagement for T
 {
 }
-impl<T: DeploymentStarter + DeploymentGetDeployment + DeploymentUnpauser + DeploymentWaiter>
-    StartDeploymentManagement for T
+
+// Container dependencies to recreate the container of a deployment
+pub trait StartContainerManagement:
+    DeploymentContainerRemover + DeploymentContainerInspector
 {
 }
+impl<T: DeploymentContainerRemover + DeploymentContainerInspector> StartContainerManagement for T {}
+
+// Interaction dependencies for the start command
+pub trait StartInteraction: ConfirmationPrompt + SpinnerInteraction + Send {}
+impl<T: ConfirmationPrompt + SpinnerInteraction + Send> StartInteraction for T {}
 
 pub struct Start {
     deployment_name: String,
 
     wait_for_healthy: bool,
     wait_for_healthy_timeout: Duration,
-
-    interaction: Box<dyn SpinnerInteraction + Send>,
-    deployment_management: Box<dyn StartDeploymentManagement + Send>,
+    timeout_action: TimeoutAction,
+    wait_for_primary: bool,
+
+    recreate: bool,
+    force: bool,
+
+    interaction: Box<dyn StartInteraction>,
+    deployment_management: Box<dyn StartDeploymentManagement + Send + Sync>,
+    // How to reconnect to Docker while waiting for the deployment, with --reconnectOnDrop
+    docker_settings: DockerSettings,
+    container_management: Box<dyn StartContainerManagement + Send + Sync>,
+    primary_waiter: Box<dyn PrimaryWaiter + Send + Sync>,
 }
 
-impl TryFrom<args::Start> for Start {
+impl TryFrom<(args::Start, &Settings)> for Start {
     type Error = anyhow::Error;
 
-    fn try_from(args: args::Start) -> Result<Self> {
+    fn try_from((args, settings): (args::Start, &Settings)) -> Result<Self> {
+        let docker = connect_docker(&settings.docker).context("connecting to Docker")?;
+
         Ok(Self {
             deployment_name: args.deployment_name,
             wait_for_healthy: args.wait_for_healthy,
             wait_for_healthy_timeout: args.wait_for_healthy_timeout,
-
-            interaction: Box::new(Interaction::new()),
-            deployment_management: Box::new(Client::new(
-                Docker::connect_with_defaults().context("connecting to Docker")?,
-            )),
+            timeout_action: args.timeout_action,
+            wait_for_primary: args.wait_for_primary,
+            recreate: args.recreate,
+            force: args.force,
+
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
+            deployment_management: Box::new(Client::new(docker.clone())),
+            docker_settings: settings.docker.clone(),
+            container_management: Box::new(docker),
+            primary_waiter: Box::new(LocalDeploymentPrimaryWaiter::new(settings.docker.clone())),
         })
     }
 }
@@ -62,18 +110,32 @@ impl TryFrom<args::Start> for Start {
 pub enum StartResult {
     Started {
         deployment_name: String,
+        // Set when the deployment is reported as started without confirming it is healthy
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warning: Option<String>,
     },
     Failed {
         deployment_name: String,
         error: String,
     },
+    Canceled {
+        deployment_name: String,
+        reason: CancelReason,
+    },
 }
 
 impl Display for StartResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Started { deployment_name } => {
-                write!(f, "Deployment '{}' started", deployment_name)
+            Self::Started {
+                deployment_name,
+                warning,
+            } => {
+                write!(f, "Deployment '{}' started", deployment_name)?;
+                if let Some(warning) = warning {
+                    write!(f, "\nWarning: {}", warning)?;
+                }
+                Ok(())
             }
             Self::Failed {
                 deployment_name,
@@ -85,6 +147,22 @@ impl Display for StartResult {
                     deployment_name, error
                 )
             }
+            Self::Canceled { .. } => write!(f, "Deployment not recreated"),
+        }
+    }
+}
+
+impl CompletionNotification for StartResult {
+    fn completion_notification(&self) -> String {
+        match self {
+            Self::Started {
+                deployment_name,
+                warning: None,
+            } => {
+                format!("Deployment '{deployment_name}' is ready")
+            }
+            Self::Started { .. } => self.to_string(),
+            Self::Failed { .. } | Self::Canceled { .. } => self.to_string(),
         }
     }
 }
@@ -98,41 +176,60 @@ impl CommandWithOutput for Start {
             deployment_name=?self.deployment_name,
             wait_for_healthy=self.wait_for_healthy,
             wait_for_healthy_timeout=?self.wait_for_healthy_timeout,
+            timeout_action=?self.timeout_action,
+            recreate=self.recreate,
         "executing start command");
 
         // Start the spinner
         // When start_spinner goes out of scope, the spinner will be stopped (when the command returns early)
         // Or when start_spinner gets dropped later in the code (before waiting for healthy deployment)
-        let start_spinner = self
-            .interaction
-            .start_spinner("Starting deployment...".to_string())?;
+        let mut start_spinner = Some(
+            self.interaction
+                .start_spinner(message(Message::StartDeployment).to_string())?,
+        );
 
         debug!("searching for deployment '{}'", self.deployment_name);
 
         // Get the deployment
         // If the deployment is not found, return a failed result
-        let deployment = match self
-            .deployment_management
-            .get_deployment(&self.deployment_name)
-            .await
-        {
-            Ok(deployment) => deployment,
-            Err(err) => match err {
-                atlas_local::GetDeploymentError::ContainerInspect(error) => {
-                    return Ok(StartResult::Failed {
-                        deployment_name: self.deployment_name.clone(),
-                        error: error.to_string(),
-                    });
-                }
-                atlas_local::GetDeploymentError::IntoDeployment(e) => {
-                    bail!("Failed to get deployment, into deployment error: {}", e)
-                }
-            },
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(StartResult::Failed {
+                deployment_name: self.deployment_name.clone(),
+                error: "deployment not found".to_string(),
+            });
         };
 
         debug!(?deployment, "deployment found");
 
         match deployment.state {
+            State::Exited | State::Dead if self.recreate => {
+                // recreate the container from the configuration of the current one, the volumes are kept
+                debug!(state=?deployment.state, "recreating deployment");
+
+                // Stop the spinner while asking for confirmation
+                drop(start_spinner.take());
+
+                if !self.force && !self.confirm_recreate()? {
+                    // Operation cancelled by user.
+                    return Ok(StartResult::Canceled {
+                        deployment_name: self.deployment_name.clone(),
+                        reason: CancelReason::Prompt,
+                    });
+                }
+
+                start_spinner = Some(
+                    self.interaction
+                        .start_spinner(message(Message::RecreateDeployment).to_string())?,
+                );
+
+                if let Some(failed) = self.recreate_deployment(&deployment).await? {
+                    return Ok(failed);
+                }
+
+                debug!("deployment recreated");
+            }
             State::Created | State::Exited => {
                 // start the deployment when the container is not running (created or exited)
                 debug!(state=?deployment.state, "starting deployment");
@@ -182,16 +279,14 @@ impl CommandWithOutput for Start {
         if !self.wait_for_healthy {
             info!("deployment started, health check skipped (--wait-for-healthy=false)");
 
-            return Ok(StartResult::Started {
-                deployment_name: self.deployment_name.clone(),
-            });
+            return self.wait_for_primary_if_requested().await;
         }
 
         // Start the wait for health deployment spinner
         // When wait_for_healthy_spinner goes out of scope, the spinner will be stopped
         let _wait_for_healthy_deployment_spinner = self
             .interaction
-            .start_spinner("Waiting for deployment to become healthy...".to_string())?;
+            .start_spinner(message(Message::WaitForHealthy).to_string())?;
 
         // now wait till the deployment is healthy
         // based on the initial state, it is possible for the deployment to start as unhealthy
@@ -213,27 +308,47 @@ impl CommandWithOutput for Start {
             // - healthy -> unhealthy
             // Because of this, we can never transition from unhealthy to healthy, so we return false
             State::Running => false,
-            // these states should never be encountered
+            // Dead deployments are only started when they are recreated, which results in a new container
+            // possible state transitions:
+            // - starting -> healthy
+            // - starting -> unhealthy
+            State::Dead => false,
+            // this state should never be encountered
             // but we return false to be safe in case of some race condition (state changing between last calls)
-            State::Dead | State::Removing => false,
+            State::Removing => false,
         };
 
         debug!(can_start_unhealthy, "waiting for healthy deployment");
 
         // wait till the deployment is healthy
         // If the deployment is not healthy within the timeout, return a failed result
-        if let Err(err) = self
-            .deployment_management
-            .wait_for_healthy_deployment(
-                &self.deployment_name,
-                WatchOptions::builder()
-                    .allow_unhealthy_initial_state(can_start_unhealthy)
-                    .timeout_duration(self.wait_for_healthy_timeout)
-                    .build(),
-            )
-            .await
+        if let Err(err) = wait_for_healthy(
+            self.deployment_management.as_ref(),
+            &self.docker_settings,
+            &self.deployment_name,
+            WatchOptions::builder()
+                .allow_unhealthy_initial_state(can_start_unhealthy)
+                .timeout_duration(self.wait_for_healthy_timeout)
+                .build(),
+        )
+        .await
         {
             match err {
+                WatchDeploymentError::Timeout { deployment_name: _ }
+                    if self.timeout_action == TimeoutAction::Continue =>
+                {
+                    debug!(
+                        "waiting for healthy deployment timed out, continuing (--timeoutAction=continue)"
+                    );
+
+                    return Ok(StartResult::Started {
+                        deployment_name: self.deployment_name.clone(),
+                        warning: Some(
+                            "Waiting for deployment to become healthy timed out, the deployment might still become healthy"
+                                .to_string(),
+                        ),
+                    });
+                }
                 WatchDeploymentError::Timeout { deployment_name: _ } => {
                     return Ok(StartResult::Failed {
                         deployment_name: self.deployment_name.clone(),
@@ -253,54 +368,154 @@ impl CommandWithOutput for Start {
             }
         }
 
+        self.wait_for_primary_if_requested().await
+    }
+}
+
+impl Start {
+    /// Wait for the deployment to have a primary when `--wait-for-primary` is set.
+    ///
+    /// A healthy container doesn't guarantee that the replica set has elected a primary yet.
+    async fn wait_for_primary_if_requested(&mut self) -> Result<StartResult> {
+        if self.wait_for_primary {
+            // When _wait_for_primary_spinner goes out of scope, the spinner will be stopped
+            let _wait_for_primary_spinner = self
+                .interaction
+                .start_spinner(message(Message::WaitForPrimary).to_string())?;
+
+            debug!("waiting for primary");
+
+            let has_primary = self
+                .primary_waiter
+                .wait_for_primary(&self.deployment_name, WAIT_FOR_PRIMARY_TIMEOUT)
+                .await
+                .context("waiting for primary")?;
+
+            if !has_primary {
+                return Ok(StartResult::Failed {
+                    deployment_name: self.deployment_name.clone(),
+                    error: "Waiting for deployment to have a primary timed out".to_string(),
+                });
+            }
+        }
+
         Ok(StartResult::Started {
             deployment_name: self.deployment_name.clone(),
+            warning: None,
         })
     }
+
+    fn failed(&self, error: String) -> StartResult {
+        StartResult::Failed {
+            deployment_name: self.deployment_name.clone(),
+            error,
+        }
+    }
+
+    /// Ask the user to confirm recreating the container of the deployment.
+    fn confirm_recreate(&self) -> Result<bool> {
+        let confirmation = self
+            .interaction
+            .confirm(
+                ConfirmationPromptOptions::builder()
+                    .pre_confirmation_help_text(
+                        "This operation will remove the container of the deployment and create a new one, its data volumes are kept."
+                            .to_string(),
+                    )
+                    .message(format!(
+                        "Are you sure you want to recreate '{}'?",
+                        self.deployment_name
+                    ))
+                    .default(false)
+                    .build(),
+            )
+            .context("confirming recreation")?;
+
+        Ok(matches!(confirmation, ConfirmationPromptResult::Yes))
+    }
+
+    /// Remove the container of the deployment and create a new one with the same configuration, this also starts the new container.
+    ///
+    /// Returns a failed result when the deployment could not be recreated.
+    async fn recreate_deployment(
+        &mut self,
+        deployment: &Deployment,
+    ) -> Result<Option<StartResult>> {
+        // Build the options before removing the container, so a deployment we can't recreate is left untouched
+        let Some(container) = self
+            .container_management
+            .inspect_container_raw(&self.deployment_name)
+            .await
+            .map_err(DockerError::from)
+            .context("inspecting the container of the deployment")?
+        else {
+            return Ok(Some(self.failed("deployment not found".to_string())));
+        };
+        let image = container.config.and_then(|config| config.image);
+        let mut create_deployment_options = DeploymentConfig::new(deployment, image)
+            .create_deployment_options(&self.deployment_name)?;
+        // The deployment keeps the tool that created it
+        create_deployment_options.creation_source = deployment.creation_source.clone();
+
+        if let Err(e) = self
+            .container_management
+            .remove_container_keep_volumes(&self.deployment_name)
+            .await
+        {
+            return Ok(Some(self.failed(e.to_string())));
+        }
+
+        debug!("container removed, creating a new one");
+
+        if let Err(e) = self
+            .deployment_management
+            .create_deployment(create_deployment_options)
+            .wait_for_deployment_outcome()
+            .await
+        {
+            return Ok(Some(self.failed(format!(
+                "failed to recreate the container: {e}. The old container was removed but its data volumes were kept, run setup {} with the same options to create the container again",
+                self.deployment_name
+            ))));
+        }
+
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::mocks::MockDocker;
-    use crate::interaction::SpinnerHandle;
+    use crate::commands::test_support::{
+        create_deployment, create_progress, create_spinner_handle, create_successful_progress,
+    };
+    use crate::dependencies::RemoveContainerError;
+    use crate::dependencies::mocks::{MockDocker, MockMongoDB};
     use crate::interaction::mocks::MockInteraction;
     use atlas_local::{
-        GetDeploymentError,
+        CreateDeploymentError, GetDeploymentError,
         client::{StartDeploymentError, UnpauseDeploymentError, WatchDeploymentError},
-        models::{Deployment as AtlasDeployment, IntoDeploymentError},
+        models::{BindingType, ImageTag, IntoDeploymentError, MongoDBPortBinding},
     };
     use bollard::errors::Error as BollardError;
+    use bollard::models::{ContainerConfig, ContainerInspectResponse};
     use bollard::secret::HealthStatusEnum;
-    use semver::Version;
     use std::io;
 
-    fn create_spinner_handle() -> SpinnerHandle {
-        SpinnerHandle::new(Box::new(|| {}))
-    }
-
-    fn create_deployment(name: &str, state: State) -> AtlasDeployment {
-        AtlasDeployment {
-            name: Some(name.to_string()),
-            container_id: format!("container-{}", name),
-            mongodb_version: Version::parse("8.2.2").unwrap(),
-            state,
-            port_bindings: None,
-            mongodb_type: atlas_local::models::MongodbType::Community,
-            creation_source: None,
-            local_seed_location: None,
-            mongodb_initdb_database: None,
-            mongodb_initdb_root_password_file: None,
-            mongodb_initdb_root_password: None,
-            mongodb_initdb_root_username_file: None,
-            mongodb_initdb_root_username: None,
-            mongodb_load_sample_data: None,
-            mongot_log_file: None,
-            runner_log_file: None,
-            do_not_track: true,
-            telemetry_base_url: None,
-            voyage_api_key: None,
-        }
+    fn create_container_management() -> MockDocker {
+        let mut mock_container_management = MockDocker::new();
+        mock_container_management
+            .expect_inspect_container_raw()
+            .returning(|_| {
+                Ok(Some(ContainerInspectResponse {
+                    config: Some(ContainerConfig {
+                        image: Some("mongodb/mongodb-atlas-local:8.2.2".to_string()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }))
+            });
+        mock_container_management
     }
 
     // State-based tests (wait_for_healthy=false)
@@ -333,8 +548,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -345,7 +567,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -378,8 +601,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -390,7 +620,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -423,8 +654,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -435,7 +673,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -462,8 +701,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -474,7 +720,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -506,8 +753,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -518,7 +772,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -545,8 +800,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -585,8 +847,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -650,8 +919,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -662,7 +938,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -712,8 +989,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -724,7 +1008,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -774,8 +1059,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -786,7 +1078,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -830,8 +1123,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -842,7 +1142,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -891,8 +1192,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -903,7 +1211,8 @@ mod tests {
         assert_eq!(
             result,
             StartResult::Started {
-                deployment_name: deployment_name.clone()
+                deployment_name: deployment_name.clone(),
+                warning: None,
             }
         );
     }
@@ -926,17 +1235,27 @@ mod tests {
             .expect_get_deployment()
             .withf(move |name| name == &deployment_name_clone)
             .return_once(|_| {
-                Err(GetDeploymentError::ContainerInspect(BollardError::from(
-                    io::Error::new(io::ErrorKind::NotFound, "container not found"),
-                )))
+                Err(GetDeploymentError::ContainerInspect(
+                    BollardError::DockerResponseServerError {
+                        status_code: 404,
+                        message: "No such container: test-deployment".to_string(),
+                    },
+                ))
             });
 
         let mut start_command = Start {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -948,11 +1267,53 @@ mod tests {
             result,
             StartResult::Failed {
                 deployment_name: deployment_name.clone(),
-                error: "container not found".to_string()
+                error: "deployment not found".to_string()
             }
         );
     }
 
+    #[tokio::test]
+    async fn test_start_get_deployment_docker_error() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|_| {
+                Err(GetDeploymentError::ContainerInspect(
+                    BollardError::DockerResponseServerError {
+                        status_code: 500,
+                        message: "internal server error".to_string(),
+                    },
+                ))
+            });
+
+        let mut start_command = Start {
+            deployment_name: "test-deployment".to_string(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let error = start_command
+            .execute()
+            .await
+            .expect_err("execute should fail");
+
+        assert!(error.chain().any(|cause| cause.is::<DockerError>()));
+    }
+
     #[tokio::test]
     async fn test_start_get_deployment_into_deployment_error() {
         let deployment_name = "test-deployment".to_string();
@@ -978,8 +1339,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command.execute().await;
@@ -1025,8 +1393,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command.execute().await;
@@ -1066,8 +1441,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: false,
             wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command.execute().await;
@@ -1124,8 +1506,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -1142,6 +1531,122 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_start_wait_timeout_continue() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Created)));
+        mock_deployment_management
+            .expect_start()
+            .return_once(|_| Ok(()));
+        mock_deployment_management
+            .expect_wait_for_healthy_deployment()
+            .return_once(|name, _| {
+                Err(WatchDeploymentError::Timeout {
+                    deployment_name: name.to_string(),
+                })
+            });
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: true,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Continue,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Started {
+                deployment_name: deployment_name.clone(),
+                warning: Some(
+                    "Waiting for deployment to become healthy timed out, the deployment might still become healthy"
+                        .to_string()
+                ),
+            }
+        );
+        assert_eq!(
+            result.to_string(),
+            "Deployment 'test-deployment' started\nWarning: Waiting for deployment to become healthy timed out, the deployment might still become healthy"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_wait_unhealthy_with_timeout_action_continue() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Created)));
+        mock_deployment_management
+            .expect_start()
+            .return_once(|_| Ok(()));
+        mock_deployment_management
+            .expect_wait_for_healthy_deployment()
+            .return_once(|name, _| {
+                Err(WatchDeploymentError::UnhealthyDeployment {
+                    deployment_name: name.to_string(),
+                    status: HealthStatusEnum::UNHEALTHY,
+                })
+            });
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: true,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Continue,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Failed {
+                deployment_name: deployment_name.clone(),
+                error: "Deployment became unhealthy".to_string()
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_start_wait_unhealthy() {
         let deployment_name = "test-deployment".to_string();
@@ -1192,8 +1697,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command
@@ -1259,8 +1771,15 @@ mod tests {
             deployment_name: deployment_name.clone(),
             wait_for_healthy: true,
             wait_for_healthy_timeout: timeout,
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: false,
+            force: false,
             interaction: Box::new(mock_interaction),
             deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(MockMongoDB::new()),
         };
 
         let result = start_command.execute().await;
@@ -1273,4 +1792,424 @@ mod tests {
                 .contains("Failed to wait for healthy deployment")
         );
     }
+
+    // Recreate tests
+
+    #[tokio::test]
+    async fn test_start_recreate_dead_deployment() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction.expect_confirm().never();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut dead_deployment = create_deployment(&deployment_name, State::Dead);
+        dead_deployment.port_bindings =
+            Some(MongoDBPortBinding::new(Some(27017), BindingType::Loopback));
+        dead_deployment.mongodb_initdb_root_username = Some("admin".to_string());
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_for_get = dead_deployment.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(deployment_for_get));
+        mock_deployment_management.expect_start().never();
+
+        let recreated_deployment = create_deployment(&deployment_name, State::Running);
+        let expected_name = deployment_name.clone();
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(move |options| {
+                assert_eq!(options.name, Some(expected_name));
+                assert_eq!(
+                    options.image,
+                    Some("mongodb/mongodb-atlas-local".to_string())
+                );
+                assert_eq!(
+                    options.image_tag,
+                    Some(ImageTag::try_from("8.2.2").unwrap())
+                );
+                assert_eq!(options.skip_pull_image, Some(true));
+                assert_eq!(options.wait_until_healthy, Some(false));
+                assert_eq!(
+                    options
+                        .mongodb_port_binding
+                        .and_then(|binding| binding.port),
+                    Some(27017)
+                );
+                assert_eq!(
+                    options.mongodb_initdb_root_username,
+                    Some("admin".to_string())
+                );
+                create_successful_progress(recreated_deployment)
+            });
+
+        let mut mock_remover = create_container_management();
+        let deployment_name_clone = deployment_name.clone();
+        mock_remover
+            .expect_remove_container_keep_volumes()
+            .withf(move |name| name == &deployment_name_clone)
+            .return_once(|_| Ok(()));
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: true,
+            force: true,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(mock_remover),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Started {
+                deployment_name: deployment_name.clone(),
+                warning: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_recreate_user_cancels() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+        mock_interaction
+            .expect_confirm()
+            .return_once(|_| Ok(ConfirmationPromptResult::No));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Exited)));
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut mock_remover = MockDocker::new();
+        mock_remover.expect_remove_container_keep_volumes().never();
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: true,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(mock_remover),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Canceled {
+                deployment_name: deployment_name.clone(),
+                reason: CancelReason::Prompt,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_recreate_running_deployment_is_not_recreated() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+        mock_interaction.expect_confirm().never();
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Running)));
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut mock_remover = MockDocker::new();
+        mock_remover.expect_remove_container_keep_volumes().never();
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: true,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(mock_remover),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Started {
+                deployment_name: deployment_name.clone(),
+                warning: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_recreate_remove_container_error() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Exited)));
+        mock_deployment_management
+            .expect_create_deployment()
+            .never();
+
+        let mut mock_remover = create_container_management();
+        mock_remover
+            .expect_remove_container_keep_volumes()
+            .return_once(|_| {
+                Err(RemoveContainerError::Docker(
+                    BollardError::RequestTimeoutError,
+                ))
+            });
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: true,
+            force: true,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(mock_remover),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Failed {
+                deployment_name: deployment_name.clone(),
+                error: format!(
+                    "failed to remove the container: {}",
+                    BollardError::RequestTimeoutError
+                )
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_recreate_create_deployment_error() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Exited)));
+        mock_deployment_management
+            .expect_create_deployment()
+            .return_once(|_| {
+                create_progress(Err(CreateDeploymentError::PullImage(
+                    atlas_local::client::PullImageError::from(BollardError::from(
+                        io::Error::other("Failed to pull image"),
+                    )),
+                )))
+            });
+
+        let mut mock_remover = create_container_management();
+        mock_remover
+            .expect_remove_container_keep_volumes()
+            .return_once(|_| Ok(()));
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: false,
+            recreate: true,
+            force: true,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(mock_remover),
+            primary_waiter: Box::new(MockMongoDB::new()),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        let StartResult::Failed { error, .. } = result else {
+            panic!("expected a failed result, got {result:?}");
+        };
+        assert!(error.starts_with("failed to recreate the container: "));
+        assert!(error.contains("its data volumes were kept"));
+    }
+
+    #[tokio::test]
+    async fn test_start_wait_for_primary_success() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Exited)));
+        mock_deployment_management
+            .expect_start()
+            .return_once(|_| Ok(()));
+
+        let mut mock_primary_waiter = MockMongoDB::new();
+        let deployment_name_clone = deployment_name.clone();
+        mock_primary_waiter
+            .expect_wait_for_primary()
+            .withf(move |name, timeout| {
+                name == deployment_name_clone && *timeout == WAIT_FOR_PRIMARY_TIMEOUT
+            })
+            .return_once(|_, _| Ok(true));
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: true,
+            recreate: false,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(mock_primary_waiter),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Started {
+                deployment_name: deployment_name.clone(),
+                warning: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_wait_for_primary_timeout() {
+        let deployment_name = "test-deployment".to_string();
+
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .returning(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        let deployment_name_for_get = deployment_name.clone();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(move |_| Ok(create_deployment(&deployment_name_for_get, State::Exited)));
+        mock_deployment_management
+            .expect_start()
+            .return_once(|_| Ok(()));
+
+        let mut mock_primary_waiter = MockMongoDB::new();
+        let deployment_name_clone = deployment_name.clone();
+        mock_primary_waiter
+            .expect_wait_for_primary()
+            .withf(move |name, timeout| {
+                name == deployment_name_clone && *timeout == WAIT_FOR_PRIMARY_TIMEOUT
+            })
+            .return_once(|_, _| Ok(false));
+
+        let mut start_command = Start {
+            deployment_name: deployment_name.clone(),
+            wait_for_healthy: false,
+            wait_for_healthy_timeout: Duration::from_secs(30),
+            timeout_action: TimeoutAction::Fail,
+            wait_for_primary: true,
+            recreate: false,
+            force: false,
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+            docker_settings: DockerSettings::default(),
+            container_management: Box::new(MockDocker::new()),
+            primary_waiter: Box::new(mock_primary_waiter),
+        };
+
+        let result = start_command
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            StartResult::Failed {
+                deployment_name: deployment_name.clone(),
+                error: "Waiting for deployment to have a primary timed out".to_string()
+            }
+        );
+    }
 }
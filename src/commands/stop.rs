@@ -1,17 +1,17 @@
 use std::fmt::Display;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use atlas_local::{Client, models::State};
-use bollard::Docker;
 use serde::Serialize;
 use tracing::{debug, trace};
 
 use crate::{
     args,
     commands::CommandWithOutput,
-    dependencies::{DeploymentGetDeployment, DeploymentStopper},
+    dependencies::{DeploymentGetDeployment, DeploymentStopper, connect_docker, find_deployment},
     interaction::{Interaction, SpinnerInteraction},
+    settings::Settings,
 };
 
 // Stop dependencies for the stop command
@@ -25,16 +25,16 @@ pub struct Stop {
     deployment_management: Box<dyn StopDeploymentManagement + Send>,
 }
 
-impl TryFrom<args::Stop> for Stop {
+impl TryFrom<(args::Stop, &Settings)> for Stop {
     type Error = anyhow::Error;
 
-    fn try_from(args: args::Stop) -> Result<Self> {
+    fn try_from((args, settings): (args::Stop, &Settings)) -> Result<Self> {
         Ok(Self {
             deployment_name: args.deployment_name,
 
-            interaction: Box::new(Interaction::new()),
+            interaction: Box::new(Interaction::new(settings.spinner_delay)),
             deployment_management: Box::new(Client::new(
-                Docker::connect_with_defaults().context("connecting to Docker")?,
+                connect_docker(&settings.docker).context("connecting to Docker")?,
             )),
         })
     }
@@ -91,23 +91,13 @@ impl CommandWithOutput for Stop {
 
         // Get the deployment
         // If the deployment is not found, return a failed result
-        let deployment = match self
-            .deployment_management
-            .get_deployment(&self.deployment_name)
-            .await
-        {
-            Ok(deployment) => deployment,
-            Err(err) => match err {
-                atlas_local::GetDeploymentError::ContainerInspect(error) => {
-                    return Ok(StopResult::Failed {
-                        deployment_name: self.deployment_name.clone(),
-                        error: error.to_string(),
-                    });
-                }
-                atlas_local::GetDeploymentError::IntoDeployment(e) => {
-                    bail!("Failed to get deployment, into deployment error: {}", e)
-                }
-            },
+        let Some(deployment) =
+            find_deployment(self.deployment_management.as_ref(), &self.deployment_name).await?
+        else {
+            return Ok(StopResult::Failed {
+                deployment_name: self.deployment_name.clone(),
+                error: "deployment not found".to_string(),
+            });
         };
 
         debug!(?deployment, "deployment found");
@@ -154,46 +144,15 @@ impl CommandWithOutput for Stop {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dependencies::mocks::MockDocker;
-    use crate::interaction::SpinnerHandle;
+    use crate::commands::test_support::{create_deployment, create_spinner_handle};
+    use crate::dependencies::{DockerError, mocks::MockDocker};
     use crate::interaction::mocks::MockInteraction;
     use atlas_local::{
-        GetDeploymentError,
-        client::StopDeploymentError,
-        models::{Deployment as AtlasDeployment, IntoDeploymentError},
+        GetDeploymentError, client::StopDeploymentError, models::IntoDeploymentError,
     };
     use bollard::errors::Error as BollardError;
-    use semver::Version;
     use std::io;
 
-    fn create_spinner_handle() -> SpinnerHandle {
-        SpinnerHandle::new(Box::new(|| {}))
-    }
-
-    fn create_deployment(name: &str, state: State) -> AtlasDeployment {
-        AtlasDeployment {
-            name: Some(name.to_string()),
-            container_id: format!("container-{}", name),
-            mongodb_version: Version::parse("8.2.2").unwrap(),
-            state,
-            port_bindings: None,
-            mongodb_type: atlas_local::models::MongodbType::Community,
-            creation_source: None,
-            local_seed_location: None,
-            mongodb_initdb_database: None,
-            mongodb_initdb_root_password_file: None,
-            mongodb_initdb_root_password: None,
-            mongodb_initdb_root_username_file: None,
-            mongodb_initdb_root_username: None,
-            mongodb_load_sample_data: None,
-            mongot_log_file: None,
-            runner_log_file: None,
-            do_not_track: true,
-            telemetry_base_url: None,
-            voyage_api_key: None,
-        }
-    }
-
     // State-based tests
 
     #[tokio::test]
@@ -498,9 +457,12 @@ mod tests {
             .expect_get_deployment()
             .withf(move |name| name == &deployment_name_clone)
             .return_once(|_| {
-                Err(GetDeploymentError::ContainerInspect(BollardError::from(
-                    io::Error::new(io::ErrorKind::NotFound, "container not found"),
-                )))
+                Err(GetDeploymentError::ContainerInspect(
+                    BollardError::DockerResponseServerError {
+                        status_code: 404,
+                        message: "No such container: test-deployment".to_string(),
+                    },
+                ))
             });
 
         let mut stop_command = Stop {
@@ -518,11 +480,41 @@ mod tests {
             result,
             StopResult::Failed {
                 deployment_name: deployment_name.clone(),
-                error: "container not found".to_string()
+                error: "deployment not found".to_string()
             }
         );
     }
 
+    #[tokio::test]
+    async fn test_stop_get_deployment_docker_error() {
+        let mut mock_interaction = MockInteraction::new();
+        mock_interaction
+            .expect_start_spinner()
+            .return_once(|_| Ok(create_spinner_handle()));
+
+        let mut mock_deployment_management = MockDocker::new();
+        mock_deployment_management
+            .expect_get_deployment()
+            .return_once(|_| {
+                Err(GetDeploymentError::ContainerInspect(BollardError::from(
+                    io::Error::new(io::ErrorKind::ConnectionRefused, "connection refused"),
+                )))
+            });
+
+        let mut stop_command = Stop {
+            deployment_name: "test-deployment".to_string(),
+            interaction: Box::new(mock_interaction),
+            deployment_management: Box::new(mock_deployment_management),
+        };
+
+        let error = stop_command
+            .execute()
+            .await
+            .expect_err("execute should fail");
+
+        assert!(error.chain().any(|cause| cause.is::<DockerError>()));
+    }
+
     #[tokio::test]
     async fn test_stop_get_deployment_into_deployment_error() {
         let deployment_name = "test-deployment".to_string();
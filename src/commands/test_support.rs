@@ -0,0 +1,70 @@
+//! Factories shared by the tests of the commands.
+
+use atlas_local::{
+    CreateDeploymentError,
+    client::{CreateDeploymentProgress, CreateDeploymentStepOutcome},
+    models::{Deployment, MongodbType, State},
+};
+use futures_util::FutureExt;
+use semver::Version;
+
+use crate::interaction::SpinnerHandle;
+
+pub fn create_spinner_handle() -> SpinnerHandle {
+    SpinnerHandle::new(Box::new(|| {}))
+}
+
+/// A community deployment of MongoDB 8.2.2 without port binding, credentials or seed scripts.
+pub fn create_deployment(name: &str, state: State) -> Deployment {
+    Deployment {
+        name: Some(name.to_string()),
+        container_id: format!("container-{}", name),
+        mongodb_version: Version::parse("8.2.2").unwrap(),
+        state,
+        port_bindings: None,
+        mongodb_type: MongodbType::Community,
+        creation_source: None,
+        local_seed_location: None,
+        mongodb_initdb_database: None,
+        mongodb_initdb_root_password_file: None,
+        mongodb_initdb_root_password: None,
+        mongodb_initdb_root_username_file: None,
+        mongodb_initdb_root_username: None,
+        mongodb_load_sample_data: None,
+        mongot_log_file: None,
+        runner_log_file: None,
+        do_not_track: true,
+        telemetry_base_url: None,
+        voyage_api_key: None,
+    }
+}
+
+/// Progress of creating a deployment where every step succeeded, with the outcome of the deployment.
+pub fn create_progress(
+    deployment_result: Result<Deployment, CreateDeploymentError>,
+) -> CreateDeploymentProgress {
+    use tokio::sync::oneshot;
+
+    let (pull_s, pull_r) = oneshot::channel();
+    let _ = pull_s.send(CreateDeploymentStepOutcome::Success);
+    let (create_s, create_r) = oneshot::channel();
+    let _ = create_s.send(CreateDeploymentStepOutcome::Success);
+    let (start_s, start_r) = oneshot::channel();
+    let _ = start_s.send(CreateDeploymentStepOutcome::Success);
+    let (healthy_s, healthy_r) = oneshot::channel();
+    let _ = healthy_s.send(CreateDeploymentStepOutcome::Success);
+    let (deploy_s, deploy_r) = oneshot::channel();
+    let _ = deploy_s.send(deployment_result);
+
+    CreateDeploymentProgress {
+        pull_image_finished: pull_r.fuse(),
+        create_container_finished: create_r.fuse(),
+        start_container_finished: start_r.fuse(),
+        wait_for_healthy_deployment_finished: healthy_r.fuse(),
+        deployment: deploy_r.fuse(),
+    }
+}
+
+pub fn create_successful_progress(deployment: Deployment) -> CreateDeploymentProgress {
+    create_progress(Ok(deployment))
+}
@@ -43,10 +43,99 @@ impl InputValidator for ImageTagValidator {
     }
 }
 
+/// Support status of a MongoDB major version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionSupport {
+    Supported,
+    EndOfLife,
+}
+
+/// Support status of the MongoDB major versions, see https://www.mongodb.com/legal/support-policy/lifecycles.
+///
+/// Major versions older than the oldest entry are end of life, newer ones are assumed to be supported.
+const MAJOR_VERSION_SUPPORT: &[(u64, VersionSupport)] = &[
+    (4, VersionSupport::EndOfLife),
+    (5, VersionSupport::EndOfLife),
+    (6, VersionSupport::EndOfLife),
+    (7, VersionSupport::Supported),
+    (8, VersionSupport::Supported),
+];
+
+/// Support status of a major version, according to [`MAJOR_VERSION_SUPPORT`].
+fn major_version_support(major: u64) -> VersionSupport {
+    match MAJOR_VERSION_SUPPORT.iter().find(|(m, _)| *m == major) {
+        Some((_, support)) => *support,
+        None if major < MAJOR_VERSION_SUPPORT[0].0 => VersionSupport::EndOfLife,
+        None => VersionSupport::Supported,
+    }
+}
+
+/// Newest supported major version, suggested when an end-of-life version is requested.
+fn newest_supported_major_version() -> u64 {
+    MAJOR_VERSION_SUPPORT
+        .iter()
+        .rev()
+        .find(|(_, support)| *support == VersionSupport::Supported)
+        .map(|(major, _)| *major)
+        .unwrap_or_default()
+}
+
+/// Warning for image tags of an end-of-life MongoDB version, suggesting a supported version instead.
+///
+/// `latest` and `preview` always point to a supported version, so they never get a warning.
+pub fn end_of_life_warning(tag: &ImageTag) -> Option<String> {
+    let (ImageTag::Semver(version) | ImageTag::SemverTimestamp(version, _)) = tag else {
+        return None;
+    };
+    let major = match version {
+        MongoDBVersion::Major(MongoDBVersionMajor { major })
+        | MongoDBVersion::MajorMinor(MongoDBVersionMajorMinor { major, .. })
+        | MongoDBVersion::MajorMinorPatch(MongoDBVersionMajorMinorPatch { major, .. }) => {
+            // Compare as u64, independent of the integer type used by atlas-local
+            major.to_string().parse::<u64>().ok()?
+        }
+    };
+
+    (major_version_support(major) == VersionSupport::EndOfLife).then(|| {
+        format!(
+            "MongoDB {} reached its end of life and no longer receives fixes, consider using MongoDB {} instead",
+            major,
+            newest_supported_major_version()
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_major_version_support() {
+        assert_eq!(major_version_support(3), VersionSupport::EndOfLife);
+        assert_eq!(major_version_support(6), VersionSupport::EndOfLife);
+        assert_eq!(major_version_support(7), VersionSupport::Supported);
+        assert_eq!(major_version_support(8), VersionSupport::Supported);
+        assert_eq!(major_version_support(9), VersionSupport::Supported);
+        assert_eq!(newest_supported_major_version(), 8);
+    }
+
+    #[test]
+    fn test_end_of_life_warning() {
+        assert_eq!(
+            end_of_life_warning(&ImageTag::try_from("6.0.5").unwrap()),
+            Some(
+                "MongoDB 6 reached its end of life and no longer receives fixes, consider using MongoDB 8 instead"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            end_of_life_warning(&ImageTag::try_from("8.2").unwrap()),
+            None
+        );
+        assert_eq!(end_of_life_warning(&ImageTag::Latest), None);
+        assert_eq!(end_of_life_warning(&ImageTag::Preview), None);
+    }
+
     #[test]
     fn test_image_tag_validator() {
         let validator = ImageTagValidator;
@@ -9,6 +9,6 @@ pub mod port;
 pub mod yes_no;
 
 pub use deployment_name::DeploymentNameValidator;
-pub use image_tag::ImageTagValidator;
+pub use image_tag::{ImageTagValidator, end_of_life_warning};
 pub use port::PortValidator;
 pub use yes_no::{YesNoValidator, yes_no_to_bool};
@@ -0,0 +1,220 @@
+//! Command to show the version of the CLI.
+//!
+//! This module implements the `version` command. Besides the version of the CLI, it reports details that help
+//! troubleshooting platform specific issues: the host OS and architecture, the Docker server version and the
+//! platform of the images the CLI pulls. The Docker details are best-effort, so the command also works offline.
+
+use std::fmt::Display;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{
+    args,
+    commands::CommandWithOutput,
+    dependencies::{DockerServerVersion, connect_docker},
+    settings::Settings,
+};
+
+/// Version of the CLI.
+pub(super) const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Operating system of the deployment images, Docker runs them in a Linux VM on macOS and Windows.
+const IMAGE_OS: &str = "linux";
+
+/// Command to show the version of the CLI.
+pub struct Version {
+    host_os: &'static str,
+    host_arch: &'static str,
+
+    // `None` when connecting to Docker failed, e.g. because of an invalid DOCKER_HOST
+    docker_version_getter: Option<Box<dyn DockerServerVersion + Send + Sync>>,
+}
+
+/// The Docker connection is best-effort, the version is shown even when Docker can't be reached.
+impl From<(args::Version, &Settings)> for Version {
+    fn from((_, settings): (args::Version, &Settings)) -> Self {
+        Self {
+            host_os: std::env::consts::OS,
+            host_arch: std::env::consts::ARCH,
+
+            docker_version_getter: connect_docker(&settings.docker)
+                .inspect_err(|e| debug!(error = %e, "connecting to Docker failed"))
+                .ok()
+                .map(|docker| Box::new(docker) as Box<dyn DockerServerVersion + Send + Sync>),
+        }
+    }
+}
+
+/// Result of the version command.
+///
+/// With `--output json` the host and Docker details are nested objects:
+/// `{"version":"0.11.2","host":{"os":"linux","arch":"x86_64"},"docker":{"status":"available","server_version":"28.0.1","api_version":"1.48"},"image_platform":"linux/amd64"}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionResult {
+    pub version: String,
+    pub host: HostInfo,
+    pub docker: DockerInfo,
+    /// Platform of the deployment images, Docker pulls the images for the architecture of the Docker server.
+    pub image_platform: String,
+}
+
+/// Operating system and architecture of the machine the CLI runs on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+}
+
+/// Version of the Docker server, when it can be reached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DockerInfo {
+    Available {
+        server_version: String,
+        api_version: String,
+    },
+    Unavailable,
+}
+
+impl Display for VersionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Host: {}/{}", self.host.os, self.host.arch)?;
+        match &self.docker {
+            DockerInfo::Available {
+                server_version,
+                api_version,
+            } => writeln!(f, "Docker: {} (API {})", server_version, api_version)?,
+            DockerInfo::Unavailable => writeln!(f, "Docker: unavailable")?,
+        }
+        write!(f, "Image platform: {}", self.image_platform)
+    }
+}
+
+#[async_trait]
+impl CommandWithOutput for Version {
+    type Output = VersionResult;
+
+    async fn execute(&mut self) -> Result<Self::Output> {
+        let server_version = match &self.docker_version_getter {
+            Some(docker_version_getter) => docker_version_getter
+                .server_version()
+                .await
+                .inspect_err(|e| debug!(error = %e, "getting the Docker server version failed"))
+                .ok(),
+            None => None,
+        };
+
+        // Docker pulls the images for its own architecture, which can differ from the host, e.g. with a remote Docker host
+        let image_arch = server_version
+            .as_ref()
+            .and_then(|server_version| server_version.arch.clone())
+            .unwrap_or_else(|| docker_arch(self.host_arch).to_string());
+
+        let docker = match server_version {
+            Some(server_version) => DockerInfo::Available {
+                server_version: server_version.version.unwrap_or_default(),
+                api_version: server_version.api_version.unwrap_or_default(),
+            },
+            None => DockerInfo::Unavailable,
+        };
+
+        Ok(VersionResult {
+            version: CLI_VERSION.to_string(),
+            host: HostInfo {
+                os: self.host_os.to_string(),
+                arch: self.host_arch.to_string(),
+            },
+            docker,
+            image_platform: format!("{IMAGE_OS}/{image_arch}"),
+        })
+    }
+}
+
+/// Name Docker uses for the architecture, e.g. amd64 for x86_64.
+fn docker_arch(arch: &str) -> &str {
+    match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" => "386",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::models::SystemVersion;
+
+    use super::*;
+    use crate::dependencies::mocks::MockDocker;
+
+    fn create_version_command(docker_version_getter: MockDocker) -> Version {
+        Version {
+            host_os: "macos",
+            host_arch: "aarch64",
+            docker_version_getter: Some(Box::new(docker_version_getter)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_version_with_docker() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker.expect_server_version().return_once(|| {
+            Ok(SystemVersion {
+                version: Some("28.0.1".to_string()),
+                api_version: Some("1.48".to_string()),
+                arch: Some("amd64".to_string()),
+                ..Default::default()
+            })
+        });
+
+        let result = create_version_command(mock_docker)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(
+            result,
+            VersionResult {
+                version: CLI_VERSION.to_string(),
+                host: HostInfo {
+                    os: "macos".to_string(),
+                    arch: "aarch64".to_string(),
+                },
+                docker: DockerInfo::Available {
+                    server_version: "28.0.1".to_string(),
+                    api_version: "1.48".to_string(),
+                },
+                // The images match the Docker server, not the host
+                image_platform: "linux/amd64".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version_without_docker() {
+        let mut mock_docker = MockDocker::new();
+        mock_docker.expect_server_version().return_once(|| {
+            Err(bollard::errors::Error::SocketNotFoundError(
+                "/var/run/docker.sock".to_string(),
+            ))
+        });
+
+        let result = create_version_command(mock_docker)
+            .execute()
+            .await
+            .expect("execute should succeed");
+
+        assert_eq!(result.docker, DockerInfo::Unavailable);
+        assert_eq!(result.image_platform, "linux/arm64");
+        assert_eq!(
+            result.to_string(),
+            format!(
+                "Version: {CLI_VERSION}\nHost: macos/aarch64\nDocker: unavailable\nImage platform: linux/arm64"
+            )
+        );
+    }
+}
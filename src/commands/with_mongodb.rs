@@ -1,13 +1,39 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
 use anyhow::{Context, Result};
-use bollard::Docker;
+use async_trait::async_trait;
 use mongodb::{
     Client,
+    bson::doc,
     options::{ClientOptions, ConnectionString, Credential},
 };
+use semver::Version;
+use tracing::debug;
+
+use crate::{
+    dependencies::{
+        CapabilityProber, ConnectionStringPinger, DatabaseInfo, DatabaseLister, DockerSettings,
+        FsyncLocker, PrimaryWaiter, ProbedCapabilities, connect_docker,
+    },
+    settings::Settings,
+};
+
+/// Maximum time to wait for a deployment to have a primary, after it became healthy.
+///
+/// Used by setup, start and connect with --waitForPrimary.
+pub const WAIT_FOR_PRIMARY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Interval between checks for a primary.
+const PRIMARY_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 pub trait TryFromWithMongodbClient<T>: Sized {
     fn try_from_with_mongodb(
         value: T,
+        settings: &Settings,
+        settings: &Settings,
         client: Result<Client, TryToGetMongodbClientError>,
     ) -> Result<Self>;
 }
@@ -30,6 +56,8 @@ where
 {
     async fn with_mongodb_client_for_local_deployment(
         args: Args,
+        settings: &Settings,
+        clients: &mut MongodbClientCache,
         local_deployment_name_fn: FDeployment,
         username_fn: FUsername,
         password_fn: FPassword,
@@ -46,6 +74,8 @@ where
 {
     async fn with_mongodb_client_for_local_deployment(
         args: Args,
+        settings: &Settings,
+        clients: &mut MongodbClientCache,
         local_deployment_name_fn: FDeployment,
         username_fn: FUsername,
         password_fn: FPassword,
@@ -55,24 +85,74 @@ where
         let username = username_fn(&args);
         let password = password_fn(&args);
 
-        // Try to get a mongodb client for the local deployment.
-        let client_result =
-            try_get_mongodb_client_for_local_deployment(local_deployment_name, username, password)
-                .await;
+        // Try to get a mongodb client for the local deployment, reusing one created earlier in this invocation.
+        let client_result = clients
+            .get_or_connect(local_deployment_name, username, password)
+            .await;
 
         // Finally create a new instance of the command using the arguments and the mongodb client.
-        Self::try_from_with_mongodb(args, client_result)
+        Self::try_from_with_mongodb(args, settings, client_result)
+    }
+}
+
+/// Deployment and credentials a cached client was created for.
+type MongodbClientKey = (String, Option<String>, Option<String>);
+
+/// MongoDB clients created during a single invocation, keyed by deployment and credentials.
+///
+/// A cloned client shares the connection pool of the original,
+/// so commands running against the same deployment don't repeat the connection handshake.
+pub struct MongodbClientCache {
+    docker: DockerSettings,
+    clients: HashMap<MongodbClientKey, Client>,
+}
+
+impl MongodbClientCache {
+    /// A cache without clients, the clients are created for deployments of the Docker selected with `docker`.
+    pub fn new(docker: DockerSettings) -> Self {
+        Self {
+            docker,
+            clients: HashMap::new(),
+        }
+    }
+
+    /// Get the client for the local deployment, creating it when there is none yet.
+    ///
+    /// Failures are not cached, a later call tries to connect again.
+    pub async fn get_or_connect(
+        &mut self,
+        local_deployment_name: String,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Result<Client, TryToGetMongodbClientError> {
+        let key = (local_deployment_name, username, password);
+        if let Some(client) = self.clients.get(&key) {
+            debug!(deployment_name = %key.0, "reusing mongodb client");
+            return Ok(client.clone());
+        }
+
+        let client = try_get_mongodb_client_for_local_deployment(
+            &self.docker,
+            key.0.clone(),
+            key.1.clone(),
+            key.2.clone(),
+        )
+        .await?;
+        self.clients.insert(key, client.clone());
+
+        Ok(client)
     }
 }
 
 async fn try_get_mongodb_client_for_local_deployment(
+    docker: &DockerSettings,
     local_deployment_name: String,
     username: Option<String>,
     password: Option<String>,
 ) -> Result<Client, TryToGetMongodbClientError> {
     // Connect to docker and create a new client.
     let client = atlas_local::Client::new(
-        Docker::connect_with_defaults()
+        connect_docker(docker)
             .context("connecting to docker")
             .map_err(TryToGetMongodbClientError::ConnectingToDocker)?,
     );
@@ -106,3 +186,249 @@ async fn try_get_mongodb_client_for_local_deployment(
         .context("creating mongodb client")
         .map_err(TryToGetMongodbClientError::CreatingMongodbClient)
 }
+
+/// Waits for a local deployment to have a primary by polling the `hello` command.
+///
+/// The container healthcheck passing doesn't guarantee the replica set has elected a primary yet.
+/// The MongoDB client is created when waiting, because the deployment might not be running when the command is created.
+pub struct LocalDeploymentPrimaryWaiter {
+    docker: DockerSettings,
+}
+
+impl LocalDeploymentPrimaryWaiter {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl PrimaryWaiter for LocalDeploymentPrimaryWaiter {
+    async fn wait_for_primary(&self, deployment_name: &str, timeout: Duration) -> Result<bool> {
+        let client = try_get_mongodb_client_for_local_deployment(
+            &self.docker,
+            deployment_name.to_string(),
+            None,
+            None,
+        )
+        .await?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Bound every check by the remaining time, server selection can take longer than the timeout
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match tokio::time::timeout(remaining, is_writable_primary(&client)).await {
+                Ok(Ok(true)) => return Ok(true),
+                Ok(Ok(false)) => debug!("deployment has no primary yet"),
+                Ok(Err(e)) => debug!(?e, "checking for a primary failed, retrying"),
+                Err(_) => return Ok(false),
+            }
+
+            if Instant::now() + PRIMARY_POLL_INTERVAL >= deadline {
+                return Ok(false);
+            }
+            tokio::time::sleep(PRIMARY_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Pings a deployment with the MongoDB driver, to check that a connection string is usable.
+pub struct MongodbPinger;
+
+#[async_trait]
+impl ConnectionStringPinger for MongodbPinger {
+    async fn ping(&self, connection_string: &str, timeout: Duration) -> Result<()> {
+        let mut client_options = ClientOptions::parse(connection_string)
+            .await
+            .context("parsing connection string")?;
+        // Fail within the timeout when the deployment can't be reached, instead of the driver's default of 30 seconds
+        client_options.server_selection_timeout = Some(timeout);
+        client_options.connect_timeout = Some(timeout);
+
+        let client = Client::with_options(client_options).context("creating mongodb client")?;
+        client
+            .database("admin")
+            .run_command(doc! { "ping": 1 })
+            .await
+            .context("running ping command")?;
+
+        Ok(())
+    }
+}
+
+/// Locks and unlocks the writes of a local deployment with the `fsync` command.
+///
+/// The lock is held by the server, not by the connection, so the MongoDB client is created for each call.
+pub struct LocalDeploymentFsyncLocker {
+    docker: DockerSettings,
+}
+
+impl LocalDeploymentFsyncLocker {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl FsyncLocker for LocalDeploymentFsyncLocker {
+    async fn fsync_lock(&self, deployment_name: &str) -> Result<()> {
+        let client = try_get_mongodb_client_for_local_deployment(
+            &self.docker,
+            deployment_name.to_string(),
+            None,
+            None,
+        )
+        .await?;
+
+        client
+            .database("admin")
+            .run_command(doc! { "fsync": 1, "lock": true })
+            .await
+            .context("running fsync command")?;
+
+        Ok(())
+    }
+
+    async fn fsync_unlock(&self, deployment_name: &str) -> Result<()> {
+        let client = try_get_mongodb_client_for_local_deployment(
+            &self.docker,
+            deployment_name.to_string(),
+            None,
+            None,
+        )
+        .await?;
+
+        client
+            .database("admin")
+            .run_command(doc! { "fsyncUnlock": 1 })
+            .await
+            .context("running fsyncUnlock command")?;
+
+        Ok(())
+    }
+}
+
+/// Asks a local deployment for its capabilities with the MongoDB driver.
+pub struct LocalDeploymentCapabilityProber {
+    docker: DockerSettings,
+}
+
+impl LocalDeploymentCapabilityProber {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl CapabilityProber for LocalDeploymentCapabilityProber {
+    async fn probe_capabilities(&self, deployment_name: &str) -> Result<ProbedCapabilities> {
+        let client = try_get_mongodb_client_for_local_deployment(
+            &self.docker,
+            deployment_name.to_string(),
+            None,
+            None,
+        )
+        .await?;
+        let admin = client.database("admin");
+
+        let build_info = admin
+            .run_command(doc! { "buildInfo": 1 })
+            .await
+            .context("running buildInfo command")?;
+        let version = build_info
+            .get_str("version")
+            .context("reading version from buildInfo")?;
+        let mongodb_version = Version::parse(version)
+            .with_context(|| format!("parsing MongoDB version {version}"))?;
+
+        // mongod only forwards search commands when it is configured with the address of mongot
+        let search = match admin
+            .run_command(doc! { "getParameter": 1, "mongotHost": 1 })
+            .await
+        {
+            Ok(reply) => reply
+                .get_str("mongotHost")
+                .is_ok_and(|mongot_host| !mongot_host.is_empty()),
+            Err(e) => {
+                debug!(?e, "getting the mongotHost parameter failed");
+                false
+            }
+        };
+
+        Ok(ProbedCapabilities {
+            mongodb_version,
+            search,
+        })
+    }
+}
+
+/// Lists the databases of a local deployment with the MongoDB driver.
+pub struct LocalDeploymentDatabaseLister {
+    docker: DockerSettings,
+}
+
+impl LocalDeploymentDatabaseLister {
+    pub fn new(docker: DockerSettings) -> Self {
+        Self { docker }
+    }
+}
+
+#[async_trait]
+impl DatabaseLister for LocalDeploymentDatabaseLister {
+    async fn list_databases(&self, deployment_name: &str) -> Result<Vec<DatabaseInfo>> {
+        let client = try_get_mongodb_client_for_local_deployment(
+            &self.docker,
+            deployment_name.to_string(),
+            None,
+            None,
+        )
+        .await?;
+
+        let databases = client
+            .list_databases()
+            .await
+            .context("running listDatabases command")?;
+
+        Ok(databases
+            .into_iter()
+            .map(|database| DatabaseInfo {
+                name: database.name,
+                size_on_disk: database.size_on_disk,
+            })
+            .collect())
+    }
+}
+
+/// Returns whether the server the client is connected to is a writable primary.
+async fn is_writable_primary(client: &Client) -> Result<bool> {
+    let reply = client
+        .database("admin")
+        .run_command(doc! { "hello": 1 })
+        .await
+        .context("running hello command")?;
+
+    Ok(reply.get_bool("isWritablePrimary").unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_client_cache_reuses_client_for_same_deployment() {
+        // Creating a client doesn't connect, so this doesn't need a running deployment
+        let client = Client::with_uri_str("mongodb://127.0.0.1:27017")
+            .await
+            .expect("creating the client should succeed");
+        let mut clients = MongodbClientCache::new(DockerSettings::default());
+        clients
+            .clients
+            .insert(("local1".to_string(), None, None), client);
+
+        // A cached client is returned without connecting to Docker
+        let result = clients
+            .get_or_connect("local1".to_string(), None, None)
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(clients.clients.len(), 1);
+    }
+}
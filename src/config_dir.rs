@@ -0,0 +1,191 @@
+//! Directory where the CLI saves its state, e.g. the contexts created with `context set`.
+//!
+//! The directory is selected with the global `--configDir` option, the `MONGODB_ATLAS_LOCAL_CONFIG_DIR` environment variable,
+//! or defaults to the `atlas-local` directory in the user's configuration directory.
+//! Every component that reads or writes state must resolve its files with [`config_dir`].
+//!
+//! The state is saved in TOML files, which are loaded with [`load_toml`] and saved with [`save_toml`].
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Serialize, de::DeserializeOwned};
+use tracing::debug;
+
+use crate::{
+    dependencies::{FileReader, FileWriter, PathResolver},
+    env,
+    settings::Settings,
+};
+
+/// Name of the configuration directory inside the user's configuration directory.
+const CONFIG_DIR_NAME: &str = "atlas-local";
+
+/// Directory the CLI saves its state in.
+pub fn config_dir(settings: &Settings) -> Result<PathBuf> {
+    resolve_config_dir(settings.config_dir.clone(), |name| std::env::var_os(name))
+}
+
+/// Resolve the configuration directory, `var` looks up environment variables.
+fn resolve_config_dir(
+    override_dir: Option<PathBuf>,
+    var: impl Fn(&str) -> Option<OsString>,
+) -> Result<PathBuf> {
+    // Precedence: --configDir, then the environment variable, then the user's configuration directory
+    if let Some(dir) =
+        override_dir.or_else(|| var(env::MONGODB_ATLAS_LOCAL_CONFIG_DIR).map(PathBuf::from))
+    {
+        return Ok(dir);
+    }
+
+    let user_config_dir = if cfg!(target_os = "windows") {
+        var(env::APPDATA).map(PathBuf::from)
+    } else {
+        var(env::XDG_CONFIG_HOME)
+            .map(PathBuf::from)
+            .or_else(|| var(env::HOME).map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    user_config_dir
+        .map(|dir| dir.join(CONFIG_DIR_NAME))
+        .ok_or_else(|| anyhow!("unable to find the configuration directory"))
+}
+
+// Filesystem dependencies to load and save the state files in the configuration directory
+pub trait ConfigFs: FileReader + FileWriter + PathResolver + Send + Sync {}
+impl<T: FileReader + FileWriter + PathResolver + Send + Sync> ConfigFs for T {}
+
+/// Load a state file, nothing is saved yet when the file doesn't exist, so the default state is returned.
+pub async fn load_toml<T: DeserializeOwned + Default>(fs: &dyn ConfigFs, path: &Path) -> Result<T> {
+    if fs.path_kind(path).await?.is_none() {
+        debug!(path = %path.display(), "state file doesn't exist, nothing saved yet");
+        return Ok(T::default());
+    }
+
+    let contents = fs.read_to_string(path).await?;
+    toml::from_str(&contents).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// Save a state file, creating the configuration directory when needed.
+pub async fn save_toml<T: Serialize>(fs: &dyn ConfigFs, path: &Path, state: &T) -> Result<()> {
+    let contents =
+        toml::to_string(state).with_context(|| format!("serializing {}", path.display()))?;
+
+    if let Some(dir) = path.parent() {
+        fs.create_dir_all(dir).await?;
+    }
+    fs.write_string(path, &contents).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::dependencies::{PathKind, mocks::MockTokioFs};
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct State {
+        #[serde(default)]
+        names: BTreeMap<String, String>,
+    }
+
+    fn vars(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars = HashMap::<_, _>::from_iter(vars.iter().copied());
+        move |name| vars.get(name).map(OsString::from)
+    }
+
+    #[test]
+    fn test_resolve_config_dir_precedence() {
+        let all_vars = vars(&[
+            (env::MONGODB_ATLAS_LOCAL_CONFIG_DIR, "/env/config"),
+            (env::XDG_CONFIG_HOME, "/xdg"),
+            (env::APPDATA, "/xdg"),
+        ]);
+
+        assert_eq!(
+            resolve_config_dir(Some(PathBuf::from("/flag/config")), &all_vars).unwrap(),
+            PathBuf::from("/flag/config")
+        );
+        assert_eq!(
+            resolve_config_dir(None, &all_vars).unwrap(),
+            PathBuf::from("/env/config")
+        );
+    }
+
+    #[test]
+    fn test_resolve_config_dir_default() {
+        assert_eq!(
+            resolve_config_dir(
+                None,
+                vars(&[(env::XDG_CONFIG_HOME, "/xdg"), (env::APPDATA, "/xdg")])
+            )
+            .unwrap(),
+            PathBuf::from("/xdg").join(CONFIG_DIR_NAME)
+        );
+        assert!(resolve_config_dir(None, vars(&[])).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_toml_without_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs.expect_path_kind().return_once(|_| Ok(None));
+        mock_fs.expect_read_to_string().never();
+
+        let state: State = load_toml(&mock_fs, Path::new("state.toml"))
+            .await
+            .expect("loading state should succeed");
+
+        assert_eq!(state, State::default());
+    }
+
+    #[tokio::test]
+    async fn test_load_toml_invalid_file() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_path_kind()
+            .return_once(|_| Ok(Some(PathKind::File)));
+        mock_fs
+            .expect_read_to_string()
+            .return_once(|_| Ok("names = 42\n".to_string()));
+
+        let error = load_toml::<State>(&mock_fs, Path::new("state.toml"))
+            .await
+            .expect_err("loading state should fail");
+
+        assert!(error.to_string().contains("parsing state.toml"));
+    }
+
+    #[tokio::test]
+    async fn test_save_toml_creates_config_dir() {
+        let mut mock_fs = MockTokioFs::new();
+        mock_fs
+            .expect_create_dir_all()
+            .withf(|dir| dir == Path::new("/config/atlas-local"))
+            .return_once(|_| Ok(()));
+        mock_fs
+            .expect_write_string()
+            .withf(|path, contents| {
+                path == Path::new("/config/atlas-local/state.toml")
+                    && contents.contains("[names]")
+                    && contents.contains("local1 = \"shop\"")
+            })
+            .return_once(|_, _| Ok(()));
+
+        let state = State {
+            names: BTreeMap::from([("local1".to_string(), "shop".to_string())]),
+        };
+        save_toml(
+            &mock_fs,
+            Path::new("/config/atlas-local/state.toml"),
+            &state,
+        )
+        .await
+        .expect("saving state should succeed");
+    }
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+/// Waits using the tokio timer.
+#[derive(Debug, Clone)]
+pub struct TokioClock;
+
+impl TokioClock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+// Dependency to wait between polls, so tests can run without waiting
+#[async_trait]
+pub trait Sleeper {
+    async fn sleep(&self, duration: Duration);
+}
+
+#[async_trait]
+impl Sleeper for TokioClock {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+#[cfg(test)]
+pub mod mocks {
+    use super::*;
+    use mockall::mock;
+
+    mock! {
+        pub TokioClock {}
+
+        #[async_trait]
+        impl Sleeper for TokioClock {
+            async fn sleep(&self, duration: Duration);
+        }
+    }
+}
@@ -1,3 +1,11 @@
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
 use async_trait::async_trait;
 use atlas_local::{
     Client, GetConnectionStringError, GetDeploymentError, GetLogsError,
@@ -7,6 +15,22 @@ use atlas_local::{
     },
     models::{CreateDeploymentOptions, Deployment, LogOutput, LogsOptions, WatchOptions},
 };
+use bollard::{
+    Docker,
+    container::LogOutput as ContainerLogOutput,
+    exec::{CreateExecOptions, StartExecOptions, StartExecResults},
+    models::{ContainerCreateBody, ContainerInspectResponse, HostConfig, SystemVersion},
+    query_parameters::{
+        CreateContainerOptionsBuilder, CreateImageOptionsBuilder,
+        DownloadFromContainerOptionsBuilder, EventsOptionsBuilder, RemoveContainerOptionsBuilder,
+        StartContainerOptions, UploadToContainerOptionsBuilder, WaitContainerOptions,
+    },
+};
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, warn};
+
+use crate::env;
 
 // Dependency to list deployments
 #[async_trait]
@@ -136,6 +160,119 @@ impl DeploymentWaiter for Client {
     }
 }
 
+/// Maximum number of times to reconnect to Docker during a single wait, with `--reconnectOnDrop`.
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+/// Delay before reconnecting to Docker, gives a restarting Docker daemon time to come back.
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Waits for the deployment to be healthy, with `--reconnectOnDrop` the wait reconnects to Docker when the connection
+/// drops, e.g. because the Docker daemon restarted.
+pub async fn wait_for_healthy<W>(
+    waiter: &W,
+    docker: &DockerSettings,
+    deployment_name: &str,
+    options: WatchOptions,
+) -> Result<(), WatchDeploymentError>
+where
+    W: DeploymentWaiter + Sync + ?Sized,
+{
+    if !docker.reconnect_on_drop {
+        return waiter
+            .wait_for_healthy_deployment(deployment_name, options)
+            .await;
+    }
+
+    wait_for_healthy_deployment_reconnecting(
+        waiter,
+        || connect_docker(docker).map(Client::new),
+        deployment_name,
+        options,
+        RECONNECT_DELAY,
+    )
+    .await
+}
+
+/// Returns whether the Docker error means that the connection to Docker dropped.
+///
+/// While the Docker daemon restarts it can't be reached, so unreachable errors also count as a dropped connection.
+fn is_connection_dropped(error: &bollard::errors::Error) -> bool {
+    is_transient(error) || is_docker_unreachable(error)
+}
+
+/// Waits for the deployment to be healthy, reconnecting to Docker when the connection drops during the wait.
+///
+/// `connect` creates a waiter with a new connection to Docker. The timeout of the options covers the whole wait,
+/// including the reconnects. After a reconnect the deployment may be unhealthy at first, as it restarts with Docker.
+pub async fn wait_for_healthy_deployment_reconnecting<W, R>(
+    waiter: &W,
+    connect: impl Fn() -> Result<R, bollard::errors::Error>,
+    deployment_name: &str,
+    options: WatchOptions,
+    reconnect_delay: Duration,
+) -> Result<(), WatchDeploymentError>
+where
+    W: DeploymentWaiter + ?Sized,
+    R: DeploymentWaiter,
+{
+    let deadline = options
+        .timeout_duration
+        .map(|timeout| Instant::now() + timeout);
+
+    let mut result = waiter
+        .wait_for_healthy_deployment(deployment_name, options)
+        .await;
+    let mut attempt = 0;
+    loop {
+        let error = match result {
+            Err(WatchDeploymentError::ContainerInspect(error))
+                if attempt < MAX_RECONNECT_ATTEMPTS && is_connection_dropped(&error) =>
+            {
+                error
+            }
+            result => return result,
+        };
+
+        attempt += 1;
+        warn!(
+            attempt,
+            error = %error,
+            "connection to Docker dropped while waiting for the deployment, reconnecting"
+        );
+        tokio::time::sleep(reconnect_delay).await;
+
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if remaining == Some(Duration::ZERO) {
+            return Err(WatchDeploymentError::Timeout {
+                deployment_name: deployment_name.to_string(),
+            });
+        }
+
+        result = match connect() {
+            Ok(reconnected) => {
+                debug!(attempt, "reconnected to Docker");
+                reconnected
+                    .wait_for_healthy_deployment(deployment_name, watch_options(remaining))
+                    .await
+            }
+            Err(error) => Err(WatchDeploymentError::ContainerInspect(error)),
+        };
+    }
+}
+
+/// Options to wait for a deployment that may be unhealthy at first, with the remaining timeout.
+fn watch_options(timeout: Option<Duration>) -> WatchOptions {
+    match timeout {
+        Some(timeout) => WatchOptions::builder()
+            .allow_unhealthy_initial_state(true)
+            .timeout_duration(timeout)
+            .build(),
+        None => WatchOptions::builder()
+            .allow_unhealthy_initial_state(true)
+            .build(),
+    }
+}
+
 pub trait DeploymentCreator {
     fn create_deployment(
         &self,
@@ -169,6 +306,956 @@ impl DeploymentGetConnectionString for Client {
         self.get_connection_string(container_id_or_name).await
     }
 }
+/// Error returned when the Docker daemon failed to handle a request, e.g. because it is not running.
+///
+/// Unlike a container that doesn't exist, this is not an outcome of the command but an actual error.
+#[derive(Debug, thiserror::Error)]
+#[error("Docker error: {0}")]
+pub struct DockerError(#[from] pub bollard::errors::Error);
+
+/// Returns whether the Docker error means that the container does not exist.
+pub fn is_container_not_found(error: &bollard::errors::Error) -> bool {
+    matches!(
+        error,
+        bollard::errors::Error::DockerResponseServerError {
+            status_code: 404,
+            ..
+        }
+    )
+}
+
+/// Get a deployment, `None` when its container doesn't exist.
+///
+/// Other inspect errors mean Docker failed, not that the deployment doesn't exist, so they are returned as errors.
+pub async fn find_deployment(
+    deployment_getter: &(impl DeploymentGetDeployment + ?Sized),
+    deployment_name: &str,
+) -> anyhow::Result<Option<Deployment>> {
+    match deployment_getter.get_deployment(deployment_name).await {
+        Ok(deployment) => Ok(Some(deployment)),
+        Err(GetDeploymentError::ContainerInspect(error)) if is_container_not_found(&error) => {
+            Ok(None)
+        }
+        Err(GetDeploymentError::ContainerInspect(error)) => {
+            Err(DockerError::from(error)).context("getting deployment")
+        }
+        Err(GetDeploymentError::IntoDeployment(e)) => {
+            anyhow::bail!("Failed to get deployment, into deployment error: {}", e)
+        }
+    }
+}
+
+/// Returns whether the Docker error means that the Docker daemon can't be reached, e.g. because it is not running.
+///
+/// Errors returned by a running daemon, like a container that doesn't exist, are not considered unreachable.
+pub fn is_docker_unreachable(error: &bollard::errors::Error) -> bool {
+    match error {
+        bollard::errors::Error::SocketNotFoundError(_) => true,
+        bollard::errors::Error::IOError { err } => matches!(
+            err.kind(),
+            std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::NotFound
+        ),
+        bollard::errors::Error::HyperLegacyError { err } => err.is_connect(),
+        _ => false,
+    }
+}
+
+/// Returns whether the Docker error is transient, so retrying the operation can succeed.
+///
+/// Timeouts, dropped connections and server errors (5xx) are transient.
+/// Client errors (4xx) like a container that doesn't exist, an unreachable daemon and configuration errors are permanent, retrying them is useless.
+pub fn is_transient(error: &bollard::errors::Error) -> bool {
+    match error {
+        bollard::errors::Error::RequestTimeoutError => true,
+        bollard::errors::Error::DockerResponseServerError { status_code, .. } => {
+            *status_code >= 500
+        }
+        bollard::errors::Error::IOError { err } => matches!(
+            err.kind(),
+            std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::Interrupted
+                | std::io::ErrorKind::UnexpectedEof
+        ),
+        bollard::errors::Error::HyperResponseError { err } => {
+            err.is_timeout() || err.is_closed() || err.is_incomplete_message()
+        }
+        // Failing to connect means the daemon is unreachable, other errors mean the connection dropped during the request
+        bollard::errors::Error::HyperLegacyError { err } => !err.is_connect(),
+        _ => false,
+    }
+}
+
+/// How to connect to Docker, selected with the global options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DockerSettings {
+    /// Docker host selected with `--dockerHost`, `--context` or a Docker context, it takes precedence over
+    /// `DOCKER_HOST`. The socket of Podman when no Docker is found, see [`DockerSettings::resolve_host`].
+    pub host: Option<String>,
+    /// TLS certificates selected with the `--tls*` options or `DOCKER_TLS_VERIFY`.
+    pub tls: Option<DockerTls>,
+    /// Whether long waits reconnect to Docker when the connection drops, selected with `--reconnectOnDrop`.
+    pub reconnect_on_drop: bool,
+}
+
+impl DockerSettings {
+    /// Use the socket of a running Podman service as the host when no Docker host is selected and Docker is not found.
+    ///
+    /// Called once after the Docker host is selected, so the Docker connection and the docker CLI run by the connectors
+    /// connect to the same host.
+    pub fn resolve_host(self) -> Self {
+        self.resolve_host_with(|name| std::env::var_os(name), |path| path.exists())
+    }
+
+    /// Like [`DockerSettings::resolve_host`], `var` looks up environment variables and `exists` checks sockets.
+    fn resolve_host_with(
+        mut self,
+        var: impl Fn(&str) -> Option<OsString>,
+        exists: impl Fn(&Path) -> bool,
+    ) -> Self {
+        // Podman on Windows runs in a machine that is set up as the default Docker engine, and TLS hosts are remote
+        if self.host.is_some() || self.tls.is_some() || cfg!(target_os = "windows") {
+            return self;
+        }
+
+        if let Some(socket) = find_podman_socket(var, exists) {
+            debug!(socket = %socket.display(), "no Docker socket found, connecting to Podman");
+            self.host = Some(format!("unix://{}", socket.display()));
+        }
+
+        self
+    }
+}
+
+/// TLS certificates to connect to a remote Docker host with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DockerTls {
+    /// CA certificate to verify the Docker host with.
+    pub ca_cert: PathBuf,
+    /// Client certificate to authenticate with.
+    pub cert: PathBuf,
+    /// Private key of the client certificate.
+    pub key: PathBuf,
+}
+
+/// Docker host used with TLS when no Docker host is selected, like the Docker CLI.
+const DEFAULT_TLS_DOCKER_HOST: &str = "tcp://localhost:2376";
+
+/// Timeout of requests to the Docker host in seconds, the default of bollard.
+const DOCKER_TIMEOUT_SECS: u64 = 120;
+
+/// TLS certificates of the Docker CLI environment variables, when `DOCKER_TLS_VERIFY` is set.
+pub fn docker_tls_from_env() -> Option<DockerTls> {
+    resolve_docker_tls_from_env(|name| std::env::var_os(name))
+}
+
+/// Resolve the TLS certificates like the Docker CLI does, `var` looks up environment variables.
+///
+/// The certificates are read from `DOCKER_CERT_PATH`, or the Docker configuration directory when it is not set.
+fn resolve_docker_tls_from_env(var: impl Fn(&str) -> Option<OsString>) -> Option<DockerTls> {
+    // Like the Docker CLI, any non-empty value enables TLS, even "0" or "false"
+    var(env::DOCKER_TLS_VERIFY).filter(|verify| !verify.is_empty())?;
+
+    let cert_path = var(env::DOCKER_CERT_PATH)
+        .filter(|cert_path| !cert_path.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| var(env::DOCKER_CONFIG).map(PathBuf::from))
+        .or_else(|| {
+            let home = if cfg!(target_os = "windows") {
+                var(env::USERPROFILE)
+            } else {
+                var(env::HOME)
+            };
+            home.map(|home| PathBuf::from(home).join(".docker"))
+        })?;
+
+    Some(DockerTls {
+        ca_cert: cert_path.join("ca.pem"),
+        cert: cert_path.join("cert.pem"),
+        key: cert_path.join("key.pem"),
+    })
+}
+
+/// Connect to Docker using the selected Docker host, or the defaults (`DOCKER_HOST` or the local socket) when none is selected.
+///
+/// With TLS certificates, the Docker host is connected to over TLS. Podman serves the same API as Docker, its socket is
+/// selected as the host by [`DockerSettings::resolve_host`].
+pub fn connect_docker(settings: &DockerSettings) -> Result<Docker, bollard::errors::Error> {
+    if let Some(tls) = &settings.tls {
+        let host = settings
+            .host
+            .clone()
+            .or_else(|| std::env::var(env::DOCKER_HOST).ok())
+            .unwrap_or_else(|| DEFAULT_TLS_DOCKER_HOST.to_string());
+
+        // TLS only applies to TCP hosts, local sockets are connected to without it
+        if !host.starts_with("unix://") && !host.starts_with("npipe://") {
+            return Docker::connect_with_ssl(
+                &host,
+                &tls.key,
+                &tls.cert,
+                &tls.ca_cert,
+                DOCKER_TIMEOUT_SECS,
+                bollard::API_DEFAULT_VERSION,
+            );
+        }
+    }
+
+    match &settings.host {
+        Some(host) => Docker::connect_with_host(host),
+        None => Docker::connect_with_defaults(),
+    }
+}
+
+/// Default socket of the Docker daemon on Linux and macOS.
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Socket of the rootful Podman service on Linux.
+const PODMAN_ROOTFUL_SOCKET: &str = "/run/podman/podman.sock";
+
+/// Find the socket of a Podman service, when Docker is not configured and not installed.
+///
+/// `var` looks up environment variables and `exists` checks whether a socket exists. The sockets are checked in
+/// order: rootless and rootful Podman on Linux, then the API socket of a Podman machine on macOS.
+fn find_podman_socket(
+    var: impl Fn(&str) -> Option<OsString>,
+    exists: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    // DOCKER_HOST and the Docker socket take precedence, Podman is only a fallback
+    if var(env::DOCKER_HOST).is_some() || exists(Path::new(DOCKER_SOCKET)) {
+        return None;
+    }
+
+    let home = var(env::HOME).map(PathBuf::from);
+    let candidates = [
+        var(env::XDG_RUNTIME_DIR).map(|dir| PathBuf::from(dir).join("podman/podman.sock")),
+        Some(PathBuf::from(PODMAN_ROOTFUL_SOCKET)),
+        // Podman 5 machines expose the API in the temporary directory, older versions in the data directory
+        var(env::TMPDIR)
+            .map(|dir| PathBuf::from(dir).join("podman/podman-machine-default-api.sock")),
+        home.as_ref()
+            .map(|home| home.join(".local/share/containers/podman/machine/podman.sock")),
+        home.map(|home| home.join(".local/share/containers/podman/machine/qemu/podman.sock")),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|socket| exists(socket))
+}
+
+/// Label set by atlas_local on all containers of local deployments.
+const LOCAL_DEPLOYMENT_LABEL_KEY: &str = "mongodb-atlas-local";
+const LOCAL_DEPLOYMENT_LABEL_VALUE: &str = "container";
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveContainerError {
+    #[error("deployment not found")]
+    NotFound,
+    #[error("failed to remove the container: {0}")]
+    Docker(#[from] bollard::errors::Error),
+}
+
+// Dependency to remove the container of a deployment without removing its volumes
+#[async_trait]
+pub trait DeploymentContainerRemover {
+    /// Removes the container of a local deployment, stopping it if needed, but keeps its volumes.
+    async fn remove_container_keep_volumes(
+        &self,
+        deployment_name: &str,
+    ) -> Result<(), RemoveContainerError>;
+}
+
+#[async_trait]
+impl DeploymentContainerRemover for Docker {
+    async fn remove_container_keep_volumes(
+        &self,
+        deployment_name: &str,
+    ) -> Result<(), RemoveContainerError> {
+        // Make sure the container belongs to a local deployment before removing it.
+        if inspect_local_deployment_container(self, deployment_name)
+            .await?
+            .is_none()
+        {
+            return Err(RemoveContainerError::NotFound);
+        }
+
+        self.remove_container(
+            deployment_name,
+            Some(
+                RemoveContainerOptionsBuilder::default()
+                    .force(true)
+                    .v(false)
+                    .build(),
+            ),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+// Dependency to get the unprocessed Docker inspect output of a deployment
+#[async_trait]
+pub trait DeploymentContainerInspector {
+    /// Returns the Docker inspect output of the container of a local deployment, or `None` when the deployment doesn't exist.
+    async fn inspect_container_raw(
+        &self,
+        deployment_name: &str,
+    ) -> Result<Option<ContainerInspectResponse>, bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DeploymentContainerInspector for Docker {
+    async fn inspect_container_raw(
+        &self,
+        deployment_name: &str,
+    ) -> Result<Option<ContainerInspectResponse>, bollard::errors::Error> {
+        inspect_local_deployment_container(self, deployment_name).await
+    }
+}
+
+// Dependency to get when the container of a deployment was last started
+#[async_trait]
+pub trait DeploymentStartedAt {
+    /// Returns when the container of a local deployment was last started, or `None` when the deployment doesn't exist or never started.
+    async fn started_at(
+        &self,
+        deployment_name: &str,
+    ) -> Result<Option<SystemTime>, bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DeploymentStartedAt for Docker {
+    async fn started_at(
+        &self,
+        deployment_name: &str,
+    ) -> Result<Option<SystemTime>, bollard::errors::Error> {
+        let started_at = inspect_local_deployment_container(self, deployment_name)
+            .await?
+            .and_then(|container| container.state)
+            .and_then(|state| state.started_at);
+
+        // Docker reports "0001-01-01T00:00:00Z" for containers that never started, it's before the unix epoch and fails to parse
+        Ok(started_at.and_then(|started_at| humantime::parse_rfc3339(&started_at).ok()))
+    }
+}
+
+/// Inspects the container with the given name, returns `None` when it doesn't exist or isn't a local deployment.
+async fn inspect_local_deployment_container(
+    docker: &Docker,
+    deployment_name: &str,
+) -> Result<Option<ContainerInspectResponse>, bollard::errors::Error> {
+    let container = match docker.inspect_container(deployment_name, None).await {
+        Ok(container) => container,
+        Err(e) if is_container_not_found(&e) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let is_local_deployment = container
+        .config
+        .as_ref()
+        .and_then(|config| config.labels.as_ref())
+        .and_then(|labels| labels.get(LOCAL_DEPLOYMENT_LABEL_KEY))
+        .is_some_and(|value| value == LOCAL_DEPLOYMENT_LABEL_VALUE);
+
+    Ok(is_local_deployment.then_some(container))
+}
+
+// Dependency to pull the image of a deployment
+#[async_trait]
+pub trait DeploymentImagePuller {
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<(), bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DeploymentImagePuller for Docker {
+    async fn pull_image(&self, image: &str, tag: &str) -> Result<(), bollard::errors::Error> {
+        let options = CreateImageOptionsBuilder::new()
+            .from_image(image)
+            .tag(tag)
+            .build();
+
+        // The pull only completes once the progress stream is fully consumed
+        self.create_image(Some(options), None, None)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(())
+    }
+}
+
+// Dependency to check whether the image of a deployment is available locally
+#[async_trait]
+pub trait DeploymentImageExists {
+    async fn image_exists(&self, image: &str, tag: &str) -> Result<bool, bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DeploymentImageExists for Docker {
+    async fn image_exists(&self, image: &str, tag: &str) -> Result<bool, bollard::errors::Error> {
+        match self.inspect_image(&format!("{image}:{tag}")).await {
+            Ok(_) => Ok(true),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// Dependency to check whether a Docker volume exists
+#[async_trait]
+pub trait DockerVolumeExists {
+    async fn volume_exists(&self, volume_name: &str) -> Result<bool, bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DockerVolumeExists for Docker {
+    async fn volume_exists(&self, volume_name: &str) -> Result<bool, bollard::errors::Error> {
+        match self.inspect_volume(volume_name).await {
+            Ok(_) => Ok(true),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// Dependency to get the version of the Docker server
+#[async_trait]
+pub trait DockerServerVersion {
+    async fn server_version(&self) -> Result<SystemVersion, bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DockerServerVersion for Docker {
+    async fn server_version(&self) -> Result<SystemVersion, bollard::errors::Error> {
+        self.version().await
+    }
+}
+
+// Dependency to copy the files of a Docker volume into another volume
+#[async_trait]
+pub trait VolumeCopier {
+    /// Replaces the files of the target volume with the files of the source volume, using a short-lived container of the image.
+    ///
+    /// Neither volume may be in use by a running container while it is copied.
+    async fn copy_volume(
+        &self,
+        image: &str,
+        source_volume: &str,
+        target_volume: &str,
+    ) -> Result<(), bollard::errors::Error>;
+}
+
+#[async_trait]
+impl VolumeCopier for Docker {
+    async fn copy_volume(
+        &self,
+        image: &str,
+        source_volume: &str,
+        target_volume: &str,
+    ) -> Result<(), bollard::errors::Error> {
+        let container_name = format!("atlas-local-copy-{target_volume}");
+        let body = ContainerCreateBody {
+            image: Some(image.to_string()),
+            // The image's entrypoint starts MongoDB, the copy only needs a shell
+            entrypoint: Some(vec!["sh".to_string(), "-c".to_string()]),
+            cmd: Some(vec![
+                "find /to -mindepth 1 -delete && cp -a /from/. /to/".to_string(),
+            ]),
+            host_config: Some(HostConfig {
+                binds: Some(vec![
+                    format!("{source_volume}:/from:ro"),
+                    format!("{target_volume}:/to"),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        create_helper_container(self, &container_name, body).await?;
+        // Waiting fails when the copy exits with a non-zero code
+        let copy_result = run_helper_container(self, &container_name).await;
+        remove_helper_container(self, &container_name).await;
+
+        copy_result
+    }
+}
+
+// Dependency to pause the container of a deployment
+#[async_trait]
+pub trait DeploymentPauser {
+    async fn pause(&self, deployment_name: &str) -> Result<(), bollard::errors::Error>;
+}
+
+#[async_trait]
+impl DeploymentPauser for Docker {
+    async fn pause(&self, deployment_name: &str) -> Result<(), bollard::errors::Error> {
+        self.pause_container(deployment_name).await
+    }
+}
+
+/// Body of a helper container that deletes the files of the volume mounted on the path.
+///
+/// The path comes from the snapshot description, which can be edited, so it is passed to find as an argument and
+/// never parsed by a shell.
+fn empty_volume_body(image: &str, volume: &str, path: &str) -> ContainerCreateBody {
+    ContainerCreateBody {
+        image: Some(image.to_string()),
+        // The image's entrypoint starts MongoDB, emptying the volume only needs find
+        entrypoint: Some(vec!["find".to_string()]),
+        cmd: Some(vec![
+            path.to_string(),
+            "-mindepth".to_string(),
+            "1".to_string(),
+            "-delete".to_string(),
+        ]),
+        host_config: Some(HostConfig {
+            binds: Some(vec![format!("{volume}:{path}")]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+// Dependency to replace the files of a Docker volume with the files of a tar archive
+#[async_trait]
+pub trait VolumeArchiveRestorer {
+    /// Replaces the files of the volume with the files of an archive created by [`DeploymentArchiver::archive_path`], using a short-lived container of the image.
+    ///
+    /// The volume is mounted on the path it was archived from, so the entries of the archive end up in the volume. The volume may not be in use by a running container.
+    async fn restore_volume(
+        &self,
+        image: &str,
+        volume: &str,
+        path: &str,
+        archive: &Path,
+    ) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+impl VolumeArchiveRestorer for Docker {
+    async fn restore_volume(
+        &self,
+        image: &str,
+        volume: &str,
+        path: &str,
+        archive: &Path,
+    ) -> anyhow::Result<()> {
+        // The entries of the archive are prefixed with the name of the archived directory
+        let parent = Path::new(path)
+            .parent()
+            .and_then(|parent| parent.to_str())
+            .with_context(|| format!("{path} has no parent directory"))?;
+
+        let container_name = format!("atlas-local-restore-{volume}");
+        create_helper_container(
+            self,
+            &container_name,
+            empty_volume_body(image, volume, path),
+        )
+        .await
+        .map_err(DockerError::from)
+        .context("creating the restore container")?;
+
+        let restore_result = async {
+            run_helper_container(self, &container_name)
+                .await
+                .map_err(DockerError::from)
+                .context("emptying the volume")?;
+
+            // Docker extracts archives into the volumes of stopped containers as well
+            let file = tokio::fs::File::open(archive)
+                .await
+                .with_context(|| format!("failed to read file: {}", archive.display()))?;
+            let chunks = futures::stream::try_unfold(file, |mut file| async move {
+                let mut chunk = vec![0; ARCHIVE_CHUNK_SIZE];
+                let read = file.read(&mut chunk).await?;
+                if read == 0 {
+                    return Ok(None);
+                }
+                chunk.truncate(read);
+                Ok(Some((chunk.into(), file)))
+            });
+
+            self.upload_to_container(
+                &container_name,
+                Some(
+                    UploadToContainerOptionsBuilder::default()
+                        .path(parent)
+                        .build(),
+                ),
+                bollard::body_try_stream(chunks),
+            )
+            .await
+            .map_err(DockerError::from)
+            .context("extracting the archive into the volume")
+        }
+        .await;
+
+        remove_helper_container(self, &container_name).await;
+
+        restore_result
+    }
+}
+
+/// Size of the chunks an archive is uploaded to Docker in.
+const ARCHIVE_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Creates a short-lived container to work on volumes, it is run with [`run_helper_container`].
+async fn create_helper_container(
+    docker: &Docker,
+    container_name: &str,
+    body: ContainerCreateBody,
+) -> Result<(), bollard::errors::Error> {
+    docker
+        .create_container(
+            Some(
+                CreateContainerOptionsBuilder::default()
+                    .name(container_name)
+                    .build(),
+            ),
+            body,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Starts the helper container and waits until it exits, waiting fails when it exits with a non-zero code.
+async fn run_helper_container(
+    docker: &Docker,
+    container_name: &str,
+) -> Result<(), bollard::errors::Error> {
+    docker
+        .start_container(container_name, None::<StartContainerOptions>)
+        .await?;
+    docker
+        .wait_container(container_name, None::<WaitContainerOptions>)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(())
+}
+
+/// Removes the helper container, whether its work succeeded or not.
+async fn remove_helper_container(docker: &Docker, container_name: &str) {
+    if let Err(e) = docker
+        .remove_container(
+            container_name,
+            Some(RemoveContainerOptionsBuilder::default().force(true).build()),
+        )
+        .await
+    {
+        warn!(container_name, error = %e, "removing the helper container failed");
+    }
+}
+
+// Dependency to save a directory of the container of a deployment to a tar archive
+#[async_trait]
+pub trait DeploymentArchiver {
+    /// Writes the directory of the container to the archive file and returns the size of the archive in bytes.
+    ///
+    /// Like `docker cp`, the entries of the archive are prefixed with the name of the directory. The container doesn't have to run.
+    async fn archive_path(
+        &self,
+        deployment_name: &str,
+        path: &str,
+        archive: &Path,
+    ) -> anyhow::Result<u64>;
+}
+
+#[async_trait]
+impl DeploymentArchiver for Docker {
+    async fn archive_path(
+        &self,
+        deployment_name: &str,
+        path: &str,
+        archive: &Path,
+    ) -> anyhow::Result<u64> {
+        let mut file = tokio::fs::File::create(archive)
+            .await
+            .with_context(|| format!("failed to create file: {}", archive.display()))?;
+
+        let mut chunks = self.download_from_container(
+            deployment_name,
+            Some(
+                DownloadFromContainerOptionsBuilder::default()
+                    .path(path)
+                    .build(),
+            ),
+        );
+
+        // The archive is streamed to the file, data volumes can be larger than the available memory
+        let mut size = 0;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk
+                .map_err(DockerError::from)
+                .with_context(|| format!("downloading {path} from the container"))?;
+            file.write_all(&chunk)
+                .await
+                .with_context(|| format!("failed to write file: {}", archive.display()))?;
+            size += chunk.len() as u64;
+        }
+        file.flush()
+            .await
+            .with_context(|| format!("failed to write file: {}", archive.display()))?;
+
+        Ok(size)
+    }
+}
+
+/// Output of a command that ran in the container of a deployment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecOutput {
+    pub exit_code: i64,
+    pub stdout: String,
+    pub stderr: String,
+    /// Whether output was dropped because stdout or stderr exceeded the maximum output.
+    pub truncated: bool,
+}
+
+impl ExecOutput {
+    /// The last line the command wrote to stderr, usually the error of a command that failed.
+    pub fn last_stderr_line(&self) -> Option<&str> {
+        self.stderr
+            .lines()
+            .map(str::trim)
+            .rev()
+            .find(|line| !line.is_empty())
+    }
+}
+
+// Dependency to run a command in the container of a deployment
+#[async_trait]
+pub trait ContainerExecutor {
+    /// Runs the command in the container of the deployment and waits until it exits.
+    ///
+    /// `stdin` is written to the standard input of the command, which is closed afterwards. The output is captured, so the command can't be interactive.
+    async fn exec(
+        &self,
+        deployment_name: &str,
+        cmd: Vec<String>,
+        stdin: Option<Vec<u8>>,
+    ) -> anyhow::Result<ExecOutput>;
+
+    /// Runs the command like [`exec`](ContainerExecutor::exec), keeping at most `max_output` bytes of stdout and of stderr.
+    ///
+    /// The output after the maximum is read and dropped, so a command with a large output doesn't fill the memory.
+    async fn exec_with_max_output(
+        &self,
+        deployment_name: &str,
+        cmd: Vec<String>,
+        max_output: usize,
+    ) -> anyhow::Result<ExecOutput>;
+}
+
+#[async_trait]
+impl ContainerExecutor for Docker {
+    async fn exec(
+        &self,
+        deployment_name: &str,
+        cmd: Vec<String>,
+        stdin: Option<Vec<u8>>,
+    ) -> anyhow::Result<ExecOutput> {
+        exec_in_container(self, deployment_name, cmd, stdin, None).await
+    }
+
+    async fn exec_with_max_output(
+        &self,
+        deployment_name: &str,
+        cmd: Vec<String>,
+        max_output: usize,
+    ) -> anyhow::Result<ExecOutput> {
+        exec_in_container(self, deployment_name, cmd, None, Some(max_output)).await
+    }
+}
+
+/// Runs the command in the container of the deployment, see [`ContainerExecutor`].
+async fn exec_in_container(
+    docker: &Docker,
+    deployment_name: &str,
+    cmd: Vec<String>,
+    stdin: Option<Vec<u8>>,
+    max_output: Option<usize>,
+) -> anyhow::Result<ExecOutput> {
+    let exec = docker
+        .create_exec(
+            deployment_name,
+            CreateExecOptions {
+                attach_stdin: Some(stdin.is_some()),
+                attach_stdout: Some(true),
+                attach_stderr: Some(true),
+                cmd: Some(cmd),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(DockerError::from)
+        .context("creating exec")?;
+
+    let StartExecResults::Attached {
+        mut output,
+        mut input,
+    } = docker
+        .start_exec(&exec.id, None::<StartExecOptions>)
+        .await
+        .map_err(DockerError::from)
+        .context("starting exec")?
+    else {
+        anyhow::bail!("exec started detached");
+    };
+
+    if let Some(stdin) = stdin {
+        input
+            .write_all(&stdin)
+            .await
+            .context("writing to the standard input of the command")?;
+        // Closing the input lets the command read until the end of its standard input
+        input
+            .shutdown()
+            .await
+            .context("closing the standard input of the command")?;
+    }
+
+    // Multi-byte characters can be split across chunks, so the output is decoded at the end
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut truncated = false;
+    while let Some(chunk) = output.next().await {
+        match chunk
+            .map_err(DockerError::from)
+            .context("reading exec output")?
+        {
+            ContainerLogOutput::StdOut { message } => {
+                truncated |= !append_output(&mut stdout, &message, max_output);
+            }
+            ContainerLogOutput::StdErr { message } => {
+                truncated |= !append_output(&mut stderr, &message, max_output);
+            }
+            _ => {}
+        }
+    }
+
+    let exit_code = docker
+        .inspect_exec(&exec.id)
+        .await
+        .map_err(DockerError::from)
+        .context("inspecting exec")?
+        .exit_code
+        .unwrap_or_default();
+
+    Ok(ExecOutput {
+        exit_code,
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        truncated,
+    })
+}
+
+/// Append the message to the output, up to `max_output` bytes. Returns whether the whole message was appended.
+fn append_output(output: &mut Vec<u8>, message: &[u8], max_output: Option<usize>) -> bool {
+    let available = max_output.map_or(message.len(), |max_output| {
+        max_output.saturating_sub(output.len())
+    });
+    let appended = message.len().min(available);
+    output.extend_from_slice(&message[..appended]);
+    appended == message.len()
+}
+
+/// Container events that are relevant for local deployments.
+const DEPLOYMENT_EVENT_ACTIONS: &[&str] = &[
+    "create", "start", "stop", "pause", "unpause", "die", "destroy",
+];
+
+/// Options for streaming deployment events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeploymentEventsOptions {
+    /// Show events created since this time.
+    pub since: Option<SystemTime>,
+    /// Stop streaming at this time.
+    pub until: Option<SystemTime>,
+}
+
+/// An event that happened to the container of a local deployment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentEvent {
+    /// When the event happened.
+    pub time: SystemTime,
+    /// The event type, e.g. "start" or "die".
+    pub action: String,
+    /// The name of the deployment.
+    pub deployment_name: String,
+    /// The ID of the container of the deployment.
+    pub container_id: String,
+}
+
+// Dependency to stream deployment events
+pub trait DockerEvents {
+    /// Returns a stream of container events for local deployments.
+    /// The stream ends when `until` is reached, or never when `until` is not set.
+    fn deployment_events(
+        &self,
+        options: DeploymentEventsOptions,
+    ) -> BoxStream<'static, Result<DeploymentEvent, bollard::errors::Error>>;
+}
+
+impl DockerEvents for Docker {
+    fn deployment_events(
+        &self,
+        options: DeploymentEventsOptions,
+    ) -> BoxStream<'static, Result<DeploymentEvent, bollard::errors::Error>> {
+        let label = format!("{LOCAL_DEPLOYMENT_LABEL_KEY}={LOCAL_DEPLOYMENT_LABEL_VALUE}");
+        let filters = HashMap::from([
+            ("type", vec!["container"]),
+            ("label", vec![label.as_str()]),
+            ("event", DEPLOYMENT_EVENT_ACTIONS.to_vec()),
+        ]);
+
+        // Docker expects the times as unix timestamps.
+        let unix_timestamp = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+                .to_string()
+        };
+
+        let mut events_options = EventsOptionsBuilder::default().filters(&filters);
+        if let Some(since) = options.since {
+            events_options = events_options.since(&unix_timestamp(since));
+        }
+        if let Some(until) = options.until {
+            events_options = events_options.until(&unix_timestamp(until));
+        }
+
+        self.events(Some(events_options.build()))
+            .map_ok(|event| {
+                let actor = event.actor.unwrap_or_default();
+                let time = event
+                    .time_nano
+                    .and_then(|nanos| u64::try_from(nanos).ok())
+                    .map(|nanos| UNIX_EPOCH + Duration::from_nanos(nanos))
+                    .unwrap_or_else(SystemTime::now);
+
+                DeploymentEvent {
+                    time,
+                    action: event.action.unwrap_or_default(),
+                    deployment_name: actor
+                        .attributes
+                        .and_then(|mut attributes| attributes.remove("name"))
+                        .unwrap_or_default(),
+                    container_id: actor.id.unwrap_or_default(),
+                }
+            })
+            .boxed()
+    }
+}
+
 #[cfg(test)]
 pub mod mocks {
     use super::*;
@@ -225,5 +1312,448 @@ pub mod mocks {
         impl DeploymentGetConnectionString for Docker {
             async fn get_connection_string(&self, container_id_or_name: String) -> Result<String, GetConnectionStringError>;
         }
+
+        #[async_trait]
+        impl DeploymentContainerRemover for Docker {
+            async fn remove_container_keep_volumes(&self, deployment_name: &str) -> Result<(), RemoveContainerError>;
+        }
+
+        #[async_trait]
+        impl DeploymentContainerInspector for Docker {
+            async fn inspect_container_raw(&self, deployment_name: &str) -> Result<Option<ContainerInspectResponse>, bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl DeploymentStartedAt for Docker {
+            async fn started_at(&self, deployment_name: &str) -> Result<Option<SystemTime>, bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl DeploymentImagePuller for Docker {
+            async fn pull_image(&self, image: &str, tag: &str) -> Result<(), bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl DeploymentImageExists for Docker {
+            async fn image_exists(&self, image: &str, tag: &str) -> Result<bool, bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl DockerVolumeExists for Docker {
+            async fn volume_exists(&self, volume_name: &str) -> Result<bool, bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl DockerServerVersion for Docker {
+            async fn server_version(&self) -> Result<SystemVersion, bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl VolumeCopier for Docker {
+            async fn copy_volume(&self, image: &str, source_volume: &str, target_volume: &str) -> Result<(), bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl DeploymentPauser for Docker {
+            async fn pause(&self, deployment_name: &str) -> Result<(), bollard::errors::Error>;
+        }
+
+        #[async_trait]
+        impl VolumeArchiveRestorer for Docker {
+            async fn restore_volume(&self, image: &str, volume: &str, path: &str, archive: &Path) -> anyhow::Result<()>;
+        }
+
+        #[async_trait]
+        impl DeploymentArchiver for Docker {
+            async fn archive_path(&self, deployment_name: &str, path: &str, archive: &Path) -> anyhow::Result<u64>;
+        }
+
+        #[async_trait]
+        impl ContainerExecutor for Docker {
+            async fn exec(&self, deployment_name: &str, cmd: Vec<String>, stdin: Option<Vec<u8>>) -> anyhow::Result<ExecOutput>;
+            async fn exec_with_max_output(&self, deployment_name: &str, cmd: Vec<String>, max_output: usize) -> anyhow::Result<ExecOutput>;
+        }
+
+        impl DockerEvents for Docker {
+            fn deployment_events(&self, options: DeploymentEventsOptions) -> BoxStream<'static, Result<DeploymentEvent, bollard::errors::Error>>;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    fn server_error(status_code: u16) -> bollard::errors::Error {
+        bollard::errors::Error::DockerResponseServerError {
+            status_code,
+            message: "error".to_string(),
+        }
+    }
+
+    fn io_error(kind: std::io::ErrorKind) -> bollard::errors::Error {
+        bollard::errors::Error::from(std::io::Error::new(kind, "error"))
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(&bollard::errors::Error::RequestTimeoutError));
+        assert!(is_transient(&server_error(500)));
+        assert!(is_transient(&server_error(503)));
+        assert!(is_transient(&io_error(std::io::ErrorKind::ConnectionReset)));
+        assert!(is_transient(&io_error(std::io::ErrorKind::TimedOut)));
+    }
+
+    #[test]
+    fn test_append_output_truncates_at_max_output() {
+        let mut output = Vec::new();
+
+        assert!(append_output(&mut output, b"hello ", Some(8)));
+        assert!(!append_output(&mut output, b"world", Some(8)));
+        assert_eq!(output, b"hello wo");
+
+        // Once the maximum is reached, the rest of the output is dropped
+        assert!(!append_output(&mut output, b"!", Some(8)));
+        assert_eq!(output, b"hello wo");
+    }
+
+    #[test]
+    fn test_append_output_without_max_output() {
+        let mut output = Vec::new();
+
+        assert!(append_output(&mut output, b"hello ", None));
+        assert!(append_output(&mut output, b"world", None));
+        assert_eq!(output, b"hello world");
+    }
+
+    #[tokio::test]
+    async fn test_find_deployment_tells_missing_deployments_from_docker_errors() {
+        let mut mock_docker = mocks::MockDocker::new();
+        mock_docker
+            .expect_get_deployment()
+            .withf(|name| name == "missing")
+            .return_once(|_| Err(GetDeploymentError::ContainerInspect(server_error(404))));
+        mock_docker
+            .expect_get_deployment()
+            .withf(|name| name == "local1")
+            .return_once(|_| Err(GetDeploymentError::ContainerInspect(server_error(500))));
+
+        assert!(
+            find_deployment(&mock_docker, "missing")
+                .await
+                .expect("a missing deployment is not an error")
+                .is_none()
+        );
+
+        let error = find_deployment(&mock_docker, "local1")
+            .await
+            .expect_err("a Docker error is returned");
+        assert!(error.chain().any(|cause| cause.is::<DockerError>()));
+    }
+
+    #[test]
+    fn test_empty_volume_body_passes_path_as_argument() {
+        let body = empty_volume_body(
+            "mongodb/mongodb-atlas-local:8.2.2",
+            "local1-data",
+            "/data/my db; rm -rf /",
+        );
+
+        assert_eq!(body.entrypoint, Some(vec!["find".to_string()]));
+        assert_eq!(
+            body.cmd,
+            Some(vec![
+                "/data/my db; rm -rf /".to_string(),
+                "-mindepth".to_string(),
+                "1".to_string(),
+                "-delete".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_is_not_transient() {
+        assert!(!is_transient(&server_error(400)));
+        assert!(!is_transient(&server_error(404)));
+        assert!(!is_transient(&server_error(409)));
+        assert!(!is_transient(&io_error(
+            std::io::ErrorKind::ConnectionRefused
+        )));
+        assert!(!is_transient(&io_error(
+            std::io::ErrorKind::PermissionDenied
+        )));
+        assert!(!is_transient(&bollard::errors::Error::SocketNotFoundError(
+            "/var/run/docker.sock".to_string()
+        )));
+        assert!(!is_transient(
+            &bollard::errors::Error::UnsupportedURISchemeError {
+                uri: "ftp://localhost".to_string()
+            }
+        ));
+    }
+
+    fn vars(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars = HashMap::<_, _>::from_iter(vars.iter().copied());
+        move |name| vars.get(name).map(OsString::from)
+    }
+
+    fn sockets(sockets: &[&'static str]) -> impl Fn(&Path) -> bool {
+        let sockets = sockets.iter().map(PathBuf::from).collect::<Vec<_>>();
+        move |path| sockets.iter().any(|socket| socket == path)
+    }
+
+    #[test]
+    fn test_find_podman_socket() {
+        let podman_vars = vars(&[
+            (env::XDG_RUNTIME_DIR, "/run/user/1000"),
+            (env::HOME, "/home/user"),
+            (env::TMPDIR, "/tmp"),
+        ]);
+
+        // Rootless Podman takes precedence over rootful Podman
+        assert_eq!(
+            find_podman_socket(
+                &podman_vars,
+                sockets(&["/run/user/1000/podman/podman.sock", PODMAN_ROOTFUL_SOCKET])
+            ),
+            Some(PathBuf::from("/run/user/1000/podman/podman.sock"))
+        );
+        assert_eq!(
+            find_podman_socket(&podman_vars, sockets(&[PODMAN_ROOTFUL_SOCKET])),
+            Some(PathBuf::from(PODMAN_ROOTFUL_SOCKET))
+        );
+        assert_eq!(
+            find_podman_socket(
+                &podman_vars,
+                sockets(&["/home/user/.local/share/containers/podman/machine/podman.sock"])
+            ),
+            Some(PathBuf::from(
+                "/home/user/.local/share/containers/podman/machine/podman.sock"
+            ))
+        );
+        assert_eq!(find_podman_socket(&podman_vars, sockets(&[])), None);
+    }
+
+    #[test]
+    fn test_resolve_host_falls_back_to_podman() {
+        let podman_vars = vars(&[(env::XDG_RUNTIME_DIR, "/run/user/1000")]);
+        let podman_sockets = || sockets(&["/run/user/1000/podman/podman.sock"]);
+
+        let resolved = DockerSettings::default().resolve_host_with(&podman_vars, podman_sockets());
+        let expected = if cfg!(target_os = "windows") {
+            None
+        } else {
+            Some("unix:///run/user/1000/podman/podman.sock".to_string())
+        };
+        assert_eq!(resolved.host, expected);
+
+        // A selected Docker host is kept
+        let selected = DockerSettings {
+            host: Some("tcp://docker.example.com:2375".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            selected
+                .clone()
+                .resolve_host_with(&podman_vars, podman_sockets()),
+            selected
+        );
+
+        // TLS connects to a remote host, not to a local socket
+        let tls = DockerSettings {
+            tls: Some(DockerTls {
+                ca_cert: PathBuf::from("ca.pem"),
+                cert: PathBuf::from("cert.pem"),
+                key: PathBuf::from("key.pem"),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            tls.clone()
+                .resolve_host_with(&podman_vars, podman_sockets()),
+            tls
+        );
+
+        // Without Podman the defaults are used
+        assert_eq!(
+            DockerSettings::default().resolve_host_with(&podman_vars, sockets(&[])),
+            DockerSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_resolve_docker_tls_from_env() {
+        assert_eq!(
+            resolve_docker_tls_from_env(vars(&[
+                (env::DOCKER_TLS_VERIFY, "1"),
+                (env::DOCKER_CERT_PATH, "/certs"),
+                (env::HOME, "/home/user"),
+            ])),
+            Some(DockerTls {
+                ca_cert: PathBuf::from("/certs/ca.pem"),
+                cert: PathBuf::from("/certs/cert.pem"),
+                key: PathBuf::from("/certs/key.pem"),
+            })
+        );
+        assert_eq!(
+            resolve_docker_tls_from_env(vars(&[
+                (env::DOCKER_TLS_VERIFY, "1"),
+                (env::DOCKER_CONFIG, "/home/user/.docker"),
+            ])),
+            Some(DockerTls {
+                ca_cert: PathBuf::from("/home/user/.docker/ca.pem"),
+                cert: PathBuf::from("/home/user/.docker/cert.pem"),
+                key: PathBuf::from("/home/user/.docker/key.pem"),
+            })
+        );
+        assert_eq!(
+            resolve_docker_tls_from_env(vars(&[
+                (env::DOCKER_TLS_VERIFY, ""),
+                (env::DOCKER_CERT_PATH, "/certs"),
+            ])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_podman_socket_prefers_docker() {
+        assert_eq!(
+            find_podman_socket(vars(&[]), sockets(&[DOCKER_SOCKET, PODMAN_ROOTFUL_SOCKET])),
+            None
+        );
+        assert_eq!(
+            find_podman_socket(
+                vars(&[(env::DOCKER_HOST, "tcp://localhost:2375")]),
+                sockets(&[PODMAN_ROOTFUL_SOCKET])
+            ),
+            None
+        );
+    }
+
+    fn watch_options() -> WatchOptions {
+        WatchOptions::builder()
+            .allow_unhealthy_initial_state(false)
+            .timeout_duration(Duration::from_secs(60))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_wait_reconnects_when_connection_drops() {
+        let mut dropping_waiter = mocks::MockDocker::new();
+        dropping_waiter
+            .expect_wait_for_healthy_deployment()
+            .times(1)
+            .returning(|_, _| {
+                Err(WatchDeploymentError::ContainerInspect(io_error(
+                    std::io::ErrorKind::ConnectionReset,
+                )))
+            });
+
+        let connects = std::sync::atomic::AtomicU32::new(0);
+        let result = wait_for_healthy_deployment_reconnecting(
+            &dropping_waiter,
+            || {
+                connects.fetch_add(1, Ordering::SeqCst);
+                let mut recovered_waiter = mocks::MockDocker::new();
+                recovered_waiter
+                    .expect_wait_for_healthy_deployment()
+                    .withf(|name, options| {
+                        name == "test-deployment" && options.allow_unhealthy_initial_state
+                    })
+                    .return_once(|_, _| Ok(()));
+                Ok(recovered_waiter)
+            },
+            "test-deployment",
+            watch_options(),
+            Duration::ZERO,
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(connects.into_inner(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_healthy_without_reconnect_on_drop() {
+        let mut dropping_waiter = mocks::MockDocker::new();
+        dropping_waiter
+            .expect_wait_for_healthy_deployment()
+            .times(1)
+            .returning(|_, _| {
+                Err(WatchDeploymentError::ContainerInspect(io_error(
+                    std::io::ErrorKind::ConnectionReset,
+                )))
+            });
+
+        let result = wait_for_healthy(
+            &dropping_waiter,
+            &DockerSettings::default(),
+            "test-deployment",
+            watch_options(),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(WatchDeploymentError::ContainerInspect(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_does_not_reconnect_on_other_errors() {
+        let mut waiter = mocks::MockDocker::new();
+        waiter
+            .expect_wait_for_healthy_deployment()
+            .times(1)
+            .returning(|_, _| Err(WatchDeploymentError::ContainerInspect(server_error(404))));
+
+        let result = wait_for_healthy_deployment_reconnecting(
+            &waiter,
+            || -> Result<mocks::MockDocker, bollard::errors::Error> {
+                panic!("should not reconnect")
+            },
+            "test-deployment",
+            watch_options(),
+            Duration::ZERO,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(WatchDeploymentError::ContainerInspect(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_gives_up_after_max_reconnect_attempts() {
+        let dropped = || {
+            let mut waiter = mocks::MockDocker::new();
+            waiter
+                .expect_wait_for_healthy_deployment()
+                .returning(|_, _| {
+                    Err(WatchDeploymentError::ContainerInspect(io_error(
+                        std::io::ErrorKind::ConnectionReset,
+                    )))
+                });
+            waiter
+        };
+
+        let connects = std::sync::atomic::AtomicU32::new(0);
+        let result = wait_for_healthy_deployment_reconnecting(
+            &dropped(),
+            || {
+                connects.fetch_add(1, Ordering::SeqCst);
+                Ok(dropped())
+            },
+            "test-deployment",
+            watch_options(),
+            Duration::ZERO,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(connects.into_inner(), MAX_RECONNECT_ATTEMPTS);
     }
 }
@@ -1,7 +1,8 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use tokio::io::AsyncReadExt;
 
 #[derive(Debug, Clone)]
 pub struct TokioFs;
@@ -26,6 +27,193 @@ impl FileReader for TokioFs {
             .with_context(|| format!("failed to read file: {}", path.display()))
     }
 }
+
+// Dependency to read the standard input, e.g. a configuration piped into the command
+#[async_trait]
+pub trait StdinReader {
+    /// Reads the standard input until it is closed.
+    async fn read_stdin_to_string(&self) -> Result<String>;
+}
+
+#[async_trait]
+impl StdinReader for TokioFs {
+    async fn read_stdin_to_string(&self) -> Result<String> {
+        let mut contents = String::new();
+        tokio::io::stdin()
+            .read_to_string(&mut contents)
+            .await
+            .context("failed to read from stdin")?;
+
+        Ok(contents)
+    }
+}
+
+/// The kind of filesystem entry a path points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathKind {
+    File,
+    Directory,
+    Other,
+}
+
+// Dependency to inspect and resolve paths
+#[async_trait]
+pub trait PathResolver {
+    /// Returns the kind of entry the path points to, or `None` when the path doesn't exist.
+    async fn path_kind(&self, path: &Path) -> Result<Option<PathKind>>;
+
+    /// Returns the absolute path with all symbolic links resolved.
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf>;
+}
+
+#[async_trait]
+impl PathResolver for TokioFs {
+    async fn path_kind(&self, path: &Path) -> Result<Option<PathKind>> {
+        let metadata = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("failed to inspect path: {}", path.display()));
+            }
+        };
+
+        Ok(Some(if metadata.is_file() {
+            PathKind::File
+        } else if metadata.is_dir() {
+            PathKind::Directory
+        } else {
+            PathKind::Other
+        }))
+    }
+
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        tokio::fs::canonicalize(path)
+            .await
+            .with_context(|| format!("failed to resolve path: {}", path.display()))
+    }
+}
+
+// Dependency to list the entries of a directory
+#[async_trait]
+pub trait DirectoryLister {
+    /// Returns the paths of the entries of the directory, in no particular order.
+    async fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
+}
+
+#[async_trait]
+impl DirectoryLister for TokioFs {
+    async fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = tokio::fs::read_dir(path)
+            .await
+            .with_context(|| format!("failed to read directory: {}", path.display()))?;
+
+        let mut paths = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .with_context(|| format!("failed to read directory: {}", path.display()))?
+        {
+            paths.push(entry.path());
+        }
+
+        Ok(paths)
+    }
+}
+
+// Dependency to write files
+#[async_trait]
+pub trait FileWriter {
+    /// Writes the contents to the file, replacing the file if it already exists.
+    async fn write_string(&self, path: &Path, contents: &str) -> Result<()>;
+
+    /// Creates the directory and all of its missing parents.
+    async fn create_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Writes the contents to a file only the current user can read, e.g. a file with credentials.
+    async fn write_private_string(&self, path: &Path, contents: &str) -> Result<()>;
+
+    /// Creates the directory and all of its missing parents, the new directories are only accessible by the
+    /// current user.
+    async fn create_private_dir_all(&self, path: &Path) -> Result<()>;
+}
+
+#[async_trait]
+impl FileWriter for TokioFs {
+    async fn write_string(&self, path: &Path, contents: &str) -> Result<()> {
+        tokio::fs::write(path, contents)
+            .await
+            .with_context(|| format!("failed to write file: {}", path.display()))
+    }
+
+    async fn create_dir_all(&self, path: &Path) -> Result<()> {
+        tokio::fs::create_dir_all(path)
+            .await
+            .with_context(|| format!("failed to create directory: {}", path.display()))
+    }
+
+    async fn write_private_string(&self, path: &Path, contents: &str) -> Result<()> {
+        write_private(path, contents)
+            .await
+            .with_context(|| format!("failed to write file: {}", path.display()))
+    }
+
+    async fn create_private_dir_all(&self, path: &Path) -> Result<()> {
+        let mut builder = tokio::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        builder.mode(0o700);
+
+        builder
+            .create(path)
+            .await
+            .with_context(|| format!("failed to create directory: {}", path.display()))
+    }
+}
+
+/// Write a file with permissions for the current user only.
+#[cfg(unix)]
+async fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .await?;
+    // The mode only applies to new files, an existing file keeps its permissions
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+        .await?;
+    file.write_all(contents.as_bytes()).await?;
+    file.flush().await
+}
+
+/// Write a file with permissions for the current user only.
+#[cfg(not(unix))]
+async fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    // Files in the user's profile are only accessible by the user by default
+    tokio::fs::write(path, contents).await
+}
+
+// Dependency to remove directories
+#[async_trait]
+pub trait DirectoryRemover {
+    /// Removes the directory with all of its contents.
+    async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+}
+
+#[async_trait]
+impl DirectoryRemover for TokioFs {
+    async fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        tokio::fs::remove_dir_all(path)
+            .await
+            .with_context(|| format!("failed to remove directory: {}", path.display()))
+    }
+}
+
 #[cfg(test)]
 pub mod mocks {
     use super::*;
@@ -38,5 +226,129 @@ pub mod mocks {
         impl FileReader for TokioFs {
             async fn read_to_string(&self, path: &Path) -> Result<String>;
         }
+
+        #[async_trait]
+        impl StdinReader for TokioFs {
+            async fn read_stdin_to_string(&self) -> Result<String>;
+        }
+
+        #[async_trait]
+        impl PathResolver for TokioFs {
+            async fn path_kind(&self, path: &Path) -> Result<Option<PathKind>>;
+            async fn canonicalize(&self, path: &Path) -> Result<PathBuf>;
+        }
+
+        #[async_trait]
+        impl DirectoryLister for TokioFs {
+            async fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
+        }
+
+        #[async_trait]
+        impl FileWriter for TokioFs {
+            async fn write_string(&self, path: &Path, contents: &str) -> Result<()>;
+            async fn create_dir_all(&self, path: &Path) -> Result<()>;
+            async fn write_private_string(&self, path: &Path, contents: &str) -> Result<()>;
+            async fn create_private_dir_all(&self, path: &Path) -> Result<()>;
+        }
+
+        #[async_trait]
+        impl DirectoryRemover for TokioFs {
+            async fn remove_dir_all(&self, path: &Path) -> Result<()>;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tokio_fs_write_and_read() {
+        let dir = std::env::temp_dir().join(format!("atlas-local-fs-test-{}", std::process::id()));
+        let path = dir.join("nested").join("config.json");
+        let fs = TokioFs::new();
+
+        fs.create_dir_all(path.parent().unwrap())
+            .await
+            .expect("creating the directory should succeed");
+        fs.write_string(&path, r#"{"port":27017}"#)
+            .await
+            .expect("writing the file should succeed");
+
+        let contents = fs
+            .read_to_string(&path)
+            .await
+            .expect("reading the file should succeed");
+        assert_eq!(contents, r#"{"port":27017}"#);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_tokio_fs_write_to_missing_directory() {
+        let path = std::env::temp_dir()
+            .join(format!("atlas-local-fs-missing-{}", std::process::id()))
+            .join("config.json");
+
+        let error = TokioFs::new()
+            .write_string(&path, "{}")
+            .await
+            .expect_err("writing to a missing directory should fail");
+        assert!(error.to_string().contains("failed to write file"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_tokio_fs_write_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir =
+            std::env::temp_dir().join(format!("atlas-local-fs-private-{}", std::process::id()));
+        let private_dir = dir.join("private");
+        let path = private_dir.join("snapshot.json");
+        let fs = TokioFs::new();
+
+        fs.create_private_dir_all(&private_dir)
+            .await
+            .expect("creating the directory should succeed");
+        // An existing file is made private as well
+        std::fs::write(&path, "{}").expect("writing the file should succeed");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))
+            .expect("setting the permissions should succeed");
+        fs.write_private_string(&path, r#"{"password":"secret"}"#)
+            .await
+            .expect("writing the file should succeed");
+
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&private_dir), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            r#"{"password":"secret"}"#
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_tokio_fs_path_kind_and_canonicalize() {
+        let dir = std::env::temp_dir().join(format!("atlas-local-fs-kind-{}", std::process::id()));
+        let file = dir.join("init.js");
+        std::fs::create_dir_all(&dir).expect("creating the directory should succeed");
+        std::fs::write(&file, "db.test.insertOne({})").expect("writing the file should succeed");
+        let fs = TokioFs::new();
+
+        assert_eq!(fs.path_kind(&dir).await.unwrap(), Some(PathKind::Directory));
+        assert_eq!(fs.path_kind(&file).await.unwrap(), Some(PathKind::File));
+        assert_eq!(fs.path_kind(&dir.join("missing")).await.unwrap(), None);
+
+        let canonical = fs
+            .canonicalize(&dir.join(".").join("init.js"))
+            .await
+            .expect("canonicalizing an existing path should succeed");
+        assert!(canonical.is_absolute());
+        assert!(canonical.ends_with("init.js"));
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -1,17 +1,26 @@
 //! This module defines traits for external dependencies (such as Docker interactions) to make them
 //! easier to mock and substitute in tests or other environments. By abstracting external services
 //! behind traits, components can be decoupled and dependency-injected, improving testability and maintainability.
+pub mod clock;
 pub mod docker;
 pub mod fs;
 pub mod mongodb;
+pub mod notifier;
+pub mod system;
 
+pub use clock::*;
 pub use docker::*;
 pub use fs::*;
 pub use mongodb::*;
+pub use notifier::*;
+pub use system::*;
 
 #[cfg(test)]
 pub mod mocks {
+    pub use super::clock::mocks::*;
     pub use super::docker::mocks::*;
     pub use super::fs::mocks::*;
     pub use super::mongodb::mocks::*;
+    pub use super::notifier::mocks::*;
+    pub use super::system::mocks::*;
 }
@@ -1,9 +1,13 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use futures::TryStreamExt;
-use mongodb::{Client, SearchIndexModel, bson::Document};
+use mongodb::{
+    Client, SearchIndexModel,
+    bson::{Document, doc},
+};
+use semver::Version;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, trace};
 
@@ -48,6 +52,18 @@ pub trait SearchIndexDeleter {
     ) -> Result<()>;
 }
 
+/// Trait for updating the definition of a search index by name.
+#[async_trait]
+pub trait SearchIndexUpdater {
+    async fn update_search_index(
+        &self,
+        database_name: String,
+        collection_name: String,
+        index_name: String,
+        definition: Document,
+    ) -> Result<()>;
+}
+
 /// Trait for describing (getting) a search index by ID.
 #[async_trait]
 pub trait SearchIndexDescriber {
@@ -60,6 +76,91 @@ pub trait SearchIndexCreator {
     async fn create_search_index(&self, model: CreateSearchIndexModel) -> Result<String>;
 }
 
+/// A search index together with its latest definition, as returned by `$listSearchIndexes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchIndexWithDefinition {
+    /// The unique identifier of the search index.
+    pub index_id: String,
+    /// The name of the search index.
+    pub name: String,
+    /// The type of the search index (e.g., "search" or "vectorSearch").
+    pub index_type: Option<String>,
+    /// The latest definition of the search index.
+    pub definition: Document,
+}
+
+/// Trait for getting the definition of a search index by name.
+#[async_trait]
+pub trait SearchIndexDefinitionGetter {
+    async fn get_search_index_definition(
+        &self,
+        database_name: String,
+        collection_name: String,
+        index_name: String,
+    ) -> Result<Option<SearchIndexWithDefinition>>;
+}
+
+/// Trait for listing the collections in a database.
+#[async_trait]
+pub trait CollectionLister {
+    /// Lists the names of the collections in the database, excluding views and system collections.
+    async fn list_collection_names(&self, database_name: String) -> Result<Vec<String>>;
+}
+
+// Dependency to wait for a deployment to have a primary
+#[async_trait]
+pub trait PrimaryWaiter {
+    /// Waits until the deployment has a writable primary.
+    /// Returns false when there is no primary within the timeout.
+    async fn wait_for_primary(&self, deployment_name: &str, timeout: Duration) -> Result<bool>;
+}
+
+// Dependency to check that a connection string can be used to connect
+#[async_trait]
+pub trait ConnectionStringPinger {
+    /// Connects with the connection string and runs the `ping` command.
+    /// Fails when the deployment can't be reached within the timeout.
+    async fn ping(&self, connection_string: &str, timeout: Duration) -> Result<()>;
+}
+
+/// Capabilities of a deployment, as reported by the deployment itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbedCapabilities {
+    pub mongodb_version: Version,
+    pub search: bool,
+}
+
+// Dependency to ask a deployment what it supports
+#[async_trait]
+pub trait CapabilityProber {
+    /// Connects to the deployment and asks it for its version and whether search is configured.
+    async fn probe_capabilities(&self, deployment_name: &str) -> Result<ProbedCapabilities>;
+}
+
+// Dependency to block the writes of a deployment while its data files are copied
+#[async_trait]
+pub trait FsyncLocker {
+    /// Flushes all pending writes to disk and blocks new writes until [`FsyncLocker::fsync_unlock`] is called.
+    async fn fsync_lock(&self, deployment_name: &str) -> Result<()>;
+
+    /// Allows writes again after [`FsyncLocker::fsync_lock`].
+    async fn fsync_unlock(&self, deployment_name: &str) -> Result<()>;
+}
+
+/// A database of a deployment, as reported by `listDatabases`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseInfo {
+    pub name: String,
+    pub size_on_disk: u64,
+}
+
+// Dependency to list the databases of a deployment
+#[async_trait]
+pub trait DatabaseLister {
+    /// Connects to the deployment and lists its databases, including the system databases.
+    async fn list_databases(&self, deployment_name: &str) -> Result<Vec<DatabaseInfo>>;
+}
+
 #[async_trait]
 pub trait SearchIndexStatusGetter {
     async fn get_search_index_status(
@@ -211,10 +312,13 @@ impl SearchIndexStatusGetter for Client {
             database_name, collection_name, "all search index definitions"
         );
 
-        let search_index = search_index_definitions
+        // An index that was deleted is no longer listed
+        let Some(search_index) = search_index_definitions
             .iter()
             .find(|index| index.name == index_name)
-            .context("finding search index")?;
+        else {
+            return Ok(None);
+        };
         trace!(
             ?search_index,
             "search index definition for index name: {index_name}"
@@ -224,6 +328,60 @@ impl SearchIndexStatusGetter for Client {
     }
 }
 
+#[async_trait]
+impl SearchIndexDefinitionGetter for Client {
+    async fn get_search_index_definition(
+        &self,
+        database_name: String,
+        collection_name: String,
+        index_name: String,
+    ) -> Result<Option<SearchIndexWithDefinition>> {
+        debug!(
+            database_name,
+            collection_name, index_name, "getting search index definition"
+        );
+
+        let search_indexes = self
+            .database(&database_name)
+            .collection::<()>(&collection_name)
+            .list_search_indexes()
+            .await
+            .context("listing search indexes")?;
+
+        // Internal struct to deserialize the raw search index document.
+        // See: https://www.mongodb.com/docs/manual/reference/operator/aggregation/listSearchIndexes/
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        struct RawSearchIndex {
+            #[serde(rename = "id")]
+            index_id: String,
+            name: String,
+            #[serde(rename = "type")]
+            index_type: Option<String>,
+            #[serde(rename = "latestDefinition", default)]
+            latest_definition: Document,
+        }
+
+        let raw_search_indexes = search_indexes
+            .with_type::<RawSearchIndex>()
+            .try_collect::<Vec<_>>()
+            .await
+            .context("collecting search index definitions")?;
+
+        let search_index = raw_search_indexes
+            .into_iter()
+            .find(|raw| raw.name == index_name)
+            .map(|raw| SearchIndexWithDefinition {
+                index_id: raw.index_id,
+                name: raw.name,
+                index_type: raw.index_type,
+                definition: raw.latest_definition,
+            });
+        trace!(?search_index, "search index definition");
+
+        Ok(search_index)
+    }
+}
+
 #[async_trait]
 impl SearchIndexLister for Client {
     async fn list_search_indexes(
@@ -277,6 +435,25 @@ impl SearchIndexLister for Client {
     }
 }
 
+#[async_trait]
+impl CollectionLister for Client {
+    async fn list_collection_names(&self, database_name: String) -> Result<Vec<String>> {
+        debug!(database_name, "listing collections");
+
+        let collection_names = self
+            .database(&database_name)
+            .list_collection_names()
+            .filter(doc! { "type": "collection" })
+            .await
+            .map_err(mongodb_error_to_user_friendly_error)?;
+
+        Ok(collection_names
+            .into_iter()
+            .filter(|name| !name.starts_with("system."))
+            .collect())
+    }
+}
+
 #[async_trait]
 impl SearchIndexDeleter for Client {
     async fn delete_search_index(
@@ -298,6 +475,28 @@ impl SearchIndexDeleter for Client {
     }
 }
 
+#[async_trait]
+impl SearchIndexUpdater for Client {
+    async fn update_search_index(
+        &self,
+        database_name: String,
+        collection_name: String,
+        index_name: String,
+        definition: Document,
+    ) -> Result<()> {
+        debug!(
+            database_name,
+            collection_name, index_name, "updating search index"
+        );
+
+        self.database(&database_name)
+            .collection::<()>(&collection_name)
+            .update_search_index(index_name, definition)
+            .await
+            .map_err(mongodb_error_to_user_friendly_error)
+    }
+}
+
 #[async_trait]
 impl SearchIndexDescriber for Client {
     async fn describe_search_index(&self, index_id: String) -> Result<Option<SearchIndex>> {
@@ -393,6 +592,16 @@ pub mod mocks {
             ) -> Result<Option<MongoDbSearchIndexStatus>>;
         }
 
+        #[async_trait]
+        impl SearchIndexDefinitionGetter for MongoDB {
+            async fn get_search_index_definition(
+                &self,
+                database_name: String,
+                collection_name: String,
+                index_name: String,
+            ) -> Result<Option<SearchIndexWithDefinition>>;
+        }
+
         #[async_trait]
         impl SearchIndexLister for MongoDB {
             async fn list_search_indexes(
@@ -402,6 +611,11 @@ pub mod mocks {
             ) -> Result<Vec<SearchIndex>>;
         }
 
+        #[async_trait]
+        impl CollectionLister for MongoDB {
+            async fn list_collection_names(&self, database_name: String) -> Result<Vec<String>>;
+        }
+
         #[async_trait]
         impl SearchIndexDeleter for MongoDB {
             async fn delete_search_index(
@@ -412,9 +626,46 @@ pub mod mocks {
             ) -> Result<()>;
         }
 
+        #[async_trait]
+        impl SearchIndexUpdater for MongoDB {
+            async fn update_search_index(
+                &self,
+                database_name: String,
+                collection_name: String,
+                index_name: String,
+                definition: Document,
+            ) -> Result<()>;
+        }
+
         #[async_trait]
         impl SearchIndexDescriber for MongoDB {
             async fn describe_search_index(&self, index_id: String) -> Result<Option<SearchIndex>>;
         }
+
+        #[async_trait]
+        impl PrimaryWaiter for MongoDB {
+            async fn wait_for_primary(&self, deployment_name: &str, timeout: Duration) -> Result<bool>;
+        }
+
+        #[async_trait]
+        impl DatabaseLister for MongoDB {
+            async fn list_databases(&self, deployment_name: &str) -> Result<Vec<DatabaseInfo>>;
+        }
+
+        #[async_trait]
+        impl ConnectionStringPinger for MongoDB {
+            async fn ping(&self, connection_string: &str, timeout: Duration) -> Result<()>;
+        }
+
+        #[async_trait]
+        impl CapabilityProber for MongoDB {
+            async fn probe_capabilities(&self, deployment_name: &str) -> Result<ProbedCapabilities>;
+        }
+
+        #[async_trait]
+        impl FsyncLocker for MongoDB {
+            async fn fsync_lock(&self, deployment_name: &str) -> Result<()>;
+            async fn fsync_unlock(&self, deployment_name: &str) -> Result<()>;
+        }
     }
 }
@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+use tracing::debug;
+
+/// Shows notifications on the desktop using the tools that ship with the OS.
+///
+/// On Linux `notify-send` is used, on macOS `osascript`.
+/// On headless systems, or when the tool is not available, notifications are silently skipped.
+#[derive(Debug, Clone)]
+pub struct DesktopNotifier;
+
+impl DesktopNotifier {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+// Dependency to show desktop notifications
+#[async_trait]
+pub trait Notifier {
+    async fn notify(&self, title: &str, message: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    async fn notify(&self, title: &str, message: &str) -> Result<()> {
+        let Some(mut command) = notification_command(title, message) else {
+            debug!("desktop notifications are not available, skipping notification");
+            return Ok(());
+        };
+
+        let status = command
+            .status()
+            .await
+            .context("failed to show desktop notification")?;
+        debug!(?status, "desktop notification shown");
+
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn notification_command(title: &str, message: &str) -> Option<Command> {
+    // Without a display server there is nobody to show the notification to.
+    if std::env::var_os("DISPLAY").is_none() && std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        return None;
+    }
+
+    let bin = which::which("notify-send").ok()?;
+    let mut command = Command::new(bin);
+    command.arg(title).arg(message);
+    Some(command)
+}
+
+#[cfg(target_os = "macos")]
+fn notification_command(title: &str, message: &str) -> Option<Command> {
+    let bin = which::which("osascript").ok()?;
+    let mut command = Command::new(bin);
+    // Pass the strings as arguments instead of interpolating them into the script, so no escaping is needed.
+    command
+        .arg("-e")
+        .arg("on run argv")
+        .arg("-e")
+        .arg("display notification (item 2 of argv) with title (item 1 of argv)")
+        .arg("-e")
+        .arg("end run")
+        .arg(title)
+        .arg(message);
+    Some(command)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn notification_command(_title: &str, _message: &str) -> Option<Command> {
+    None
+}
+
+#[cfg(test)]
+pub mod mocks {
+    use super::*;
+    use mockall::mock;
+
+    mock! {
+        pub DesktopNotifier {}
+
+        #[async_trait]
+        impl Notifier for DesktopNotifier {
+            async fn notify(&self, title: &str, message: &str) -> Result<()>;
+        }
+    }
+}
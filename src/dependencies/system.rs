@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use tokio::{net::TcpListener, process::Command};
+
+/// Inspects the resources of the machine the CLI runs on.
+///
+/// The available disk space is read with `df`, which is not available on Windows.
+#[derive(Debug, Clone)]
+pub struct HostSystem;
+
+impl HostSystem {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+// Dependency to check whether a port is free on the host
+#[async_trait]
+pub trait PortChecker {
+    /// Returns whether a process can listen on the port of the loopback interface.
+    async fn is_port_available(&self, port: u16) -> bool;
+}
+
+#[async_trait]
+impl PortChecker for HostSystem {
+    async fn is_port_available(&self, port: u16) -> bool {
+        // The listener is dropped right away, it only checks that nothing else listens on the port
+        TcpListener::bind(("127.0.0.1", port)).await.is_ok()
+    }
+}
+
+// Dependency to get the available disk space
+#[async_trait]
+pub trait DiskSpaceChecker {
+    /// Returns the number of bytes available on the filesystem of the path, or `None` when it can't be determined on this platform.
+    async fn available_disk_space(&self, path: &Path) -> Result<Option<u64>>;
+}
+
+#[async_trait]
+impl DiskSpaceChecker for HostSystem {
+    async fn available_disk_space(&self, path: &Path) -> Result<Option<u64>> {
+        if cfg!(target_os = "windows") {
+            return Ok(None);
+        }
+
+        // -P selects the portable output format, -k reports the sizes in 1024-byte blocks
+        let output = Command::new("df")
+            .arg("-Pk")
+            .arg(path)
+            .output()
+            .await
+            .context("running df")?;
+        if !output.status.success() {
+            bail!(
+                "df failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let available_kib = parse_df_available_kib(&String::from_utf8_lossy(&output.stdout))
+            .context("parsing the output of df")?;
+
+        Ok(Some(available_kib.saturating_mul(1024)))
+    }
+}
+
+/// Get the available space in KiB from the output of `df -Pk`.
+///
+/// The output is a header line and one line per filesystem: `Filesystem 1024-blocks Used Available Capacity Mounted on`.
+fn parse_df_available_kib(output: &str) -> Result<u64> {
+    let line = output
+        .lines()
+        .nth(1)
+        .context("the output has no filesystem line")?;
+    let available = line
+        .split_whitespace()
+        .nth(3)
+        .context("the filesystem line has no available column")?;
+
+    available
+        .parse()
+        .with_context(|| format!("invalid available space: {available}"))
+}
+
+#[cfg(test)]
+pub mod mocks {
+    use super::*;
+    use mockall::mock;
+
+    mock! {
+        pub HostSystem {}
+
+        #[async_trait]
+        impl PortChecker for HostSystem {
+            async fn is_port_available(&self, port: u16) -> bool;
+        }
+
+        #[async_trait]
+        impl DiskSpaceChecker for HostSystem {
+            async fn available_disk_space(&self, path: &Path) -> Result<Option<u64>>;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_df_available_kib() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/disk3s1s1   482797652  10681264 253540232       5% /\n";
+
+        assert_eq!(parse_df_available_kib(output).unwrap(), 253540232);
+        assert!(parse_df_available_kib("Filesystem 1024-blocks Used Available\n").is_err());
+    }
+}
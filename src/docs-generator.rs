@@ -435,6 +435,7 @@ pub enum FlagType {
     Number,
     Path,
     String,
+    StringArray,
 }
 
 impl FlagType {
@@ -449,6 +450,7 @@ impl FlagType {
             FlagType::Number => "int",
             FlagType::Path => "string",
             FlagType::String => "string",
+            FlagType::StringArray => "stringArray",
         }
     }
 }
@@ -482,9 +484,8 @@ fn try_get_flag_type(
                 }
             }
         },
-        clap::ArgAction::Append => {
-            bail!("Append action is not supported, we don't support lists of values")
-        }
+        // Repeatable flags are documented as a list of strings, the individual values are parsed by the value parser.
+        clap::ArgAction::Append => Some(FlagType::StringArray),
         clap::ArgAction::SetTrue | clap::ArgAction::SetFalse => Some(FlagType::Boolean),
         clap::ArgAction::Count => {
             bail!("Count action is not supported, we don't support counting flags")
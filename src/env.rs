@@ -8,6 +8,17 @@ pub const MONGODB_ATLAS_LOCAL_PREVIEW: &str = "MONGODB_ATLAS_LOCAL_PREVIEW";
 /// When set, it is passed to the deployment for use during index building.
 pub const MONGODB_ATLAS_LOCAL_VOYAGE_API_KEY: &str = "MONGODB_ATLAS_LOCAL_VOYAGE_API_KEY";
 
+/// Default port for new deployments created with `setup`.
+/// Used when the `--port` flag is not provided, must be between 1 and 65535.
+pub const MONGODB_ATLAS_LOCAL_PORT: &str = "MONGODB_ATLAS_LOCAL_PORT";
+
+/// Registry or mirror to pull the deployment image from when `--registry` is omitted, e.g. "mirror.example.com:5000".
+pub const MONGODB_ATLAS_LOCAL_REGISTRY: &str = "MONGODB_ATLAS_LOCAL_REGISTRY";
+
+/// Connector used when `--connectWith` is omitted, one of compass, mongosh, vscode or connectionString.
+/// `setup` pre-selects it in the connection prompt and uses it with `--force`, `connect` uses it directly.
+pub const MONGODB_ATLAS_LOCAL_DEFAULT_CONNECTOR: &str = "MONGODB_ATLAS_LOCAL_DEFAULT_CONNECTOR";
+
 /// Overrides the log level (e.g. "debug", "info", "warn", "error").
 /// Default is "info" when unset.
 pub const ATLAS_LOCAL_LOG: &str = "ATLAS_LOCAL_LOG";
@@ -15,3 +26,48 @@ pub const ATLAS_LOCAL_LOG: &str = "ATLAS_LOCAL_LOG";
 /// When set, show logs from all crates at the level specified by
 /// `ATLAS_LOCAL_LOG`. When unset, only this crate's logs are shown.
 pub const ATLAS_LOCAL_LOG_ALL: &str = "ATLAS_LOCAL_LOG_ALL";
+
+/// POSIX locale, e.g. "en_US.UTF-8", selects the language of the messages shown while commands run.
+/// English is used when there are no messages for the language.
+pub const LANG: &str = "LANG";
+
+/// Directory where the CLI saves its state, e.g. the contexts created with `context set`.
+/// The `--configDir` flag takes precedence, defaults to the `atlas-local` directory in the user's configuration directory.
+pub const MONGODB_ATLAS_LOCAL_CONFIG_DIR: &str = "MONGODB_ATLAS_LOCAL_CONFIG_DIR";
+
+/// Directory where the snapshots created with `snapshot create` are stored, the `--snapshotsDir` flag takes precedence.
+/// Defaults to the `snapshots` directory in the configuration directory.
+pub const MONGODB_ATLAS_LOCAL_SNAPSHOTS_DIR: &str = "MONGODB_ATLAS_LOCAL_SNAPSHOTS_DIR";
+
+/// User's configuration directory on Linux and macOS, defaults to `$HOME/.config` when unset.
+pub const XDG_CONFIG_HOME: &str = "XDG_CONFIG_HOME";
+
+/// User's home directory on Linux and macOS.
+pub const HOME: &str = "HOME";
+
+/// Address of the Docker daemon, e.g. "tcp://localhost:2375", used when no context is selected with `--context`.
+pub const DOCKER_HOST: &str = "DOCKER_HOST";
+
+/// When set to a non-empty value, connect to the Docker host with TLS, using the certificates in `DOCKER_CERT_PATH`.
+pub const DOCKER_TLS_VERIFY: &str = "DOCKER_TLS_VERIFY";
+
+/// Directory with the ca.pem, cert.pem and key.pem TLS certificates, defaults to the Docker configuration directory.
+pub const DOCKER_CERT_PATH: &str = "DOCKER_CERT_PATH";
+
+/// Docker context to use when `--dockerContext` is omitted, it takes precedence over the current context of the Docker CLI.
+pub const DOCKER_CONTEXT: &str = "DOCKER_CONTEXT";
+
+/// Directory of the Docker CLI configuration, with the metadata of the Docker contexts, defaults to `$HOME/.docker`.
+pub const DOCKER_CONFIG: &str = "DOCKER_CONFIG";
+
+/// User's home directory on Windows.
+pub const USERPROFILE: &str = "USERPROFILE";
+
+/// Directory of the user's runtime files on Linux, where rootless Podman creates its socket.
+pub const XDG_RUNTIME_DIR: &str = "XDG_RUNTIME_DIR";
+
+/// Temporary directory on macOS, where Podman machines create their API socket.
+pub const TMPDIR: &str = "TMPDIR";
+
+/// User's roaming application data directory on Windows.
+pub const APPDATA: &str = "APPDATA";
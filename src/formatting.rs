@@ -1,17 +1,87 @@
 //! This module contains the formatting logic for the application.
 //!
 //! The main entry point is the [`Formattable`] trait which provides a method to format an object as text or json.
+//!
+//! # JSON stability
+//!
+//! With `--envelope` the JSON output is wrapped in `{ "schema_version": N, "result": ... }`.
+//! [`SCHEMA_VERSION`] is bumped whenever a field is removed or renamed, or when the meaning of a field changes.
+//! Adding fields or outcomes does not bump the version, consumers should ignore fields they don't know.
+//!
+//! With `--jsonArray` single results are wrapped in a one-element array, so commands returning a single result
+//! have the same shape as commands returning a list.
 use std::fmt::Display;
 
 use anyhow::{Context, Result};
-use clap::ValueEnum;
+use clap::{ValueEnum, builder::PossibleValue};
 use serde::Serialize;
 
+/// Version of the JSON output schema, reported in the envelope.
+pub const SCHEMA_VERSION: u32 = 1;
+
 /// Format of the output.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Format {
     Text,
-    Json,
+    Json {
+        /// Wrap the output in a versioned envelope, selected with `--envelope`.
+        envelope: bool,
+        /// Wrap single results in a one-element array, selected with `--jsonArray`.
+        array: bool,
+    },
+}
+
+impl Format {
+    /// Plain JSON, selected with `--output json`.
+    pub const JSON: Format = Format::Json {
+        envelope: false,
+        array: false,
+    };
+
+    /// Wrap the JSON output in a versioned envelope, other formats are returned unchanged.
+    pub fn with_envelope(self, envelope: bool) -> Self {
+        match self {
+            Format::Json { array, .. } => Format::Json { envelope, array },
+            format => format,
+        }
+    }
+
+    /// Wrap single results of the JSON output in a one-element array, other formats are returned unchanged.
+    pub fn with_json_array(self, array: bool) -> Self {
+        match self {
+            Format::Json { envelope, .. } => Format::Json { envelope, array },
+            format => format,
+        }
+    }
+
+    /// Whether the output is JSON, with or without the envelope and array options.
+    pub fn is_json(self) -> bool {
+        matches!(self, Format::Json { .. })
+    }
+}
+
+/// The options of the JSON format are separate flags, so `--output` only selects between text and plain JSON.
+impl ValueEnum for Format {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Format::Text, Format::JSON]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Format::Text => PossibleValue::new("text"),
+            Format::Json { .. } => PossibleValue::new("json"),
+        })
+    }
+}
+
+/// Output stream errors are written to.
+///
+/// Successful output is always written to stdout, this only controls where errors end up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum ErrorsTo {
+    Stdout,
+    #[default]
+    Stderr,
 }
 
 /// Trait for types that can be formatted as text or JSON.
@@ -27,6 +97,8 @@ pub trait Formattable {
 ///
 /// When the text is requested, the object is converted to a string using the [`Display`] trait.
 /// When the JSON is requested, the object is serialized to a JSON string using the [`Serialize`] trait.
+/// When the JSON envelope is requested, the serialized object is nested under `result` next to the `schema_version`.
+/// When the JSON array is requested, objects that don't serialize to an array are wrapped in a one-element array.
 impl<T> Formattable for T
 where
     T: Display + Serialize,
@@ -34,11 +106,97 @@ where
     fn format(&self, format: Format) -> Result<String> {
         Ok(match format {
             Format::Text => self.to_string(),
-            Format::Json => serde_json::to_string(self).context("serializing to json")?,
+            Format::Json {
+                envelope: false,
+                array: false,
+            } => serde_json::to_string(self).context("serializing to json")?,
+            Format::Json {
+                envelope: true,
+                array: false,
+            } => serde_json::to_string(&Envelope {
+                schema_version: SCHEMA_VERSION,
+                result: self,
+            })
+            .context("serializing to json")?,
+            Format::Json {
+                envelope: false,
+                array: true,
+            } => serde_json::to_string(&JsonArray::new(self)?).context("serializing to json")?,
+            Format::Json {
+                envelope: true,
+                array: true,
+            } => serde_json::to_string(&Envelope {
+                schema_version: SCHEMA_VERSION,
+                result: &JsonArray::new(self)?,
+            })
+            .context("serializing to json")?,
+        })
+    }
+}
+
+/// Versioned envelope around the JSON output of a command.
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    schema_version: u32,
+    result: &'a T,
+}
+
+/// JSON output that is always an array, used with `--jsonArray`.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsonArray<'a, T> {
+    /// The object already serializes to an array, e.g. the result of the list command.
+    Array(&'a T),
+    /// Any other object is wrapped in a one-element array.
+    Wrapped([&'a T; 1]),
+}
+
+impl<'a, T: Serialize> JsonArray<'a, T> {
+    fn new(value: &'a T) -> Result<Self> {
+        // Compact JSON only starts with a bracket when the top-level value is an array
+        let json = serde_json::to_string(value).context("serializing to json")?;
+        Ok(if json.starts_with('[') {
+            JsonArray::Array(value)
+        } else {
+            JsonArray::Wrapped([value])
+        })
+    }
+}
+
+/// Format an error as text or JSON.
+///
+/// The text format matches the way Rust prints an error returned from `main`, including the chain of causes.
+/// The JSON format is an object with a single `error` field containing the error and its causes on one line.
+/// The JSON envelope format adds the `schema_version` next to the `error` field.
+/// Errors are not results, so they are never wrapped in an array.
+pub fn format_error(error: &anyhow::Error, format: Format) -> String {
+    match format {
+        Format::Text => format!("Error: {error:?}"),
+        Format::Json {
+            envelope: false, ..
+        } => serde_json::json!({ "error": format!("{error:#}") }).to_string(),
+        Format::Json { envelope: true, .. } => serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "error": format!("{error:#}"),
         })
+        .to_string(),
     }
 }
 
+/// Format an invalid command line, e.g. an unknown subcommand or a missing argument, as JSON.
+///
+/// Usage errors are reported before the command runs, so unlike [`format_error`] the error is an object with the
+/// `kind` of the error next to its `message`, e.g. `{"error":{"kind":"usage","message":"..."}}`.
+pub fn format_usage_error(message: &str) -> String {
+    serde_json::json!({
+        "error": {
+            "kind": "usage",
+            "message": message,
+        }
+    })
+    .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,7 +229,135 @@ mod tests {
             name: "test".to_string(),
             value: 42,
         };
-        let result = test.format(Format::Json).unwrap();
+        let result = test.format(Format::JSON).unwrap();
         assert_eq!(result, r#"{"name":"test","value":42}"#);
     }
+
+    #[test]
+    fn test_format_json_envelope() {
+        let test = TestStruct {
+            name: "test".to_string(),
+            value: 42,
+        };
+        let result = test.format(Format::JSON.with_envelope(true)).unwrap();
+        assert_eq!(
+            result,
+            r#"{"schema_version":1,"result":{"name":"test","value":42}}"#
+        );
+    }
+
+    #[test]
+    fn test_format_json_array_wraps_single_result() {
+        let test = TestStruct {
+            name: "test".to_string(),
+            value: 42,
+        };
+        let result = test.format(Format::JSON.with_json_array(true)).unwrap();
+        assert_eq!(result, r#"[{"name":"test","value":42}]"#);
+    }
+
+    #[test]
+    fn test_format_json_array_keeps_arrays() {
+        let test = vec![TestStruct {
+            name: "test".to_string(),
+            value: 42,
+        }];
+        let result = serde_json::to_string(&JsonArray::new(&test).unwrap()).unwrap();
+        assert_eq!(result, r#"[{"name":"test","value":42}]"#);
+    }
+
+    #[test]
+    fn test_format_json_array_envelope() {
+        let test = TestStruct {
+            name: "test".to_string(),
+            value: 42,
+        };
+        let result = test
+            .format(Format::JSON.with_json_array(true).with_envelope(true))
+            .unwrap();
+        assert_eq!(
+            result,
+            r#"{"schema_version":1,"result":[{"name":"test","value":42}]}"#
+        );
+    }
+
+    #[test]
+    fn test_with_json_array_only_applies_to_json() {
+        assert_eq!(
+            Format::JSON.with_json_array(true),
+            Format::Json {
+                envelope: false,
+                array: true
+            }
+        );
+        assert_eq!(
+            Format::JSON.with_json_array(true).with_envelope(true),
+            Format::Json {
+                envelope: true,
+                array: true
+            }
+        );
+        assert_eq!(Format::JSON.with_json_array(false), Format::JSON);
+        assert_eq!(Format::Text.with_json_array(true), Format::Text);
+    }
+
+    #[test]
+    fn test_with_envelope_only_applies_to_json() {
+        assert_eq!(
+            Format::JSON.with_envelope(true),
+            Format::Json {
+                envelope: true,
+                array: false
+            }
+        );
+        assert_eq!(Format::JSON.with_envelope(false), Format::JSON);
+        assert_eq!(Format::Text.with_envelope(true), Format::Text);
+    }
+
+    #[test]
+    fn test_format_value_enum() {
+        assert_eq!(Format::from_str("json", false).unwrap(), Format::JSON);
+        assert_eq!(Format::from_str("text", false).unwrap(), Format::Text);
+        assert!(Format::from_str("json-envelope", false).is_err());
+    }
+
+    #[test]
+    fn test_format_error_text() {
+        let error = anyhow::anyhow!("connection refused").context("connecting to Docker");
+        let result = format_error(&error, Format::Text);
+        assert!(result.starts_with("Error: connecting to Docker"));
+        assert!(result.contains("connection refused"));
+    }
+
+    #[test]
+    fn test_format_error_json() {
+        let error = anyhow::anyhow!("connection refused").context("connecting to Docker");
+        let result = format_error(&error, Format::JSON);
+        assert_eq!(
+            result,
+            r#"{"error":"connecting to Docker: connection refused"}"#
+        );
+    }
+
+    #[test]
+    fn test_format_error_json_envelope() {
+        let error = anyhow::anyhow!("connection refused").context("connecting to Docker");
+        let result: serde_json::Value =
+            serde_json::from_str(&format_error(&error, Format::JSON.with_envelope(true))).unwrap();
+        assert_eq!(
+            result,
+            serde_json::json!({
+                "schema_version": 1,
+                "error": "connecting to Docker: connection refused",
+            })
+        );
+    }
+
+    #[test]
+    fn test_format_usage_error() {
+        assert_eq!(
+            format_usage_error("unrecognized subcommand 'lst'"),
+            r#"{"error":{"kind":"usage","message":"unrecognized subcommand 'lst'"}}"#
+        );
+    }
 }
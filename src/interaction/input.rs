@@ -92,7 +92,8 @@ impl InputPrompt for Interaction {
 
 impl SelectPrompt for Interaction {
     fn select(&self, options: SelectPromptOptions) -> Result<SelectPromptResult> {
-        let select = Select::new(&options.message, options.options);
+        let select = Select::new(&options.message, options.options)
+            .with_starting_cursor(options.starting_cursor);
 
         match select.prompt() {
             Ok(selected) => Ok(SelectPromptResult::Selected(selected)),
@@ -1,17 +1,23 @@
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
 use anyhow::Result;
 use typed_builder::TypedBuilder;
 
 mod input;
+mod progress;
 mod spinner;
 
-#[derive(Debug, Default, Clone)]
-pub struct Interaction;
+pub use progress::{JsonLinesStepReporter, ProgressReporter};
+
+#[derive(Debug, Clone)]
+pub struct Interaction {
+    /// Time an operation runs before its spinner is shown, so operations that complete faster never show a spinner.
+    spinner_delay: Duration,
+}
 
 impl Interaction {
-    pub fn new() -> Self {
-        Default::default()
+    pub fn new(spinner_delay: Duration) -> Self {
+        Self { spinner_delay }
     }
 }
 
@@ -104,6 +110,9 @@ pub struct SelectPromptOptions {
         items.into_iter().map(|s| s.into()).collect()
     }))]
     options: Vec<String>,
+    // Index of the option that is selected when the prompt is shown
+    #[builder(default)]
+    starting_cursor: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -118,12 +127,27 @@ pub trait SelectPrompt {
 
 pub struct SpinnerHandle {
     stop_spinner: Option<Box<dyn FnOnce() + Send + Sync>>,
+    set_message: Option<Box<dyn Fn(String) + Send + Sync>>,
 }
 
 impl SpinnerHandle {
     pub fn new(stop_spinner: Box<dyn FnOnce() + Send + Sync>) -> Self {
         Self {
             stop_spinner: Some(stop_spinner),
+            set_message: None,
+        }
+    }
+
+    /// Use `set_message` to replace the message of the spinner, e.g. with the progress of the operation.
+    pub fn with_set_message(mut self, set_message: Box<dyn Fn(String) + Send + Sync>) -> Self {
+        self.set_message = Some(set_message);
+        self
+    }
+
+    /// Replace the message of the spinner, spinners that can't be updated keep their message.
+    pub fn set_message(&self, message: String) {
+        if let Some(set_message) = &self.set_message {
+            set_message(message);
         }
     }
 }
@@ -163,6 +187,7 @@ pub trait MultiStepSpinner {
     fn set_step_outcome(&mut self, step: usize, outcome: MultiStepSpinnerOutcome) -> Result<()>;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MultiStepSpinnerOutcome {
     Success,
     Skipped,
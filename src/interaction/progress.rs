@@ -0,0 +1,106 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::{MultiStepSpinner, MultiStepSpinnerOutcome};
+
+/// Reports the outcome of every step to multiple reporters, e.g. the spinner and a stream of JSON lines.
+pub struct ProgressReporter {
+    reporters: Vec<Box<dyn MultiStepSpinner + Send + Sync>>,
+}
+
+impl ProgressReporter {
+    pub fn new(reporters: Vec<Box<dyn MultiStepSpinner + Send + Sync>>) -> Self {
+        Self { reporters }
+    }
+}
+
+impl MultiStepSpinner for ProgressReporter {
+    fn set_step_outcome(&mut self, step: usize, outcome: MultiStepSpinnerOutcome) -> Result<()> {
+        for reporter in &mut self.reporters {
+            reporter.set_step_outcome(step, outcome)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the outcome of every step as a JSON line, e.g. `{"step":"create_container","outcome":"success"}`.
+pub struct JsonLinesStepReporter<W> {
+    step_names: Vec<&'static str>,
+    writer: W,
+}
+
+impl<W: Write> JsonLinesStepReporter<W> {
+    /// Create a reporter for the steps with the given names, in the order of the steps.
+    pub fn new(step_names: Vec<&'static str>, writer: W) -> Self {
+        Self { step_names, writer }
+    }
+}
+
+#[derive(Serialize)]
+struct StepOutcomeLine<'a> {
+    step: &'a str,
+    outcome: &'a str,
+}
+
+impl<W: Write> MultiStepSpinner for JsonLinesStepReporter<W> {
+    fn set_step_outcome(&mut self, step: usize, outcome: MultiStepSpinnerOutcome) -> Result<()> {
+        let step_name = self
+            .step_names
+            .get(step)
+            .with_context(|| format!("step {step} not found"))?;
+
+        let line = serde_json::to_string(&StepOutcomeLine {
+            step: step_name,
+            outcome: match outcome {
+                MultiStepSpinnerOutcome::Success => "success",
+                MultiStepSpinnerOutcome::Skipped => "skipped",
+                MultiStepSpinnerOutcome::Failure => "failure",
+            },
+        })
+        .context("serializing step outcome")?;
+
+        // Flush every line, consumers act on a step as soon as it completes
+        writeln!(self.writer, "{line}").context("writing step outcome")?;
+        self.writer.flush().context("flushing step outcome")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines_step_reporter() {
+        let mut reporter =
+            JsonLinesStepReporter::new(vec!["pull_image", "create_container"], Vec::new());
+
+        reporter
+            .set_step_outcome(0, MultiStepSpinnerOutcome::Skipped)
+            .expect("reporting the first step should succeed");
+        reporter
+            .set_step_outcome(1, MultiStepSpinnerOutcome::Success)
+            .expect("reporting the second step should succeed");
+
+        assert_eq!(
+            String::from_utf8(reporter.writer).unwrap(),
+            concat!(
+                "{\"step\":\"pull_image\",\"outcome\":\"skipped\"}\n",
+                "{\"step\":\"create_container\",\"outcome\":\"success\"}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_json_lines_step_reporter_unknown_step() {
+        let mut reporter = JsonLinesStepReporter::new(vec!["pull_image"], Vec::new());
+
+        assert!(
+            reporter
+                .set_step_outcome(1, MultiStepSpinnerOutcome::Success)
+                .is_err()
+        );
+    }
+}
@@ -1,22 +1,52 @@
-use std::time::Duration;
+use std::{thread, time::Duration};
 
 use anyhow::{Context, Result};
 use console::{Emoji, Style};
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 
 use super::{
     Interaction, MultiStepSpinner, MultiStepSpinnerInteraction, MultiStepSpinnerOutcome,
     MultiStepSpinnerStep, SpinnerHandle, SpinnerInteraction,
 };
 
+/// Run `show` once the spinner delay has elapsed, so operations that complete faster never show a spinner.
+///
+/// The spinners are hidden until then, they keep ticking so they are up to date when they are shown.
+fn show_after_delay(delay: Duration, show: impl FnOnce() + Send + 'static) {
+    if delay.is_zero() {
+        show();
+        return;
+    }
+
+    thread::spawn(move || {
+        thread::sleep(delay);
+        show();
+    });
+}
+
 impl SpinnerInteraction for Interaction {
     fn start_spinner(&self, message: String) -> Result<SpinnerHandle> {
-        let progress_bar = ProgressBar::new_spinner();
+        let progress_bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::hidden());
+        progress_bar.set_style(ProgressStyle::default_spinner());
         progress_bar.set_message(message);
         progress_bar.enable_steady_tick(Duration::from_millis(80));
 
+        // A weak reference, the spinner is not kept alive when the operation completes before the delay
+        let delayed_progress_bar = progress_bar.downgrade();
+        show_after_delay(self.spinner_delay, move || {
+            if let Some(progress_bar) = delayed_progress_bar.upgrade() {
+                if !progress_bar.is_finished() {
+                    progress_bar.set_draw_target(ProgressDrawTarget::stderr());
+                }
+            }
+        });
+
+        let message_progress_bar = progress_bar.clone();
         Ok(SpinnerHandle::new(Box::new(move || {
             progress_bar.finish_and_clear();
+        }))
+        .with_set_message(Box::new(move |message| {
+            message_progress_bar.set_message(message);
         })))
     }
 }
@@ -30,7 +60,7 @@ impl MultiStepSpinnerInteraction for Interaction {
         let progress_style = ProgressStyle::with_template("{prefix} {spinner} {msg}")
             .expect("failed to create progress style");
 
-        let m = MultiProgress::new();
+        let m = MultiProgress::with_draw_target(ProgressDrawTarget::hidden());
         let number_of_steps = steps.len();
 
         let step_progress_bars = steps
@@ -53,7 +83,18 @@ impl MultiStepSpinnerInteraction for Interaction {
                 // Return the progress bar
                 progress_bar
             })
-            .collect();
+            .collect::<Vec<_>>();
+
+        // Steps that all completed before the delay are never shown
+        let delayed_step_progress_bars = step_progress_bars.clone();
+        show_after_delay(self.spinner_delay, move || {
+            if !delayed_step_progress_bars
+                .iter()
+                .all(ProgressBar::is_finished)
+            {
+                m.set_draw_target(ProgressDrawTarget::stderr());
+            }
+        });
 
         Ok(Box::new(IndicatifMultiStepSpinner { step_progress_bars }))
     }
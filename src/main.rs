@@ -1,36 +1,50 @@
 //! Main entry point for the Atlas Local CLI application.
 //!
 //! This module handles the application's startup flow:
-//! 1. Parses CLI arguments using clap
+//! 1. Parses CLI arguments using clap, usage errors are printed as JSON with `--output json`
 //! 2. Converts CLI arguments into executable commands
 //! 3. Executes the commands and handles their output
+//! 4. Prints errors to the stream selected with `--errorsTo`
 //!
 //! The application can be run either as a standalone CLI (`atlas-local`) or as an Atlas CLI plugin (`atlas local`).
 
+use std::process::ExitCode;
+
 use anyhow::{Context, Result};
 use args::Cli;
-use clap::{Parser, error::ErrorKind};
+use clap::{Parser, ValueEnum, error::ErrorKind};
 use mongodb_atlas_cli::config::{self, OutputFormat};
 use tracing::debug;
 
 use crate::{
     args::{GlobalArgs, LocalArgs},
     commands::command_from_args,
-    formatting::Format,
+    dependencies::DockerError,
+    formatting::{ErrorsTo, Format, format_error, format_usage_error},
+    settings::Settings,
 };
 
 mod args;
 mod commands;
+mod config_dir;
 mod dependencies;
 mod env;
 mod formatting;
 mod interaction;
 mod logging;
+mod messages;
 mod models;
+mod settings;
 mod table;
 
+/// Exit code used when Docker failed, e.g. because the Docker daemon is not running.
+const DOCKER_ERROR_EXIT_CODE: u8 = 3;
+
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
+    // Answer the requests of the shell completion script, before anything is parsed or printed.
+    commands::completion::complete_from_env();
+
     // Parse the CLI arguments.
     let cli = match Cli::try_parse() {
         Ok(cli) => cli,
@@ -43,11 +57,9 @@ async fn main() -> Result<()> {
                 | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
                 | ErrorKind::DisplayVersion => {
                     _ = e.print();
-                    return Ok(());
-                }
-                _ => {
-                    e.exit();
+                    return ExitCode::SUCCESS;
                 }
+                _ => return print_usage_error(&e),
             }
         }
     };
@@ -59,11 +71,120 @@ async fn main() -> Result<()> {
     // Setup logging.
     logging::setup_logging(global_args.debug);
 
+    // The settings selected with the global options are passed to the command, e.g. the Docker host.
+    let settings = Settings::from(&global_args);
+
     // Get the format to use for the output.
-    let format = get_format(&global_args);
+    let format = get_format(&global_args)
+        .with_json_array(global_args.json_array)
+        .with_envelope(global_args.envelope);
+
+    // Run the command, errors are printed to the stream selected by the user.
+    if let Err(error) = run(cli_arguments, &global_args, settings, format).await {
+        print_error(&error, format, global_args.errors_to);
+        return exit_code(&error);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Print an error about an invalid command line, e.g. an unknown subcommand, and exit the way clap does.
+///
+/// Clap prints a colored human readable error, which breaks wrappers that consume the JSON output.
+/// So with `--output json` the error is printed as JSON to the stream selected with `--errorsTo` instead.
+/// The command line didn't parse, so the flags are looked up in the raw arguments.
+fn print_usage_error(e: &clap::Error) -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let format = raw_flag_value(&args, Some('o'), "output")
+        .and_then(|format| Format::from_str(format, false).ok());
+    if !format.is_some_and(Format::is_json) {
+        e.exit();
+    }
+
+    // The rendered error starts with "error: <message>", followed by the usage and a tip
+    let rendered = e.render().to_string();
+    let message = rendered.lines().next().unwrap_or_default();
+    let message = message.strip_prefix("error: ").unwrap_or(message);
+
+    let errors_to = raw_flag_value(&args, None, "errorsTo")
+        .and_then(|errors_to| ErrorsTo::from_str(errors_to, false).ok())
+        .unwrap_or_default();
+    let formatted_error = format_usage_error(message);
+    match errors_to {
+        ErrorsTo::Stdout => println!("{formatted_error}"),
+        ErrorsTo::Stderr => eprintln!("{formatted_error}"),
+    }
+
+    ExitCode::from(e.exit_code() as u8)
+}
+
+/// Get the value of a flag from the raw arguments, the last occurrence wins like it does for clap.
+///
+/// Supports `--long value`, `--long=value`, `-s value`, `-s=value` and `-svalue`, arguments after `--` are ignored.
+fn raw_flag_value<'a>(args: &'a [String], short: Option<char>, long: &str) -> Option<&'a str> {
+    let long = format!("--{long}");
+    let short = short.map(|short| format!("-{short}"));
+
+    let mut value = None;
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+
+        let flag = if arg.starts_with("--") {
+            Some(long.as_str())
+        } else {
+            short.as_deref()
+        };
+        let Some(flag) = flag else {
+            continue;
+        };
+        let Some(rest) = arg.strip_prefix(flag) else {
+            continue;
+        };
+
+        if rest.is_empty() {
+            value = args.next().or(value);
+        } else if let Some(rest) = rest.strip_prefix('=') {
+            value = Some(rest);
+        } else if !arg.starts_with("--") {
+            // Short flags can be followed by their value, e.g. -ojson
+            value = Some(rest);
+        }
+    }
+
+    value
+}
+
+/// Convert the CLI arguments into a command and execute it.
+async fn run(
+    cli_arguments: LocalArgs,
+    global_args: &GlobalArgs,
+    mut settings: Settings,
+    format: Format,
+) -> Result<()> {
+    // Select the saved context, or the Docker context, before any command connects to Docker.
+    // A Docker host selected with --dockerHost takes precedence over the Docker contexts.
+    if let Some(context) = &global_args.context {
+        commands::context::use_context(context, &mut settings)
+            .await
+            .context("selecting context")?;
+    } else if global_args.docker_host.is_none() {
+        commands::context::docker_context::use_docker_context(
+            global_args.docker_context.as_deref(),
+            &mut settings,
+        )
+        .await
+        .context("selecting Docker context")?;
+    }
+
+    // Podman is found once, so every Docker connection and the docker CLI use the same host.
+    settings.docker = settings.docker.resolve_host();
 
     // Convert the CLI arguments into a command.
-    let mut root_command = command_from_args(cli_arguments, format)
+    let mut root_command = command_from_args(cli_arguments, &settings, format)
         .await
         .context("converting CLI arguments into a command")?;
 
@@ -73,6 +194,25 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Print an error in the requested format to the requested stream.
+fn print_error(error: &anyhow::Error, format: Format, errors_to: ErrorsTo) {
+    let formatted_error = format_error(error, format);
+
+    match errors_to {
+        ErrorsTo::Stdout => println!("{formatted_error}"),
+        ErrorsTo::Stderr => eprintln!("{formatted_error}"),
+    }
+}
+
+/// Get the exit code for an error, Docker errors get a dedicated exit code so scripts can tell them apart.
+fn exit_code(error: &anyhow::Error) -> ExitCode {
+    if error.chain().any(|cause| cause.is::<DockerError>()) {
+        return ExitCode::from(DOCKER_ERROR_EXIT_CODE);
+    }
+
+    ExitCode::FAILURE
+}
+
 /// Get the format to use for the output.
 fn get_format(global_args: &GlobalArgs) -> Format {
     // If the format is set, return it.
@@ -89,7 +229,7 @@ fn get_format(global_args: &GlobalArgs) -> Format {
         if let Some(format) = config.output {
             debug!(output_format = ?format, "Using output format from Atlas CLI config");
             return match format {
-                OutputFormat::Json => Format::Json,
+                OutputFormat::Json => Format::JSON,
                 OutputFormat::Plaintext => Format::Text,
             };
         }
@@ -0,0 +1,139 @@
+//! Catalog of the messages shown while commands run, e.g. the steps of a spinner.
+//!
+//! Commands look messages up with [`message`] instead of hardcoding them, so they can be localized without touching the command logic.
+//! Placeholders in braces, e.g. `{name}`, are replaced by the command, so a translation can move them.
+//! The language is selected with the `LANG` environment variable, English is used for languages without a catalog.
+use std::sync::OnceLock;
+
+use crate::env;
+
+/// Key of a message in the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Message {
+    SetupPullImage,
+    SetupCreateDeployment,
+    SetupStartDeployment,
+    SetupWaitForHealthy,
+    StartDeployment,
+    UnpauseDeployment,
+    RecreateDeployment,
+    WaitForHealthy,
+    WaitForPrimary,
+    DownloadSampleData,
+    ImportSampleData,
+    CloneDeployment,
+    CreateSnapshot,
+    RestoreSnapshot,
+    ImportDump,
+    SeedDeployment,
+    DeleteDeployments,
+    BuildSearchIndex,
+    BuildSearchIndexes,
+    UpdateSearchIndex,
+    /// Has an `{image}` placeholder for the image reference.
+    PullImage,
+    /// Has a `{name}` placeholder for the name of the deployment.
+    CreateNamedDeployment,
+    /// Has a `{volume}` placeholder for the name of the volume.
+    CopyVolume,
+}
+
+/// Language the messages are shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+}
+
+impl Language {
+    /// Get the language from the `LANG` environment variable.
+    pub fn from_env() -> Self {
+        std::env::var(env::LANG)
+            .ok()
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// Get the language of a POSIX locale such as `en_US.UTF-8`, `None` when there is no catalog for it.
+    fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        match language {
+            "en" | "C" | "POSIX" => Some(Language::English),
+            _ => None,
+        }
+    }
+
+    /// Look up a message in the catalog of this language.
+    pub fn message(self, message: Message) -> &'static str {
+        match self {
+            Language::English => english(message),
+        }
+    }
+}
+
+/// Look up a message in the catalog of the language selected by the user.
+pub fn message(message: Message) -> &'static str {
+    static LANGUAGE: OnceLock<Language> = OnceLock::new();
+    LANGUAGE.get_or_init(Language::from_env).message(message)
+}
+
+fn english(message: Message) -> &'static str {
+    match message {
+        Message::SetupPullImage => "Pulling the latest version of the MongoDB image...",
+        Message::SetupCreateDeployment => "Creating the deployment...",
+        Message::SetupStartDeployment => "Starting the deployment...",
+        Message::SetupWaitForHealthy => "Waiting for the deployment to be healthy...",
+        Message::StartDeployment => "Starting deployment...",
+        Message::UnpauseDeployment => "Unpausing deployment...",
+        Message::RecreateDeployment => "Recreating deployment...",
+        Message::WaitForHealthy => "Waiting for deployment to become healthy...",
+        Message::WaitForPrimary => "Waiting for deployment to have a primary...",
+        Message::DownloadSampleData => "Downloading sample data...",
+        Message::ImportSampleData => "Importing sample data...",
+        Message::CloneDeployment => "Cloning deployment...",
+        Message::CreateSnapshot => "Creating snapshot...",
+        Message::RestoreSnapshot => "Restoring snapshot...",
+        Message::ImportDump => "Importing dump...",
+        Message::SeedDeployment => "Seeding deployment...",
+        Message::DeleteDeployments => "Deleting deployments...",
+        Message::BuildSearchIndex => "Building search index...",
+        Message::BuildSearchIndexes => "Building search indexes...",
+        Message::UpdateSearchIndex => "Updating search index...",
+        Message::PullImage => "Pulling {image}...",
+        Message::CreateNamedDeployment => "Creating deployment '{name}'...",
+        Message::CopyVolume => "Copying the data of volume '{volume}'...",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(
+            Language::from_locale("en_US.UTF-8"),
+            Some(Language::English)
+        );
+        assert_eq!(Language::from_locale("C"), Some(Language::English));
+        assert_eq!(Language::from_locale("nl_BE.UTF-8"), None);
+    }
+
+    #[test]
+    fn test_english_catalog() {
+        assert_eq!(
+            Language::English.message(Message::WaitForPrimary),
+            "Waiting for deployment to have a primary..."
+        );
+    }
+
+    #[test]
+    fn test_english_catalog_placeholder() {
+        assert_eq!(
+            Language::English
+                .message(Message::CreateNamedDeployment)
+                .replace("{name}", "orders"),
+            "Creating deployment 'orders'..."
+        );
+    }
+}
@@ -23,6 +23,14 @@ pub struct Deployment {
 
     /// State of the deployment.
     pub state: State,
+
+    /// Port the deployment is bound to on the host, only set when the connection string is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    /// Connection string of the deployment, only set when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_string: Option<String>,
 }
 
 /// Convert from the underlying library's deployment model to the CLI's deployment model.
@@ -35,6 +43,8 @@ impl From<atlas_local::models::Deployment> for Deployment {
             name: deployment.name.unwrap_or(deployment.container_id),
             mongo_db_version: deployment.mongodb_version,
             state: deployment.state,
+            port: None,
+            connection_string: None,
         }
     }
 }
@@ -71,6 +81,8 @@ mod tests {
             name: "test-deployment".to_string(),
             mongo_db_version: Version::parse("8.2.2").unwrap(),
             state: State::Running,
+            port: None,
+            connection_string: None,
         };
         let actual = Deployment::from(atlas_local_lib_deployment);
         assert_eq!(actual, expected);
@@ -104,6 +116,8 @@ mod tests {
             name: "test-container-id".to_string(),
             mongo_db_version: Version::parse("8.2.2").unwrap(),
             state: State::Paused,
+            port: None,
+            connection_string: None,
         };
         let actual = Deployment::from(atlas_local_lib_deployment);
         assert_eq!(actual, expected);
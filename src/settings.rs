@@ -0,0 +1,125 @@
+//! Settings of a single invocation, selected with the global options.
+//!
+//! The settings are created from the [`GlobalArgs`] before the command is created, and are passed to every command
+//! that connects to Docker, reads or writes state, or shows spinners.
+
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    args::GlobalArgs,
+    dependencies::{DockerSettings, DockerTls, docker_tls_from_env},
+};
+
+/// Time an operation runs before its spinner is shown, the default of `--spinnerDelay`.
+const DEFAULT_SPINNER_DELAY: Duration = Duration::from_millis(200);
+
+/// Settings of the invocation, the global options that apply to every command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// Directory selected with `--configDir`, see [`config_dir`](crate::config_dir::config_dir).
+    pub config_dir: Option<PathBuf>,
+    /// How to connect to Docker.
+    pub docker: DockerSettings,
+    /// Time an operation runs before its spinner is shown, selected with `--spinnerDelay`.
+    pub spinner_delay: Duration,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            config_dir: None,
+            docker: DockerSettings::default(),
+            spinner_delay: DEFAULT_SPINNER_DELAY,
+        }
+    }
+}
+
+impl From<&GlobalArgs> for Settings {
+    fn from(global_args: &GlobalArgs) -> Self {
+        // The TLS options take precedence over the TLS environment variables of the Docker CLI
+        let tls = match (
+            global_args.tls_ca_cert.clone(),
+            global_args.tls_cert.clone(),
+            global_args.tls_key.clone(),
+        ) {
+            (Some(ca_cert), Some(cert), Some(key)) => Some(DockerTls { ca_cert, cert, key }),
+            _ => docker_tls_from_env(),
+        };
+
+        Self {
+            config_dir: global_args.config_dir.clone(),
+            docker: DockerSettings {
+                host: global_args.docker_host.clone(),
+                tls,
+                reconnect_on_drop: global_args.reconnect_on_drop,
+            },
+            spinner_delay: global_args.spinner_delay,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        global_args: GlobalArgs,
+    }
+
+    fn settings_from(args: &[&str]) -> Settings {
+        let cli =
+            TestCli::try_parse_from(std::iter::once("atlas-local").chain(args.iter().copied()))
+                .expect("parsing the global options should succeed");
+        Settings::from(&cli.global_args)
+    }
+
+    #[test]
+    fn test_settings_from_global_args() {
+        let settings = settings_from(&[
+            "--dockerHost",
+            "tcp://docker.example.com:2376",
+            "--tlsCaCert",
+            "/certs/ca.pem",
+            "--tlsCert",
+            "/certs/cert.pem",
+            "--tlsKey",
+            "/certs/key.pem",
+            "--reconnectOnDrop",
+            "--spinnerDelay",
+            "0s",
+            "--configDir",
+            "/config",
+        ]);
+
+        assert_eq!(
+            settings,
+            Settings {
+                config_dir: Some(PathBuf::from("/config")),
+                docker: DockerSettings {
+                    host: Some("tcp://docker.example.com:2376".to_string()),
+                    tls: Some(DockerTls {
+                        ca_cert: PathBuf::from("/certs/ca.pem"),
+                        cert: PathBuf::from("/certs/cert.pem"),
+                        key: PathBuf::from("/certs/key.pem"),
+                    }),
+                    reconnect_on_drop: true,
+                },
+                spinner_delay: Duration::ZERO,
+            }
+        );
+    }
+
+    #[test]
+    fn test_settings_defaults() {
+        let settings = settings_from(&[]);
+
+        assert_eq!(settings.config_dir, None);
+        assert_eq!(settings.docker.host, None);
+        assert!(!settings.docker.reconnect_on_drop);
+        assert_eq!(settings.spinner_delay, Settings::default().spinner_delay);
+    }
+}
@@ -1,19 +1,38 @@
 //! This module contains the table logic for the application.
 //!
 //! The main entry point is the [`Table`] struct which represents a table.
-//! It also contains the [`IntoTable`] trait which allows any type to be converted into a table.
+//! A table is either created from an iterator of items with [`Table::from_iter`],
+//! or built column by column and row by row with [`Table::add_column`] and [`Table::add_row`].
 use std::{fmt::Display, iter};
 
+use console::measure_text_width;
+
+/// Padding added after every cell.
+const CELL_PADDING: usize = 4;
+
+/// Alignment of the cells of a column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+}
+
 /// Table representation.
 ///
 /// A table is a collection of rows and columns.
 ///
 /// The table is printed using the [`Display`] trait.
 /// It's following the same format as the tables printed using the Atlas CLI.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Table {
     /// Header of the table.
     pub header: Vec<String>,
     pub rows: Vec<Vec<String>>,
+    /// Alignment of every column, columns without an alignment are aligned left.
+    alignments: Vec<Alignment>,
+    /// Whether the header is printed.
+    show_header: bool,
 }
 
 /// Column definition.
@@ -29,7 +48,54 @@ impl Table {
     /// * `header` - The header of the table.
     /// * `rows` - The rows of the table.
     pub fn new(header: Vec<String>, rows: Vec<Vec<String>>) -> Self {
-        Self { header, rows }
+        Self {
+            header,
+            rows,
+            alignments: Vec::new(),
+            show_header: true,
+        }
+    }
+
+    /// Create a table without columns, to build with [`Table::add_column`] and [`Table::add_row`].
+    pub fn builder() -> Self {
+        Self::new(Vec::new(), Vec::new())
+    }
+
+    /// Add a column with the given name and alignment.
+    pub fn add_column(mut self, name: impl Into<String>, alignment: Alignment) -> Self {
+        self.header.push(name.into());
+        self.alignments
+            .resize(self.header.len() - 1, Alignment::default());
+        self.alignments.push(alignment);
+        self
+    }
+
+    /// Add a row, with a cell for every column.
+    pub fn add_row<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Don't print the header, e.g. when the output is processed by scripts.
+    pub fn without_header(mut self) -> Self {
+        self.show_header = false;
+        self
+    }
+
+    /// Render the table to a string, the same as formatting it with [`Display`].
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+
+    /// The rows that are printed, including the header when it is shown.
+    fn printed_rows(&self) -> impl Iterator<Item = &Vec<String>> {
+        iter::once(&self.header)
+            .filter(|_| self.show_header)
+            .chain(self.rows.iter())
     }
 
     /// Create a new table from an iterator of items.
@@ -64,12 +130,12 @@ impl Display for Table {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // calculate the max width of each column
         // combine the header and rows into a single iterator
-        // then get the length of each cell + 4 (for the padding)
-        let max_column_widths: Vec<usize> = iter::once(&self.header)
-            .chain(self.rows.iter())
+        // then get the width of each cell, ignoring color codes
+        let max_column_widths: Vec<usize> = self
+            .printed_rows()
             .map(|row| {
                 row.iter()
-                    .map(|cell| cell.len() + 4)
+                    .map(|cell| measure_text_width(cell))
                     .collect::<Vec<usize>>()
             })
             .fold(vec![0; self.header.len()], |acc, vec| {
@@ -77,10 +143,16 @@ impl Display for Table {
             });
 
         // print the rows with tab separation
-        for row in iter::once(&self.header).chain(self.rows.iter()) {
+        for row in self.printed_rows() {
             // print the cells with fixed widths
-            for (cell, width) in row.iter().zip(max_column_widths.iter()) {
-                write!(f, "{:<width$}", cell, width = width)?;
+            // the padding is computed manually, the format width would count the color codes
+            for (column, (cell, width)) in row.iter().zip(max_column_widths.iter()).enumerate() {
+                let fill = " ".repeat(width - measure_text_width(cell));
+                let padding = " ".repeat(CELL_PADDING);
+                match self.alignments.get(column).copied().unwrap_or_default() {
+                    Alignment::Left => write!(f, "{cell}{fill}{padding}")?,
+                    Alignment::Right => write!(f, "{fill}{cell}{padding}")?,
+                }
             }
 
             // end the row
@@ -232,4 +304,41 @@ mod tests {
             "ID    Name      Price    \n1     Widget    9.99     \n2     Gadget    19.99    \n"
         );
     }
+
+    #[test]
+    fn test_builder() {
+        let table = Table::builder()
+            .add_column("Name", Alignment::Left)
+            .add_column("Age", Alignment::Right)
+            .add_row(["Alice", "30"])
+            .add_row(["Bob", "5"]);
+
+        assert_eq!(
+            table.render(),
+            "Name     Age    \nAlice     30    \nBob        5    \n"
+        );
+    }
+
+    #[test]
+    fn test_builder_without_header() {
+        let table = Table::builder()
+            .add_column("Name", Alignment::Left)
+            .add_row(["Alice"])
+            .without_header();
+
+        assert_eq!(table.render(), "Alice    \n");
+    }
+
+    #[test]
+    fn test_builder_ignores_color_codes_in_widths() {
+        let table = Table::builder()
+            .add_column("State", Alignment::Left)
+            .add_column("Name", Alignment::Left)
+            .add_row(["\u{1b}[32mok\u{1b}[0m", "local1"]);
+
+        assert_eq!(
+            table.render(),
+            "State    Name      \n\u{1b}[32mok\u{1b}[0m       local1    \n"
+        );
+    }
 }